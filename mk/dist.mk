@@ -58,6 +58,7 @@ PKG_FILES := \
       libcollectionstest                       \
       libcoretest                              \
       libbacktrace                             \
+      linkchecker                              \
       rt                                       \
       rtstartup                                \
       rustllvm                                 \
@@ -60,8 +60,25 @@ RUSTBOOK_EXE = $(HBIN2_H_$(CFG_BUILD))/rustbook$(X_$(CFG_BUILD))
 RUSTBOOK = $(RPATH_VAR2_T_$(CFG_BUILD)_H_$(CFG_BUILD)) $(RUSTBOOK_EXE)
 
 # The error-index-generator executable...
-ERR_IDX_GEN_EXE = $(HBIN2_H_$(CFG_BUILD))/error-index-generator$(X_$(CFG_BUILD))
-ERR_IDX_GEN = $(RPATH_VAR2_T_$(CFG_BUILD)_H_$(CFG_BUILD)) $(ERR_IDX_GEN_EXE)
+#
+# The error index is made of the metadata the compiler crates write while
+# they are compiled, which already happens when the snapshot builds them.
+# So if nothing but the error index is being built, the generator is taken
+# from the stage1 sysroot, where it is built by the stage0 compiler against
+# the stage0 libraries, instead of waiting for the stage2 compiler.
+ifneq ($(MAKECMDGOALS),)
+ifeq ($(filter-out error-index doc/error-index.html,$(MAKECMDGOALS)),)
+ERR_IDX_GEN_STAGE ?= 1
+endif
+endif
+ERR_IDX_GEN_STAGE ?= 2
+ERR_IDX_GEN_EXE = \
+  $(HBIN$(ERR_IDX_GEN_STAGE)_H_$(CFG_BUILD))/error-index-generator$(X_$(CFG_BUILD))
+ERR_IDX_GEN = $(RPATH_VAR$(ERR_IDX_GEN_STAGE)_T_$(CFG_BUILD)_H_$(CFG_BUILD)) $(ERR_IDX_GEN_EXE)
+
+# The linkchecker executable...
+LINKCHECKER_EXE = $(HBIN2_H_$(CFG_BUILD))/linkchecker$(X_$(CFG_BUILD))
+LINKCHECKER = $(RPATH_VAR2_T_$(CFG_BUILD)_H_$(CFG_BUILD)) $(LINKCHECKER_EXE)
 
 D := $(S)src/doc
 
@@ -173,6 +190,12 @@ doc/$(1)/index.html: $$(LIB_DOC_DEP_$(1)) doc/$(1)/
 	@$$(call E, rustdoc: $$@)
 	$$(Q)CFG_LLVM_LINKAGE_FILE=$$(LLVM_LINKAGE_PATH_$(CFG_BUILD)) \
 		$$(RUSTDOC) --cfg dox --cfg stage2 $$(RUSTFLAGS_$(1)) $$<
+
+# Documents just this crate and the documented crates it depends on, so
+# that links to them resolve, e.g. `make doc-collections`.
+doc-$(1): doc/$(1)/index.html \
+	$$(foreach dep,$$(filter $$(DOC_CRATES), $$(RUST_DEPS_$(1))),doc-$$(dep))
+.PHONY: doc-$(1)
 endef
 
 $(foreach crate,$(CRATES),$(eval $(call DEF_LIB_DOC,$(crate))))
@@ -180,8 +203,10 @@ $(foreach crate,$(CRATES),$(eval $(call DEF_LIB_DOC,$(crate))))
 COMPILER_DOC_TARGETS := $(CRATES:%=doc/%/index.html)
 ifdef CFG_ENABLE_COMPILER_DOCS
   DOC_TARGETS += $(COMPILER_DOC_TARGETS)
+  UNDOCUMENTED_CRATES :=
 else
   DOC_TARGETS += $(DOC_CRATES:%=doc/%/index.html)
+  UNDOCUMENTED_CRATES := $(filter-out $(DOC_CRATES),$(CRATES))
 endif
 
 ifdef CFG_DISABLE_DOCS
@@ -192,6 +217,14 @@ endif
 
 docs: $(DOC_TARGETS)
 doc: docs
+
+# Checks that every link in the generated documentation resolves. This
+# depends on the files it checks rather than on the `docs` alias.
+linkcheck: $(LINKCHECKER_EXE) $(DOC_TARGETS)
+	@$(call E, linkchecker: doc)
+	$(Q)$(LINKCHECKER) doc $(UNDOCUMENTED_CRATES)
+.PHONY: linkcheck
+
 compiler-docs: $(COMPILER_DOC_TARGETS)
 
 book: doc/book/index.html
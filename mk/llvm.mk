@@ -63,8 +63,8 @@ $$(LLVM_AR_$(1)): $$(LLVM_CONFIG_$(1))
 # This is used to independently force an LLVM clean rebuild
 # when we changed something not otherwise captured by builtin
 # dependencies. In these cases, commit a change that touches
-# the stamp in the source dir.
-$$(LLVM_STAMP_$(1)): $$(S)src/rustllvm/llvm-auto-clean-trigger
+# the stamp in the source dir. A toolchain upgrade cleans it as well.
+$$(LLVM_STAMP_$(1)): $$(S)src/rustllvm/llvm-auto-clean-trigger toolchain.stamp
 	@$$(call E, make: cleaning llvm)
 	$$(Q)touch $$@.start_time
 	$$(Q)$$(MAKE) clean-llvm$(1)
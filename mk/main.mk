@@ -181,6 +181,55 @@ endif
 # platform-specific auto-configuration
 include $(CFG_SRC_DIR)mk/platform.mk
 
+# Upgrading one of the tools the build runs, e.g. the C compiler, doesn't
+# change any file the build depends on. Keep a fingerprint of their
+# versions in toolchain.stamp and rewrite it when they change, so that
+# everything depending on $(MKFILE_DEPS) is rebuilt, and LLVM is cleaned.
+TOOLCHAIN_TOOLS := $(CC_$(CFG_BUILD)) $(CFG_PYTHON)
+ifdef CFG_CMAKE
+  TOOLCHAIN_TOOLS += $(CFG_CMAKE)
+endif
+ifdef CFG_ENABLE_LOCAL_RUST
+  TOOLCHAIN_TOOLS += $(CFG_LOCAL_RUST_ROOT)/bin/rustc$(CFG_EXE_SUFFIX_$(CFG_BUILD))
+endif
+TOOLCHAIN_FINGERPRINT := $(shell for tool in $(TOOLCHAIN_TOOLS); do \
+	command -v $$tool; $$tool --version 2>&1 | head -n 1; done | cksum)
+
+# `make -n` only prints what would be done, so don't touch the stamp then
+ifneq ($(findstring n,$(firstword -$(MAKEFLAGS))),)
+  CFG_DRY_RUN := 1
+endif
+
+ifneq ($(TOOLCHAIN_FINGERPRINT),$(shell cat toolchain.stamp 2>/dev/null))
+ifneq ($(wildcard toolchain.stamp),)
+  CFG_INFO := $(info cfg: toolchain changed since the last build, rebuilding)
+endif
+ifndef CFG_DRY_RUN
+  CFG_INFO := $(shell echo "$(TOOLCHAIN_FINGERPRINT)" > toolchain.stamp)
+endif
+endif
+
+ifeq ($(NO_MKFILE_DEPS),)
+  MKFILE_DEPS += toolchain.stamp
+endif
+
+# With BUILD_STATS=1, every make run and the time each crate takes to build
+# are appended to build-stats.log, which `make stats` summarizes. Only the
+# top-level make records the run, and only once if it restarts after
+# remaking its makefiles.
+BUILD_STATS_LOG := build-stats.log
+ifdef BUILD_STATS
+ifndef CFG_DRY_RUN
+ifeq ($(MAKELEVEL)$(MAKE_RESTARTS),0)
+  CFG_INFO := $(shell $(CFG_PYTHON) $(S)src/etc/build-stats.py build \
+	$(BUILD_STATS_LOG) "$(MAKECMDGOALS)")
+endif
+  # $(1) is the name of the step, $(2) the stamp touched when it started
+  RECORD_BUILD_STEP = $(CFG_PYTHON) $(S)src/etc/build-stats.py step \
+	$(BUILD_STATS_LOG) $(1) $(2)
+endif
+endif
+
 # Run the stage1/2 compilers under valgrind
 ifdef VALGRIND_COMPILE
   CFG_VALGRIND_COMPILE := $(CFG_VALGRIND)
@@ -601,6 +650,19 @@ ALL_TARGET_RULES = $(foreach target,$(CFG_TARGET), \
 
 all: $(ALL_TARGET_RULES) $(GENERATED) docs
 
+# `all` split in two, so that CI can build the compilers for the hosts
+# separately from the standard libraries for targets that aren't hosts.
+ALL_HOST_RULES = $(foreach target,$(filter $(CFG_HOST),$(CFG_TARGET)), \
+	$(foreach host,$(CFG_HOST), \
+ all-target-$(target)-host-$(host)))
+
+ALL_TARGET_ONLY_RULES = $(foreach target,$(filter-out $(CFG_HOST),$(CFG_TARGET)), \
+	$(foreach host,$(CFG_HOST), \
+ all-target-$(target)-host-$(host)))
+
+all-hosts: $(ALL_HOST_RULES)
+all-targets: $(ALL_TARGET_ONLY_RULES)
+
 ######################################################################
 # Build system documentation
 ######################################################################
@@ -618,3 +680,6 @@ tips:
 
 nitty-gritty:
 	$(call SHOW_DOCS,nitty-gritty)
+
+stats:
+	$(Q)$(CFG_PYTHON) $(S)src/etc/build-stats.py summary $(BUILD_STATS_LOG)
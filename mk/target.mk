@@ -21,9 +21,18 @@ export CFG_DEFAULT_AR
 # code, make sure that these common warnings are denied by default. These can
 # be overridden during development temporarily. For stage0, we allow warnings
 # which may be bugs in stage0 (should be fixed in stage1+)
+#
+# INTERNAL_LINTS=1 also turns on the lints for the compiler's own code, which
+# the stage0 compiler may not know about yet. Too much code doesn't pass them
+# yet, so that build only warns, and `-D warnings` is left out.
 RUST_LIB_FLAGS_ST0 += -W warnings
+ifdef INTERNAL_LINTS
+RUST_LIB_FLAGS_ST1 += -W rustc_internal
+RUST_LIB_FLAGS_ST2 += -W rustc_internal
+else
 RUST_LIB_FLAGS_ST1 += -D warnings
 RUST_LIB_FLAGS_ST2 += -D warnings
+endif
 
 # Macro that generates the full list of dependencies for a crate at a particular
 # stage/target/host tuple.
@@ -99,6 +108,8 @@ $$(TLIB$(1)_T_$(2)_H_$(3))/stamp.$(4): \
 		--out-dir $$(@D) \
 		-C extra-filename=-$$(CFG_FILENAME_EXTRA) \
 		$$<
+	$$(if $$(RECORD_BUILD_STEP),@$$(call RECORD_BUILD_STEP, \
+	    stage$(1)-$(2)-$(4),$$@.start_time))
 	@touch -r $$@.start_time $$@ && rm $$@.start_time
 	$$(call LIST_ALL_OLD_GLOB_MATCHES, \
 	    $$(dir $$@)$$(call CFG_LIB_GLOB_$(2),$(4)))
@@ -59,7 +59,7 @@ CTEST_TESTARGS := $(TESTARGS)
 
 # --bench is only relevant for crate tests, not for the compile tests
 ifdef PLEASE_BENCH
-  TESTARGS += --bench
+  TESTARGS += --bench $(BENCH_ARGS)
 endif
 
 ifdef VERBOSE
@@ -78,6 +78,7 @@ endif
 
 TEST_LOG_FILE=tmp/check-stage$(1)-T-$(2)-H-$(3)-$(4).log
 TEST_OK_FILE=tmp/check-stage$(1)-T-$(2)-H-$(3)-$(4).ok
+TEST_BENCH_RESULTS_FILE=tmp/check-stage$(1)-T-$(2)-H-$(3)-$(4)-bench.json
 
 define DEF_TARGET_COMMANDS
 
@@ -171,14 +172,36 @@ $(foreach file,$(wildcard $(S)src/doc/nomicon/*.md), \
 # Main test targets
 ######################################################################
 
+# The fingerprint of the sources the stage2 compiler was last built from
+SOURCE_FINGERPRINT := source-fingerprint.stamp
+
+$(SOURCE_FINGERPRINT): $(SREQ2_T_$(CFG_BUILD)_H_$(CFG_BUILD))
+	@$(call E, fingerprint: $@)
+	$(Q)$(CFG_PYTHON) $(S)src/etc/source-fingerprint.py record $@ $(S)
+
 # The main testing target. Tests lots of stuff.
-check: check-sanitycheck cleantmptestlogs cleantestlibs all check-stage2 tidy
+check: check-sanitycheck cleantmptestlogs cleantestlibs all $(SOURCE_FINGERPRINT) \
+	check-stage2 tidy
 	$(Q)$(CFG_PYTHON) $(S)src/etc/check-summary.py tmp/*.log
 
 # As above but don't bother running tidy.
-check-notidy: check-sanitycheck cleantmptestlogs cleantestlibs all check-stage2
+check-notidy: check-sanitycheck cleantmptestlogs cleantestlibs all $(SOURCE_FINGERPRINT) \
+	check-stage2
 	$(Q)$(CFG_PYTHON) $(S)src/etc/check-summary.py tmp/*.log
 
+# As above, but if nothing but src/test changed since the stage2 compiler
+# was built, only run the test suites whose tests changed, without
+# rebuilding the compiler. Unlike make, this looks at the contents of the
+# sources rather than at their modification times.
+check-tests: check-sanitycheck
+	$(Q)if $(CFG_PYTHON) $(S)src/etc/source-fingerprint.py only-tests-changed \
+	        $(SOURCE_FINGERPRINT) $(S); then \
+	    $(MAKE) NO_REBUILD=1 CHECK_TESTS_ONLY=1 cleantmptestlogs cleantestlibs check-stage2 && \
+	    $(CFG_PYTHON) $(S)src/etc/check-summary.py tmp/*.log; \
+	else \
+	    $(MAKE) check-notidy; \
+	fi
+
 # A slightly smaller set of tests for smoke testing.
 check-lite: check-sanitycheck cleantestlibs cleantmptestlogs \
 	$(foreach crate,$(TEST_TARGET_CRATES),check-stage2-$(crate)) \
@@ -193,12 +216,13 @@ check-ref: check-sanitycheck cleantestlibs cleantmptestlogs check-stage2-rpass \
 	$(Q)$(CFG_PYTHON) $(S)src/etc/check-summary.py tmp/*.log
 
 # Only check the docs.
-check-docs: check-sanitycheck cleantestlibs cleantmptestlogs check-stage2-docs
+check-docs: check-sanitycheck cleantestlibs cleantmptestlogs check-stage2-docs linkcheck
 	$(Q)$(CFG_PYTHON) $(S)src/etc/check-summary.py tmp/*.log
 
 # Some less critical tests that are not prone to breakage.
 # Not run as part of the normal test suite, but tested by bors on checkin.
-check-secondary: check-build-compiletest check-build-lexer-verifier check-lexer check-pretty
+check-secondary: check-build-compiletest check-build-lexer-verifier check-lexer check-pretty \
+	check-linkchecker
 
 .PHONY: check-sanitycheck
 
@@ -226,6 +250,20 @@ check-build-compiletest: \
 	check-stage1-build-compiletest \
 	check-stage2-build-compiletest
 
+# The unit tests of the linkchecker, built like the tool itself.
+LINKCHECKER_TEST_EXE = $(CFG_BUILD)/test/linkchecker-test$(X_$(CFG_BUILD))
+
+$(LINKCHECKER_TEST_EXE): $(TOOL_SOURCE_linkchecker) $(SREQ2_T_$(CFG_BUILD)_H_$(CFG_BUILD))
+	@$(call E, rustc: $@)
+	@mkdir -p $(@D)
+	$(Q)$(STAGE2_T_$(CFG_BUILD)_H_$(CFG_BUILD)) --test -o $@ $<
+
+check-linkchecker: $(LINKCHECKER_TEST_EXE)
+	@$(call E, run: $<)
+	$(Q)$(RPATH_VAR2_T_$(CFG_BUILD)_H_$(CFG_BUILD)) $<
+
+.PHONY: check-linkchecker
+
 .PHONY: cleantmptestlogs cleantestlibs
 
 cleantmptestlogs:
@@ -321,6 +359,8 @@ check-stage$(1)-T-$(2)-H-$(3)-exec: \
 	check-stage$(1)-T-$(2)-H-$(3)-debuginfo-gdb-exec \
 	check-stage$(1)-T-$(2)-H-$(3)-debuginfo-lldb-exec \
 	check-stage$(1)-T-$(2)-H-$(3)-codegen-exec \
+	check-stage$(1)-T-$(2)-H-$(3)-codegen-units-exec \
+	check-stage$(1)-T-$(2)-H-$(3)-json-diagnostics-exec \
 	check-stage$(1)-T-$(2)-H-$(3)-doc-exec \
 	check-stage$(1)-T-$(2)-H-$(3)-pretty-exec
 
@@ -413,6 +453,8 @@ $$(call TEST_OK_FILE,$(1),$(2),$(3),$(4)): \
 	$$(Q)touch $$@.start_time
 	$$(Q)$$(call CFG_RUN_TEST_$(2),$$<,$(1),$(2),$(3)) $$(TESTARGS) \
 	    --logfile $$(call TEST_LOG_FILE,$(1),$(2),$(3),$(4)) \
+	    $$(if $$(PLEASE_BENCH),--bench-results \
+	        $$(call TEST_BENCH_RESULTS_FILE,$(1),$(2),$(3),$(4))) \
 	    $$(call CRATE_TEST_EXTRA_ARGS,$(1),$(2),$(3),$(4)) \
 	    && touch -r $$@.start_time $$@ && rm $$@.start_time
 endef
@@ -485,6 +527,9 @@ DEBUGINFO_GDB_RS := $(wildcard $(S)src/test/debuginfo/*.rs)
 DEBUGINFO_LLDB_RS := $(wildcard $(S)src/test/debuginfo/*.rs)
 CODEGEN_RS := $(wildcard $(S)src/test/codegen/*.rs)
 CODEGEN_CC := $(wildcard $(S)src/test/codegen/*.cc)
+CODEGEN_UNITS_RS := $(wildcard $(S)src/test/codegen-units/*.rs)
+JSON_DIAGNOSTICS_RS := $(wildcard $(S)src/test/json-diagnostics/*.rs)
+JSON_DIAGNOSTICS_JSON := $(wildcard $(S)src/test/json-diagnostics/*.json)
 RUSTDOCCK_RS := $(wildcard $(S)src/test/rustdoc/*.rs)
 
 # perf tests are the same as bench tests only they run under
@@ -505,6 +550,8 @@ PRETTY_TESTS := $(PRETTY_RS)
 DEBUGINFO_GDB_TESTS := $(DEBUGINFO_GDB_RS)
 DEBUGINFO_LLDB_TESTS := $(DEBUGINFO_LLDB_RS)
 CODEGEN_TESTS := $(CODEGEN_RS) $(CODEGEN_CC)
+CODEGEN_UNITS_TESTS := $(CODEGEN_UNITS_RS)
+JSON_DIAGNOSTICS_TESTS := $(JSON_DIAGNOSTICS_RS) $(JSON_DIAGNOSTICS_JSON)
 RUSTDOCCK_TESTS := $(RUSTDOCCK_RS)
 
 CTEST_SRC_BASE_rpass = run-pass
@@ -572,6 +619,16 @@ CTEST_BUILD_BASE_codegen = codegen
 CTEST_MODE_codegen = codegen
 CTEST_RUNTOOL_codegen = $(CTEST_RUNTOOL)
 
+CTEST_SRC_BASE_codegen-units = codegen-units
+CTEST_BUILD_BASE_codegen-units = codegen-units
+CTEST_MODE_codegen-units = codegen-units
+CTEST_RUNTOOL_codegen-units = $(CTEST_RUNTOOL)
+
+CTEST_SRC_BASE_json-diagnostics = json-diagnostics
+CTEST_BUILD_BASE_json-diagnostics = json-diagnostics
+CTEST_MODE_json-diagnostics = json-diagnostics
+CTEST_RUNTOOL_json-diagnostics = $(CTEST_RUNTOOL)
+
 CTEST_SRC_BASE_rustdocck = rustdoc
 CTEST_BUILD_BASE_rustdocck = rustdoc
 CTEST_MODE_rustdocck = rustdoc
@@ -621,10 +678,18 @@ define DEF_CTEST_VARS
 # $(2) is the target triple to test
 # $(3) is the host triple to test
 
-# Prerequisites for compiletest tests
+CTEST_EXE$(1)_H_$(3) = $$(HBIN$(1)_H_$(3))/compiletest$$(X_$(3))
+
+# Prerequisites for compiletest tests. check-tests sets CHECK_TESTS_ONLY to
+# break the dependencies on compiletest and the compiler, so that a test
+# suite is only run again when its tests change.
+ifeq ($(CHECK_TESTS_ONLY),)
 TEST_SREQ$(1)_T_$(2)_H_$(3) = \
-	$$(HBIN$(1)_H_$(3))/compiletest$$(X_$(3)) \
+	$$(CTEST_EXE$(1)_H_$(3)) \
 	$$(SREQ$(1)_T_$(2)_H_$(3))
+else
+TEST_SREQ$(1)_T_$(2)_H_$(3) =
+endif
 
 # Rules for the cfail/rfail/rpass/bench/perf test runner
 
@@ -696,6 +761,8 @@ CTEST_DEPS_debuginfo-lldb_$(1)-T-$(2)-H-$(3) = $$(DEBUGINFO_LLDB_TESTS) \
                                                $(S)src/etc/lldb_batchmode.py \
                                                $(S)src/etc/lldb_rust_formatters.py
 CTEST_DEPS_codegen_$(1)-T-$(2)-H-$(3) = $$(CODEGEN_TESTS)
+CTEST_DEPS_codegen-units_$(1)-T-$(2)-H-$(3) = $$(CODEGEN_UNITS_TESTS)
+CTEST_DEPS_json-diagnostics_$(1)-T-$(2)-H-$(3) = $$(JSON_DIAGNOSTICS_TESTS)
 CTEST_DEPS_rustdocck_$(1)-T-$(2)-H-$(3) = $$(RUSTDOCCK_TESTS) \
         $$(HBIN$(1)_H_$(3))/rustdoc$$(X_$(3)) \
 	$(S)src/etc/htmldocck.py
@@ -743,9 +810,9 @@ ifeq ($$(CTEST_DONT_RUN_$(1)-T-$(2)-H-$(3)-$(4)),)
 $$(call TEST_OK_FILE,$(1),$(2),$(3),$(4)): \
 		$$(TEST_SREQ$(1)_T_$(2)_H_$(3)) \
                 $$(CTEST_DEPS_$(4)_$(1)-T-$(2)-H-$(3))
-	@$$(call E, run $(4) [$(2)]: $$<)
+	@$$(call E, run $(4) [$(2)]: $$(CTEST_EXE$(1)_H_$(3)))
 	$$(Q)touch $$@.start_time
-	$$(Q)$$(call CFG_RUN_CTEST_$(2),$(1),$$<,$(3)) \
+	$$(Q)$$(call CFG_RUN_CTEST_$(2),$(1),$$(CTEST_EXE$(1)_H_$(3)),$(3)) \
 		$$(CTEST_ARGS$(1)-T-$(2)-H-$(3)-$(4)) \
 		--logfile $$(call TEST_LOG_FILE,$(1),$(2),$(3),$(4)) \
                 && touch -r $$@.start_time $$@ && rm $$@.start_time
@@ -762,7 +829,7 @@ endif
 endef
 
 CTEST_NAMES = rpass rpass-valgrind rpass-full rfail-full cfail-full rfail cfail pfail \
-	bench perf debuginfo-gdb debuginfo-lldb codegen rustdocck
+	bench perf debuginfo-gdb debuginfo-lldb codegen codegen-units json-diagnostics rustdocck
 
 $(foreach host,$(CFG_HOST), \
  $(eval $(foreach target,$(CFG_TARGET), \
@@ -811,9 +878,9 @@ $$(call TEST_OK_FILE,$(1),$(2),$(3),$(4)): \
 	        $$(TEST_SREQ$(1)_T_$(2)_H_$(3)) \
 	        $$(PRETTY_DEPS_$(4)) \
 	        $$(PRETTY_DEPS$(1)_T_$(2)_H_$(3)_$(4))
-	@$$(call E, run pretty-rpass [$(2)]: $$<)
+	@$$(call E, run pretty-rpass [$(2)]: $$(CTEST_EXE$(1)_H_$(3)))
 	$$(Q)touch $$@.start_time
-	$$(Q)$$(call CFG_RUN_CTEST_$(2),$(1),$$<,$(3)) \
+	$$(Q)$$(call CFG_RUN_CTEST_$(2),$(1),$$(CTEST_EXE$(1)_H_$(3)),$(3)) \
 		$$(PRETTY_ARGS$(1)-T-$(2)-H-$(3)-$(4)) \
 		--logfile $$(call TEST_LOG_FILE,$(1),$(2),$(3),$(4)) \
                 && touch -r $$@.start_time $$@ && rm $$@.start_time
@@ -841,6 +908,23 @@ $(foreach host,$(CFG_HOST), \
   $(foreach stage,$(STAGES), \
    $(eval $(call DEF_RUSTDOC,$(stage),$(target),$(host))))))
 
+# The doc tests of a target other than the build triple are only run if
+# CFG_DOCTEST_RUNNER_<target> gives the command to run its executables with,
+# e.g. an emulator. They are linked with the linker of the target.
+
+define DEF_DOC_TEST_TARGET
+ifneq ($(1),$$(CFG_BUILD))
+ifneq ($$(CFG_DOCTEST_RUNNER_$(1)),)
+DOC_TEST_TARGET_ARGS_$(1) = --target $(1) \
+	--linker $$(call FIND_COMPILER,$$(LINK_$(1))) \
+	--runner "$$(CFG_DOCTEST_RUNNER_$(1))"
+endif
+endif
+endef
+
+$(foreach target,$(CFG_TARGET), \
+  $(eval $(call DEF_DOC_TEST_TARGET,$(target))))
+
 # Freestanding
 
 define DEF_DOC_TEST
@@ -859,12 +943,12 @@ else
 DOCTESTDEP_$(1)_$(2)_$(3)_$(4) = $$(DOCFILE_$(4))
 endif
 
-ifeq ($(2),$$(CFG_BUILD))
+ifneq ($$(filter $(2),$$(CFG_BUILD))$$(DOC_TEST_TARGET_ARGS_$(2)),)
 $$(call TEST_OK_FILE,$(1),$(2),$(3),doc-$(4)): $$(DOCTESTDEP_$(1)_$(2)_$(3)_$(4))
 	@$$(call E, run doc-$(4) [$(2)])
 	$$(Q)touch $$@.start_time
 	$$(Q)$$(RUSTDOC_$(1)_T_$(2)_H_$(3)) --cfg dox --test $$< \
-		--test-args "$$(TESTARGS)" && \
+		$$(DOC_TEST_TARGET_ARGS_$(2)) --test-args "$$(TESTARGS)" && \
 		touch -r $$@.start_time $$@ && rm $$@.start_time
 else
 $$(call TEST_OK_FILE,$(1),$(2),$(3),doc-$(4)):
@@ -897,13 +981,13 @@ endif
 check-stage$(1)-T-$(2)-H-$(3)-doc-crate-$(4)-exec: \
 	$$(call TEST_OK_FILE,$(1),$(2),$(3),doc-crate-$(4))
 
-ifeq ($(2),$$(CFG_BUILD))
+ifneq ($$(filter $(2),$$(CFG_BUILD))$$(DOC_TEST_TARGET_ARGS_$(2)),)
 $$(call TEST_OK_FILE,$(1),$(2),$(3),doc-crate-$(4)): $$(CRATEDOCTESTDEP_$(1)_$(2)_$(3)_$(4))
 	@$$(call E, run doc-crate-$(4) [$(2)])
 	$$(Q)touch $$@.start_time
 	$$(Q)CFG_LLVM_LINKAGE_FILE=$$(LLVM_LINKAGE_PATH_$(2)) \
-	    $$(RUSTDOC_$(1)_T_$(2)_H_$(3)) --test --cfg dox \
-	        $$(CRATEFILE_$(4)) --test-args "$$(TESTARGS)" && \
+	    $$(RUSTDOC_$(1)_T_$(2)_H_$(3)) --test --cfg dox $$(RUSTFLAGS_$(4)) \
+	        $$(CRATEFILE_$(4)) $$(DOC_TEST_TARGET_ARGS_$(2)) --test-args "$$(TESTARGS)" && \
 	        touch -r $$@.start_time $$@ && rm $$@.start_time
 else
 $$(call TEST_OK_FILE,$(1),$(2),$(3),doc-crate-$(4)):
@@ -941,6 +1025,8 @@ TEST_GROUPS = \
 	debuginfo-gdb \
 	debuginfo-lldb \
 	codegen \
+	codegen-units \
+	json-diagnostics \
 	doc \
 	$(foreach docname,$(DOC_NAMES),doc-$(docname)) \
 	pretty \
@@ -1053,9 +1139,18 @@ $(3)/test/run-make/%-$(1)-T-$(2)-H-$(3).ok: \
 	export INCLUDE := $$(CFG_MSVC_INCLUDE_PATH_$$(HOST_$(3)))
 $(3)/test/run-make/%-$(1)-T-$(2)-H-$(3).ok: \
 	export LIB := $$(CFG_MSVC_LIB_PATH_$$(HOST_$(3)))
+# Tests either have a Makefile, or an rmake.rs which doesn't need make
 $(3)/test/run-make/%-$(1)-T-$(2)-H-$(3).ok: \
 		$(S)src/test/run-make/%/Makefile \
 		$$(CSREQ$(1)_T_$(2)_H_$(3))
+	$$(RMAKE_RUN_$(1)_T_$(2)_H_$(3))
+$(3)/test/run-make/%-$(1)-T-$(2)-H-$(3).ok: \
+		$(S)src/test/run-make/%/rmake.rs \
+		$(S)src/test/run-make/tools.rs \
+		$$(CSREQ$(1)_T_$(2)_H_$(3))
+	$$(RMAKE_RUN_$(1)_T_$(2)_H_$(3))
+
+define RMAKE_RUN_$(1)_T_$(2)_H_$(3)
 	@rm -rf $(3)/test/run-make/$$*
 	@mkdir -p $(3)/test/run-make/$$*
 	$$(Q)touch $$@.start_time
@@ -1074,6 +1169,7 @@ $(3)/test/run-make/%-$(1)-T-$(2)-H-$(3).ok: \
 	    $$(S) \
 	    $(3)
 	@touch -r $$@.start_time $$@ && rm $$@.start_time
+endef
 else
 # FIXME #11094 - The above rule doesn't work right for multiple targets
 check-stage$(1)-T-$(2)-H-$(3)-rmake-exec:
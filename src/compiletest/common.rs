@@ -25,6 +25,8 @@ pub enum Mode {
     DebugInfoLldb,
     Codegen,
     Rustdoc,
+    CodegenUnits,
+    JsonDiagnostics,
 }
 
 impl FromStr for Mode {
@@ -41,6 +43,8 @@ impl FromStr for Mode {
           "debuginfo-gdb" => Ok(DebugInfoGdb),
           "codegen" => Ok(Codegen),
           "rustdoc" => Ok(Rustdoc),
+          "codegen-units" => Ok(CodegenUnits),
+          "json-diagnostics" => Ok(JsonDiagnostics),
           _ => Err(()),
         }
     }
@@ -59,6 +63,8 @@ impl fmt::Display for Mode {
             DebugInfoLldb => "debuginfo-lldb",
             Codegen => "codegen",
             Rustdoc => "rustdoc",
+            CodegenUnits => "codegen-units",
+            JsonDiagnostics => "json-diagnostics",
         }, f)
     }
 }
@@ -31,9 +31,10 @@ extern crate log;
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::process::Command;
 use getopts::{optopt, optflag, reqopt};
 use common::Config;
-use common::{Pretty, DebugInfoGdb, DebugInfoLldb};
+use common::{Mode, Pretty, DebugInfoGdb, DebugInfoLldb};
 use util::logv;
 
 pub mod procsrv;
@@ -123,6 +124,18 @@ pub fn parse_config(args: Vec<String> ) -> Config {
         None
     };
 
+    let mode: Mode = matches.opt_str("mode").unwrap().parse().ok().expect("invalid mode");
+    let gdb_version = if mode == DebugInfoGdb {
+        debugger_version_line(matches.opt_str("gdb-version"), "gdb")
+    } else {
+        matches.opt_str("gdb-version")
+    };
+    let lldb_version = if mode == DebugInfoLldb {
+        debugger_version_line(matches.opt_str("lldb-version"), "lldb")
+    } else {
+        matches.opt_str("lldb-version")
+    };
+
     Config {
         compile_lib_path: matches.opt_str("compile-lib-path").unwrap(),
         run_lib_path: matches.opt_str("run-lib-path").unwrap(),
@@ -136,7 +149,7 @@ pub fn parse_config(args: Vec<String> ) -> Config {
         build_base: opt_path(matches, "build-base"),
         aux_base: opt_path(matches, "aux-base"),
         stage_id: matches.opt_str("stage-id").unwrap(),
-        mode: matches.opt_str("mode").unwrap().parse().ok().expect("invalid mode"),
+        mode: mode,
         run_ignored: matches.opt_present("ignored"),
         filter: filter,
         logfile: matches.opt_str("logfile").map(|s| PathBuf::from(&s)),
@@ -145,8 +158,8 @@ pub fn parse_config(args: Vec<String> ) -> Config {
         target_rustcflags: matches.opt_str("target-rustcflags"),
         target: opt_str2(matches.opt_str("target")),
         host: opt_str2(matches.opt_str("host")),
-        gdb_version: extract_gdb_version(matches.opt_str("gdb-version")),
-        lldb_version: extract_lldb_version(matches.opt_str("lldb-version")),
+        gdb_version: extract_gdb_version(gdb_version),
+        lldb_version: extract_lldb_version(lldb_version),
         android_cross_path: opt_path(matches, "android-cross-path"),
         adb_path: opt_str2(matches.opt_str("adb-path")),
         adb_test_dir: format!("{}/{}",
@@ -184,6 +197,8 @@ pub fn log_config(config: &Config) {
                     opt_str(&config.target_rustcflags)));
     logv(c, format!("target: {}", config.target));
     logv(c, format!("host: {}", config.host));
+    logv(c, format!("gdb_version: {}", opt_str(&config.gdb_version)));
+    logv(c, format!("lldb_version: {}", opt_str(&config.lldb_version)));
     logv(c, format!("android-cross-path: {:?}",
                     config.android_cross_path.display()));
     logv(c, format!("adb_path: {:?}", config.adb_path));
@@ -262,6 +277,8 @@ pub fn test_opts(config: &Config) -> test::TestOpts {
         bench_benchmarks: true,
         nocapture: env::var("RUST_TEST_NOCAPTURE").is_ok(),
         color: test::AutoColor,
+        bench_opts: Default::default(),
+        bench_results: None,
     }
 }
 
@@ -340,24 +357,43 @@ pub fn make_test_closure(config: &Config, testfile: &Path) -> test::TestFn {
     }))
 }
 
+/// The version line the build system passed for `debugger`, or if it didn't
+/// pass one (e.g. when compiletest is run by hand), the first line of
+/// `debugger --version`.
+fn debugger_version_line(given: Option<String>, debugger: &str) -> Option<String> {
+    if let Some(ref line) = given {
+        if !line.trim().is_empty() {
+            return Some(line.clone());
+        }
+    }
+    match Command::new(debugger).arg("--version").output() {
+        Ok(output) => {
+            String::from_utf8_lossy(&output.stdout).lines().next().map(|l| l.to_owned())
+        }
+        Err(..) => None,
+    }
+}
+
 fn extract_gdb_version(full_version_line: Option<String>) -> Option<String> {
     match full_version_line {
         Some(ref full_version_line)
           if !full_version_line.trim().is_empty() => {
             let full_version_line = full_version_line.trim();
-
-            // used to be a regex "(^|[^0-9])([0-9]\.[0-9]+)"
-            for (pos, c) in full_version_line.char_indices() {
-                if !c.is_digit(10) { continue }
-                if pos + 2 >= full_version_line.len() { continue }
-                if full_version_line.char_at(pos + 1) != '.' { continue }
-                if !full_version_line.char_at(pos + 2).is_digit(10) { continue }
-                if pos > 0 && full_version_line.char_at_reverse(pos).is_digit(10) {
-                    continue
+            let bytes = full_version_line.as_bytes();
+            let is_digit = |pos: usize| pos < bytes.len() && (bytes[pos] as char).is_digit(10);
+
+            // used to be a regex "(^|[^0-9])([0-9]+\.[0-9]+)"
+            for pos in 0..bytes.len() {
+                if !is_digit(pos) { continue }
+                if pos > 0 && is_digit(pos - 1) { continue }
+                let mut end = pos;
+                while is_digit(end) {
+                    end += 1;
                 }
-                let mut end = pos + 3;
-                while end < full_version_line.len() &&
-                      full_version_line.char_at(end).is_digit(10) {
+                if end >= bytes.len() || bytes[end] != b'.' { continue }
+                if !is_digit(end + 1) { continue }
+                end += 1;
+                while is_digit(end) {
                     end += 1;
                 }
                 return Some(full_version_line[pos..end].to_owned());
@@ -21,7 +21,10 @@ use util;
 pub struct TestProps {
     // Lines that should be expected, in order, on standard out
     pub error_patterns: Vec<String> ,
-    // Extra flags to pass to the compiler
+    // Extra flags to pass to the compiler, from all the `compile-flags`
+    // lines in order. They come after the flags compiletest passes itself
+    // and the --host-rustcflags/--target-rustcflags defaults, so they
+    // override the defaults.
     pub compile_flags: Option<String>,
     // Extra flags to pass when the compiled code is run (such as --bench)
     pub run_flags: Option<String>,
@@ -71,8 +74,11 @@ pub fn load_props(testfile: &Path) -> TestProps {
            error_patterns.push(ep);
         }
 
-        if compile_flags.is_none() {
-            compile_flags = parse_compile_flags(ln);
+        if let Some(flags) = parse_compile_flags(ln) {
+            compile_flags = match compile_flags.take() {
+                Some(prev) => Some(format!("{} {}", prev, flags)),
+                None => Some(flags),
+            };
         }
 
         if run_flags.is_none() {
@@ -12,13 +12,14 @@ use self::TargetLocation::*;
 
 use common::Config;
 use common::{CompileFail, ParseFail, Pretty, RunFail, RunPass, RunPassValgrind};
-use common::{Codegen, DebugInfoLldb, DebugInfoGdb, Rustdoc};
+use common::{Codegen, DebugInfoLldb, DebugInfoGdb, Rustdoc, CodegenUnits, JsonDiagnostics};
 use errors;
 use header::TestProps;
 use header;
 use procsrv;
 use util::logv;
 
+use std::collections::HashSet;
 use std::env;
 use std::fmt;
 use std::fs::{self, File};
@@ -47,6 +48,7 @@ pub fn run(config: Config, testfile: &Path) {
     debug!("running {:?}", testfile.display());
     let props = header::load_props(&testfile);
     debug!("loaded props");
+    check_compile_flags(&config, &props, &testfile);
     match config.mode {
         CompileFail => run_cfail_test(&config, &props, &testfile),
         ParseFail => run_cfail_test(&config, &props, &testfile),
@@ -58,6 +60,38 @@ pub fn run(config: Config, testfile: &Path) {
         DebugInfoLldb => run_debuginfo_lldb_test(&config, &props, &testfile),
         Codegen => run_codegen_test(&config, &props, &testfile),
         Rustdoc => run_rustdoc_test(&config, &props, &testfile),
+        CodegenUnits => run_codegen_units_test(&config, &props, &testfile),
+        JsonDiagnostics => run_json_diagnostics_test(&config, &props, &testfile),
+    }
+}
+
+/// Flags that compiletest passes itself in the current mode. rustc takes
+/// the last occurrence of these, so a test that also passes them in its
+/// `compile-flags` would quietly put its output somewhere compiletest
+/// doesn't look, or build it for the wrong target.
+fn mandated_flags(config: &Config) -> Vec<&'static str> {
+    match config.mode {
+        Rustdoc => vec!["-o", "--output"],
+        Codegen => vec!["-o", "--out-dir", "--target", "--emit"],
+        Pretty => vec!["-o", "--out-dir", "--target", "--pretty", "--unpretty"],
+        _ => vec!["-o", "--out-dir", "--target"],
+    }
+}
+
+fn check_compile_flags(config: &Config, props: &TestProps, testfile: &Path) {
+    let mandated = mandated_flags(config);
+    for arg in split_maybe_args(&props.compile_flags) {
+        let conflict = mandated.iter().find(|&&flag| {
+            arg == flag ||
+            arg.starts_with(&format!("{}=", flag)) ||
+            // short options can have their value attached, e.g. `-ofoo`
+            (flag.len() == 2 && arg.starts_with(flag))
+        });
+        if let Some(flag) = conflict {
+            fatal(&format!("{}: `compile-flags` contains `{}`, but `{}` is passed by \
+                            compiletest in {} mode and can't be overridden by a test",
+                           testfile.display(), arg, flag, config.mode));
+        }
     }
 }
 
@@ -295,6 +329,8 @@ actual:\n\
 ------------------------------------------\n\
 \n",
                      expected, actual);
+            println!("note: tests that can't be pretty-printed faithfully can opt \
+                      out with `// ignore-pretty`");
             panic!();
         }
     }
@@ -342,7 +378,7 @@ fn run_debuginfo_gdb_test(config: &Config, props: &TestProps, testfile: &Path) {
         commands,
         check_lines,
         breakpoint_lines
-    } = parse_debugger_commands(testfile, "gdb");
+    } = parse_debugger_commands(testfile, "gdb", config.gdb_version.as_ref().map(|v| &v[..]));
     let mut cmds = commands.join("\n");
 
     // compile test file (it should have 'compile-flags:-g' in the header)
@@ -634,7 +670,7 @@ fn run_debuginfo_lldb_test(config: &Config, props: &TestProps, testfile: &Path)
         check_lines,
         breakpoint_lines,
         ..
-    } = parse_debugger_commands(testfile, "lldb");
+    } = parse_debugger_commands(testfile, "lldb", config.lldb_version.as_ref().map(|v| &v[..]));
 
     // Write debugger script:
     // We don't want to hang when calling `quit` while the process is still running
@@ -740,10 +776,19 @@ struct DebuggerCommands {
     breakpoint_lines: Vec<usize>,
 }
 
-fn parse_debugger_commands(file_path: &Path, debugger_prefix: &str)
+/// Parses the `<debugger>-command` and `<debugger>-check` lines of a test.
+///
+/// Where the output differs between debugger versions, a test can also give
+/// `<debugger><major>-command` and `<debugger><major>-check` lines, e.g.
+/// `gdb7-check`, which are only used if the major version of the debugger
+/// is known and matches.
+fn parse_debugger_commands(file_path: &Path,
+                           debugger_prefix: &str,
+                           debugger_version: Option<&str>)
                            -> DebuggerCommands {
     let command_directive = format!("{}-command", debugger_prefix);
     let check_directive = format!("{}-check", debugger_prefix);
+    let major_version = debugger_version.map(|v| v.split('.').next().unwrap());
 
     let mut breakpoint_lines = vec!();
     let mut commands = vec!();
@@ -768,6 +813,19 @@ fn parse_debugger_commands(file_path: &Path, debugger_prefix: &str)
                         &check_directive).map(|cmd| {
                     check_lines.push(cmd)
                 });
+
+                // If the version is unknown, e.g. for an lldb that isn't
+                // Apple's, all the versioned directives are skipped.
+                if let Some((version, kind, value)) =
+                        parse_versioned_debugger_directive(&line, debugger_prefix) {
+                    if major_version == Some(version) {
+                        if kind == "command" {
+                            commands.push(value)
+                        } else {
+                            check_lines.push(value)
+                        }
+                    }
+                }
             }
             Err(e) => {
                 fatal(&format!("Error while parsing debugger commands: {}", e))
@@ -783,6 +841,29 @@ fn parse_debugger_commands(file_path: &Path, debugger_prefix: &str)
     }
 }
 
+/// Parses a `<debugger><major>-command:` or `<debugger><major>-check:`
+/// directive into the major version, the kind of directive, and its value.
+fn parse_versioned_debugger_directive<'a>(line: &'a str, debugger_prefix: &str)
+                                         -> Option<(&'a str, &'static str, String)> {
+    let start = match line.find(debugger_prefix) {
+        Some(i) => i + debugger_prefix.len(),
+        None => return None,
+    };
+    let rest = &line[start..];
+    let digits = rest.chars().take_while(|c| c.is_digit(10)).count();
+    if digits == 0 {
+        return None;
+    }
+    let (version, rest) = rest.split_at(digits);
+    for kind in &["command", "check"] {
+        let directive = format!("-{}:", kind);
+        if rest.starts_with(&directive) {
+            return Some((version, *kind, rest[directive.len()..].to_owned()));
+        }
+    }
+    None
+}
+
 fn cleanup_debug_info_options(options: &Option<String>) -> Option<String> {
     if options.is_none() {
         return None;
@@ -1206,6 +1287,7 @@ fn compose_and_run_compiler(config: &Config, props: &TestProps,
     for rel_ab in &props.aux_builds {
         let abs_ab = config.aux_base.join(rel_ab);
         let aux_props = header::load_props(&abs_ab);
+        check_compile_flags(config, &aux_props, &abs_ab);
         let mut crate_type = if aux_props.no_prefer_dynamic {
             Vec::new()
         } else {
@@ -1681,9 +1763,14 @@ fn compile_test_and_save_ir(config: &Config, props: &TestProps,
     compose_and_run_compiler(config, props, testfile, args, None)
 }
 
+fn filecheck_path(config: &Config) -> PathBuf {
+    let name = format!("FileCheck{}", env::consts::EXE_SUFFIX);
+    config.llvm_bin_path.as_ref().unwrap().join(name)
+}
+
 fn check_ir_with_filecheck(config: &Config, testfile: &Path) -> ProcRes {
     let irfile = output_base_name(config, testfile).with_extension("ll");
-    let prog = config.llvm_bin_path.as_ref().unwrap().join("FileCheck");
+    let prog = filecheck_path(config);
     let proc_args = ProcArgs {
         // FIXME (#9639): This needs to handle non-utf8 paths
         prog: prog.to_str().unwrap().to_owned(),
@@ -1698,6 +1785,11 @@ fn run_codegen_test(config: &Config, props: &TestProps, testfile: &Path) {
     if config.llvm_bin_path.is_none() {
         fatal("missing --llvm-bin-path");
     }
+    if !filecheck_path(config).exists() {
+        fatal(&format!("`{}` does not exist; codegen tests are checked with LLVM's \
+                        FileCheck, which --llvm-bin-path has to point at",
+                       filecheck_path(config).display()));
+    }
 
     let mut proc_res = compile_test_and_save_ir(config, props, testfile);
     if !proc_res.status.success() {
@@ -1739,3 +1831,87 @@ fn run_rustdoc_test(config: &Config, props: &TestProps, testfile: &Path) {
         fatal_proc_rec("htmldocck failed!", &res);
     }
 }
+
+// The test is compiled with `-Z json-errors`, and the diagnostics it prints
+// have to be the contents of the `.json` file next to it, line by line. The
+// directory of the test is written `$DIR` in the expected output, so it
+// doesn't depend on where the source tree is.
+fn run_json_diagnostics_test(config: &Config, props: &TestProps, testfile: &Path) {
+    let aux_dir = aux_output_dir_name(config, testfile);
+    let extra_args = vec!["-L".to_owned(),
+                          aux_dir.to_str().unwrap().to_owned(),
+                          "-Z".to_owned(),
+                          "json-errors".to_owned()];
+    let args = make_compile_args(config,
+                                 props,
+                                 extra_args,
+                                 |a, b| TargetLocation::ThisFile(make_exe_name(a, b)),
+                                 testfile);
+    let proc_res = compose_and_run_compiler(config, props, testfile, args, None);
+
+    let expected_file = testfile.with_extension("json");
+    let mut expected = String::new();
+    if let Err(e) = File::open(&expected_file).and_then(|mut f| f.read_to_string(&mut expected)) {
+        fatal_proc_rec(&format!("failed to read {}: {}", expected_file.display(), e),
+                       &proc_res);
+    }
+
+    // The path is escaped like any other JSON string.
+    let dir = testfile.parent().unwrap().to_str().unwrap().replace(r"\", r"\\");
+    let actual = proc_res.stderr.replace(&dir, "$DIR");
+
+    let expected_lines = expected.lines().collect::<Vec<_>>();
+    let actual_lines = actual.lines().collect::<Vec<_>>();
+    if expected_lines != actual_lines {
+        println!("expected diagnostics:\n{}", expected);
+        println!("actual diagnostics:\n{}", actual);
+        for (i, (e, a)) in expected_lines.iter().zip(&actual_lines).enumerate() {
+            if e != a {
+                println!("first difference on line {}:\n-{}\n+{}", i + 1, e, a);
+                break;
+            }
+        }
+        fatal_proc_rec("diagnostics differ from the expected JSON", &proc_res);
+    }
+}
+
+fn run_codegen_units_test(config: &Config, props: &TestProps, testfile: &Path) {
+    let proc_res = compile_test(config, props, testfile);
+
+    if !proc_res.status.success() {
+        fatal_proc_rec("compilation failed!", &proc_res);
+    }
+
+    check_no_compiler_crash(&proc_res);
+
+    let prefix = "TRANS_ITEM ";
+
+    let actual: HashSet<String> = proc_res.stdout
+                                          .lines()
+                                          .filter(|line| line.starts_with(prefix))
+                                          .map(|line| line[prefix.len()..].trim().to_owned())
+                                          .collect();
+
+    let expected: HashSet<String> = errors::load_errors(testfile)
+                                           .into_iter()
+                                           .filter(|e| e.kind == "trans_item")
+                                           .map(|e| e.msg)
+                                           .collect();
+
+    let mut missing: Vec<_> = expected.difference(&actual).cloned().collect();
+    let mut unexpected: Vec<_> = actual.difference(&expected).cloned().collect();
+    missing.sort();
+    unexpected.sort();
+
+    if !missing.is_empty() || !unexpected.is_empty() {
+        println!("Missing translation items:");
+        for item in &missing {
+            println!("    {}", item);
+        }
+        println!("Unexpected translation items:");
+        for item in &unexpected {
+            println!("    {}", item);
+        }
+        fatal_proc_rec("translation items differ from the expected set", &proc_res);
+    }
+}
@@ -0,0 +1,99 @@
+#!/usr/bin/env python
+#
+# Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+# file at the top-level directory of this distribution and at
+# http://rust-lang.org/COPYRIGHT.
+#
+# Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+# http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+# <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+# option. This file may not be copied, modified, or distributed
+# except according to those terms.
+
+# Local build statistics, recorded when make runs with BUILD_STATS=1.
+#
+# Every record is one tab-separated line of the log, which lives in the
+# build directory and is never sent anywhere:
+#
+#   build <unix time> <make goals>
+#   step <name> <seconds>
+#
+# A step belongs to the last build recorded before it. Steps make finds
+# up to date aren't run, so they aren't recorded either; the steps of a
+# build are the ones it had to rebuild.
+#
+# usage: build-stats.py build LOG GOALS
+#        build-stats.py step LOG NAME START_STAMP
+#        build-stats.py summary LOG
+
+import os
+import sys
+import time
+
+SHOWN_BUILDS = 10
+
+
+def append(log, fields):
+    with open(log, 'a') as fd:
+        fd.write('\t'.join(fields) + '\n')
+
+
+def read_builds(log):
+    builds = []
+    if not os.path.exists(log):
+        return builds
+    with open(log) as fd:
+        for line in fd:
+            fields = line.rstrip('\n').split('\t')
+            if fields[0] == 'build' and len(fields) == 3:
+                builds.append((float(fields[1]), fields[2], []))
+            elif fields[0] == 'step' and len(fields) == 3 and builds:
+                builds[-1][2].append((fields[1], float(fields[2])))
+    return builds
+
+
+def summarize(log):
+    builds = read_builds(log)
+    if not builds:
+        print("no build statistics in %s, build with BUILD_STATS=1 first" % log)
+        return
+
+    print("last %d of %d builds:" % (min(SHOWN_BUILDS, len(builds)), len(builds)))
+    for start, goals, steps in builds[-SHOWN_BUILDS:]:
+        total = sum(secs for _, secs in steps)
+        print("  %s  make %s: %d steps rebuilt in %.1fs" %
+              (time.strftime('%Y-%m-%d %H:%M', time.localtime(start)),
+               goals, len(steps), total))
+    print("")
+
+    # The last run of each step, against the mean of its earlier runs.
+    runs = {}
+    for _, _, steps in builds:
+        for name, secs in steps:
+            runs.setdefault(name, []).append(secs)
+    print("%-50s %5s %9s %9s %7s" % ("step", "runs", "last", "before", "change"))
+    for name in sorted(runs):
+        times = runs[name]
+        last = times[-1]
+        if len(times) == 1:
+            print("%-50s %5d %8.1fs %9s %7s" % (name, 1, last, "-", "-"))
+            continue
+        before = sum(times[:-1]) / len(times[:-1])
+        change = (last - before) / before * 100 if before > 0 else 0
+        print("%-50s %5d %8.1fs %8.1fs %+6.0f%%" %
+              (name, len(times), last, before, change))
+
+
+if __name__ == '__main__':
+    if len(sys.argv) == 4 and sys.argv[1] == 'build':
+        append(sys.argv[2], ['build', '%d' % time.time(), sys.argv[3] or 'all'])
+    elif len(sys.argv) == 5 and sys.argv[1] == 'step':
+        # The step started when make touched its start stamp.
+        secs = time.time() - os.path.getmtime(sys.argv[4])
+        append(sys.argv[2], ['step', sys.argv[3], '%.2f' % secs])
+    elif len(sys.argv) == 3 and sys.argv[1] == 'summary':
+        summarize(sys.argv[2])
+    else:
+        sys.stderr.write("usage: build-stats.py (build LOG GOALS | step LOG NAME START_STAMP "
+                         "| summary LOG)\n")
+        sys.exit(1)
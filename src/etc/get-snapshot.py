@@ -63,7 +63,11 @@ def main(argv):
         print("determined most recent snapshot: " + snap)
 
         if (not os.path.exists(dl)):
-            get_url_to_file(url, dl)
+            try:
+                get_url_to_file(url, dl)
+            except Exception as e:
+                raise Exception("%s\nwithout network access, download the snapshot "
+                                "elsewhere and put it at %s" % (e, dl))
 
         if (snap_filename_hash_part(snap) == hash_file(dl)):
             print("got download with ok hash")
@@ -14,6 +14,11 @@ import sys
 
 target_triple = sys.argv[14]
 
+# Tests with an `rmake.rs` are compiled against `run-make/tools.rs` and run
+# directly, without make or a unix shell, so their environment has to use
+# native paths.
+rmake = os.path.exists(os.path.join(sys.argv[1], 'rmake.rs'))
+
 def normalize_path(v):
     """msys1/msys2 automatically converts `/abs/path1:/abs/path2` into
     `c:\real\abs\path1;c:\real\abs\path2` (semicolons) if shell thinks
@@ -32,9 +37,9 @@ def normalize_path(v):
 
 
 def putenv(name, value):
-    if os.name == 'nt':
+    if os.name == 'nt' and not rmake:
         value = normalize_path(value)
-    os.putenv(name, value)
+    os.environ[name] = value
 
 
 def convert_path_spec(name, value):
@@ -54,10 +59,10 @@ putenv('TARGET_RPATH_DIR', os.path.abspath(sys.argv[11]))
 putenv('RUST_BUILD_STAGE', sys.argv[12])
 putenv('S', os.path.abspath(sys.argv[13]))
 putenv('PYTHON', sys.executable)
-os.putenv('TARGET', target_triple)
+os.environ['TARGET'] = target_triple
 
 if 'msvc' in target_triple:
-    os.putenv('IS_MSVC', '1')
+    os.environ['IS_MSVC'] = '1'
 
 if filt not in sys.argv[1]:
     sys.exit(0)
@@ -72,11 +77,35 @@ if path[-1] == '/':
     # msys2 seems to fix this problem.
     path = path[:-1]
 
-proc = subprocess.Popen([make, '-C', path],
-                        stdout=subprocess.PIPE,
-                        stderr=subprocess.PIPE)
-out, err = proc.communicate()
-i = proc.wait()
+
+def run(args, cwd=None, env=None):
+    proc = subprocess.Popen(args,
+                            cwd=cwd,
+                            env=env,
+                            stdout=subprocess.PIPE,
+                            stderr=subprocess.PIPE)
+    out, err = proc.communicate()
+    return proc.wait(), out, err
+
+if rmake:
+    tmpdir = os.environ['TMPDIR']
+    exe = os.path.join(tmpdir, 'rmake.exe' if os.name == 'nt' else 'rmake')
+    tools = os.path.join(os.environ['S'], 'src', 'test', 'run-make', 'tools.rs')
+    # the same as $(HOST_RPATH_ENV) in tools.mk
+    rustc_env = dict(os.environ)
+    lib_path_var = os.environ['LD_LIB_PATH_ENVVAR']
+    rustc_env[lib_path_var] = os.pathsep.join([os.environ['HOST_RPATH_DIR'],
+                                               rustc_env.get(lib_path_var, '')])
+    i, out, err = run([os.environ['RUSTC'], tools, '--out-dir', tmpdir],
+                      env=rustc_env)
+    if i == 0:
+        i, out, err = run([os.environ['RUSTC'], os.path.join(path, 'rmake.rs'),
+                           '-L', tmpdir, '-o', exe],
+                          env=rustc_env)
+    if i == 0:
+        i, out, err = run([exe], cwd=path)
+else:
+    i, out, err = run([make, '-C', path])
 
 if i != 0:
     print """\
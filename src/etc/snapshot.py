@@ -15,6 +15,7 @@ import glob
 import tarfile
 import shutil
 import subprocess
+import time
 import distutils.spawn
 
 try:
@@ -145,23 +146,34 @@ def local_rev_committer_date():
     return local_rev_info("ci")
 
 
-def get_url_to_file(u, f):
+def get_url_to_file(u, f, attempts=3):
     # no security issue, just to stop partial download leaving a stale file
     tmpf = f + '.tmp'
 
-    returncode = -1
-    if distutils.spawn.find_executable("curl"):
-        returncode = subprocess.call(["curl", "-o", tmpf, u])
-    elif distutils.spawn.find_executable("wget"):
-        returncode = subprocess.call(["wget", "-O", tmpf, u])
+    # curl and wget both pick up the proxy from `http_proxy`/`https_proxy`
+    for attempt in range(attempts):
+        if attempt > 0:
+            delay = 2 ** attempt
+            print("download failed, retrying in %d seconds" % delay)
+            time.sleep(delay)
+
+        returncode = -1
+        if distutils.spawn.find_executable("curl"):
+            returncode = subprocess.call(["curl", "-f", "-o", tmpf, u])
+        elif distutils.spawn.find_executable("wget"):
+            returncode = subprocess.call(["wget", "-O", tmpf, u])
+        else:
+            raise Exception("neither curl nor wget was found to fetch " + u)
 
-    if returncode != 0:
+        if returncode == 0:
+            os.rename(tmpf, f)
+            return
         try:
             os.unlink(tmpf)
         except OSError:
             pass
-        raise Exception("failed to fetch url")
-    os.rename(tmpf, f)
+
+    raise Exception("failed to fetch url " + u)
 
 
 def snap_filename_hash_part(snap):
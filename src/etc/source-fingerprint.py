@@ -0,0 +1,123 @@
+#!/usr/bin/env python
+#
+# Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+# file at the top-level directory of this distribution and at
+# http://rust-lang.org/COPYRIGHT.
+#
+# Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+# http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+# <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+# option. This file may not be copied, modified, or distributed
+# except according to those terms.
+
+# Fingerprints of the sources the compiler is built from, for
+# `make check-tests`.
+#
+# `record` writes the hash of every file of the source tree to the stamp
+# when the stage2 compiler has been built. `only-tests-changed` exits with
+# 0 if the contents of the files outside of src/test are the same as then,
+# so that the tests can be run without rebuilding the compiler even if make
+# would rebuild it because of the modification times, e.g. after switching
+# branches back and forth.
+#
+# The hash of a file is only recomputed if its size or modification time
+# differs from the ones in the stamp, so that checking the LLVM sources
+# doesn't take long.
+#
+# usage: source-fingerprint.py record STAMP SRC_DIR
+#        source-fingerprint.py only-tests-changed STAMP SRC_DIR
+
+import hashlib
+import json
+import os
+import sys
+
+# Relative to the source directory. Everything under src/ but the tests
+# is part of the compiler.
+SOURCES = ['configure', 'Makefile.in', 'mk', 'src']
+TESTS = os.path.join('src', 'test')
+
+
+def walk(src_dir):
+    for source in SOURCES:
+        path = os.path.join(src_dir, source)
+        if os.path.isfile(path):
+            yield source
+            continue
+        for dirpath, dirnames, filenames in os.walk(path):
+            rel = os.path.relpath(dirpath, src_dir)
+            dirnames[:] = [d for d in dirnames
+                           if d != '.git' and os.path.join(rel, d) != TESTS]
+            for filename in filenames:
+                full = os.path.join(dirpath, filename)
+                if os.path.isfile(full):
+                    yield os.path.relpath(full, src_dir)
+
+
+def file_hash(path):
+    sha = hashlib.sha1()
+    with open(path, 'rb') as fd:
+        for chunk in iter(lambda: fd.read(1 << 16), b''):
+            sha.update(chunk)
+    return sha.hexdigest()
+
+
+def fingerprint(src_dir, previous):
+    files = {}
+    for name in walk(src_dir):
+        st = os.stat(os.path.join(src_dir, name))
+        old = previous.get(name)
+        if old is not None and old[0] == st.st_size and old[1] == st.st_mtime:
+            files[name] = old
+        else:
+            files[name] = [st.st_size, st.st_mtime, file_hash(os.path.join(src_dir, name))]
+    return files
+
+
+def read_stamp(stamp):
+    if not os.path.exists(stamp):
+        return None
+    with open(stamp) as fd:
+        try:
+            return json.load(fd)
+        except ValueError:
+            return None
+
+
+def write_stamp(stamp, files):
+    with open(stamp + '.tmp', 'w') as fd:
+        json.dump(files, fd)
+    os.rename(stamp + '.tmp', stamp)
+
+
+def only_tests_changed(stamp, src_dir):
+    previous = read_stamp(stamp)
+    if previous is None:
+        print("cfg: no fingerprint of the last build, running all of `make check`")
+        return False
+
+    files = fingerprint(src_dir, previous)
+    changed = [name for name in set(files) | set(previous)
+               if name not in files or name not in previous or
+               files[name][2] != previous[name][2]]
+    if changed:
+        print("cfg: %s changed since the last build, running all of `make check`"
+              % sorted(changed)[0])
+        return False
+
+    # Remember the new modification times of the unchanged files, they
+    # needn't be hashed again next time.
+    write_stamp(stamp, files)
+    print("cfg: only src/test changed since the last build, not rebuilding the compiler")
+    return True
+
+
+if __name__ == '__main__':
+    if len(sys.argv) == 4 and sys.argv[1] == 'record':
+        write_stamp(sys.argv[2], fingerprint(sys.argv[3], read_stamp(sys.argv[2]) or {}))
+    elif len(sys.argv) == 4 and sys.argv[1] == 'only-tests-changed':
+        sys.exit(0 if only_tests_changed(sys.argv[2], sys.argv[3]) else 1)
+    else:
+        sys.stderr.write("usage: source-fingerprint.py (record | only-tests-changed) "
+                         "STAMP SRC_DIR\n")
+        sys.exit(1)
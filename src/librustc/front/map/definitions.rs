@@ -11,7 +11,9 @@
 use metadata::cstore::LOCAL_CRATE;
 use middle::def_id::{DefId, DefIndex};
 use rustc_data_structures::fnv::FnvHashMap;
+use rustc_data_structures::stable_hasher::StableHasher;
 use rustc_front::hir;
+use std::hash::{Hash, Hasher};
 use syntax::ast;
 use syntax::parse::token::InternedString;
 use util::nodemap::NodeMap;
@@ -121,6 +123,11 @@ impl Definitions {
         make_def_path(index, |p| self.def_key(p))
     }
 
+    /// A hash of `def_path(index)`, see `def_path_hash`.
+    pub fn def_path_hash(&self, index: DefIndex) -> u64 {
+        def_path_hash(&self.def_path(index))
+    }
+
     pub fn opt_def_index(&self, node: ast::NodeId) -> Option<DefIndex> {
         self.node_map.get(&node).cloned()
     }
@@ -233,6 +240,48 @@ impl DefPathData {
     pub fn to_string(&self) -> String {
         self.as_interned_str().to_string()
     }
+
+    /// Hashes the kind of the data and the string of its name, rather than
+    /// the interner index of the name that `#[derive(Hash)]` uses, which
+    /// differs from one compilation session to the next.
+    pub fn stable_hash<H: Hasher>(&self, state: &mut H) {
+        use self::DefPathData::*;
+        let kind: u8 = match *self {
+            CrateRoot => 0,
+            InlinedRoot(..) => 1,
+            Misc => 2,
+            Impl => 3,
+            Type(..) => 4,
+            Mod(..) => 5,
+            Value(..) => 6,
+            MacroDef(..) => 7,
+            ClosureExpr => 8,
+            TypeParam(..) => 9,
+            LifetimeDef(..) => 10,
+            EnumVariant(..) => 11,
+            PositionalField => 12,
+            Field(..) => 13,
+            StructCtor => 14,
+            Initializer => 15,
+            Binding(..) => 16,
+            DetachedCrate(..) => 17,
+        };
+        kind.hash(state);
+        self.as_interned_str()[..].hash(state);
+    }
+}
+
+/// A hash of a `DefPath` that stays the same across compilation sessions
+/// as long as the path does. Unlike a `DefIndex` or a `NodeId`, it doesn't
+/// change when unrelated items are added, removed or reordered, so it can
+/// be used to identify a definition from one compilation to the next.
+pub fn def_path_hash(path: &DefPath) -> u64 {
+    let mut state = StableHasher::new();
+    for data in path {
+        data.data.stable_hash(&mut state);
+        data.disambiguator.hash(&mut state);
+    }
+    state.finish()
 }
 
 pub fn make_def_path<FN>(start_index: DefIndex, mut get_key: FN) -> DefPath
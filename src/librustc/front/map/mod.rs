@@ -285,6 +285,11 @@ impl<'ast> Map<'ast> {
         self.definitions.borrow().def_path(def_id.index)
     }
 
+    pub fn def_path_hash(&self, def_id: DefId) -> u64 {
+        assert!(def_id.is_local());
+        self.definitions.borrow().def_path_hash(def_id.index)
+    }
+
     pub fn local_def_id(&self, node: NodeId) -> DefId {
         self.opt_local_def_id(node).unwrap_or_else(|| {
             panic!("local_def_id: no entry for `{}`, which has a map of `{:?}`",
@@ -418,6 +423,18 @@ impl<'ast> Map<'ast> {
         }
     }
 
+    /// Returns the NodeId of the module containing `id`, which is
+    /// `CRATE_NODE_ID` for the items at the top level of the crate.
+    pub fn get_module_parent(&self, id: NodeId) -> NodeId {
+        match self.walk_parent_nodes(id, |node| match *node {
+            NodeItem(&Item { node: ItemMod(_), .. }) => true,
+            _ => false,
+        }) {
+            Ok(id) => id,
+            Err(id) => id,
+        }
+    }
+
     /// Returns the nearest enclosing scope. A scope is an item or block.
     /// FIXME it is not clear to me that all items qualify as scopes - statics
     /// and associated types probably shouldn't, for example. Behaviour in this
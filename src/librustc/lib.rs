@@ -125,6 +125,7 @@ pub mod middle {
     pub mod lang_items;
     pub mod liveness;
     pub mod mem_categorization;
+    pub mod module_times;
     pub mod pat_util;
     pub mod privacy;
     pub mod reachable;
@@ -132,6 +133,7 @@ pub mod middle {
     pub mod recursion_limit;
     pub mod resolve_lifetime;
     pub mod stability;
+    pub mod stable_hash;
     pub mod subst;
     pub mod traits;
     pub mod ty;
@@ -148,11 +150,11 @@ pub mod lint;
 
 pub mod util {
     pub use rustc_back::sha2;
+    pub use syntax::util::lev_distance;
 
     pub mod common;
     pub mod ppaux;
     pub mod nodemap;
-    pub mod lev_distance;
     pub mod num;
     pub mod fs;
 }
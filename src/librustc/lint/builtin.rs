@@ -117,6 +117,37 @@ declare_lint! {
     Allow,
     "detects trivial casts of numeric types which could be removed"
 }
+
+declare_lint! {
+    pub UNEXPECTED_CFGS,
+    Allow,
+    "detects `cfg` names and values that are never set"
+}
+
+declare_lint! {
+    pub MODULE_AND_STRUCT_WITH_SAME_NAME,
+    Warn,
+    "detects a module and a struct with the same name, which will become an error"
+}
+
+declare_lint! {
+    pub IMPORTS_SHADOWING_PRELUDE,
+    Warn,
+    "detects imports hiding an item of the prelude behind an unrelated item of the same name"
+}
+
+declare_lint! {
+    pub GLOB_AND_EXPLICIT_IMPORT_COLLISIONS,
+    Warn,
+    "detects names a glob import and an explicit import bring in as unrelated items"
+}
+
+declare_lint! {
+    pub UNUSED_REEXPORTS,
+    Allow,
+    "detects `pub use` re-exports that are neither used in their crate nor part of its public API"
+}
+
 /// Does nothing as a lint pass, but registers some `Lint`s
 /// which are used by other parts of the compiler.
 #[derive(Copy, Clone)]
@@ -141,6 +172,11 @@ impl LintPass for HardwiredLints {
             FAT_PTR_TRANSMUTES,
             TRIVIAL_CASTS,
             TRIVIAL_NUMERIC_CASTS,
+            UNEXPECTED_CFGS,
+            MODULE_AND_STRUCT_WITH_SAME_NAME,
+            IMPORTS_SHADOWING_PRELUDE,
+            GLOB_AND_EXPLICIT_IMPORT_COLLISIONS,
+            UNUSED_REEXPORTS,
             CONST_ERR
         )
     }
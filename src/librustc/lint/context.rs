@@ -27,12 +27,13 @@ use self::TargetLint::*;
 
 use middle::privacy::ExportedItems;
 use middle::ty::{self, Ty};
-use session::{early_error, Session};
+use session::{early_error, Session, SessionLintNote};
 use lint::{Level, LevelSource, Lint, LintId, LintArray, LintPass};
 use lint::{EarlyLintPass, EarlyLintPassObject, LateLintPass, LateLintPassObject};
+#[cfg_attr(not(stage0), allow(imports_shadowing_prelude))] // `lint::Default`
 use lint::{Default, CommandLine, Node, Allow, Warn, Deny, Forbid};
 use lint::builtin;
-use util::nodemap::FnvHashMap;
+use util::nodemap::{FnvHashMap, FnvHashSet};
 
 use std::cell::RefCell;
 use std::cmp;
@@ -73,8 +74,18 @@ pub struct LintStore {
     /// is true if the lint group was added by a plugin.
     lint_groups: FnvHashMap<&'static str, (Vec<LintId>, bool)>,
 
+    /// Lints for code that is accepted for now, but will become an error.
+    future_incompatible: FnvHashSet<LintId>,
+
     /// Maximum level a lint can be
     lint_cap: Option<Level>,
+
+    /// Maximum level a lint can be inside generated code, i.e. items marked
+    /// `#[automatically_derived]`, as set by `#![generated_code_lints]`.
+    generated_code_cap: Option<Level>,
+
+    /// Number of generated items the lint traversal is currently inside.
+    generated_code_depth: usize,
 }
 
 /// The targed of the `by_name` map, which accounts for renaming/deprecation.
@@ -107,6 +118,11 @@ impl LintStore {
         if let Some(cap) = self.lint_cap {
             lvlsrc.0 = cmp::min(lvlsrc.0, cap);
         }
+        if self.generated_code_depth > 0 {
+            if let Some(cap) = self.generated_code_cap {
+                lvlsrc.0 = cmp::min(lvlsrc.0, cap);
+            }
+        }
         if lvlsrc.0 == Allow {
             self.levels.remove(&lint);
         } else {
@@ -122,7 +138,10 @@ impl LintStore {
             by_name: FnvHashMap(),
             levels: FnvHashMap(),
             lint_groups: FnvHashMap(),
+            future_incompatible: FnvHashSet(),
             lint_cap: None,
+            generated_code_cap: None,
+            generated_code_depth: 0,
         }
     }
 
@@ -199,6 +218,19 @@ impl LintStore {
         }
     }
 
+    /// Registers lints for code that will become an error. They get a note
+    /// saying so, and make up the `future_incompatible` lint group.
+    pub fn register_future_incompatible(&mut self,
+                                        sess: Option<&Session>,
+                                        lints: Vec<LintId>) {
+        self.future_incompatible.extend(lints.iter().cloned());
+        self.register_group(sess, false, "future_incompatible", lints);
+    }
+
+    pub fn is_future_incompatible(&self, lint: LintId) -> bool {
+        self.future_incompatible.contains(&lint)
+    }
+
     pub fn register_renamed(&mut self, old_name: &str, new_name: &str) {
         let target = match self.by_name.get(new_name) {
             Some(&Id(lint_id)) => lint_id.clone(),
@@ -428,6 +460,15 @@ pub trait LintContext: Sized {
         };
 
         raw_emit_lint(&self.sess(), lint, (level, src), span, msg);
+
+        if level != Allow && self.lints().is_future_incompatible(LintId::of(lint)) {
+            let note = "this was previously accepted by the compiler but is being phased out; \
+                        it will become a hard error in a future release!";
+            match span {
+                Some(span) => self.sess().fileline_note(span, note),
+                None => self.sess().note(note),
+            }
+        }
     }
 
     /// Emit a lint at the appropriate level, for a particular span.
@@ -457,6 +498,16 @@ pub trait LintContext: Sized {
         }
     }
 
+    /// Emit a lint at the appropriate level, for a particular span, with a
+    /// suggestion to replace the code at `suggestion_span`.
+    fn span_lint_suggestion(&self, lint: &'static Lint, span: Span, msg: &str,
+                            suggestion_span: Span, help: &str, suggestion: String) {
+        self.span_lint(lint, span, msg);
+        if self.current_level(lint) != Level::Allow {
+            self.sess().span_suggestion(suggestion_span, help, suggestion)
+        }
+    }
+
     /// Emit a lint at the appropriate level, with no associated span.
     fn lint(&self, lint: &'static Lint, msg: &str) {
         self.lookup_and_emit(lint, None, msg);
@@ -476,6 +527,25 @@ pub trait LintContext: Sized {
         // specified closure
         let mut pushed = 0;
 
+        // Inside generated code, lower every lint that is above the cap the
+        // crate set for it; `set_level` keeps any nested attributes in
+        // check until we leave again.
+        let generated = attr::contains_name(attrs, "automatically_derived") &&
+                        self.lints().generated_code_cap.is_some();
+        if generated {
+            self.mut_lints().generated_code_depth += 1;
+            let cap = self.lints().generated_code_cap.unwrap();
+            let capped: Vec<_> = self.lints().levels.iter()
+                                     .filter(|&(_, &(level, _))| level > cap)
+                                     .map(|(&lint_id, &lvlsrc)| (lint_id, lvlsrc))
+                                     .collect();
+            for (lint_id, lvlsrc) in capped {
+                self.level_stack().push((lint_id, lvlsrc));
+                pushed += 1;
+                self.mut_lints().set_level(lint_id, lvlsrc);
+            }
+        }
+
         for result in gather_attrs(attrs) {
             let v = match result {
                 Err(span) => {
@@ -526,6 +596,10 @@ pub trait LintContext: Sized {
         f(self);
         self.exit_attrs(attrs);
 
+        if generated {
+            self.mut_lints().generated_code_depth -= 1;
+        }
+
         // rollback
         for _ in 0..pushed {
             let (lint, lvlsrc) = self.level_stack().pop().unwrap();
@@ -959,8 +1033,17 @@ impl<'a, 'tcx> IdVisitingOperation for LateContext<'a, 'tcx> {
             None => {}
             Some(lints) => {
                 debug!("LateContext::visit_id: id={:?} lints={:?}", id, lints);
-                for (lint_id, span, msg) in lints {
-                    self.span_lint(lint_id.lint, span, &msg[..])
+                for (lint_id, span, msg, note) in lints {
+                    match note {
+                        Some(SessionLintNote::Note(note_span, note)) => {
+                            self.span_lint_note(lint_id.lint, span, &msg[..], note_span, &note[..])
+                        }
+                        Some(SessionLintNote::Suggestion(sugg_span, help, suggestion)) => {
+                            self.span_lint_suggestion(lint_id.lint, span, &msg[..],
+                                                      sugg_span, &help[..], suggestion)
+                        }
+                        None => self.span_lint(lint_id.lint, span, &msg[..]),
+                    }
                 }
             }
         }
@@ -971,8 +1054,17 @@ impl<'a> IdVisitingOperation for EarlyContext<'a> {
         match self.sess.lints.borrow_mut().remove(&id) {
             None => {}
             Some(lints) => {
-                for (lint_id, span, msg) in lints {
-                    self.span_lint(lint_id.lint, span, &msg[..])
+                for (lint_id, span, msg, note) in lints {
+                    match note {
+                        Some(SessionLintNote::Note(note_span, note)) => {
+                            self.span_lint_note(lint_id.lint, span, &msg[..], note_span, &note[..])
+                        }
+                        Some(SessionLintNote::Suggestion(sugg_span, help, suggestion)) => {
+                            self.span_lint_suggestion(lint_id.lint, span, &msg[..],
+                                                      sugg_span, &help[..], suggestion)
+                        }
+                        None => self.span_lint(lint_id.lint, span, &msg[..]),
+                    }
                 }
             }
         }
@@ -1011,6 +1103,21 @@ impl LateLintPass for GatherNodeLevels {
     }
 }
 
+/// The level `#![generated_code_lints = "..."]` caps lints at inside
+/// generated code, if the crate has the attribute.
+fn generated_code_cap(sess: &Session, attrs: &[ast::Attribute]) -> Option<Level> {
+    let attr = match attrs.iter().find(|attr| attr.check_name("generated_code_lints")) {
+        Some(attr) => attr,
+        None => return None,
+    };
+    let level = attr.value_str().and_then(|level| Level::from_str(&level));
+    if level.is_none() {
+        sess.span_err(attr.span, "malformed `generated_code_lints` attribute, expected \
+                                  `allow`, `warn`, `deny` or `forbid`");
+    }
+    level
+}
+
 /// Perform lint checking on a crate.
 ///
 /// Consumes the `lint_store` field of the `Session`.
@@ -1036,7 +1143,7 @@ pub fn check_crate(tcx: &ty::ctxt,
     // If we missed any lints added to the session, then there's a bug somewhere
     // in the iteration code.
     for (id, v) in tcx.sess.lints.borrow().iter() {
-        for &(lint, span, ref msg) in v {
+        for &(lint, span, ref msg, _) in v {
             tcx.sess.span_bug(span,
                               &format!("unprocessed lint {} at {}: {}",
                                        lint.as_str(), tcx.map.node_to_string(*id), *msg))
@@ -1049,6 +1156,10 @@ pub fn check_crate(tcx: &ty::ctxt,
 pub fn check_ast_crate(sess: &Session, krate: &ast::Crate) {
     let mut cx = EarlyContext::new(sess, krate);
 
+    // The lint store goes back into the session afterwards, so the late
+    // lint pass sees the same cap.
+    cx.lints.generated_code_cap = generated_code_cap(sess, &krate.attrs);
+
     // Visit the whole crate.
     cx.with_lint_attrs(&krate.attrs, |cx| {
         cx.visit_id(ast::CRATE_NODE_ID);
@@ -1070,7 +1181,7 @@ pub fn check_ast_crate(sess: &Session, krate: &ast::Crate) {
     // If we missed any lints added to the session, then there's a bug somewhere
     // in the iteration code.
     for (_, v) in sess.lints.borrow().iter() {
-        for &(lint, span, ref msg) in v {
+        for &(lint, span, ref msg, _) in v {
             sess.span_bug(span,
                           &format!("unprocessed lint {}: {}",
                                    lint.as_str(), *msg))
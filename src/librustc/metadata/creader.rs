@@ -225,7 +225,20 @@ impl<'a> CrateReader<'a> {
                       -> Option<ast::CrateNum> {
         let mut ret = None;
         self.sess.cstore.iter_crate_data(|cnum, data| {
-            if data.name != name { return }
+            if data.name != name {
+                // Crates are registered under their real name, but a
+                // top-level crate may be requested through an alias passed
+                // as `--extern alias=path`. In that case it's the same
+                // crate if it was loaded from one of those paths.
+                if hash.is_none() {
+                    if let Some(locs) = self.sess.opts.externs.get(name) {
+                        if self.loaded_from(cnum, locs) {
+                            ret = Some(cnum);
+                        }
+                    }
+                }
+                return
+            }
 
             match hash {
                 Some(hash) if *hash == data.hash() => { ret = Some(cnum); return }
@@ -242,14 +255,8 @@ impl<'a> CrateReader<'a> {
             // We're also sure to compare *paths*, not actual byte slices. The
             // `source` stores paths which are normalized which may be different
             // from the strings on the command line.
-            let source = self.sess.cstore.get_used_crate_source(cnum).unwrap();
             if let Some(locs) = self.sess.opts.externs.get(name) {
-                let found = locs.iter().any(|l| {
-                    let l = fs::canonicalize(l).ok();
-                    source.dylib.as_ref().map(|p| &p.0) == l.as_ref() ||
-                    source.rlib.as_ref().map(|p| &p.0) == l.as_ref()
-                });
-                if found {
+                if self.loaded_from(cnum, locs) {
                     ret = Some(cnum);
                 }
                 return
@@ -261,6 +268,7 @@ impl<'a> CrateReader<'a> {
             // have to make sure that this crate was found in the crate lookup
             // path (this is a top-level dependency) as we don't want to
             // implicitly load anything inside the dependency lookup path.
+            let source = self.sess.cstore.get_used_crate_source(cnum).unwrap();
             let prev_kind = source.dylib.as_ref().or(source.rlib.as_ref())
                                   .unwrap().1;
             if ret.is_none() && (prev_kind == kind || prev_kind == PathKind::All) {
@@ -270,6 +278,15 @@ impl<'a> CrateReader<'a> {
         return ret;
     }
 
+    fn loaded_from(&self, cnum: ast::CrateNum, locs: &[String]) -> bool {
+        let source = self.sess.cstore.get_used_crate_source(cnum).unwrap();
+        locs.iter().any(|l| {
+            let l = fs::canonicalize(l).ok();
+            source.dylib.as_ref().map(|p| &p.0) == l.as_ref() ||
+            source.rlib.as_ref().map(|p| &p.0) == l.as_ref()
+        })
+    }
+
     fn verify_rustc_version(&self,
                             name: &str,
                             span: Span,
@@ -322,7 +339,7 @@ impl<'a> CrateReader<'a> {
         let staged_api = self.is_staged_api(metadata.as_slice());
 
         let cmeta = Rc::new(cstore::crate_metadata {
-            name: name.to_string(),
+            name: decoder::get_crate_name(metadata.as_slice()),
             local_path: RefCell::new(SmallVector::zero()),
             local_def_path: RefCell::new(vec![]),
             index: decoder::load_index(metadata.as_slice()),
@@ -383,6 +400,7 @@ impl<'a> CrateReader<'a> {
                     rejected_via_hash: vec!(),
                     rejected_via_triple: vec!(),
                     rejected_via_kind: vec!(),
+                    rejected_via_name: vec!(),
                     should_match_name: true,
                 };
                 let library = load_ctxt.load_library_crate();
@@ -440,6 +458,7 @@ impl<'a> CrateReader<'a> {
             rejected_via_hash: vec!(),
             rejected_via_triple: vec!(),
             rejected_via_kind: vec!(),
+            rejected_via_name: vec!(),
             should_match_name: true,
         };
         let library = match load_ctxt.maybe_load_library_crate() {
@@ -572,7 +591,7 @@ impl<'a> CrateReader<'a> {
         let libs = self.sess.cstore.get_used_libraries();
         for (lib, list) in self.foreign_item_map.iter() {
             let is_static = libs.borrow().iter().any(|&(ref name, kind)| {
-                lib == name && kind == cstore::NativeStatic
+                lib == name && kind.is_static()
             });
             if is_static {
                 for id in list {
@@ -801,6 +820,17 @@ impl<'a, 'b> LocalCrateReader<'a, 'b> {
                 }
                 None => cstore::NativeUnknown
             };
+            let modifiers = items.iter().find(|k| {
+                k.check_name("modifiers")
+            }).and_then(|a| a.value_str());
+            let kind = match modifiers.map(|m| kind.with_modifiers(&m)) {
+                None => kind,
+                Some(Ok(kind)) => kind,
+                Some(Err(msg)) => {
+                    self.sess.span_err(m.span, &msg);
+                    kind
+                }
+            };
             let n = items.iter().find(|n| {
                 n.check_name("name")
             }).and_then(|a| a.value_str());
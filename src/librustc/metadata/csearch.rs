@@ -19,6 +19,8 @@ use middle::lang_items;
 use middle::ty;
 use util::nodemap::FnvHashMap;
 
+use rustc_data_structures::stable_hasher::StableHasher;
+use std::hash::{Hash, Hasher};
 use std::rc::Rc;
 use syntax::ast;
 use syntax::attr;
@@ -361,6 +363,13 @@ pub fn get_stability(cstore: &cstore::CStore,
     decoder::get_stability(&*cdata, def.index)
 }
 
+pub fn get_deprecation(cstore: &cstore::CStore,
+                       def: DefId)
+                       -> Option<attr::Deprecation> {
+    let cdata = cstore.get_crate_data(def.krate);
+    decoder::get_deprecation(&*cdata, def.index)
+}
+
 pub fn is_staged_api(cstore: &cstore::CStore, krate: ast::CrateNum) -> bool {
     cstore.get_crate_data(krate).staged_api
 }
@@ -399,6 +408,12 @@ pub fn closure_ty<'tcx>(tcx: &ty::ctxt<'tcx>, def_id: DefId) -> ty::ClosureTy<'t
     decoder::closure_ty(&*cdata, def_id.index, tcx)
 }
 
+/// The item or module `def` is defined in, `None` for the root of a crate.
+pub fn get_def_parent(cstore: &cstore::CStore, def: DefId) -> Option<DefId> {
+    let cdata = cstore.get_crate_data(def.krate);
+    decoder::def_parent(&*cdata, def.index).map(|index| DefId { krate: def.krate, index: index })
+}
+
 pub fn def_path(tcx: &ty::ctxt, def: DefId) -> ast_map::DefPath {
     let cstore = &tcx.sess.cstore;
     let cdata = cstore.get_crate_data(def.krate);
@@ -407,3 +422,16 @@ pub fn def_path(tcx: &ty::ctxt, def: DefId) -> ast_map::DefPath {
     local_path.into_iter().chain(path).collect()
 }
 
+/// A hash of the name of the crate `def` is defined in, and of its path
+/// from the root of that crate. Unlike `def_path`, it doesn't depend on
+/// where the crate is imported in the local crate.
+pub fn def_path_hash(tcx: &ty::ctxt, def: DefId) -> u64 {
+    let cstore = &tcx.sess.cstore;
+    let cdata = cstore.get_crate_data(def.krate);
+    let path = decoder::def_path(&*cdata, def.index);
+    let mut state = StableHasher::new();
+    cdata.name().hash(&mut state);
+    ast_map::definitions::def_path_hash(&path).hash(&mut state);
+    state.finish()
+}
+
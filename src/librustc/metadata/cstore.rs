@@ -43,6 +43,8 @@ pub type cnum_map = FnvHashMap<ast::CrateNum, ast::CrateNum>;
 pub enum MetadataBlob {
     MetadataVec(Bytes),
     MetadataArchive(loader::ArchiveMetadata),
+    /// Metadata that `-Z server` keeps for the compilations after this one.
+    MetadataShared(Rc<MetadataBlob>),
 }
 
 /// Holds information about a codemap::FileMap imported from another crate.
@@ -89,6 +91,37 @@ enum_from_u32! {
         NativeStatic,    // native static library (.a archive)
         NativeFramework, // OSX-specific
         NativeUnknown,   // default way to specify a dynamic library
+        NativeStaticNoWholeArchive, // static library, only linking the objects used
+    }
+}
+
+impl NativeLibraryKind {
+    pub fn is_static(self) -> bool {
+        match self {
+            NativeStatic | NativeStaticNoWholeArchive => true,
+            NativeFramework | NativeUnknown => false,
+        }
+    }
+
+    /// Applies a comma-separated list of linking modifiers, as given in
+    /// `-l static:-whole-archive=foo` or `#[link(modifiers = "...")]`.
+    pub fn with_modifiers(self, modifiers: &str) -> Result<NativeLibraryKind, String> {
+        let mut kind = self;
+        for modifier in modifiers.split(',') {
+            kind = match (modifier, kind.is_static()) {
+                ("+whole-archive", true) => NativeStatic,
+                ("-whole-archive", true) => NativeStaticNoWholeArchive,
+                ("+whole-archive", false) | ("-whole-archive", false) => {
+                    return Err(format!("linking modifier `{}` is only compatible with \
+                                        `static` linking kind", modifier));
+                }
+                _ => {
+                    return Err(format!("unknown linking modifier `{}`, expected one of \
+                                        +whole-archive or -whole-archive", modifier));
+                }
+            };
+        }
+        Ok(kind)
     }
 }
 
@@ -272,6 +305,7 @@ impl CStore {
 impl crate_metadata {
     pub fn data<'a>(&'a self) -> &'a [u8] { self.data.as_slice() }
     pub fn name(&self) -> String { decoder::get_crate_name(self.data()) }
+    pub fn disambiguator(&self) -> String { decoder::get_crate_disambiguator(self.data()) }
     pub fn hash(&self) -> Svh { decoder::get_crate_hash(self.data()) }
     pub fn imported_filemaps<'a>(&'a self, codemap: &codemap::CodeMap)
                                  -> Ref<'a, Vec<ImportedFileMap>> {
@@ -348,6 +382,7 @@ impl MetadataBlob {
         let slice = match *self {
             MetadataVec(ref vec) => &vec[..],
             MetadataArchive(ref ar) => ar.as_slice(),
+            MetadataShared(ref blob) => return blob.as_slice(),
         };
         if slice.len() < 4 {
             &[] // corrupt metadata
@@ -525,6 +525,14 @@ pub fn get_stability(cdata: Cmd, id: DefIndex) -> Option<attr::Stability> {
     })
 }
 
+pub fn get_deprecation(cdata: Cmd, id: DefIndex) -> Option<attr::Deprecation> {
+    let item = cdata.lookup_item(id);
+    reader::maybe_get_doc(item, tag_items_data_item_deprecation).map(|doc| {
+        let mut decoder = reader::Decoder::new(doc);
+        Decodable::decode(&mut decoder).unwrap()
+    })
+}
+
 pub fn get_repr_attrs(cdata: Cmd, id: DefIndex) -> Vec<attr::ReprAttr> {
     let item = cdata.lookup_item(id);
     match reader::maybe_get_doc(item, tag_items_data_item_repr).map(|doc| {
@@ -1209,6 +1217,15 @@ pub fn maybe_get_crate_name(data: &[u8]) -> Option<String> {
     })
 }
 
+/// The crate disambiguator, or the empty string for crates compiled before
+/// it was recorded.
+pub fn get_crate_disambiguator(data: &[u8]) -> String {
+    let cratedoc = rbml::Doc::new(data);
+    reader::maybe_get_doc(cratedoc, tag_crate_disambiguator).map(|doc| {
+        doc.as_str_slice().to_string()
+    }).unwrap_or(String::new())
+}
+
 pub fn get_crate_triple(data: &[u8]) -> Option<String> {
     let cratedoc = rbml::Doc::new(data);
     let triple_doc = reader::maybe_get_doc(cratedoc, tag_crate_triple);
@@ -1222,6 +1239,7 @@ pub fn get_crate_name(data: &[u8]) -> String {
 pub fn list_crate_metadata(bytes: &[u8], out: &mut io::Write) -> io::Result<()> {
     let hash = get_crate_hash(bytes);
     let md = rbml::Doc::new(bytes);
+    try!(write!(out, "=Crate Disambiguator={}\n\n", get_crate_disambiguator(bytes)));
     try!(list_crate_attributes(md, &hash, out));
     list_crate_deps(bytes, out)
 }
@@ -1594,6 +1612,10 @@ fn def_key(item_doc: rbml::Doc) -> hir_map::DefKey {
     }
 }
 
+pub fn def_parent(cdata: Cmd, id: DefIndex) -> Option<DefIndex> {
+    def_key(cdata.lookup_item(id)).parent
+}
+
 pub fn def_path(cdata: Cmd, id: DefIndex) -> hir_map::DefPath {
     debug!("def_path(id={:?})", id);
     hir_map::definitions::make_def_path(id, |parent| {
@@ -218,7 +218,7 @@ use session::search_paths::PathKind;
 use llvm;
 use llvm::{False, ObjectFile, mk_section_iter};
 use llvm::archive_ro::ArchiveRO;
-use metadata::cstore::{MetadataBlob, MetadataVec, MetadataArchive};
+use metadata::cstore::{MetadataBlob, MetadataVec, MetadataArchive, MetadataShared};
 use metadata::decoder;
 use metadata::encoder;
 use metadata::filesearch::{FileSearch, FileMatches, FileDoesntMatch};
@@ -227,6 +227,7 @@ use syntax::diagnostic::SpanHandler;
 use util::common;
 use rustc_back::target::Target;
 
+use std::cell::RefCell;
 use std::cmp;
 use std::collections::HashMap;
 use std::fs;
@@ -234,6 +235,7 @@ use std::io::prelude::*;
 use std::io;
 use std::path::{Path, PathBuf};
 use std::ptr;
+use std::rc::Rc;
 use std::slice;
 use std::time::Duration;
 
@@ -258,6 +260,7 @@ pub struct Context<'a> {
     pub rejected_via_hash: Vec<CrateMismatch>,
     pub rejected_via_triple: Vec<CrateMismatch>,
     pub rejected_via_kind: Vec<CrateMismatch>,
+    pub rejected_via_name: Vec<CrateMismatch>,
     pub should_match_name: bool,
 }
 
@@ -369,6 +372,20 @@ impl<'a> Context<'a> {
                                                  self.ident, i+1, path.display()));
             }
         }
+        if !self.rejected_via_name.is_empty() {
+            let mismatches = self.rejected_via_name.iter();
+            for (i, &CrateMismatch { ref path, ref got }) in mismatches.enumerate() {
+                self.sess.fileline_note(self.span,
+                    &format!("crate `{}`, path #{}, found crate `{}` instead: {}",
+                            self.ident, i+1, got, path.display()));
+            }
+            if self.hash.is_none() {
+                self.sess.fileline_help(self.span,
+                    &format!("to use one of these under the name `{}`, pass it with \
+                              `--extern {}=PATH`",
+                             self.crate_name, self.crate_name));
+            }
+        }
         self.sess.abort_if_errors();
     }
 
@@ -575,7 +592,20 @@ impl<'a> Context<'a> {
         if self.should_match_name {
             match decoder::maybe_get_crate_name(crate_data) {
                 Some(ref name) if self.crate_name == *name => {}
-                _ => { info!("Rejecting via crate name"); return false }
+                Some(name) => {
+                    info!("Rejecting via crate name: expected {} got {}",
+                          self.crate_name, name);
+                    // Files are found by prefix, so `libfoo_bar` shows up
+                    // when looking for `foo`; that's not worth reporting.
+                    if !name.starts_with(self.crate_name) {
+                        self.rejected_via_name.push(CrateMismatch {
+                            path: libpath.to_path_buf(),
+                            got: name,
+                        });
+                    }
+                    return false
+                }
+                None => { info!("Rejecting via lack of crate name"); return false }
             }
         }
         let hash = match decoder::maybe_get_crate_hash(crate_data) {
@@ -690,6 +720,37 @@ impl<'a> Context<'a> {
     }
 }
 
+/// Whether the library search paths or the sysroot contain an rlib or a
+/// dylib for the target whose file name says it is the crate `crate_name`.
+/// Only the file names are looked at, the crates aren't loaded.
+pub fn library_exists(sess: &Session, crate_name: &str) -> bool {
+    let t = &sess.target.target;
+    let dylib_prefix = format!("{}{}", t.options.dll_prefix, crate_name);
+    let rlib_prefix = format!("lib{}", crate_name);
+
+    let mut found = false;
+    sess.target_filesearch(PathKind::Crate).search(|path, _| {
+        let file = match path.file_name().and_then(|s| s.to_str()) {
+            None => return FileDoesntMatch,
+            Some(file) => file,
+        };
+        let hash = if file.starts_with(&rlib_prefix[..]) && file.ends_with(".rlib") {
+            &file[rlib_prefix.len()..]
+        } else if file.starts_with(&dylib_prefix[..]) &&
+                  file.ends_with(&t.options.dll_suffix[..]) {
+            &file[dylib_prefix.len()..]
+        } else {
+            return FileDoesntMatch
+        };
+        // Don't take `libfoobar.rlib` for the crate `foo`.
+        if hash.starts_with("-") || hash.starts_with(".") {
+            found = true;
+        }
+        FileDoesntMatch
+    });
+    found
+}
+
 pub fn note_crate_name(diag: &SpanHandler, name: &str) {
     diag.handler().note(&format!("crate name: {}", name));
 }
@@ -718,15 +779,77 @@ impl ArchiveMetadata {
     pub fn as_slice<'a>(&'a self) -> &'a [u8] { unsafe { &*self.data } }
 }
 
-// Just a small wrapper to time how long reading metadata takes.
+/// The length and modification time of a file, which change when it is
+/// written again.
+type FileStamp = (u64, i64, i64);
+
+// The metadata read so far on this thread, by the file it was read from.
+// `None` unless `keep_metadata` was called.
+thread_local! {
+    static METADATA_CACHE: RefCell<Option<HashMap<PathBuf, (FileStamp, Rc<MetadataBlob>)>>> =
+        RefCell::new(None)
+}
+
+/// Keeps the metadata read on this thread for the compilations after the
+/// current one, for as long as the files it was read from don't change.
+/// This is what `-Z server` saves on decoding the same crates again.
+pub fn keep_metadata() {
+    METADATA_CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        if cache.is_none() {
+            *cache = Some(HashMap::new());
+        }
+    });
+}
+
+#[cfg(unix)]
+fn file_stamp(filename: &Path) -> Option<FileStamp> {
+    use std::os::unix::fs::MetadataExt;
+    fs::metadata(filename).ok().map(|m| (m.len(), m.mtime() as i64, m.mtime_nsec() as i64))
+}
+
+#[cfg(windows)]
+fn file_stamp(filename: &Path) -> Option<FileStamp> {
+    use std::os::windows::fs::MetadataExt;
+    fs::metadata(filename).ok().map(|m| (m.len(), m.last_write_time() as i64, 0))
+}
+
+// Just a small wrapper to time how long reading metadata takes, and to
+// look it up in the metadata kept by `keep_metadata` first.
 fn get_metadata_section(target: &Target, filename: &Path)
                         -> Result<MetadataBlob, String> {
+    let stamp = file_stamp(filename);
+    let kept = METADATA_CACHE.with(|cache| {
+        match (cache.borrow().as_ref(), stamp) {
+            (Some(cache), Some(stamp)) => match cache.get(filename) {
+                Some(&(kept_stamp, ref blob)) if kept_stamp == stamp => Some(blob.clone()),
+                _ => None,
+            },
+            _ => None,
+        }
+    });
+    if let Some(blob) = kept {
+        info!("reusing the metadata of {:?}", filename.file_name().unwrap());
+        return Ok(MetadataShared(blob));
+    }
+
     let mut ret = None;
     let dur = Duration::span(|| {
         ret = Some(get_metadata_section_imp(target, filename));
     });
     info!("reading {:?} => {:?}", filename.file_name().unwrap(), dur);
-    ret.unwrap()
+    let blob = try!(ret.unwrap());
+
+    METADATA_CACHE.with(|cache| {
+        match (cache.borrow_mut().as_mut(), stamp) {
+            (Some(cache), Some(stamp)) => {
+                let blob = Rc::new(blob);
+                cache.insert(filename.to_path_buf(), (stamp, blob.clone()));
+                Ok(MetadataShared(blob))
+            }
+            _ => Ok(blob),
+        }
+    })
 }
 
 fn get_metadata_section_imp(target: &Target, filename: &Path)
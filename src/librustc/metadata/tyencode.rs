@@ -39,6 +39,9 @@ pub struct ctxt<'a, 'tcx: 'a> {
     pub diag: &'a SpanHandler,
     // Def -> str Callback:
     pub ds: fn(DefId) -> String,
+    // Def -> str Callback used instead of `ds` when set, for encodings that
+    // must not depend on crate numbers and def-indices:
+    pub stable_ds: Option<fn(&ty::ctxt<'tcx>, DefId) -> String>,
     // The type context.
     pub tcx: &'a ty::ctxt<'tcx>,
     pub abbrevs: &'a abbrev_map<'tcx>
@@ -53,6 +56,13 @@ pub struct ty_abbrev {
 
 pub type abbrev_map<'tcx> = RefCell<FnvHashMap<Ty<'tcx>, ty_abbrev>>;
 
+fn def_to_string(cx: &ctxt, did: DefId) -> String {
+    match cx.stable_ds {
+        Some(stable_ds) => stable_ds(cx.tcx, did),
+        None => (cx.ds)(did),
+    }
+}
+
 pub fn enc_ty<'a, 'tcx>(w: &mut Encoder, cx: &ctxt<'a, 'tcx>, t: Ty<'tcx>) {
     match cx.abbrevs.borrow_mut().get(&t) {
         Some(a) => { w.writer.write_all(&a.s); return; }
@@ -90,7 +100,7 @@ pub fn enc_ty<'a, 'tcx>(w: &mut Encoder, cx: &ctxt<'a, 'tcx>, t: Ty<'tcx>) {
             }
         }
         ty::TyEnum(def, substs) => {
-            mywrite!(w, "t[{}|", (cx.ds)(def.did));
+            mywrite!(w, "t[{}|", def_to_string(cx, def.did));
             enc_substs(w, cx, substs);
             mywrite!(w, "]");
         }
@@ -128,7 +138,7 @@ pub fn enc_ty<'a, 'tcx>(w: &mut Encoder, cx: &ctxt<'a, 'tcx>, t: Ty<'tcx>) {
         }
         ty::TyBareFn(Some(def_id), f) => {
             mywrite!(w, "F");
-            mywrite!(w, "{}|", (cx.ds)(def_id));
+            mywrite!(w, "{}|", def_to_string(cx, def_id));
             enc_bare_fn_ty(w, cx, f);
         }
         ty::TyBareFn(None, f) => {
@@ -142,12 +152,12 @@ pub fn enc_ty<'a, 'tcx>(w: &mut Encoder, cx: &ctxt<'a, 'tcx>, t: Ty<'tcx>) {
             mywrite!(w, "p[{}|{}|{}]", idx, space.to_uint(), name)
         }
         ty::TyStruct(def, substs) => {
-            mywrite!(w, "a[{}|", (cx.ds)(def.did));
+            mywrite!(w, "a[{}|", def_to_string(cx, def.did));
             enc_substs(w, cx, substs);
             mywrite!(w, "]");
         }
         ty::TyClosure(def, ref substs) => {
-            mywrite!(w, "k[{}|", (cx.ds)(def));
+            mywrite!(w, "k[{}|", def_to_string(cx, def));
             enc_substs(w, cx, &substs.func_substs);
             for ty in &substs.upvar_tys {
                 enc_ty(w, cx, ty);
@@ -254,7 +264,7 @@ pub fn enc_region(w: &mut Encoder, cx: &ctxt, r: ty::Region) {
         }
         ty::ReEarlyBound(ref data) => {
             mywrite!(w, "B[{}|{}|{}|{}]",
-                     (cx.ds)(data.def_id),
+                     def_to_string(cx, data.def_id),
                      data.space.to_uint(),
                      data.index,
                      data.name);
@@ -302,7 +312,7 @@ fn enc_bound_region(w: &mut Encoder, cx: &ctxt, br: ty::BoundRegion) {
         }
         ty::BrNamed(d, name) => {
             mywrite!(w, "[{}|{}]",
-                     (cx.ds)(d),
+                     def_to_string(cx, d),
                      name);
         }
         ty::BrFresh(id) => {
@@ -316,7 +326,7 @@ fn enc_bound_region(w: &mut Encoder, cx: &ctxt, br: ty::BoundRegion) {
 
 pub fn enc_trait_ref<'a, 'tcx>(w: &mut Encoder, cx: &ctxt<'a, 'tcx>,
                                s: ty::TraitRef<'tcx>) {
-    mywrite!(w, "{}|", (cx.ds)(s.def_id));
+    mywrite!(w, "{}|", def_to_string(cx, s.def_id));
     enc_substs(w, cx, s.substs);
 }
 
@@ -400,8 +410,8 @@ pub fn enc_existential_bounds<'a,'tcx>(w: &mut Encoder,
 pub fn enc_type_param_def<'a, 'tcx>(w: &mut Encoder, cx: &ctxt<'a, 'tcx>,
                                     v: &ty::TypeParameterDef<'tcx>) {
     mywrite!(w, "{}:{}|{}|{}|{}|",
-             v.name, (cx.ds)(v.def_id),
-             v.space.to_uint(), v.index, (cx.ds)(v.default_def_id));
+             v.name, def_to_string(cx, v.def_id),
+             v.space.to_uint(), v.index, def_to_string(cx, v.default_def_id));
     enc_opt(w, v.default, |w, t| enc_ty(w, cx, t));
     enc_object_lifetime_default(w, cx, v.object_lifetime_default);
 }
@@ -409,7 +419,7 @@ pub fn enc_type_param_def<'a, 'tcx>(w: &mut Encoder, cx: &ctxt<'a, 'tcx>,
 pub fn enc_region_param_def(w: &mut Encoder, cx: &ctxt,
                             v: &ty::RegionParameterDef) {
     mywrite!(w, "{}:{}|{}|{}|",
-             v.name, (cx.ds)(v.def_id),
+             v.name, def_to_string(cx, v.def_id),
              v.space.to_uint(), v.index);
     for &r in &v.bounds {
         mywrite!(w, "R");
@@ -465,7 +475,7 @@ pub fn enc_predicate<'a, 'tcx>(w: &mut Encoder,
             enc_ty(w, cx, data);
         }
         ty::Predicate::ObjectSafe(trait_def_id) => {
-            mywrite!(w, "O{}|", (cx.ds)(trait_def_id));
+            mywrite!(w, "O{}|", def_to_string(cx, trait_def_id));
         }
     }
 }
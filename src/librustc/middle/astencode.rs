@@ -562,6 +562,7 @@ impl<'a, 'tcx> get_ty_str_ctxt<'tcx> for e::EncodeContext<'a, 'tcx> {
         tyencode::ctxt {
             diag: self.tcx.sess.diagnostic(),
             ds: e::def_to_string,
+            stable_ds: None,
             tcx: self.tcx,
             abbrevs: &self.type_abbrevs
         }
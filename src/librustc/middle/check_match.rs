@@ -284,6 +284,7 @@ fn check_for_static_nan(cx: &MatchCheckCtxt, pat: &Pat) {
                     span_err!(cx.tcx.sess, err.span, E0471,
                               "constant evaluation error: {}",
                               err.description());
+                    err.note(cx.tcx.sess);
                     if !p.span.contains(err.span) {
                         cx.tcx.sess.span_note(p.span,
                                               "in pattern here")
@@ -23,6 +23,7 @@ use middle::def_id::DefId;
 use middle::pat_util::def_to_path;
 use middle::ty::{self, Ty};
 use middle::astconv_util::ast_ty_to_prim_ty;
+use session::Session;
 use util::num::ToPrimitive;
 use util::nodemap::NodeMap;
 
@@ -30,6 +31,7 @@ use syntax::{ast, abi};
 use rustc_front::hir::Expr;
 use rustc_front::hir;
 use rustc_front::intravisit::FnKind;
+use rustc_front::print::pprust::path_to_string;
 use syntax::codemap::Span;
 use syntax::parse::token::InternedString;
 use syntax::ptr::P;
@@ -312,6 +314,19 @@ impl ConstVal {
             Function(_) => "function definition",
         }
     }
+
+    /// The value as it would be written in the source, for the scalar
+    /// values that can be shown in a diagnostic.
+    pub fn to_source_string(&self) -> Option<String> {
+        match *self {
+            Float(f) => Some(f.to_string()),
+            Int(i) => Some(i.to_string()),
+            Uint(u) => Some(u.to_string()),
+            Str(ref s) => Some(format!("{:?}", &s[..])),
+            Bool(b) => Some(b.to_string()),
+            ByteStr(_) | Struct(_) | Tuple(_) | Function(_) => None,
+        }
+    }
 }
 
 pub fn const_expr_to_pat(tcx: &ty::ctxt, expr: &Expr, span: Span) -> P<hir::Pat> {
@@ -374,7 +389,7 @@ pub fn const_expr_to_pat(tcx: &ty::ctxt, expr: &Expr, span: Span) -> P<hir::Pat>
 pub fn eval_const_expr(tcx: &ty::ctxt, e: &Expr) -> ConstVal {
     match eval_const_expr_partial(tcx, e, ExprTypeChecked, None) {
         Ok(r) => r,
-        Err(s) => tcx.sess.span_fatal(s.span, &s.description())
+        Err(s) => s.span_fatal(tcx.sess, s.span)
     }
 }
 
@@ -384,6 +399,10 @@ pub type FnArgMap<'a> = Option<&'a NodeMap<ConstVal>>;
 pub struct ConstEvalErr {
     pub span: Span,
     pub kind: ErrKind,
+    /// Notes explaining how the evaluation got to the error, innermost
+    /// first: the values of the operands of the failed operation, then the
+    /// uses of the constants whose values were being evaluated.
+    pub notes: Vec<(Span, String)>,
 }
 
 #[derive(Clone)]
@@ -463,6 +482,32 @@ impl ConstEvalErr {
             MiscCatchAll => "unsupported constant expr".into_cow(),
         }
     }
+
+    /// Emits the notes of the error, after the error itself was reported.
+    pub fn note(&self, sess: &Session) {
+        for &(span, ref note) in &self.notes {
+            sess.span_note(span, note);
+        }
+    }
+
+    /// Reports the error at `span` as a fatal error, along with its notes.
+    pub fn span_fatal(&self, sess: &Session, span: Span) -> ! {
+        sess.span_err(span, &self.description());
+        self.note(sess);
+        sess.abort_if_errors();
+        unreachable!()
+    }
+
+    /// Notes the value of `operand`, an operand of the failed operation,
+    /// unless it is a literal, whose value can be read from the source.
+    fn note_operand(&mut self, operand: &Expr, val: &ConstVal) {
+        if let hir::ExprLit(_) = operand.node {
+            return;
+        }
+        if let Some(val) = val.to_source_string() {
+            self.notes.push((operand.span, format!("this evaluates to `{}`", val)));
+        }
+    }
 }
 
 pub type EvalResult = Result<ConstVal, ConstEvalErr>;
@@ -531,7 +576,7 @@ impl UintTy {
 
 macro_rules! signal {
     ($e:expr, $exn:expr) => {
-        return Err(ConstEvalErr { span: $e.span, kind: $exn })
+        return Err(ConstEvalErr { span: $e.span, kind: $exn, notes: Vec::new() })
     }
 }
 
@@ -760,6 +805,104 @@ pub_fn_checked_op!{ const_uint_checked_shr_via_int(a: u64, b: i64,.. UintTy) {
            uint_shift_body overflowing_shr Uint ShiftRightWithOverflow
 }}
 
+/// Applies the binary operator of `e` to the values of its operands.
+fn eval_const_binop(e: &Expr,
+                    op: hir::BinOp,
+                    a: ConstVal,
+                    b: ConstVal,
+                    expr_int_type: Option<IntTy>,
+                    expr_uint_type: Option<UintTy>)
+                    -> EvalResult {
+    Ok(match (a, b) {
+        (Float(a), Float(b)) => {
+            match op.node {
+                hir::BiAdd => Float(a + b),
+                hir::BiSub => Float(a - b),
+                hir::BiMul => Float(a * b),
+                hir::BiDiv => Float(a / b),
+                hir::BiRem => Float(a % b),
+                hir::BiEq => Bool(a == b),
+                hir::BiLt => Bool(a < b),
+                hir::BiLe => Bool(a <= b),
+                hir::BiNe => Bool(a != b),
+                hir::BiGe => Bool(a >= b),
+                hir::BiGt => Bool(a > b),
+                _ => signal!(e, InvalidOpForFloats(op.node)),
+            }
+        }
+        (Int(a), Int(b)) => {
+            match op.node {
+                hir::BiAdd => try!(const_int_checked_add(a,b,e,expr_int_type)),
+                hir::BiSub => try!(const_int_checked_sub(a,b,e,expr_int_type)),
+                hir::BiMul => try!(const_int_checked_mul(a,b,e,expr_int_type)),
+                hir::BiDiv => try!(const_int_checked_div(a,b,e,expr_int_type)),
+                hir::BiRem => try!(const_int_checked_rem(a,b,e,expr_int_type)),
+                hir::BiBitAnd => Int(a & b),
+                hir::BiBitOr => Int(a | b),
+                hir::BiBitXor => Int(a ^ b),
+                hir::BiShl => try!(const_int_checked_shl(a,b,e,expr_int_type)),
+                hir::BiShr => try!(const_int_checked_shr(a,b,e,expr_int_type)),
+                hir::BiEq => Bool(a == b),
+                hir::BiLt => Bool(a < b),
+                hir::BiLe => Bool(a <= b),
+                hir::BiNe => Bool(a != b),
+                hir::BiGe => Bool(a >= b),
+                hir::BiGt => Bool(a > b),
+                _ => signal!(e, InvalidOpForInts(op.node)),
+            }
+        }
+        (Uint(a), Uint(b)) => {
+            match op.node {
+                hir::BiAdd => try!(const_uint_checked_add(a,b,e,expr_uint_type)),
+                hir::BiSub => try!(const_uint_checked_sub(a,b,e,expr_uint_type)),
+                hir::BiMul => try!(const_uint_checked_mul(a,b,e,expr_uint_type)),
+                hir::BiDiv => try!(const_uint_checked_div(a,b,e,expr_uint_type)),
+                hir::BiRem => try!(const_uint_checked_rem(a,b,e,expr_uint_type)),
+                hir::BiBitAnd => Uint(a & b),
+                hir::BiBitOr => Uint(a | b),
+                hir::BiBitXor => Uint(a ^ b),
+                hir::BiShl => try!(const_uint_checked_shl(a,b,e,expr_uint_type)),
+                hir::BiShr => try!(const_uint_checked_shr(a,b,e,expr_uint_type)),
+                hir::BiEq => Bool(a == b),
+                hir::BiLt => Bool(a < b),
+                hir::BiLe => Bool(a <= b),
+                hir::BiNe => Bool(a != b),
+                hir::BiGe => Bool(a >= b),
+                hir::BiGt => Bool(a > b),
+                _ => signal!(e, InvalidOpForUInts(op.node)),
+            }
+        }
+        // shifts can have any integral type as their rhs
+        (Int(a), Uint(b)) => {
+            match op.node {
+                hir::BiShl => try!(const_int_checked_shl_via_uint(a,b,e,expr_int_type)),
+                hir::BiShr => try!(const_int_checked_shr_via_uint(a,b,e,expr_int_type)),
+                _ => signal!(e, InvalidOpForIntUint(op.node)),
+            }
+        }
+        (Uint(a), Int(b)) => {
+            match op.node {
+                hir::BiShl => try!(const_uint_checked_shl_via_int(a,b,e,expr_uint_type)),
+                hir::BiShr => try!(const_uint_checked_shr_via_int(a,b,e,expr_uint_type)),
+                _ => signal!(e, InvalidOpForUintInt(op.node)),
+            }
+        }
+        (Bool(a), Bool(b)) => {
+            Bool(match op.node {
+                hir::BiAnd => a && b,
+                hir::BiOr => a || b,
+                hir::BiBitXor => a ^ b,
+                hir::BiBitAnd => a & b,
+                hir::BiBitOr => a | b,
+                hir::BiEq => a == b,
+                hir::BiNe => a != b,
+                _ => signal!(e, InvalidOpForBools(op.node)),
+            })
+        }
+        _ => signal!(e, MiscBinaryOp),
+    })
+}
+
 /// Evaluate a constant expression in a context where the expression isn't
 /// guaranteed to be evaluatable. `ty_hint` is usually ExprTypeChecked,
 /// but a few places need to evaluate constants during type-checking, like
@@ -830,95 +973,16 @@ pub fn eval_const_expr_partial<'tcx>(tcx: &ty::ctxt<'tcx>,
             }
             _ => ty_hint
         };
-        match (try!(eval_const_expr_partial(tcx, &**a, ty_hint, fn_args)),
-               try!(eval_const_expr_partial(tcx, &**b, b_ty, fn_args))) {
-          (Float(a), Float(b)) => {
-            match op.node {
-              hir::BiAdd => Float(a + b),
-              hir::BiSub => Float(a - b),
-              hir::BiMul => Float(a * b),
-              hir::BiDiv => Float(a / b),
-              hir::BiRem => Float(a % b),
-              hir::BiEq => Bool(a == b),
-              hir::BiLt => Bool(a < b),
-              hir::BiLe => Bool(a <= b),
-              hir::BiNe => Bool(a != b),
-              hir::BiGe => Bool(a >= b),
-              hir::BiGt => Bool(a > b),
-              _ => signal!(e, InvalidOpForFloats(op.node)),
-            }
-          }
-          (Int(a), Int(b)) => {
-            match op.node {
-              hir::BiAdd => try!(const_int_checked_add(a,b,e,expr_int_type)),
-              hir::BiSub => try!(const_int_checked_sub(a,b,e,expr_int_type)),
-              hir::BiMul => try!(const_int_checked_mul(a,b,e,expr_int_type)),
-              hir::BiDiv => try!(const_int_checked_div(a,b,e,expr_int_type)),
-              hir::BiRem => try!(const_int_checked_rem(a,b,e,expr_int_type)),
-              hir::BiBitAnd => Int(a & b),
-              hir::BiBitOr => Int(a | b),
-              hir::BiBitXor => Int(a ^ b),
-              hir::BiShl => try!(const_int_checked_shl(a,b,e,expr_int_type)),
-              hir::BiShr => try!(const_int_checked_shr(a,b,e,expr_int_type)),
-              hir::BiEq => Bool(a == b),
-              hir::BiLt => Bool(a < b),
-              hir::BiLe => Bool(a <= b),
-              hir::BiNe => Bool(a != b),
-              hir::BiGe => Bool(a >= b),
-              hir::BiGt => Bool(a > b),
-              _ => signal!(e, InvalidOpForInts(op.node)),
-            }
-          }
-          (Uint(a), Uint(b)) => {
-            match op.node {
-              hir::BiAdd => try!(const_uint_checked_add(a,b,e,expr_uint_type)),
-              hir::BiSub => try!(const_uint_checked_sub(a,b,e,expr_uint_type)),
-              hir::BiMul => try!(const_uint_checked_mul(a,b,e,expr_uint_type)),
-              hir::BiDiv => try!(const_uint_checked_div(a,b,e,expr_uint_type)),
-              hir::BiRem => try!(const_uint_checked_rem(a,b,e,expr_uint_type)),
-              hir::BiBitAnd => Uint(a & b),
-              hir::BiBitOr => Uint(a | b),
-              hir::BiBitXor => Uint(a ^ b),
-              hir::BiShl => try!(const_uint_checked_shl(a,b,e,expr_uint_type)),
-              hir::BiShr => try!(const_uint_checked_shr(a,b,e,expr_uint_type)),
-              hir::BiEq => Bool(a == b),
-              hir::BiLt => Bool(a < b),
-              hir::BiLe => Bool(a <= b),
-              hir::BiNe => Bool(a != b),
-              hir::BiGe => Bool(a >= b),
-              hir::BiGt => Bool(a > b),
-              _ => signal!(e, InvalidOpForUInts(op.node)),
-            }
-          }
-          // shifts can have any integral type as their rhs
-          (Int(a), Uint(b)) => {
-            match op.node {
-              hir::BiShl => try!(const_int_checked_shl_via_uint(a,b,e,expr_int_type)),
-              hir::BiShr => try!(const_int_checked_shr_via_uint(a,b,e,expr_int_type)),
-              _ => signal!(e, InvalidOpForIntUint(op.node)),
-            }
-          }
-          (Uint(a), Int(b)) => {
-            match op.node {
-              hir::BiShl => try!(const_uint_checked_shl_via_int(a,b,e,expr_uint_type)),
-              hir::BiShr => try!(const_uint_checked_shr_via_int(a,b,e,expr_uint_type)),
-              _ => signal!(e, InvalidOpForUintInt(op.node)),
+        let a_val = try!(eval_const_expr_partial(tcx, &**a, ty_hint, fn_args));
+        let b_val = try!(eval_const_expr_partial(tcx, &**b, b_ty, fn_args));
+        match eval_const_binop(e, op, a_val.clone(), b_val.clone(),
+                               expr_int_type, expr_uint_type) {
+            Ok(val) => val,
+            Err(mut err) => {
+                err.note_operand(a, &a_val);
+                err.note_operand(b, &b_val);
+                return Err(err);
             }
-          }
-          (Bool(a), Bool(b)) => {
-            Bool(match op.node {
-              hir::BiAnd => a && b,
-              hir::BiOr => a || b,
-              hir::BiBitXor => a ^ b,
-              hir::BiBitAnd => a & b,
-              hir::BiBitOr => a | b,
-              hir::BiEq => a == b,
-              hir::BiNe => a != b,
-              _ => signal!(e, InvalidOpForBools(op.node)),
-             })
-          }
-
-          _ => signal!(e, MiscBinaryOp),
         }
       }
       hir::ExprCast(ref base, ref target_ty) => {
@@ -943,10 +1007,10 @@ pub fn eval_const_expr_partial<'tcx>(tcx: &ty::ctxt<'tcx>,
         let val = try!(eval_const_expr_partial(tcx, &**base, base_hint, fn_args));
         match cast_const(tcx, val, ety) {
             Ok(val) => val,
-            Err(kind) => return Err(ConstEvalErr { span: e.span, kind: kind }),
+            Err(kind) => return Err(ConstEvalErr { span: e.span, kind: kind, notes: Vec::new() }),
         }
       }
-      hir::ExprPath(..) => {
+      hir::ExprPath(_, ref path) => {
           let opt_def = if let Some(def) = tcx.def_map.borrow().get(&e.id) {
               // After type-checking, def_map contains definition of the
               // item referred to by the path. During type-checking, it
@@ -1045,7 +1109,14 @@ pub fn eval_const_expr_partial<'tcx>(tcx: &ty::ctxt<'tcx>,
           } else {
               ty_hint
           };
-          try!(eval_const_expr_partial(tcx, const_expr, item_hint, fn_args))
+          match eval_const_expr_partial(tcx, const_expr, item_hint, fn_args) {
+              Ok(val) => val,
+              Err(mut err) => {
+                  err.notes.push((e.span, format!("while evaluating the constant `{}`",
+                                                  path_to_string(path))));
+                  return Err(err);
+              }
+          }
       }
       hir::ExprCall(ref callee, ref args) => {
           let sub_ty_hint = if let ExprTypeChecked = ty_hint {
@@ -1340,6 +1411,7 @@ pub fn compare_lit_exprs<'tcx>(tcx: &ty::ctxt<'tcx>,
         Ok(a) => a,
         Err(e) => {
             tcx.sess.span_err(a.span, &e.description());
+            e.note(tcx.sess);
             return None;
         }
     };
@@ -1347,6 +1419,7 @@ pub fn compare_lit_exprs<'tcx>(tcx: &ty::ctxt<'tcx>,
         Ok(b) => b,
         Err(e) => {
             tcx.sess.span_err(b.span, &e.description());
+            e.note(tcx.sess);
             return None;
         }
     };
@@ -498,11 +498,17 @@ impl<'a, 'tcx> ErrorReporting<'tcx> for InferCtxt<'a, 'tcx> {
                 // We compare strings because PathMod and PathName can be different
                 // for imported and non-imported crates
                 if exp_path == found_path {
-                    let crate_name = self.tcx.sess.cstore
-                                         .get_crate_data(did1.krate).name();
+                    let crate1 = self.tcx.sess.cstore.get_crate_data(did1.krate);
+                    let crate2 = self.tcx.sess.cstore.get_crate_data(did2.krate);
                     self.tcx.sess.span_note(sp, &format!("Perhaps two different versions \
                                                           of crate `{}` are being used?",
-                                                          crate_name));
+                                                          crate1.name()));
+                    if crate1.disambiguator() != crate2.disambiguator() {
+                        self.tcx.sess.fileline_help(sp, &format!(
+                            "the two copies were compiled with different `-C metadata` \
+                             values (crate disambiguators `{}` and `{}`)",
+                            crate1.disambiguator(), crate2.disambiguator()));
+                    }
                 }
             }
         };
@@ -713,6 +713,8 @@ impl<'a, 'tcx> InferCtxt<'a, 'tcx> {
 
     fn rollback_to(&self, cause: &str, snapshot: CombinedSnapshot) {
         debug!("rollback_to(cause={})", cause);
+        let rollbacks = &self.tcx.sess.perf_stats.snapshot_rollbacks;
+        rollbacks.set(rollbacks.get() + 1);
         let CombinedSnapshot { type_snapshot,
                                int_snapshot,
                                float_snapshot,
@@ -1247,7 +1249,8 @@ impl<'a, 'tcx> InferCtxt<'a, 'tcx> {
     // An invariant is that anytime the expected or actual type is TyError (the special
     // error type, meaning that an error occurred when typechecking this expression),
     // this is a derived error. The error cascaded from another error (that was already
-    // reported), so it's not useful to display it to the user.
+    // reported), so it's not useful to display it to the user, unless
+    // `-Z show-cascading-errors` is given.
     // The following four methods -- type_error_message_str, type_error_message_str_with_expected,
     // type_error_message, and report_mismatched_types -- implement this logic.
     // They check if either the actual or expected type is TyError, and don't print the error
@@ -1276,7 +1279,7 @@ impl<'a, 'tcx> InferCtxt<'a, 'tcx> {
 
         let resolved_expected = expected_ty.map(|e_ty| self.resolve_type_vars_if_possible(&e_ty));
 
-        if !resolved_expected.references_error() {
+        if !self.tcx.sess.suppress_cascading_error(resolved_expected.references_error()) {
             let error_str = err.map_or("".to_string(), |t_err| {
                 format!(" ({})", t_err)
             });
@@ -1301,7 +1304,7 @@ impl<'a, 'tcx> InferCtxt<'a, 'tcx> {
         let actual_ty = self.resolve_type_vars_if_possible(&actual_ty);
 
         // Don't report an error if actual type is TyError.
-        if actual_ty.references_error() {
+        if self.tcx.sess.suppress_cascading_error(actual_ty.references_error()) {
             return;
         }
 
@@ -1618,6 +1618,10 @@ impl<'a, 'tcx> Liveness<'a, 'tcx> {
     }
 
     fn report_dead_assign(&self, id: NodeId, sp: Span, var: Variable, is_argument: bool) {
+        if self.ir.tcx.sess.opts.debugging_opts.mir_lints {
+            // dead assignments are reported from the MIR instead
+            return;
+        }
         if let Some(name) = self.should_warn(var) {
             if is_argument {
                 self.ir.tcx.sess.add_lint(lint::builtin::UNUSED_ASSIGNMENTS, id, sp,
@@ -0,0 +1,109 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! `-Z module-times`: attributes the time spent type checking and
+//! translating each item to the module containing it.
+//!
+//! LLVM works on the whole crate (or codegen unit) at once, so its time
+//! can't be measured per item. Trans records how many LLVM instructions it
+//! generated for each module instead, and the driver divides the time spent
+//! in LLVM between the modules accordingly when it prints the report.
+
+use middle::ty;
+use session::{ModuleTimes, Session};
+
+use std::time::Duration;
+use syntax::ast::{NodeId, CRATE_NODE_ID};
+
+/// The compiler passes that are timed per item.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Pass {
+    Typeck,
+    Trans,
+}
+
+/// The path of the module containing `id`, which is used to identify the
+/// module in the report.
+pub fn module_name(tcx: &ty::ctxt, id: NodeId) -> String {
+    let module = tcx.map.get_module_parent(id);
+    if module == CRATE_NODE_ID {
+        "<crate root>".to_string()
+    } else {
+        tcx.map.path_to_string(module)
+    }
+}
+
+fn with_module_times<F>(tcx: &ty::ctxt, id: NodeId, f: F)
+    where F: FnOnce(&mut ModuleTimes)
+{
+    let module = module_name(tcx, id);
+    f(tcx.sess.module_times.borrow_mut().entry(module).or_insert(ModuleTimes::default()))
+}
+
+fn timed<T, F>(f: F) -> (T, f64)
+    where F: FnOnce() -> T
+{
+    let mut rv = None;
+    let dur = {
+        let rvp = &mut rv;
+        Duration::span(move || *rvp = Some(f()))
+    };
+
+    const NANOS_PER_SEC: f64 = 1_000_000_000.0;
+    (rv.unwrap(), dur.as_secs() as f64 + dur.subsec_nanos() as f64 / NANOS_PER_SEC)
+}
+
+/// Runs `f`, the `pass` for the item `id`, adding the time it takes to the
+/// module of the item when `-Z module-times` is on. `f` must not time the
+/// items nested in the item itself, or they would be counted twice.
+pub fn time_item<T, F>(tcx: &ty::ctxt, pass: Pass, id: NodeId, f: F) -> T
+    where F: FnOnce() -> T
+{
+    if !tcx.sess.module_times() {
+        return f();
+    }
+
+    let (rv, secs) = timed(f);
+    with_module_times(tcx, id, |times| {
+        match pass {
+            Pass::Typeck => times.typeck += secs,
+            Pass::Trans => times.trans += secs,
+        }
+    });
+
+    rv
+}
+
+/// Adds `insns` LLVM instructions generated for the item `id` to its module.
+pub fn record_llvm_insns(tcx: &ty::ctxt, id: NodeId, insns: usize) {
+    with_module_times(tcx, id, |times| times.llvm_insns += insns);
+}
+
+/// Adds LLVM instructions that don't belong to any item of the crate, e.g.
+/// drop glue or code inlined from other crates.
+pub fn record_other_llvm_insns(tcx: &ty::ctxt, insns: usize) {
+    let mut module_times = tcx.sess.module_times.borrow_mut();
+    let times = module_times.entry("<glue and inlined code>".to_string())
+                            .or_insert(ModuleTimes::default());
+    times.llvm_insns += insns;
+}
+
+/// Runs `f`, the LLVM passes, and prints the report when `-Z module-times`
+/// is on.
+pub fn time_llvm<F>(sess: &Session, f: F)
+    where F: FnOnce()
+{
+    if !sess.module_times() {
+        return f();
+    }
+
+    let ((), secs) = timed(f);
+    sess.print_module_times(secs);
+}
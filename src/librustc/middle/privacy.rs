@@ -17,7 +17,8 @@ pub use self::ImportUse::*;
 pub use self::LastPrivate::*;
 
 use middle::def_id::DefId;
-use util::nodemap::{DefIdSet, NodeSet};
+use util::nodemap::{DefIdSet, NodeMap, NodeSet};
+use syntax::ast::NodeId;
 
 /// A set of AST nodes exported by the crate.
 pub type ExportedItems = NodeSet;
@@ -31,6 +32,11 @@ pub type ExternalExports = DefIdSet;
 /// reexporting a public struct doesn't inline the doc).
 pub type PublicItems = NodeSet;
 
+/// Maps a single import that takes its name from an import of another module,
+/// rather than from an item, to that other import. Following it from an
+/// import gives the chain of re-exports the import reaches its item through.
+pub type ImportSources = NodeMap<NodeId>;
+
 #[derive(Copy, Clone, Debug)]
 pub enum LastPrivate {
     LastMod(PrivateDep),
@@ -25,8 +25,8 @@ use syntax::parse::token::InternedString;
 use syntax::codemap::{Span, DUMMY_SP};
 use syntax::ast;
 use syntax::ast::{NodeId, Attribute};
-use syntax::feature_gate::{GateIssue, emit_feature_err};
-use syntax::attr::{self, Stability, AttrMetaMethods};
+use syntax::feature_gate::{GateIssue, emit_feature_err, find_similar_lang_feature};
+use syntax::attr::{self, Stability, Deprecation, AttrMetaMethods};
 use util::nodemap::{DefIdMap, FnvHashSet, FnvHashMap};
 
 use rustc_front::hir;
@@ -64,6 +64,10 @@ pub struct Index<'tcx> {
     /// are filled by the annotator.
     map: DefIdMap<Option<&'tcx Stability>>,
 
+    /// Like `map`, but for the `#[deprecated]` attributes of crates that
+    /// aren't part of the staged API.
+    depr_map: DefIdMap<Option<Deprecation>>,
+
     /// Maps for each crate whether it is part of the staged API.
     staged_api: FnvHashMap<ast::CrateNum, bool>
 }
@@ -73,6 +77,7 @@ struct Annotator<'a, 'tcx: 'a> {
     tcx: &'a ty::ctxt<'tcx>,
     index: &'a mut Index<'tcx>,
     parent: Option<&'tcx Stability>,
+    parent_depr: Option<Deprecation>,
     export_map: &'a PublicItems,
     in_trait_impl: bool,
     in_enum: bool,
@@ -110,7 +115,7 @@ impl<'a, 'tcx: 'a> Annotator<'a, 'tcx> {
 
                 // Check if deprecated_since < stable_since. If it is,
                 // this is *almost surely* an accident.
-                if let (&Some(attr::Deprecation {since: ref dep_since, ..}),
+                if let (&Some(attr::RustcDeprecation {since: ref dep_since, ..}),
                         &attr::Stable {since: ref stab_since}) = (&stab.depr, &stab.level) {
                     // Explicit version of iter::order::lt to handle parse errors properly
                     for (dep_v, stab_v) in dep_since.split(".").zip(stab_since.split(".")) {
@@ -162,13 +167,25 @@ impl<'a, 'tcx: 'a> Annotator<'a, 'tcx> {
             // Emit errors for non-staged-api crates.
             for attr in attrs {
                 let tag = attr.name();
-                if tag == "unstable" || tag == "stable" || tag == "deprecated" {
+                if tag == "unstable" || tag == "stable" {
                     attr::mark_used(attr);
                     self.tcx.sess.span_err(attr.span(), "stability attributes may not be used \
                                                          outside of the standard library");
                 }
             }
+
+            // Everything inside a deprecated item is deprecated too,
+            // unless it says otherwise.
+            let depr = attr::find_deprecation(self.tcx.sess.diagnostic(), attrs, item_sp);
+            let depr = depr.or_else(|| self.parent_depr.clone());
+            if depr.is_some() {
+                let def_id = self.tcx.map.local_def_id(id);
+                self.index.depr_map.insert(def_id, depr.clone());
+            }
+
+            let parent_depr = replace(&mut self.parent_depr, depr);
             visit_children(self);
+            self.parent_depr = parent_depr;
         }
     }
 }
@@ -271,6 +288,7 @@ impl<'tcx> Index<'tcx> {
             tcx: tcx,
             index: self,
             parent: None,
+            parent_depr: None,
             export_map: export_map,
             in_trait_impl: false,
             in_enum: false,
@@ -295,6 +313,7 @@ impl<'tcx> Index<'tcx> {
         Index {
             staged_api: staged_api,
             map: DefIdMap(),
+            depr_map: DefIdMap(),
         }
     }
 }
@@ -337,6 +356,12 @@ impl<'a, 'tcx> Checker<'a, 'tcx> {
             return
         }
 
+        // Crates outside the staged API have no stability levels to
+        // check, only deprecations, which are handled by a lint.
+        if !is_staged_api(self.tcx, id) {
+            return
+        }
+
         // We don't need to check for stability - presumably compiler generated code.
         if self.in_skip_block > 0 {
             return;
@@ -398,31 +423,31 @@ impl<'a, 'v, 'tcx> Visitor<'v> for Checker<'a, 'tcx> {
         if item.span == DUMMY_SP && item.name.as_str() == "__test" { return }
 
         check_item(self.tcx, item, true,
-                   &mut |id, sp, stab| self.check(id, sp, stab));
+                   &mut |id, sp, stab, _| self.check(id, sp, stab));
         intravisit::walk_item(self, item);
     }
 
     fn visit_expr(&mut self, ex: &hir::Expr) {
         check_expr(self.tcx, ex,
-                   &mut |id, sp, stab| self.check(id, sp, stab));
+                   &mut |id, sp, stab, _| self.check(id, sp, stab));
         intravisit::walk_expr(self, ex);
     }
 
     fn visit_path(&mut self, path: &hir::Path, id: ast::NodeId) {
         check_path(self.tcx, path, id,
-                   &mut |id, sp, stab| self.check(id, sp, stab));
+                   &mut |id, sp, stab, _| self.check(id, sp, stab));
         intravisit::walk_path(self, path)
     }
 
     fn visit_path_list_item(&mut self, prefix: &hir::Path, item: &hir::PathListItem) {
         check_path_list_item(self.tcx, item,
-                   &mut |id, sp, stab| self.check(id, sp, stab));
+                   &mut |id, sp, stab, _| self.check(id, sp, stab));
         intravisit::walk_path_list_item(self, prefix, item)
     }
 
     fn visit_pat(&mut self, pat: &hir::Pat) {
         check_pat(self.tcx, pat,
-                  &mut |id, sp, stab| self.check(id, sp, stab));
+                  &mut |id, sp, stab, _| self.check(id, sp, stab));
         intravisit::walk_pat(self, pat)
     }
 
@@ -444,7 +469,7 @@ impl<'a, 'v, 'tcx> Visitor<'v> for Checker<'a, 'tcx> {
 
 /// Helper for discovering nodes to check for stability
 pub fn check_item(tcx: &ty::ctxt, item: &hir::Item, warn_about_defns: bool,
-                  cb: &mut FnMut(DefId, Span, &Option<&Stability>)) {
+                  cb: &mut FnMut(DefId, Span, &Option<&Stability>, &Option<Deprecation>)) {
     match item.node {
         hir::ItemExternCrate(_) => {
             // compiler-generated `extern crate` items have a dummy span.
@@ -481,7 +506,7 @@ pub fn check_item(tcx: &ty::ctxt, item: &hir::Item, warn_about_defns: bool,
 
 /// Helper for discovering nodes to check for stability
 pub fn check_expr(tcx: &ty::ctxt, e: &hir::Expr,
-                  cb: &mut FnMut(DefId, Span, &Option<&Stability>)) {
+                  cb: &mut FnMut(DefId, Span, &Option<&Stability>, &Option<Deprecation>)) {
     let span;
     let id = match e.node {
         hir::ExprMethodCall(i, _, _) => {
@@ -542,7 +567,7 @@ pub fn check_expr(tcx: &ty::ctxt, e: &hir::Expr,
 }
 
 pub fn check_path(tcx: &ty::ctxt, path: &hir::Path, id: ast::NodeId,
-                  cb: &mut FnMut(DefId, Span, &Option<&Stability>)) {
+                  cb: &mut FnMut(DefId, Span, &Option<&Stability>, &Option<Deprecation>)) {
     match tcx.def_map.borrow().get(&id).map(|d| d.full_def()) {
         Some(def::DefPrimTy(..)) => {}
         Some(def::DefSelfTy(..)) => {}
@@ -554,7 +579,7 @@ pub fn check_path(tcx: &ty::ctxt, path: &hir::Path, id: ast::NodeId,
 }
 
 pub fn check_path_list_item(tcx: &ty::ctxt, item: &hir::PathListItem,
-                  cb: &mut FnMut(DefId, Span, &Option<&Stability>)) {
+                  cb: &mut FnMut(DefId, Span, &Option<&Stability>, &Option<Deprecation>)) {
     match tcx.def_map.borrow().get(&item.node.id()).map(|d| d.full_def()) {
         Some(def::DefPrimTy(..)) => {}
         Some(def) => {
@@ -565,7 +590,7 @@ pub fn check_path_list_item(tcx: &ty::ctxt, item: &hir::PathListItem,
 }
 
 pub fn check_pat(tcx: &ty::ctxt, pat: &hir::Pat,
-                 cb: &mut FnMut(DefId, Span, &Option<&Stability>)) {
+                 cb: &mut FnMut(DefId, Span, &Option<&Stability>, &Option<Deprecation>)) {
     debug!("check_pat(pat = {:?})", pat);
     if is_internal(tcx, pat.span) { return; }
 
@@ -594,21 +619,22 @@ pub fn check_pat(tcx: &ty::ctxt, pat: &hir::Pat,
 }
 
 fn maybe_do_stability_check(tcx: &ty::ctxt, id: DefId, span: Span,
-                            cb: &mut FnMut(DefId, Span, &Option<&Stability>)) {
-    if !is_staged_api(tcx, id) {
-        debug!("maybe_do_stability_check: \
-                skipping id={:?} since it is not staged_api", id);
-        return;
-    }
+                            cb: &mut FnMut(DefId, Span, &Option<&Stability>,
+                                           &Option<Deprecation>)) {
     if is_internal(tcx, span) {
         debug!("maybe_do_stability_check: \
                 skipping span={:?} since it is internal", span);
         return;
     }
-    let ref stability = lookup(tcx, id);
+    let (ref stability, ref deprecation) = if is_staged_api(tcx, id) {
+        (lookup(tcx, id), None)
+    } else {
+        (None, lookup_deprecation(tcx, id))
+    };
     debug!("maybe_do_stability_check: \
-            inspecting id={:?} span={:?} of stability={:?}", id, span, stability);
-    cb(id, span, stability);
+            inspecting id={:?} span={:?} of stability={:?} deprecation={:?}",
+           id, span, stability, deprecation);
+    cb(id, span, stability, deprecation);
 }
 
 fn is_internal(tcx: &ty::ctxt, span: Span) -> bool {
@@ -673,6 +699,30 @@ fn lookup_uncached<'tcx>(tcx: &ty::ctxt<'tcx>, id: DefId) -> Option<&'tcx Stabil
     })
 }
 
+/// Lookup the `#[deprecated]` attribute of an item in a crate outside the
+/// staged API, loading external crate metadata as necessary.
+pub fn lookup_deprecation(tcx: &ty::ctxt, id: DefId) -> Option<Deprecation> {
+    if let Some(depr) = tcx.stability.borrow().depr_map.get(&id) {
+        return depr.clone();
+    }
+
+    // is this definition the implementation of a trait method?
+    match tcx.trait_item_of_item(id) {
+        Some(ty::MethodTraitItemId(trait_method_id)) if trait_method_id != id => {
+            return lookup_deprecation(tcx, trait_method_id);
+        }
+        _ => {}
+    }
+
+    let depr = if id.is_local() {
+        None // Local deprecations are all filled in by the annotator
+    } else {
+        csearch::get_deprecation(&tcx.sess.cstore, id)
+    };
+    tcx.stability.borrow_mut().depr_map.insert(id, depr.clone());
+    depr
+}
+
 /// Given the list of enabled features that were not language features (i.e. that
 /// were expected to be library features), and the list of features used from
 /// libraries, identify activated features that don't exist and error about them.
@@ -706,10 +756,14 @@ pub fn check_unused_or_stable_features(sess: &Session,
         }
     }
 
-    for &span in remaining_lib_features.values() {
+    for (name, &span) in &remaining_lib_features {
+        let msg = match find_similar_lang_feature(name) {
+            Some(similar) => format!("unused or unknown feature; did you mean `{}`?", similar),
+            None => "unused or unknown feature".to_string(),
+        };
         sess.add_lint(lint::builtin::UNUSED_FEATURES,
                       ast::CRATE_NODE_ID,
                       span,
-                      "unused or unknown feature".to_string());
+                      msg);
     }
 }
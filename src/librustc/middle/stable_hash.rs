@@ -0,0 +1,255 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! `HashStable` for the HIR and for types, so that items and their types
+//! can be fingerprinted the same way in every compilation session.
+//!
+//! Names are hashed by their string, definitions by the hash of their def
+//! path, and spans and node ids are left out, so a fingerprint changes when
+//! the thing it describes does, and not when an unrelated item is added or
+//! the interners happen to be filled in a different order. This is what
+//! both incremental compilation and reproducible metadata need.
+
+use middle::def_id::DefId;
+use middle::subst::{self, Substs, VecPerParamSpace};
+use middle::ty::{self, Ty};
+
+use rustc_back::svh;
+use rustc_data_structures::stable_hasher::{HashStable, StableHasher};
+use rustc_front::hir;
+
+use std::hash::{Hash, Hasher};
+use syntax::ast;
+use syntax::codemap::Span;
+use syntax::parse::token::InternedString;
+
+/// The context the `HashStable` implementations of this module need.
+pub struct StableHashingContext<'a, 'tcx: 'a> {
+    tcx: &'a ty::ctxt<'tcx>,
+}
+
+impl<'a, 'tcx> StableHashingContext<'a, 'tcx> {
+    pub fn new(tcx: &'a ty::ctxt<'tcx>) -> StableHashingContext<'a, 'tcx> {
+        StableHashingContext { tcx: tcx }
+    }
+
+    pub fn tcx(&self) -> &'a ty::ctxt<'tcx> {
+        self.tcx
+    }
+}
+
+/// The fingerprint of `value`.
+pub fn fingerprint<'a, 'tcx, T>(tcx: &'a ty::ctxt<'tcx>, value: &T) -> u64
+    where T: ?Sized + HashStable<StableHashingContext<'a, 'tcx>>
+{
+    let mut hcx = StableHashingContext::new(tcx);
+    let mut hasher = StableHasher::new();
+    value.hash_stable(&mut hcx, &mut hasher);
+    hasher.finish()
+}
+
+impl<'a, 'tcx> HashStable<StableHashingContext<'a, 'tcx>> for ast::Name {
+    fn hash_stable(&self, _: &mut StableHashingContext<'a, 'tcx>, hasher: &mut StableHasher) {
+        self.as_str()[..].hash(hasher);
+    }
+}
+
+impl<'a, 'tcx> HashStable<StableHashingContext<'a, 'tcx>> for InternedString {
+    fn hash_stable(&self, _: &mut StableHashingContext<'a, 'tcx>, hasher: &mut StableHasher) {
+        self[..].hash(hasher);
+    }
+}
+
+impl<'a, 'tcx> HashStable<StableHashingContext<'a, 'tcx>> for Span {
+    // Moving code around must not change the fingerprints of items.
+    fn hash_stable(&self, _: &mut StableHashingContext<'a, 'tcx>, _: &mut StableHasher) {}
+}
+
+impl<'a, 'tcx> HashStable<StableHashingContext<'a, 'tcx>> for DefId {
+    fn hash_stable(&self, hcx: &mut StableHashingContext<'a, 'tcx>, hasher: &mut StableHasher) {
+        hcx.tcx.def_path_hash(*self).hash(hasher);
+    }
+}
+
+impl<'a, 'tcx> HashStable<StableHashingContext<'a, 'tcx>> for hir::Item {
+    // Nested items are left out, they have fingerprints of their own.
+    fn hash_stable(&self, hcx: &mut StableHashingContext<'a, 'tcx>, hasher: &mut StableHasher) {
+        svh::hash_item(hcx.tcx.map.krate(), self, hasher);
+    }
+}
+
+impl<'a, 'tcx, T> HashStable<StableHashingContext<'a, 'tcx>> for VecPerParamSpace<T>
+    where T: HashStable<StableHashingContext<'a, 'tcx>>
+{
+    fn hash_stable(&self, hcx: &mut StableHashingContext<'a, 'tcx>, hasher: &mut StableHasher) {
+        for &space in &subst::ParamSpace::all() {
+            self.get_slice(space).hash_stable(hcx, hasher);
+        }
+    }
+}
+
+impl<'a, 'tcx> HashStable<StableHashingContext<'a, 'tcx>> for Substs<'tcx> {
+    fn hash_stable(&self, hcx: &mut StableHashingContext<'a, 'tcx>, hasher: &mut StableHasher) {
+        self.types.hash_stable(hcx, hasher);
+        match self.regions {
+            subst::ErasedRegions => 0u8.hash(hasher),
+            subst::NonerasedRegions(ref regions) => {
+                1u8.hash(hasher);
+                regions.hash_stable(hcx, hasher);
+            }
+        }
+    }
+}
+
+impl<'a, 'tcx> HashStable<StableHashingContext<'a, 'tcx>> for ty::BoundRegion {
+    fn hash_stable(&self, hcx: &mut StableHashingContext<'a, 'tcx>, hasher: &mut StableHasher) {
+        match *self {
+            ty::BrAnon(index) => (0u8, index).hash_stable(hcx, hasher),
+            ty::BrNamed(def_id, name) => (1u8, def_id, name).hash_stable(hcx, hasher),
+            ty::BrFresh(index) => (2u8, index).hash_stable(hcx, hasher),
+            ty::BrEnv => 3u8.hash(hasher),
+        }
+    }
+}
+
+impl<'a, 'tcx> HashStable<StableHashingContext<'a, 'tcx>> for ty::Region {
+    fn hash_stable(&self, hcx: &mut StableHashingContext<'a, 'tcx>, hasher: &mut StableHasher) {
+        match *self {
+            ty::ReEarlyBound(ref data) => {
+                0u8.hash(hasher);
+                data.def_id.hash_stable(hcx, hasher);
+                data.space.hash(hasher);
+                data.index.hash(hasher);
+                data.name.hash_stable(hcx, hasher);
+            }
+            ty::ReLateBound(debruijn, ref bound) => {
+                1u8.hash(hasher);
+                debruijn.depth.hash(hasher);
+                bound.hash_stable(hcx, hasher);
+            }
+            // Code extents are numbered by the order of the nodes in the
+            // crate, so only the bound region of a free region is stable.
+            ty::ReFree(ref free) => {
+                2u8.hash(hasher);
+                free.bound_region.hash_stable(hcx, hasher);
+            }
+            ty::ReScope(..) => 3u8.hash(hasher),
+            ty::ReStatic => 4u8.hash(hasher),
+            ty::ReEmpty => 5u8.hash(hasher),
+            // Inference variables don't outlive type checking.
+            ty::ReVar(..) | ty::ReSkolemized(..) => {
+                hcx.tcx.sess.bug(&format!("stable hash of inference region {:?}", self))
+            }
+        }
+    }
+}
+
+impl<'a, 'tcx> HashStable<StableHashingContext<'a, 'tcx>> for ty::TraitRef<'tcx> {
+    fn hash_stable(&self, hcx: &mut StableHashingContext<'a, 'tcx>, hasher: &mut StableHasher) {
+        self.def_id.hash_stable(hcx, hasher);
+        self.substs.hash_stable(hcx, hasher);
+    }
+}
+
+impl<'a, 'tcx> HashStable<StableHashingContext<'a, 'tcx>> for ty::ProjectionTy<'tcx> {
+    fn hash_stable(&self, hcx: &mut StableHashingContext<'a, 'tcx>, hasher: &mut StableHasher) {
+        self.trait_ref.hash_stable(hcx, hasher);
+        self.item_name.hash_stable(hcx, hasher);
+    }
+}
+
+impl<'a, 'tcx> HashStable<StableHashingContext<'a, 'tcx>> for ty::FnSig<'tcx> {
+    fn hash_stable(&self, hcx: &mut StableHashingContext<'a, 'tcx>, hasher: &mut StableHasher) {
+        self.inputs.hash_stable(hcx, hasher);
+        match self.output {
+            ty::FnConverging(output) => (0u8, output).hash_stable(hcx, hasher),
+            ty::FnDiverging => 1u8.hash(hasher),
+        }
+        self.variadic.hash(hasher);
+    }
+}
+
+impl<'a, 'tcx> HashStable<StableHashingContext<'a, 'tcx>> for ty::TraitTy<'tcx> {
+    fn hash_stable(&self, hcx: &mut StableHashingContext<'a, 'tcx>, hasher: &mut StableHasher) {
+        self.principal.0.hash_stable(hcx, hasher);
+        self.bounds.region_bound.hash_stable(hcx, hasher);
+        let builtin_bounds = self.bounds.builtin_bounds.iter().map(|bound| bound as u8);
+        builtin_bounds.collect::<Vec<_>>().hash(hasher);
+        // Projection bounds are sorted by the name of their item already.
+        self.bounds.projection_bounds.len().hash(hasher);
+        for bound in &self.bounds.projection_bounds {
+            bound.0.projection_ty.hash_stable(hcx, hasher);
+            bound.0.ty.hash_stable(hcx, hasher);
+        }
+    }
+}
+
+impl<'a, 'tcx> HashStable<StableHashingContext<'a, 'tcx>> for ty::TyS<'tcx> {
+    fn hash_stable(&self, hcx: &mut StableHashingContext<'a, 'tcx>, hasher: &mut StableHasher) {
+        match self.sty {
+            ty::TyBool => 0u8.hash(hasher),
+            ty::TyChar => 1u8.hash(hasher),
+            ty::TyInt(t) => (2u8, t as u8).hash(hasher),
+            ty::TyUint(t) => (3u8, t as u8).hash(hasher),
+            ty::TyFloat(t) => (4u8, t as u8).hash(hasher),
+            ty::TyEnum(def, substs) => {
+                5u8.hash(hasher);
+                def.did.hash_stable(hcx, hasher);
+                substs.hash_stable(hcx, hasher);
+            }
+            ty::TyStruct(def, substs) => {
+                6u8.hash(hasher);
+                def.did.hash_stable(hcx, hasher);
+                substs.hash_stable(hcx, hasher);
+            }
+            ty::TyBox(t) => (7u8, t).hash_stable(hcx, hasher),
+            ty::TyStr => 8u8.hash(hasher),
+            ty::TyArray(t, len) => (9u8, t, len).hash_stable(hcx, hasher),
+            ty::TySlice(t) => (10u8, t).hash_stable(hcx, hasher),
+            ty::TyRawPtr(mt) => (11u8, mt.mutbl as u8, mt.ty).hash_stable(hcx, hasher),
+            ty::TyRef(region, mt) => {
+                12u8.hash(hasher);
+                region.hash_stable(hcx, hasher);
+                (mt.mutbl as u8, mt.ty).hash_stable(hcx, hasher);
+            }
+            ty::TyBareFn(def_id, fty) => {
+                13u8.hash(hasher);
+                def_id.hash_stable(hcx, hasher);
+                (fty.unsafety as u8).hash(hasher);
+                fty.abi.name().hash(hasher);
+                fty.sig.0.hash_stable(hcx, hasher);
+            }
+            ty::TyTrait(ref data) => (14u8, data).hash_stable(hcx, hasher),
+            ty::TyClosure(def_id, ref substs) => {
+                15u8.hash(hasher);
+                def_id.hash_stable(hcx, hasher);
+                substs.func_substs.hash_stable(hcx, hasher);
+                substs.upvar_tys.hash_stable(hcx, hasher);
+            }
+            ty::TyTuple(ref tys) => (16u8, tys).hash_stable(hcx, hasher),
+            ty::TyProjection(ref data) => (17u8, data).hash_stable(hcx, hasher),
+            ty::TyParam(ref p) => {
+                18u8.hash(hasher);
+                p.space.hash(hasher);
+                p.idx.hash(hasher);
+                p.name.hash_stable(hcx, hasher);
+            }
+            ty::TyInfer(..) | ty::TyError => {
+                hcx.tcx.sess.bug(&format!("stable hash of type `{:?}`", self))
+            }
+        }
+    }
+}
+
+/// The fingerprint of a type, e.g. to tell whether the signature of an
+/// item changed between two sessions.
+pub fn type_fingerprint<'tcx>(tcx: &ty::ctxt<'tcx>, ty: Ty<'tcx>) -> u64 {
+    fingerprint(tcx, &ty)
+}
@@ -99,7 +99,7 @@ pub fn report_projection_error<'a, 'tcx>(infcx: &InferCtxt<'a, 'tcx>,
     // like `$X = <() as Foo<$X>>::Out` and () does not implement Foo<_>,
     // then $X will be unified with TyError, but the error still needs to be
     // reported.
-    if !infcx.tcx.sess.has_errors() || !predicate.references_error() {
+    if !infcx.tcx.sess.suppress_cascading_error(predicate.references_error()) {
         span_err_or_warn!(
             is_warning(obligation), infcx.tcx.sess, obligation.cause.span, E0271,
             "type mismatch resolving `{}`: {}",
@@ -223,7 +223,8 @@ pub fn report_selection_error<'a, 'tcx>(infcx: &InferCtxt<'a, 'tcx>,
                         let trait_predicate =
                             infcx.resolve_type_vars_if_possible(trait_predicate);
 
-                        if !infcx.tcx.sess.has_errors() || !trait_predicate.references_error() {
+                        let references_error = trait_predicate.references_error();
+                        if !infcx.tcx.sess.suppress_cascading_error(references_error) {
                             let trait_ref = trait_predicate.to_poly_trait_ref();
                             span_err_or_warn!(
                                 is_warning, infcx.tcx.sess, obligation.cause.span, E0277,
@@ -303,7 +304,8 @@ pub fn report_selection_error<'a, 'tcx>(infcx: &InferCtxt<'a, 'tcx>,
         OutputTypeParameterMismatch(ref expected_trait_ref, ref actual_trait_ref, ref e) => {
             let expected_trait_ref = infcx.resolve_type_vars_if_possible(&*expected_trait_ref);
             let actual_trait_ref = infcx.resolve_type_vars_if_possible(&*actual_trait_ref);
-            if !actual_trait_ref.self_ty().references_error() {
+            let references_error = actual_trait_ref.self_ty().references_error();
+            if !infcx.tcx.sess.suppress_cascading_error(references_error) {
                 span_err_or_warn!(
                     is_warning, infcx.tcx.sess, obligation.cause.span, E0281,
                     "type mismatch: the type `{}` implements the trait `{}`, \
@@ -254,6 +254,20 @@ pub struct EvaluationCache<'tcx> {
     hashmap: RefCell<FnvHashMap<ty::PolyTraitRef<'tcx>, EvaluationResult>>
 }
 
+/// Caches the results of trait evaluation under where-clauses, shared
+/// between all the items of the crate. Every fn gets its own
+/// `ParameterEnvironment`, so its `evaluation_cache` starts out empty
+/// even when an identical set of bounds has already been seen (e.g.
+/// `T: Clone` in each of a thousand generic fns). Results are keyed by
+/// the caller bounds first, so an entry can only be found from a scope
+/// with exactly the same where-clauses in it. Bounds mentioning free
+/// regions are specific to their fn, so they keep using the cache of
+/// the `ParameterEnvironment`.
+pub struct ParamEnvEvaluationCache<'tcx> {
+    hashmap: RefCell<FnvHashMap<Vec<ty::Predicate<'tcx>>,
+                                FnvHashMap<ty::PolyTraitRef<'tcx>, EvaluationResult>>>
+}
+
 impl<'cx, 'tcx> SelectionContext<'cx, 'tcx> {
     pub fn new(infcx: &'cx InferCtxt<'cx, 'tcx>)
                -> SelectionContext<'cx, 'tcx> {
@@ -311,6 +325,9 @@ impl<'cx, 'tcx> SelectionContext<'cx, 'tcx> {
         debug!("select({:?})", obligation);
         assert!(!obligation.predicate.has_escaping_regions());
 
+        let trait_selections = &self.tcx().sess.perf_stats.trait_selections;
+        trait_selections.set(trait_selections.get() + 1);
+
         let stack = self.push_stack(TraitObligationStackList::empty(), obligation);
         match try!(self.candidate_from_obligation(&stack)) {
             None => {
@@ -675,8 +692,26 @@ impl<'cx, 'tcx> SelectionContext<'cx, 'tcx> {
     fn check_evaluation_cache(&self, trait_ref: ty::PolyTraitRef<'tcx>)
                               -> Option<EvaluationResult>
     {
-        let cache = self.pick_evaluation_cache();
-        cache.hashmap.borrow().get(&trait_ref).cloned()
+        let result = if self.use_param_env_evaluation_cache() {
+            let caller_bounds = &self.param_env().caller_bounds[..];
+            let cache = self.tcx().param_env_evaluation_cache.hashmap.borrow();
+            let result = cache.get(caller_bounds)
+                              .and_then(|results| results.get(&trait_ref).cloned());
+            if result.is_some() {
+                let shared_hits = &self.tcx().sess.perf_stats.shared_evaluation_cache_hits;
+                shared_hits.set(shared_hits.get() + 1);
+            }
+            result
+        } else {
+            let cache = self.pick_evaluation_cache();
+            let result = cache.hashmap.borrow().get(&trait_ref).cloned();
+            result
+        };
+        if result.is_some() {
+            let cache_hits = &self.tcx().sess.perf_stats.evaluation_cache_hits;
+            cache_hits.set(cache_hits.get() + 1);
+        }
+        result
     }
 
     fn insert_evaluation_cache(&mut self,
@@ -694,10 +729,36 @@ impl<'cx, 'tcx> SelectionContext<'cx, 'tcx> {
             return;
         }
 
+        if self.use_param_env_evaluation_cache() {
+            let caller_bounds = &self.param_env().caller_bounds;
+            let mut cache = self.tcx().param_env_evaluation_cache.hashmap.borrow_mut();
+            if !cache.contains_key(&caller_bounds[..]) {
+                cache.insert(caller_bounds.clone(), FnvHashMap());
+            }
+            cache.get_mut(&caller_bounds[..]).unwrap().insert(trait_ref, result);
+            return;
+        }
+
         let cache = self.pick_evaluation_cache();
         cache.hashmap.borrow_mut().insert(trait_ref, result);
     }
 
+    /// Whether evaluation results in this scope go into the crate-wide
+    /// cache keyed by the caller bounds, rather than the cache of the
+    /// `ParameterEnvironment`.
+    fn use_param_env_evaluation_cache(&self) -> bool {
+        // Coherence keeps its results to itself (see
+        // `pick_candidate_cache`). Bounds that still mention inference
+        // variables can change meaning as inference proceeds, so they
+        // are no good as a key. Free regions belong to the fn they are
+        // found in, so bounds mentioning them stay in its own cache.
+        let caller_bounds = &self.param_env().caller_bounds;
+        !self.intercrate &&
+            !caller_bounds.is_empty() &&
+            !caller_bounds.needs_infer() &&
+            !caller_bounds.has_free_regions()
+    }
+
     ///////////////////////////////////////////////////////////////////////////
     // CANDIDATE ASSEMBLY
     //
@@ -733,6 +794,8 @@ impl<'cx, 'tcx> SelectionContext<'cx, 'tcx> {
                 debug!("CACHE HIT: SELECT({:?})={:?}",
                        cache_fresh_trait_pred,
                        c);
+                let cache_hits = &self.tcx().sess.perf_stats.selection_cache_hits;
+                cache_hits.set(cache_hits.get() + 1);
                 return c;
             }
             None => { }
@@ -2945,6 +3008,14 @@ impl<'tcx> EvaluationCache<'tcx> {
     }
 }
 
+impl<'tcx> ParamEnvEvaluationCache<'tcx> {
+    pub fn new() -> ParamEnvEvaluationCache<'tcx> {
+        ParamEnvEvaluationCache {
+            hashmap: RefCell::new(FnvHashMap())
+        }
+    }
+}
+
 impl<'o,'tcx> TraitObligationStack<'o,'tcx> {
     fn list(&'o self) -> TraitObligationStackList<'o,'tcx> {
         TraitObligationStackList::with(self)
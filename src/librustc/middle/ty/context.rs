@@ -337,6 +337,10 @@ pub struct ctxt<'tcx> {
     /// Merge this with `selection_cache`?
     pub evaluation_cache: traits::EvaluationCache<'tcx>,
 
+    /// Caches the results of trait evaluation under where-clauses,
+    /// shared between all the scopes that have the same ones.
+    pub param_env_evaluation_cache: traits::ParamEnvEvaluationCache<'tcx>,
+
     /// A set of predicates that have been fulfilled *somewhere*.
     /// This is used to avoid duplicate work. Predicates are only
     /// added to this set when they mention only "global" names
@@ -356,6 +360,12 @@ pub struct ctxt<'tcx> {
     /// *from* expression of the cast, not the cast itself.
     pub cast_kinds: RefCell<NodeMap<ty::cast::CastKind>>,
 
+    /// The fns, methods, statics and constants whose bodies have been
+    /// type-checked by `check::typeck_item_body` in typeck. The types
+    /// inferred for a body are in `tables`; this only keeps the body from
+    /// being checked again.
+    pub checked_bodies: RefCell<DefIdSet>,
+
     /// Maps Fn items to a collection of fragment infos.
     ///
     /// The main goal is to identify data (each of which may be moved
@@ -518,10 +528,12 @@ impl<'tcx> ctxt<'tcx> {
             stability: RefCell::new(stability),
             selection_cache: traits::SelectionCache::new(),
             evaluation_cache: traits::EvaluationCache::new(),
+            param_env_evaluation_cache: traits::ParamEnvEvaluationCache::new(),
             repr_hint_cache: RefCell::new(DefIdMap()),
             const_qualif_map: RefCell::new(NodeMap()),
             custom_coerce_unsized_kinds: RefCell::new(DefIdMap()),
             cast_kinds: RefCell::new(NodeMap()),
+            checked_bodies: RefCell::new(DefIdSet()),
             fragment_infos: RefCell::new(DefIdMap()),
        }, f)
     }
@@ -109,6 +109,8 @@ pub struct CrateAnalysis<'a> {
     pub reachable: NodeSet,
     pub name: &'a str,
     pub glob_map: Option<GlobMap>,
+    pub import_map: Option<ImportMap>,
+    pub visible_paths: Option<VisiblePaths>,
 }
 
 
@@ -2283,6 +2285,16 @@ impl<'tcx> ctxt<'tcx> {
         }
     }
 
+    /// An identifier for `id` that, unlike the `DefId` itself, is the same
+    /// in every compilation session in which the definition keeps its path.
+    pub fn def_path_hash(&self, id: DefId) -> u64 {
+        if id.is_local() {
+            self.map.def_path_hash(id)
+        } else {
+            csearch::def_path_hash(self, id)
+        }
+    }
+
     pub fn with_path<T, F>(&self, id: DefId, f: F) -> T where
         F: FnOnce(ast_map::PathElems) -> T,
     {
@@ -2730,6 +2742,15 @@ pub type TraitMap = NodeMap<Vec<DefId>>;
 // imported.
 pub type GlobMap = HashMap<NodeId, HashSet<Name>>;
 
+// Map from a definition to the NodeIds of the `use` directives (including glob
+// imports) that import it into the crate, sorted.
+pub type ImportMap = DefIdMap<Vec<NodeId>>;
+
+// Map from an item of another crate to the path it is documented at, when
+// that's not where it is defined, because the item is only reachable through
+// re-exports. The first name of the path is the name of the crate.
+pub type VisiblePaths = DefIdMap<Vec<Name>>;
+
 impl<'tcx> ctxt<'tcx> {
     pub fn with_freevars<T, F>(&self, fid: NodeId, f: F) -> T where
         F: FnOnce(&[Freevar]) -> T,
@@ -2821,6 +2842,9 @@ pub trait HasTypeFlags {
     fn has_closure_types(&self) -> bool {
         self.has_type_flags(TypeFlags::HAS_TY_CLOSURE)
     }
+    fn has_free_regions(&self) -> bool {
+        self.has_type_flags(TypeFlags::HAS_FREE_REGIONS)
+    }
     fn has_erasable_regions(&self) -> bool {
         self.has_type_flags(TypeFlags::HAS_RE_EARLY_BOUND |
                             TypeFlags::HAS_RE_INFER |
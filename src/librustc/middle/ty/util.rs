@@ -40,6 +40,7 @@ pub trait IntTypeExt {
     fn disr_incr(&self, val: Disr) -> Option<Disr>;
     fn disr_string(&self, val: Disr) -> String;
     fn disr_wrap_incr(&self, val: Option<Disr>) -> Disr;
+    fn disr_in_range(&self, val: Disr) -> bool;
 }
 
 impl IntTypeExt for attr::IntType {
@@ -132,6 +133,25 @@ impl IntTypeExt for attr::IntType {
         }
     }
 
+    // True if `val` is representable in this type. Signed values are
+    // stored sign-extended in `Disr`, so check that truncating to the
+    // type and extending back is lossless.
+    fn disr_in_range(&self, val: Disr) -> bool {
+        match *self {
+            SignedInt(ast::TyI8)    => val as i8  as Disr == val,
+            SignedInt(ast::TyI16)   => val as i16 as Disr == val,
+            SignedInt(ast::TyI32)   => val as i32 as Disr == val,
+            SignedInt(ast::TyI64)   => val as i64 as Disr == val,
+            UnsignedInt(ast::TyU8)  => val as u8  as Disr == val,
+            UnsignedInt(ast::TyU16) => val as u16 as Disr == val,
+            UnsignedInt(ast::TyU32) => val as u32 as Disr == val,
+            UnsignedInt(ast::TyU64) => val as u64 as Disr == val,
+
+            UnsignedInt(ast::TyUs) |
+            SignedInt(ast::TyIs) => unreachable!(),
+        }
+    }
+
     fn disr_wrap_incr(&self, val: Option<Disr>) -> Disr {
         macro_rules! add1 {
             ($e:expr) => { ($e).wrapping_add(1) as Disr }
@@ -156,8 +176,10 @@ impl IntTypeExt for attr::IntType {
 
 #[derive(Copy, Clone)]
 pub enum CopyImplementationError {
-    InfrigingField(Name),
-    InfrigingVariant(Name),
+    /// The struct field that isn't `Copy`, and its name.
+    InfrigingField(DefId, Name),
+    /// The enum field that isn't `Copy`, and the name of its variant.
+    InfrigingVariant(DefId, Name),
     NotAnAdt,
     HasDestructor
 }
@@ -191,7 +213,7 @@ impl<'a, 'tcx> ParameterEnvironment<'a, 'tcx> {
                     let field_ty = field.ty(tcx, substs);
                     if infcx.type_moves_by_default(field_ty, span) {
                         return Err(CopyImplementationError::InfrigingField(
-                            field.name))
+                            field.did, field.name))
                     }
                 }
                 struct_def
@@ -202,7 +224,7 @@ impl<'a, 'tcx> ParameterEnvironment<'a, 'tcx> {
                         let field_ty = field.ty(tcx, substs);
                         if infcx.type_moves_by_default(field_ty, span) {
                             return Err(CopyImplementationError::InfrigingVariant(
-                                variant.name))
+                                field.did, variant.name))
                         }
                     }
                 }
@@ -267,6 +289,20 @@ impl<'tcx> ty::ctxt<'tcx> {
         }
     }
 
+    /// Returns the integer type used for the discriminant of the enum
+    /// `did` together with the evaluated discriminant of each of its
+    /// variants, in declaration order. The representation checks in
+    /// typeck and the layout computed by trans both go through this,
+    /// so that they always agree on the values.
+    pub fn enum_discriminants(&self, did: DefId) -> (attr::IntType, Vec<Disr>) {
+        let repr_hints = self.lookup_repr_hints(did);
+        let (repr_type, _) = self.enum_repr_type(repr_hints.get(0));
+        let discriminants = self.lookup_adt_def(did).variants.iter()
+                                .map(|v| v.disr_val)
+                                .collect();
+        (repr_type, discriminants)
+    }
+
     /// Returns `(normalized_type, ty)`, where `normalized_type` is the
     /// IntType representation of one of {i64,i32,i16,i8,u64,u32,u16,u8},
     /// and `ty` is the original type (i.e. may include `isize` or
@@ -361,6 +397,7 @@ impl<'tcx> ty::ctxt<'tcx> {
                 };
                 span_err!(self.sess, count_expr.span, E0307,
                     "expected constant integer for repeat count, {}", err_msg);
+                err.note(self.sess);
             }
         }
         0
@@ -22,10 +22,10 @@ use rustc_front::intravisit::Visitor;
 use rustc_front::intravisit;
 use rustc_front::hir;
 
-use std::collections::HashSet;
+use std::collections::HashMap;
 
 macro_rules! weak_lang_items {
-    ($($name:ident, $item:ident, $sym:ident;)*) => (
+    ($($name:ident, $item:ident, $sym:ident, $stub:expr;)*) => (
 
 struct Context<'a> {
     sess: &'a Session,
@@ -78,18 +78,30 @@ fn verify(sess: &Session, items: &lang_items::LanguageItems) {
     });
     if !needs_check { return }
 
-    let mut missing = HashSet::new();
-    sess.cstore.iter_crate_data(|cnum, _| {
+    // Remember which crates require each item, to point at them when the
+    // item is missing.
+    let mut missing = HashMap::new();
+    sess.cstore.iter_crate_data(|cnum, data| {
         for item in &csearch::get_missing_lang_items(&sess.cstore, cnum) {
-            missing.insert(*item);
+            missing.entry(*item).or_insert(vec![]).push(data.name());
         }
     });
 
     $(
-        if missing.contains(&lang_items::$item) && items.$name().is_none() {
-            sess.err(&format!("language item required, but not found: `{}`",
-                              stringify!($name)));
-
+        if let Some(crates) = missing.get_mut(&lang_items::$item) {
+            if items.$name().is_none() {
+                crates.sort();
+                let crates = crates.iter()
+                                   .map(|name| format!("`{}`", name))
+                                   .collect::<Vec<_>>()
+                                   .join(", ");
+                sess.err(&format!("language item required, but not found: `{}`",
+                                  stringify!($name)));
+                sess.note(&format!("`{}` is required by {}, and is normally provided \
+                                    by `std`", stringify!($name), crates));
+                sess.help(&format!("crates that do not link to `std` must define it \
+                                    themselves, e.g. `{}`", $stub));
+            }
         }
     )*
 }
@@ -121,7 +133,11 @@ impl<'a, 'v> Visitor<'v> for Context<'a> {
 ) }
 
 weak_lang_items! {
-    panic_fmt,          PanicFmtLangItem,           rust_begin_unwind;
-    eh_personality,     EhPersonalityLangItem,      rust_eh_personality;
-    eh_unwind_resume,   EhUnwindResumeLangItem,     rust_eh_unwind_resume;
+    panic_fmt,          PanicFmtLangItem,           rust_begin_unwind,
+        "#[lang = \"panic_fmt\"] extern fn panic_fmt(_: core::fmt::Arguments, \
+         _: &'static str, _: u32) -> ! { loop {} }";
+    eh_personality,     EhPersonalityLangItem,      rust_eh_personality,
+        "#[lang = \"eh_personality\"] extern fn eh_personality() {}";
+    eh_unwind_resume,   EhUnwindResumeLangItem,     rust_eh_unwind_resume,
+        "#[lang = \"eh_unwind_resume\"] extern fn eh_unwind_resume(_: *mut u8) -> ! { loop {} }";
 }
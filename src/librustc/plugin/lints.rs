@@ -0,0 +1,179 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Loading lint passes from the dylibs given with `-Z extra-lints`.
+//!
+//! This is a narrower interface than the one of compiler plugins. The dylib
+//! isn't looked up as a crate, needs no `#![plugin]` attribute in the crate
+//! being compiled, and can only register lint groups and `ExtraLintPass`es,
+//! which see items and expressions only. It declares its registrar with
+//! `declare_lint_registrar!`, which also exports the version of rustc the
+//! dylib was built with:
+//!
+//! ```ignore
+//! fn register(reg: &mut LintRegistry) {
+//!     reg.register_lint_pass(box MyLintPass);
+//! }
+//!
+//! declare_lint_registrar!(register);
+//! ```
+//!
+//! A dylib built with another version of rustc is refused rather than run,
+//! since the types it was built against may have changed.
+
+use lint::{LateContext, LateLintPass, LintContext, LintArray, LintId, Lint, LintPass};
+use plugin::load::dylink_symbol;
+use session::Session;
+
+use std::collections::HashMap;
+use std::mem;
+use std::path::PathBuf;
+use syntax::codemap::Span;
+use rustc_front::hir;
+
+/// The symbol a `-Z extra-lints` dylib exports its registrar under.
+pub const LINT_REGISTRAR_SYMBOL: &'static str = "__rustc_lint_registrar";
+
+/// The symbol a `-Z extra-lints` dylib exports `LINT_VERSION` under.
+pub const LINT_VERSION_SYMBOL: &'static str = "__rustc_lint_version";
+
+/// The version of rustc, which `declare_lint_registrar!` copies into the
+/// dylib it is used in.
+pub const LINT_VERSION: &'static str = concat!("rustc ", env!("CFG_VERSION"));
+
+/// Pointer to a lint registrar function.
+pub type LintRegistrarFun = fn(&mut LintRegistry);
+
+/// Exports `$registrar`, a `fn(&mut LintRegistry)`, as the lint registrar of
+/// a `-Z extra-lints` dylib, along with the version of rustc it is built with.
+#[macro_export]
+macro_rules! declare_lint_registrar {
+    ($registrar:path) => (
+        #[no_mangle]
+        #[allow(non_upper_case_globals)]
+        pub static __rustc_lint_version: &'static str = ::rustc::plugin::lints::LINT_VERSION;
+
+        #[no_mangle]
+        pub fn __rustc_lint_registrar(reg: &mut ::rustc::plugin::lints::LintRegistry) {
+            $registrar(reg)
+        }
+    )
+}
+
+/// A lint pass of a `-Z extra-lints` dylib. It is run after type checking,
+/// like a `LateLintPass`, but only sees items and expressions, through an
+/// `ExtraLintContext`.
+pub trait ExtraLintPass {
+    /// Get descriptions of the lints this pass can emit.
+    fn get_lints(&self) -> LintArray;
+
+    fn check_item(&mut self, _: &ExtraLintContext, _: &hir::Item) { }
+    fn check_expr(&mut self, _: &ExtraLintContext, _: &hir::Expr) { }
+}
+
+/// What an `ExtraLintPass` can ask about the crate it checks.
+pub struct ExtraLintContext<'a, 'b: 'a, 'tcx: 'b> {
+    cx: &'a LateContext<'b, 'tcx>,
+}
+
+impl<'a, 'b, 'tcx> ExtraLintContext<'a, 'b, 'tcx> {
+    /// Emit a lint at the level it is set to at `span`.
+    pub fn span_lint(&self, lint: &'static Lint, span: Span, msg: &str) {
+        self.cx.span_lint(lint, span, msg);
+    }
+
+    /// The type of `expr`, as error messages print it.
+    pub fn expr_ty_string(&self, expr: &hir::Expr) -> String {
+        self.cx.tcx.expr_ty(expr).to_string()
+    }
+}
+
+/// Runs an `ExtraLintPass` as a late lint pass.
+struct ExtraLints(Box<ExtraLintPass>);
+
+impl LintPass for ExtraLints {
+    fn get_lints(&self) -> LintArray {
+        self.0.get_lints()
+    }
+}
+
+impl LateLintPass for ExtraLints {
+    fn check_item(&mut self, cx: &LateContext, it: &hir::Item) {
+        self.0.check_item(&ExtraLintContext { cx: cx }, it);
+    }
+
+    fn check_expr(&mut self, cx: &LateContext, e: &hir::Expr) {
+        self.0.check_expr(&ExtraLintContext { cx: cx }, e);
+    }
+}
+
+/// What the lint registrar of a `-Z extra-lints` dylib registers.
+pub struct LintRegistry {
+    lint_passes: Vec<Box<ExtraLintPass>>,
+    lint_groups: HashMap<&'static str, Vec<LintId>>,
+}
+
+impl LintRegistry {
+    fn new() -> LintRegistry {
+        LintRegistry {
+            lint_passes: vec![],
+            lint_groups: HashMap::new(),
+        }
+    }
+
+    /// Register a lint pass.
+    pub fn register_lint_pass(&mut self, lint_pass: Box<ExtraLintPass>) {
+        self.lint_passes.push(lint_pass);
+    }
+
+    /// Register a lint group.
+    pub fn register_lint_group(&mut self, name: &'static str, to: Vec<&'static Lint>) {
+        self.lint_groups.insert(name, to.into_iter().map(|x| LintId::of(x)).collect());
+    }
+}
+
+/// Checks that the dylib at `path` was built with this version of rustc, and
+/// returns its lint registrar.
+fn dylink_lint_registrar(path: &str) -> Result<LintRegistrarFun, String> {
+    let version = match dylink_symbol(PathBuf::from(path), LINT_VERSION_SYMBOL) {
+        Ok(version) => unsafe { *(version as *const &'static str) },
+        Err(_) => {
+            return Err("it doesn't export the version of rustc it was built with; \
+                        declare its registrar with `declare_lint_registrar!`".to_string())
+        }
+    };
+    if version != LINT_VERSION {
+        return Err(format!("it was built with {}, not {}", version, LINT_VERSION));
+    }
+    let registrar = try!(dylink_symbol(PathBuf::from(path), LINT_REGISTRAR_SYMBOL));
+    Ok(unsafe { mem::transmute::<*mut u8, LintRegistrarFun>(registrar) })
+}
+
+/// Loads the dylibs given with `-Z extra-lints`, runs their registrars and
+/// adds what they registered to the lint store of the session.
+pub fn load_extra_lints(sess: &Session) {
+    let mut registry = LintRegistry::new();
+    for path in &sess.opts.debugging_opts.extra_lints {
+        let registrar = match dylink_lint_registrar(path) {
+            Ok(registrar) => registrar,
+            Err(err) => sess.fatal(&format!("couldn't load the lints of `{}`: {}", path, err)),
+        };
+        registrar(&mut registry);
+    }
+
+    let LintRegistry { lint_passes, lint_groups } = registry;
+    let mut ls = sess.lint_store.borrow_mut();
+    for pass in lint_passes {
+        ls.register_late_pass(Some(sess), true, box ExtraLints(pass));
+    }
+    for (name, to) in lint_groups {
+        ls.register_group(Some(sess), true, name, to);
+    }
+}
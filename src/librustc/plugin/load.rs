@@ -102,44 +102,36 @@ impl<'a> PluginLoader<'a> {
     }
 
     // Dynamically link a registrar function into the compiler process.
-    #[allow(deprecated)]
     fn dylink_registrar(&mut self,
                         span: Span,
                         path: PathBuf,
                         symbol: String) -> PluginRegistrarFun {
-        use std::dynamic_lib::DynamicLibrary;
-
-        // Make sure the path contains a / or the linker will search for it.
-        let path = env::current_dir().unwrap().join(&path);
-
-        let lib = match DynamicLibrary::open(Some(&path)) {
-            Ok(lib) => lib,
+        match dylink_symbol(path, &symbol[..]) {
+            Ok(registrar) => unsafe { mem::transmute::<*mut u8, PluginRegistrarFun>(registrar) },
             // this is fatal: there are almost certainly macros we need
             // inside this crate, so continue would spew "macro undefined"
             // errors
-            Err(err) => {
-                self.sess.span_fatal(span, &err[..])
-            }
-        };
-
-        unsafe {
-            let registrar =
-                match lib.symbol(&symbol[..]) {
-                    Ok(registrar) => {
-                        mem::transmute::<*mut u8,PluginRegistrarFun>(registrar)
-                    }
-                    // again fatal if we can't register macros
-                    Err(err) => {
-                        self.sess.span_fatal(span, &err[..])
-                    }
-                };
-
-            // Intentionally leak the dynamic library. We can't ever unload it
-            // since the library can make things that will live arbitrarily long
-            // (e.g. an @-box cycle or a thread).
-            mem::forget(lib);
-
-            registrar
+            Err(err) => self.sess.span_fatal(span, &err[..]),
         }
     }
 }
+
+/// Dynamically links the dylib at `path` into the compiler process and
+/// looks up `symbol` in it.
+#[allow(deprecated)]
+pub fn dylink_symbol(path: PathBuf, symbol: &str) -> Result<*mut u8, String> {
+    use std::dynamic_lib::DynamicLibrary;
+
+    // Make sure the path contains a / or the linker will search for it.
+    let path = env::current_dir().unwrap().join(&path);
+
+    let lib = try!(DynamicLibrary::open(Some(&path)));
+    let symbol = try!(unsafe { lib.symbol::<u8>(symbol) });
+
+    // Intentionally leak the dynamic library. We can't ever unload it
+    // since the library can make things that will live arbitrarily long
+    // (e.g. an @-box cycle or a thread).
+    mem::forget(lib);
+
+    Ok(symbol)
+}
@@ -69,6 +69,7 @@ pub enum OutputType {
     Object,
     Exe,
     DepInfo,
+    Mir,
 }
 
 #[derive(Clone)]
@@ -121,7 +122,9 @@ pub struct Options {
 pub enum PrintRequest {
     FileNames,
     Sysroot,
+    TargetLibdir,
     CrateName,
+    CrateDisambiguator,
 }
 
 pub enum Input {
@@ -165,6 +168,7 @@ impl OutputFilenames {
             OutputType::LlvmAssembly => base.with_extension("ll"),
             OutputType::Object => base.with_extension("o"),
             OutputType::DepInfo => base.with_extension("d"),
+            OutputType::Mir => base.with_extension("mir"),
             OutputType::Exe => base,
         }
     }
@@ -531,8 +535,17 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
         "measure time of each LLVM pass"),
     input_stats: bool = (false, parse_bool,
         "gather statistics about the input"),
+    ast_stats: bool = (false, parse_bool,
+        "print the number and size of the AST nodes of each kind after parsing and expansion"),
+    hir_stats: bool = (false, parse_bool,
+        "print the number and size of the HIR nodes of each kind after lowering"),
+    print_item_hashes: bool = (false, parse_bool,
+        "print the def-path hash and the fingerprints of every item and its type"),
     trans_stats: bool = (false, parse_bool,
         "gather trans statistics"),
+    module_times: bool = (false, parse_bool,
+        "print the time spent type checking and translating the items of each module, \
+         and an estimate of the time spent on them in LLVM"),
     asm_comments: bool = (false, parse_bool,
         "generate comments into the assembly (may change behavior)"),
     no_verify: bool = (false, parse_bool,
@@ -557,6 +570,12 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
         "print the AST as JSON and halt"),
     ast_json_noexpand: bool = (false, parse_bool,
         "print the pre-expansion AST as JSON and halt"),
+    group_import_errors: bool = (false, parse_bool,
+        "report the imports that fail on the same unresolved module path as one error"),
+    verbose_resolve_failures: bool = (false, parse_bool,
+        "note every module searched for a name that fails to resolve"),
+    json_errors: bool = (false, parse_bool,
+        "print diagnostics as JSON, one object per line, for tools"),
     ls: bool = (false, parse_bool,
         "list the symbols defined by a library crate"),
     save_analysis: bool = (false, parse_bool,
@@ -580,10 +599,16 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
           "run all passes except translation; no output"),
     treat_err_as_bug: bool = (false, parse_bool,
           "treat all errors that occur as bugs"),
+    show_cascading_errors: bool = (false, parse_bool,
+          "report errors about types that follow from earlier type errors"),
     no_analysis: bool = (false, parse_bool,
           "parse and expand the source, but run no analysis"),
     extra_plugins: Vec<String> = (Vec::new(), parse_list,
         "load extra plugins"),
+    extra_lints: Vec<String> = (Vec::new(), parse_list,
+        "load lint passes from these dylibs, which export a `__rustc_lint_registrar`"),
+    server: bool = (false, parse_bool,
+        "stay resident and compile the crates requested on stdin, one request per line"),
     unstable_options: bool = (false, parse_bool,
           "adds unstable command line options to rustc interface"),
     print_enum_sizes: bool = (false, parse_bool,
@@ -598,6 +623,10 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
           "force nonzeroing move optimization on"),
     keep_mtwt_tables: bool = (false, parse_bool,
           "don't clear the resolution tables after analysis"),
+    mir_lints: bool = (false, parse_bool,
+          "also check unreachable_code and unused_assignments on the MIR"),
+    print_trans_items: Option<String> = (None, parse_opt_string,
+          "print the result of the translation item collection pass (lazy or eager)"),
 }
 
 pub fn default_lib_output() -> CrateType {
@@ -775,7 +804,10 @@ pub fn rustc_short_optgroups() -> Vec<RustcOptGroup> {
         opt::multi("l", "",   "Link the generated crate(s) to the specified native
                              library NAME. The optional KIND can be one of,
                              static, dylib, or framework. If omitted, dylib is
-                             assumed.", "[KIND=]NAME"),
+                             assumed. Static libraries are linked in whole,
+                             unless the -whole-archive modifier is given, as
+                             in static:-whole-archive=NAME.",
+                   "[KIND[:MODIFIERS]=]NAME"),
         opt::multi("", "crate-type", "Comma separated list of types of crates
                                     for the compiler to emit",
                    "[bin|lib|rlib|dylib|staticlib]"),
@@ -783,10 +815,10 @@ pub fn rustc_short_optgroups() -> Vec<RustcOptGroup> {
                "NAME"),
         opt::multi("", "emit", "Comma separated list of types of output for \
                               the compiler to emit",
-                 "[asm|llvm-bc|llvm-ir|obj|link|dep-info]"),
+                 "[asm|llvm-bc|llvm-ir|obj|link|dep-info|mir]"),
         opt::multi("", "print", "Comma separated list of compiler information to \
                                print on stdout",
-                 "[crate-name|file-names|sysroot]"),
+                 "[crate-name|crate-disambiguator|file-names|sysroot|target-libdir]"),
         opt::flagmulti("g",  "",  "Equivalent to -C debuginfo=2"),
         opt::flagmulti("O", "", "Equivalent to -C opt-level=2"),
         opt::opt("o", "", "Write output to <filename>", "FILENAME"),
@@ -898,6 +930,13 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
 
     let debugging_opts = build_debugging_options(matches, color);
 
+    // The MIR is not built on the beta and stable channels
+    if debugging_opts.mir_lints {
+        if let UnstableFeatures::Disallow = get_unstable_features_setting() {
+            early_error(color, "-Z mir-lints is only accepted on the nightly compiler");
+        }
+    }
+
     let parse_only = debugging_opts.parse_only;
     let no_trans = debugging_opts.no_trans;
     let treat_err_as_bug = debugging_opts.treat_err_as_bug;
@@ -919,6 +958,11 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
                     "obj" => OutputType::Object,
                     "link" => OutputType::Exe,
                     "dep-info" => OutputType::DepInfo,
+                    "mir" if debugging_opts.unstable_options => OutputType::Mir,
+                    "mir" => {
+                        early_error(color, "the `mir` emission type is unstable, pass \
+                                            `-Z unstable-options` to use it")
+                    }
                     part => {
                         early_error(color, &format!("unknown emission type: `{}`",
                                                     part))
@@ -987,7 +1031,14 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
     let libs = matches.opt_strs("l").into_iter().map(|s| {
         let mut parts = s.splitn(2, '=');
         let kind = parts.next().unwrap();
-        let (name, kind) = match (parts.next(), kind) {
+        let name = parts.next();
+        // Modifiers can only be given along with a kind, as in
+        // `static:-whole-archive=foo`.
+        let (kind, modifiers) = match (name, kind.find(':')) {
+            (Some(_), Some(i)) => (&kind[..i], Some(&kind[i + 1..])),
+            _ => (kind, None),
+        };
+        let (name, kind) = match (name, kind) {
             (None, name) |
             (Some(name), "dylib") => (name, cstore::NativeUnknown),
             (Some(name), "framework") => (name, cstore::NativeFramework),
@@ -998,6 +1049,15 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
                                             s));
             }
         };
+        if modifiers.is_some() && !debugging_opts.unstable_options {
+            early_error(color, "linking modifiers are unstable, pass \
+                                `-Z unstable-options` to use them")
+        }
+        let kind = match modifiers.map(|m| kind.with_modifiers(m)) {
+            None => kind,
+            Some(Ok(kind)) => kind,
+            Some(Err(msg)) => early_error(color, &msg),
+        };
         (name.to_string(), kind)
     }).collect();
 
@@ -1007,8 +1067,10 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
     let prints = matches.opt_strs("print").into_iter().map(|s| {
         match &*s {
             "crate-name" => PrintRequest::CrateName,
+            "crate-disambiguator" => PrintRequest::CrateDisambiguator,
             "file-names" => PrintRequest::FileNames,
             "sysroot" => PrintRequest::Sysroot,
+            "target-libdir" => PrintRequest::TargetLibdir,
             req => {
                 early_error(color, &format!("unknown print request `{}`", req))
             }
@@ -13,12 +13,13 @@ use metadata::cstore::CStore;
 use metadata::filesearch;
 use middle::dependency_format;
 use session::search_paths::PathKind;
-use util::nodemap::{NodeMap, FnvHashMap};
+use util::nodemap::{NodeMap, NodeSet, FnvHashMap};
 
 use syntax::ast::{NodeId, NodeIdAssigner};
-use syntax::codemap::Span;
+use syntax::codemap::{MultiSpan, Span};
 use syntax::diagnostic::{self, Emitter};
 use syntax::diagnostics;
+use syntax::json::JsonEmitter;
 use syntax::feature_gate;
 use syntax::parse;
 use syntax::parse::token;
@@ -30,11 +31,26 @@ use rustc_back::target::Target;
 
 use std::path::{Path, PathBuf};
 use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
 use std::env;
 
 pub mod config;
 pub mod search_paths;
 
+/// A lint added to the session, to be emitted by the lint pass, with what
+/// to emit along with it.
+pub type SessionLint = (lint::LintId, Span, String, Option<SessionLintNote>);
+
+/// What is emitted along with a lint added to the session.
+#[derive(Debug)]
+pub enum SessionLintNote {
+    /// A note at a span, e.g. pointing at an earlier definition.
+    Note(Span, String),
+    /// A help message suggesting to replace the code at a span with the
+    /// given code.
+    Suggestion(Span, String, String),
+}
+
 // Represents the data associated with a compilation
 // session for a single crate.
 pub struct Session {
@@ -54,16 +70,31 @@ pub struct Session {
     pub local_crate_source_file: Option<PathBuf>,
     pub working_dir: PathBuf,
     pub lint_store: RefCell<lint::LintStore>,
-    pub lints: RefCell<NodeMap<Vec<(lint::LintId, codemap::Span, String)>>>,
+    pub lints: RefCell<NodeMap<Vec<SessionLint>>>,
+    /// The `cfg` names and values that are never set, found before the crate
+    /// is configured: the ids of the items around the attribute, innermost
+    /// last, the span of the `cfg` and the message. They are linted once the
+    /// whole crate has node ids, at the innermost item that wasn't stripped.
+    pub unexpected_cfgs: RefCell<Vec<(Vec<NodeId>, Span, String)>>,
+    /// The ids given to items while their cfgs were checked. The rest of
+    /// the crate gets its ids after expansion.
+    pub cfg_checked_items: RefCell<NodeSet>,
     pub plugin_llvm_passes: RefCell<Vec<String>>,
     pub plugin_attributes: RefCell<Vec<(String, AttributeType)>>,
     pub crate_types: RefCell<Vec<config::CrateType>>,
     pub dependency_formats: RefCell<dependency_format::Dependencies>,
     pub crate_metadata: RefCell<Vec<String>>,
+    /// Hash of the `-C metadata` values, distinguishing this crate from
+    /// other crates with the same name.
+    pub crate_disambiguator: RefCell<String>,
     pub features: RefCell<feature_gate::Features>,
 
     pub delayed_span_bug: RefCell<Option<(codemap::Span, String)>>,
 
+    /// Whether the last error was left out as a cascading error, so that
+    /// the notes following it are left out as well.
+    suppressed_error: Cell<bool>,
+
     /// The maximum recursion limit for potentially infinitely recursive
     /// operations such as auto-dereference and monomorphization.
     pub recursion_limit: Cell<usize>,
@@ -75,22 +106,69 @@ pub struct Session {
     pub injected_allocator: Cell<Option<ast::CrateNum>>,
 
     next_node_id: Cell<ast::NodeId>,
+
+    /// Counters for `-Z input-stats`.
+    pub perf_stats: PerfStats,
+
+    /// The time spent on the items of each module, by module path, for
+    /// `-Z module-times`.
+    pub module_times: RefCell<HashMap<String, ModuleTimes>>,
+}
+
+/// The time spent on the items of one module. Generic functions are
+/// translated where they are used, so the time spent on them counts towards
+/// the modules using them.
+#[derive(Clone, Default)]
+pub struct ModuleTimes {
+    /// Seconds spent type checking.
+    pub typeck: f64,
+    /// Seconds spent translating to LLVM IR.
+    pub trans: f64,
+    /// LLVM instructions in the functions translated, which the time spent
+    /// in LLVM is divided by.
+    pub llvm_insns: usize,
+}
+
+/// How often some hot paths of the compiler were taken, so that a slow
+/// compilation can be attributed to the pass that blew up.
+pub struct PerfStats {
+    /// Paths resolved by resolve, not counting imports.
+    pub path_resolutions: Cell<usize>,
+    /// Attempts to resolve an import, including the retries of imports that
+    /// couldn't be resolved yet.
+    pub import_resolutions: Cell<usize>,
+    /// Trait obligations selected.
+    pub trait_selections: Cell<usize>,
+    /// Trait selections answered by the selection cache.
+    pub selection_cache_hits: Cell<usize>,
+    /// Trait evaluations answered by one of the evaluation caches.
+    pub evaluation_cache_hits: Cell<usize>,
+    /// Of those, the ones answered by the cache shared between fns with the
+    /// same where-clauses.
+    pub shared_evaluation_cache_hits: Cell<usize>,
+    /// Inference snapshots rolled back.
+    pub snapshot_rollbacks: Cell<usize>,
+    /// Bodies of fns, methods, statics and constants type-checked.
+    pub body_checks: Cell<usize>,
 }
 
 impl Session {
     pub fn span_fatal(&self, sp: Span, msg: &str) -> ! {
+        self.suppressed_error.set(false);
         if self.opts.treat_err_as_bug {
             self.span_bug(sp, msg);
         }
         panic!(self.diagnostic().span_fatal(sp, msg))
     }
     pub fn span_fatal_with_code(&self, sp: Span, msg: &str, code: &str) -> ! {
+        self.suppressed_error.set(false);
         if self.opts.treat_err_as_bug {
             self.span_bug(sp, msg);
         }
         panic!(self.diagnostic().span_fatal_with_code(sp, msg, code))
     }
     pub fn fatal(&self, msg: &str) -> ! {
+        self.suppressed_error.set(false);
         if self.opts.treat_err_as_bug {
             self.bug(msg);
         }
@@ -104,6 +182,7 @@ impl Session {
         }
     }
     pub fn span_err(&self, sp: Span, msg: &str) {
+        self.suppressed_error.set(false);
         if self.opts.treat_err_as_bug {
             self.span_bug(sp, msg);
         }
@@ -120,6 +199,7 @@ impl Session {
                       See RFC 1214 for details."));
     }
     pub fn span_err_with_code(&self, sp: Span, msg: &str, code: &str) {
+        self.suppressed_error.set(false);
         if self.opts.treat_err_as_bug {
             self.span_bug(sp, msg);
         }
@@ -128,12 +208,34 @@ impl Session {
             None => self.diagnostic().span_err_with_code(sp, msg, code)
         }
     }
+    pub fn span_err_multi_with_code(&self, msp: MultiSpan, msg: &str, code: &str) {
+        self.suppressed_error.set(false);
+        if self.opts.treat_err_as_bug {
+            self.span_bug(msp.primary, msg);
+        }
+        match split_msg_into_multilines(msg) {
+            Some(msg) => self.diagnostic().span_err_multi_with_code(&msp, &msg[..], code),
+            None => self.diagnostic().span_err_multi_with_code(&msp, msg, code)
+        }
+    }
     pub fn err(&self, msg: &str) {
+        self.suppressed_error.set(false);
         if self.opts.treat_err_as_bug {
             self.bug(msg);
         }
         self.diagnostic().handler().err(msg)
     }
+    /// Errors about a type that references `TyError` follow from an error
+    /// reported before, e.g. a type that failed to resolve, and only bury it.
+    /// Returns whether such an error should be left out, along with its
+    /// notes, which is the case unless `-Z show-cascading-errors` is given.
+    pub fn suppress_cascading_error(&self, references_error: bool) -> bool {
+        let suppress = !self.opts.debugging_opts.show_cascading_errors &&
+                       self.has_errors() &&
+                       references_error;
+        self.suppressed_error.set(suppress);
+        suppress
+    }
     pub fn err_count(&self) -> usize {
         self.diagnostic().handler().err_count()
     }
@@ -152,16 +254,19 @@ impl Session {
         }
     }
     pub fn span_warn(&self, sp: Span, msg: &str) {
+        self.suppressed_error.set(false);
         if self.can_print_warnings {
             self.diagnostic().span_warn(sp, msg)
         }
     }
     pub fn span_warn_with_code(&self, sp: Span, msg: &str, code: &str) {
+        self.suppressed_error.set(false);
         if self.can_print_warnings {
             self.diagnostic().span_warn_with_code(sp, msg, code)
         }
     }
     pub fn warn(&self, msg: &str) {
+        self.suppressed_error.set(false);
         if self.can_print_warnings {
             self.diagnostic().handler().warn(msg)
         }
@@ -173,32 +278,48 @@ impl Session {
         }
     }
     pub fn span_note(&self, sp: Span, msg: &str) {
-        self.diagnostic().span_note(sp, msg)
+        if !self.suppressed_error.get() {
+            self.diagnostic().span_note(sp, msg)
+        }
     }
     pub fn span_end_note(&self, sp: Span, msg: &str) {
-        self.diagnostic().span_end_note(sp, msg)
+        if !self.suppressed_error.get() {
+            self.diagnostic().span_end_note(sp, msg)
+        }
     }
 
     /// Prints out a message with a suggested edit of the code.
     ///
     /// See `diagnostic::RenderSpan::Suggestion` for more information.
     pub fn span_suggestion(&self, sp: Span, msg: &str, suggestion: String) {
-        self.diagnostic().span_suggestion(sp, msg, suggestion)
+        if !self.suppressed_error.get() {
+            self.diagnostic().span_suggestion(sp, msg, suggestion)
+        }
     }
     pub fn span_help(&self, sp: Span, msg: &str) {
-        self.diagnostic().span_help(sp, msg)
+        if !self.suppressed_error.get() {
+            self.diagnostic().span_help(sp, msg)
+        }
     }
     pub fn fileline_note(&self, sp: Span, msg: &str) {
-        self.diagnostic().fileline_note(sp, msg)
+        if !self.suppressed_error.get() {
+            self.diagnostic().fileline_note(sp, msg)
+        }
     }
     pub fn fileline_help(&self, sp: Span, msg: &str) {
-        self.diagnostic().fileline_help(sp, msg)
+        if !self.suppressed_error.get() {
+            self.diagnostic().fileline_help(sp, msg)
+        }
     }
     pub fn note(&self, msg: &str) {
-        self.diagnostic().handler().note(msg)
+        if !self.suppressed_error.get() {
+            self.diagnostic().handler().note(msg)
+        }
     }
     pub fn help(&self, msg: &str) {
-        self.diagnostic().handler().help(msg)
+        if !self.suppressed_error.get() {
+            self.diagnostic().handler().help(msg)
+        }
     }
     pub fn opt_span_bug(&self, opt_sp: Option<Span>, msg: &str) -> ! {
         match opt_sp {
@@ -228,13 +349,40 @@ impl Session {
                     id: ast::NodeId,
                     sp: Span,
                     msg: String) {
-        let lint_id = lint::LintId::of(lint);
+        self.push_lint(id, (lint::LintId::of(lint), sp, msg, None));
+    }
+    /// Like `add_lint`, with a note at `note_sp` emitted along with the
+    /// lint, e.g. pointing at an earlier definition.
+    pub fn add_lint_note(&self,
+                         lint: &'static lint::Lint,
+                         id: ast::NodeId,
+                         sp: Span,
+                         msg: String,
+                         note_sp: Span,
+                         note: String) {
+        let note = SessionLintNote::Note(note_sp, note);
+        self.push_lint(id, (lint::LintId::of(lint), sp, msg, Some(note)));
+    }
+    /// Like `add_lint`, with a help message suggesting to replace the code
+    /// at `suggestion_sp` with `suggestion`, e.g. to remove it.
+    pub fn add_lint_suggestion(&self,
+                               lint: &'static lint::Lint,
+                               id: ast::NodeId,
+                               sp: Span,
+                               msg: String,
+                               suggestion_sp: Span,
+                               help: String,
+                               suggestion: String) {
+        let note = SessionLintNote::Suggestion(suggestion_sp, help, suggestion);
+        self.push_lint(id, (lint::LintId::of(lint), sp, msg, Some(note)));
+    }
+    fn push_lint(&self, id: ast::NodeId, lint: SessionLint) {
         let mut lints = self.lints.borrow_mut();
         match lints.get_mut(&id) {
-            Some(arr) => { arr.push((lint_id, sp, msg)); return; }
+            Some(arr) => { arr.push(lint); return; }
             None => {}
         }
-        lints.insert(id, vec!((lint_id, sp, msg)));
+        lints.insert(id, vec!(lint));
     }
     pub fn reserve_node_ids(&self, count: ast::NodeId) -> ast::NodeId {
         let id = self.next_node_id.get();
@@ -260,6 +408,7 @@ impl Session {
     }
     pub fn verbose(&self) -> bool { self.opts.debugging_opts.verbose }
     pub fn time_passes(&self) -> bool { self.opts.debugging_opts.time_passes }
+    pub fn module_times(&self) -> bool { self.opts.debugging_opts.module_times }
     pub fn count_llvm_insns(&self) -> bool {
         self.opts.debugging_opts.count_llvm_insns
     }
@@ -312,6 +461,41 @@ impl Session {
             &self.opts.search_paths,
             kind)
     }
+    pub fn print_perf_stats(&self) {
+        let stats = &self.perf_stats;
+        println!("Path resolutions:          {}", stats.path_resolutions.get());
+        println!("Import resolutions:        {}", stats.import_resolutions.get());
+        println!("Trait selections:          {}", stats.trait_selections.get());
+        println!("Selection cache hits:      {}", stats.selection_cache_hits.get());
+        println!("Evaluation cache hits:     {}", stats.evaluation_cache_hits.get());
+        println!("  shared between fns:      {}", stats.shared_evaluation_cache_hits.get());
+        println!("Snapshot rollbacks:        {}", stats.snapshot_rollbacks.get());
+        println!("Item bodies checked:       {}", stats.body_checks.get());
+    }
+
+    /// Prints the report of `-Z module-times`, the modules taking the most
+    /// time first. `llvm_secs` is the time spent in LLVM for the whole crate.
+    pub fn print_module_times(&self, llvm_secs: f64) {
+        let module_times = self.module_times.borrow();
+        let total_insns = module_times.values().fold(0, |n, times| n + times.llvm_insns);
+        let mut report = module_times.iter().map(|(module, times)| {
+            let llvm = if total_insns == 0 {
+                0.0
+            } else {
+                llvm_secs * times.llvm_insns as f64 / total_insns as f64
+            };
+            (module, times.typeck, times.trans, llvm)
+        }).collect::<Vec<_>>();
+        let total = |&(_, typeck, trans, llvm): &(&String, f64, f64, f64)| typeck + trans + llvm;
+        report.sort_by(|a, b| total(b).partial_cmp(&total(a)).unwrap());
+
+        println!("{:>10}{:>10}{:>10}{:>10}  {}", "total", "typeck", "trans", "llvm", "module");
+        for entry in &report {
+            let (module, typeck, trans, llvm) = *entry;
+            println!("{:>10.3}{:>10.3}{:>10.3}{:>10.3}  {}",
+                     total(entry), typeck, trans, llvm, module);
+        }
+    }
 }
 
 impl NodeIdAssigner for Session {
@@ -400,8 +584,12 @@ pub fn build_session(sopts: config::Options,
         .unwrap_or(true);
 
     let codemap = codemap::CodeMap::new();
-    let diagnostic_handler =
-        diagnostic::Handler::new(sopts.color, Some(registry), can_print_warnings);
+    let emitter: Box<Emitter + Send> = if sopts.debugging_opts.json_errors {
+        Box::new(JsonEmitter::stderr())
+    } else {
+        Box::new(diagnostic::EmitterWriter::stderr(sopts.color, Some(registry)))
+    };
+    let diagnostic_handler = diagnostic::Handler::with_emitter(can_print_warnings, emitter);
     let span_diagnostic_handler =
         diagnostic::SpanHandler::new(diagnostic_handler, codemap);
 
@@ -457,17 +645,32 @@ pub fn build_session_(sopts: config::Options,
         working_dir: env::current_dir().unwrap(),
         lint_store: RefCell::new(lint::LintStore::new()),
         lints: RefCell::new(NodeMap()),
+        unexpected_cfgs: RefCell::new(Vec::new()),
+        cfg_checked_items: RefCell::new(NodeSet()),
         plugin_llvm_passes: RefCell::new(Vec::new()),
         plugin_attributes: RefCell::new(Vec::new()),
         crate_types: RefCell::new(Vec::new()),
         dependency_formats: RefCell::new(FnvHashMap()),
         crate_metadata: RefCell::new(Vec::new()),
+        crate_disambiguator: RefCell::new(String::new()),
         delayed_span_bug: RefCell::new(None),
+        suppressed_error: Cell::new(false),
         features: RefCell::new(feature_gate::Features::new()),
         recursion_limit: Cell::new(64),
         can_print_warnings: can_print_warnings,
         next_node_id: Cell::new(1),
         injected_allocator: Cell::new(None),
+        perf_stats: PerfStats {
+            path_resolutions: Cell::new(0),
+            import_resolutions: Cell::new(0),
+            trait_selections: Cell::new(0),
+            selection_cache_hits: Cell::new(0),
+            evaluation_cache_hits: Cell::new(0),
+            shared_evaluation_cache_hits: Cell::new(0),
+            snapshot_rollbacks: Cell::new(0),
+            body_checks: Cell::new(0),
+        },
+        module_times: RefCell::new(HashMap::new()),
     };
 
     sess
@@ -480,10 +683,15 @@ pub fn expect<T, M>(sess: &Session, opt: Option<T>, msg: M) -> T where
     diagnostic::expect(sess.diagnostic(), opt, msg)
 }
 
+/// The panic payload of `early_error`. It tells drivers that the command
+/// line was at fault rather than the crate being compiled.
+#[derive(Copy, Clone, Debug)]
+pub struct EarlyError;
+
 pub fn early_error(color: diagnostic::ColorConfig, msg: &str) -> ! {
     let mut emitter = diagnostic::EmitterWriter::stderr(color, None);
     emitter.emit(None, msg, None, diagnostic::Fatal);
-    panic!(diagnostic::FatalError);
+    panic!(EarlyError);
 }
 
 pub fn early_warn(color: diagnostic::ColorConfig, msg: &str) {
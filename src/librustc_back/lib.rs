@@ -47,6 +47,7 @@ extern crate libc;
 extern crate serialize;
 extern crate rustc_llvm;
 extern crate rustc_front;
+extern crate rustc_data_structures;
 #[macro_use] extern crate log;
 
 pub mod abi;
@@ -47,9 +47,11 @@
 //! Original issue: https://github.com/rust-lang/rust/issues/10207
 
 use std::fmt;
-use std::hash::{Hash, SipHasher, Hasher};
+use std::hash::{Hash, Hasher};
+use rustc_data_structures::stable_hasher::StableHasher;
 use rustc_front::hir;
 use rustc_front::intravisit as visit;
+use rustc_front::intravisit::Visitor;
 
 #[derive(Clone, PartialEq, Debug)]
 pub struct Svh {
@@ -76,7 +78,7 @@ impl Svh {
 
         // FIXME: this should use SHA1, not SipHash. SipHash is not built to
         //        avoid collisions.
-        let mut state = SipHasher::new();
+        let mut state = StableHasher::new();
 
         for data in metadata {
             data.hash(&mut state);
@@ -116,6 +118,21 @@ impl Svh {
     }
 }
 
+/// Feeds the contents of `item` into `state`, for telling whether it
+/// changed between two compilation sessions.
+///
+/// Like the SVH, it doesn't depend on spans, so adding whitespace or
+/// comments doesn't change it. Unlike the SVH, it covers the attributes of
+/// `item` and of what's in it, except for doc comments and `#[doc]`. The
+/// items nested in `item` (including those of a module) are left out,
+/// since they have hashes of their own.
+pub fn hash_item<H: Hasher>(krate: &hir::Crate, item: &hir::Item, state: &mut H) {
+    let mut visit = svh_visitor::make(state, krate);
+    visit.walk_nested_items = false;
+    visit.hash_attributes = true;
+    visit.visit_item(item);
+}
+
 impl fmt::Display for Svh {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.pad(self.as_str())
@@ -132,6 +149,7 @@ mod svh_visitor {
     pub use self::SawStmtComponent::*;
     use self::SawAbiComponent::*;
     use syntax::ast::{self, Name, NodeId};
+    use syntax::attr::AttrMetaMethods;
     use syntax::codemap::Span;
     use syntax::parse::token;
     use rustc_front::intravisit as visit;
@@ -139,15 +157,24 @@ mod svh_visitor {
     use rustc_front::hir::*;
     use rustc_front::hir;
 
-    use std::hash::{Hash, SipHasher};
+    use std::hash::{Hash, Hasher};
 
-    pub struct StrictVersionHashVisitor<'a> {
+    pub struct StrictVersionHashVisitor<'a, H: 'a> {
         pub krate: &'a Crate,
-        pub st: &'a mut SipHasher,
+        pub st: &'a mut H,
+        /// Whether to hash the items nested in the ones visited.
+        pub walk_nested_items: bool,
+        /// Whether to hash the attributes other than the docs.
+        pub hash_attributes: bool,
     }
 
-    pub fn make<'a>(st: &'a mut SipHasher, krate: &'a Crate) -> StrictVersionHashVisitor<'a> {
-        StrictVersionHashVisitor { st: st, krate: krate }
+    pub fn make<'a, H: Hasher>(st: &'a mut H, krate: &'a Crate) -> StrictVersionHashVisitor<'a, H> {
+        StrictVersionHashVisitor {
+            st: st,
+            krate: krate,
+            walk_nested_items: true,
+            hash_attributes: false,
+        }
     }
 
     // To off-load the bulk of the hash-computation on #[derive(Hash)],
@@ -200,6 +227,10 @@ mod svh_visitor {
         SawArm,
         SawExpr(SawExprComponent<'a>),
         SawStmt(SawStmtComponent),
+        SawAttribute(ast::AttrStyle),
+        SawMetaWord(token::InternedString),
+        SawMetaList(token::InternedString),
+        SawMetaNameValue(token::InternedString, &'a ast::Lit_),
     }
 
     /// SawExprComponent carries all of the information that we want
@@ -301,9 +332,11 @@ mod svh_visitor {
         }
     }
 
-    impl<'a> Visitor<'a> for StrictVersionHashVisitor<'a> {
+    impl<'a, H: Hasher> Visitor<'a> for StrictVersionHashVisitor<'a, H> {
         fn visit_nested_item(&mut self, item: ItemId) {
-            self.visit_item(self.krate.item(item.id))
+            if self.walk_nested_items {
+                self.visit_item(self.krate.item(item.id))
+            }
         }
 
         fn visit_variant_data(&mut self, s: &'a VariantData, name: Name,
@@ -320,8 +353,8 @@ mod svh_visitor {
             visit::walk_variant(self, v, g, item_id)
         }
 
-        // All of the remaining methods just record (in the hash
-        // SipHasher) that the visitor saw that particular variant
+        // All of the remaining methods just record (in the
+        // hasher) that the visitor saw that particular variant
         // (with its payload), and continue walking as the default
         // visitor would.
         //
@@ -435,5 +468,32 @@ mod svh_visitor {
         fn visit_arm(&mut self, a: &'a Arm) {
             SawArm.hash(self.st); visit::walk_arm(self, a)
         }
+
+        fn visit_attribute(&mut self, a: &'a ast::Attribute) {
+            if !self.hash_attributes || a.node.is_sugared_doc || a.check_name("doc") {
+                return;
+            }
+            SawAttribute(a.node.style).hash(self.st);
+            self.hash_meta_item(&a.node.value);
+        }
+    }
+
+    impl<'a, H: Hasher> StrictVersionHashVisitor<'a, H> {
+        // Hashes `meta_item` without its spans.
+        fn hash_meta_item(&mut self, meta_item: &'a ast::MetaItem) {
+            match meta_item.node {
+                ast::MetaWord(ref name) => SawMetaWord(name.clone()).hash(self.st),
+                ast::MetaList(ref name, ref items) => {
+                    SawMetaList(name.clone()).hash(self.st);
+                    items.len().hash(self.st);
+                    for item in items {
+                        self.hash_meta_item(item);
+                    }
+                }
+                ast::MetaNameValue(ref name, ref lit) => {
+                    SawMetaNameValue(name.clone(), &lit.node).hash(self.st)
+                }
+            }
+        }
     }
 }
@@ -20,6 +20,7 @@ use self::UseError::*;
 
 use borrowck::*;
 use borrowck::InteriorKind::{InteriorElement, InteriorField};
+use rustc::front::map as hir_map;
 use rustc::middle::expr_use_visitor as euv;
 use rustc::middle::infer;
 use rustc::middle::mem_categorization as mc;
@@ -510,6 +511,8 @@ impl<'a, 'tcx> CheckLoanCtxt<'a, 'tcx> {
                 _ => { }
             }
 
+            self.note_receiver_borrowed_during_arguments(old_loan, new_loan, &ol);
+
             let rule_summary = match old_loan.kind {
                 ty::MutBorrow => {
                     format!("the mutable borrow prevents subsequent \
@@ -568,6 +571,53 @@ impl<'a, 'tcx> CheckLoanCtxt<'a, 'tcx> {
         true
     }
 
+    /// Explains conflicts such as `v.push(v.len())`, where the receiver of a
+    /// method call is mutably auto-borrowed before the arguments that use it
+    /// again are evaluated.
+    fn note_receiver_borrowed_during_arguments(&self,
+                                               old_loan: &Loan<'tcx>,
+                                               new_loan: &Loan<'tcx>,
+                                               ol: &str) {
+        match (old_loan.kind, old_loan.cause) {
+            (ty::MutBorrow, euv::AutoRef) => {}
+            _ => return,
+        }
+
+        let tcx = self.tcx();
+        let receiver_id = old_loan.gen_scope.node_id(&tcx.region_maps);
+        let call_id = tcx.map.get_parent_node(receiver_id);
+        let args = match tcx.map.find(call_id) {
+            Some(hir_map::NodeExpr(&hir::Expr { node: hir::ExprMethodCall(_, _, ref args), .. }))
+                    if !args.is_empty() && args[0].id == receiver_id => args,
+            _ => return,
+        };
+
+        let arg = match args[1..].iter().find(|arg| arg.span.contains(new_loan.span)) {
+            Some(arg) => arg,
+            None => return,
+        };
+
+        self.bccx.span_note(
+            arg.span,
+            &format!("`{}` is mutably borrowed as the receiver of the method call for \
+                      the whole call, including while this argument is evaluated",
+                     ol));
+        match tcx.sess.codemap().span_to_snippet(arg.span) {
+            Ok(snippet) => {
+                self.bccx.fileline_help(
+                    arg.span,
+                    &format!("consider evaluating the argument into a local before \
+                              the call: `let arg = {};`",
+                             snippet));
+            }
+            Err(_) => {
+                self.bccx.fileline_help(
+                    arg.span,
+                    "consider evaluating the argument into a local before the call");
+            }
+        }
+    }
+
     fn consume_common(&self,
                       id: ast::NodeId,
                       span: Span,
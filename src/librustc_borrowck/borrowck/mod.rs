@@ -35,11 +35,13 @@ use rustc::middle::mem_categorization::Categorization;
 use rustc::middle::region;
 use rustc::middle::ty::{self, Ty};
 
+use std::collections::HashSet;
 use std::fmt;
+use std::iter::repeat;
 use std::mem;
 use std::rc::Rc;
 use syntax::ast::{self, NodeId};
-use syntax::codemap::Span;
+use syntax::codemap::{Pos, Span};
 
 use rustc_front::hir;
 use rustc_front::hir::{FnDecl, Block};
@@ -667,11 +669,11 @@ impl<'a, 'tcx> BorrowckCtxt<'a, 'tcx> {
             move_data::Declared => {}
 
             move_data::MoveExpr => {
-                let (expr_ty, expr_span) = match self.tcx
-                                                     .map
-                                                     .find(the_move.id) {
+                let (expr, expr_ty) = match self.tcx
+                                                .map
+                                                .find(the_move.id) {
                     Some(hir_map::NodeExpr(expr)) => {
-                        (self.tcx.expr_ty_adjusted(&*expr), expr.span)
+                        (expr, self.tcx.expr_ty_adjusted(&*expr))
                     }
                     r => {
                         self.tcx.sess.bug(&format!("MoveExpr({}) maps to \
@@ -680,15 +682,16 @@ impl<'a, 'tcx> BorrowckCtxt<'a, 'tcx> {
                                                   r))
                     }
                 };
+                let expr_span = expr.span;
                 let (suggestion, _) =
-                    move_suggestion(param_env, expr_span, expr_ty, ("moved by default", ""));
+                    move_suggestion(param_env, expr_span, expr_ty, ("is moved by default", ""));
                 // If the two spans are the same, it's because the expression will be evaluated
                 // multiple times. Avoid printing the same span and adjust the wording so it makes
                 // more sense that it's from multiple evalutations.
                 if expr_span == use_span {
                     self.tcx.sess.note(
                         &format!("`{}` was previously moved here{} because it has type `{}`, \
-                                  which is {}",
+                                  which {}",
                                  ol,
                                  moved_lp_msg,
                                  expr_ty,
@@ -696,11 +699,14 @@ impl<'a, 'tcx> BorrowckCtxt<'a, 'tcx> {
                 } else {
                     self.tcx.sess.span_note(
                         expr_span,
-                        &format!("`{}` moved here{} because it has type `{}`, which is {}",
+                        &format!("`{}` moved here{} because it has type `{}`, which {}",
                                  ol,
                                  moved_lp_msg,
                                  expr_ty,
                                  suggestion));
+                    if expr_ty.moves_by_default(param_env, expr_span) {
+                        self.suggest_avoiding_move(expr, expr_ty);
+                    }
                 }
             }
 
@@ -746,12 +752,12 @@ impl<'a, 'tcx> BorrowckCtxt<'a, 'tcx> {
                     move_suggestion(param_env,
                                     expr_span,
                                     expr_ty,
-                                    ("moved by default",
+                                    ("is moved by default",
                                      "make a copy and capture that instead to override"));
                 self.tcx.sess.span_note(
                     expr_span,
                     &format!("`{}` moved into closure environment here{} because it \
-                            has type `{}`, which is {}",
+                            has type `{}`, which {}",
                             ol,
                             moved_lp_msg,
                             moved_lp.ty,
@@ -768,7 +774,7 @@ impl<'a, 'tcx> BorrowckCtxt<'a, 'tcx> {
             match ty.sty {
                 _ => {
                     if ty.moves_by_default(param_env, span) {
-                        ("non-copyable",
+                        ("does not implement the `Copy` trait",
                          "perhaps you meant to use `clone()`?")
                     } else {
                         default_msgs
@@ -778,6 +784,41 @@ impl<'a, 'tcx> BorrowckCtxt<'a, 'tcx> {
         }
     }
 
+    /// Suggests a way to avoid moving out of the lvalue `moved_expr`:
+    /// borrowing it when it is passed as an argument to a call, and
+    /// cloning it otherwise.
+    fn suggest_avoiding_move(&self, moved_expr: &hir::Expr, moved_ty: Ty<'tcx>) {
+        match moved_expr.node {
+            hir::ExprPath(..) | hir::ExprField(..) | hir::ExprTupField(..) => {}
+            _ => return,
+        }
+        let snippet = match self.tcx.sess.codemap().span_to_snippet(moved_expr.span) {
+            Ok(snippet) => snippet,
+            Err(_) => return,
+        };
+
+        let parent = self.tcx.map.get_parent_node(moved_expr.id);
+        let passed_as_argument = match self.tcx.map.find(parent) {
+            Some(hir_map::NodeExpr(&hir::Expr { node: hir::ExprCall(_, ref args), .. })) => {
+                args.iter().any(|arg| arg.id == moved_expr.id)
+            }
+            _ => false,
+        };
+
+        if passed_as_argument {
+            self.tcx.sess.span_suggestion(
+                moved_expr.span,
+                "consider borrowing the value instead, if the callee accepts a reference:",
+                format!("&{}", snippet));
+        } else {
+            self.tcx.sess.span_suggestion(
+                moved_expr.span,
+                &format!("consider cloning the value instead, if `{}` implements `Clone`:",
+                         moved_ty),
+                format!("{}.clone()", snippet));
+        }
+    }
+
     pub fn report_partial_reinitialization_of_uninitialized_structure(
             &self,
             span: Span,
@@ -1040,8 +1081,10 @@ impl<'a, 'tcx> BorrowckCtxt<'a, 'tcx> {
                     super_scope,
                     "");
                 if let Some(span) = statement_scope_span(self.tcx, super_scope) {
-                    self.tcx.sess.span_help(span,
-                        "consider using a `let` binding to increase its lifetime");
+                    if !self.suggest_let_binding_for_temporary(&err.cmt, span) {
+                        self.tcx.sess.span_help(span,
+                            "consider using a `let` binding to increase its lifetime");
+                    }
                 }
             }
 
@@ -1065,6 +1108,53 @@ impl<'a, 'tcx> BorrowckCtxt<'a, 'tcx> {
         }
     }
 
+    /// Suggests binding the temporary `cmt`, which is dropped at the end of
+    /// the statement `stmt_span`, to a variable declared right before the
+    /// statement, so that it lives until the end of the block. Returns false
+    /// if `cmt` isn't a temporary or the code can't be rewritten.
+    fn suggest_let_binding_for_temporary(&self, cmt: &mc::cmt<'tcx>, stmt_span: Span) -> bool {
+        match cmt.cat {
+            Categorization::Rvalue(..) => {}
+            _ => return false,
+        }
+        let temp_span = cmt.span;
+        if temp_span.expn_id != stmt_span.expn_id ||
+           temp_span.lo < stmt_span.lo || temp_span.hi > stmt_span.hi {
+            return false;
+        }
+
+        let codemap = self.tcx.sess.codemap();
+        let (stmt, temp) = match (codemap.span_to_snippet(stmt_span),
+                                  codemap.span_to_snippet(temp_span)) {
+            (Ok(stmt), Ok(temp)) => (stmt, temp),
+            _ => return false,
+        };
+        let start = (temp_span.lo - stmt_span.lo).to_usize();
+        let end = start + temp.len();
+        let indent = repeat(' ').take(codemap.lookup_char_pos(stmt_span.lo).col.to_usize())
+                                .collect::<String>();
+
+        // The variable gets a name that isn't used anywhere in the enclosing
+        // item, so that it can't shadow anything the rest of the block uses.
+        let item_span = self.tcx.map.span(self.tcx.map.get_parent(cmt.id));
+        let item = codemap.span_to_snippet(item_span).unwrap_or(String::new());
+        let used = item.split(|c: char| !c.is_alphanumeric() && c != '_')
+                       .collect::<HashSet<_>>();
+        let mut name = "binding".to_string();
+        let mut suffix = 0;
+        while used.contains(&name[..]) {
+            suffix += 1;
+            name = format!("binding{}", suffix);
+        }
+
+        self.tcx.sess.span_suggestion(
+            stmt_span,
+            "consider using a `let` binding to increase its lifetime:",
+            format!("let {} = {};\n{}{}{}{}",
+                    name, temp, indent, &stmt[..start], name, &stmt[end..]));
+        true
+    }
+
     pub fn append_loan_path_to_string(&self,
                                       loan_path: &LoanPath<'tcx>,
                                       out: &mut String) {
@@ -39,6 +39,7 @@ pub mod bitvec;
 pub mod graph;
 pub mod ivar;
 pub mod snapshot_vec;
+pub mod stable_hasher;
 pub mod transitive_relation;
 pub mod unify;
 pub mod fnv;
@@ -0,0 +1,233 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Hashes that stay the same from one compilation session to the next, and
+//! from one host to another.
+//!
+//! A plain `#[derive(Hash)]` is not enough for that: interned names and
+//! types hash their index or address, node ids shift whenever an unrelated
+//! item is added, hashmaps are visited in an arbitrary order, and integers
+//! are hashed with the host's width and byte order. `StableHasher` takes
+//! care of the last problem; `HashStable` implementations take care of the
+//! others, with the help of a context that knows how to turn session
+//! specific values (e.g. a `DefId`) into stable ones (e.g. a def-path hash).
+
+use std::hash::{Hash, Hasher, SipHasher};
+use std::mem;
+
+/// A `Hasher` whose result doesn't depend on the host: integers are hashed
+/// in little-endian byte order, and `usize` and `isize` are hashed as 64-bit
+/// integers. The keys of the underlying `SipHasher` are fixed.
+pub struct StableHasher {
+    state: SipHasher,
+}
+
+impl StableHasher {
+    pub fn new() -> StableHasher {
+        StableHasher { state: SipHasher::new() }
+    }
+}
+
+impl Hasher for StableHasher {
+    fn finish(&self) -> u64 {
+        self.state.finish()
+    }
+
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        self.state.write(bytes);
+    }
+
+    #[inline]
+    fn write_u16(&mut self, i: u16) {
+        let bytes: [u8; 2] = unsafe { mem::transmute(i.to_le()) };
+        self.state.write(&bytes);
+    }
+
+    #[inline]
+    fn write_u32(&mut self, i: u32) {
+        let bytes: [u8; 4] = unsafe { mem::transmute(i.to_le()) };
+        self.state.write(&bytes);
+    }
+
+    #[inline]
+    fn write_u64(&mut self, i: u64) {
+        let bytes: [u8; 8] = unsafe { mem::transmute(i.to_le()) };
+        self.state.write(&bytes);
+    }
+
+    #[inline]
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64);
+    }
+
+    // The signed integers are hashed through the unsigned ones by the
+    // default methods.
+}
+
+/// A value that can be hashed with a `StableHasher`. `CTX` provides
+/// whatever is needed to hash the values that are specific to one session,
+/// e.g. the interner for names or the definitions for def-ids.
+pub trait HashStable<CTX> {
+    fn hash_stable(&self, ctx: &mut CTX, hasher: &mut StableHasher);
+}
+
+macro_rules! impl_stable_hash_via_hash {
+    ($($t:ty),*) => {
+        $(
+            impl<CTX> HashStable<CTX> for $t {
+                #[inline]
+                fn hash_stable(&self, _: &mut CTX, hasher: &mut StableHasher) {
+                    self.hash(hasher);
+                }
+            }
+        )*
+    }
+}
+
+impl_stable_hash_via_hash!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, bool, char, str);
+
+impl<CTX> HashStable<CTX> for String {
+    #[inline]
+    fn hash_stable(&self, ctx: &mut CTX, hasher: &mut StableHasher) {
+        (&self[..]).hash_stable(ctx, hasher);
+    }
+}
+
+impl<'a, CTX, T: ?Sized + HashStable<CTX>> HashStable<CTX> for &'a T {
+    #[inline]
+    fn hash_stable(&self, ctx: &mut CTX, hasher: &mut StableHasher) {
+        (**self).hash_stable(ctx, hasher);
+    }
+}
+
+impl<CTX, T: ?Sized + HashStable<CTX>> HashStable<CTX> for Box<T> {
+    #[inline]
+    fn hash_stable(&self, ctx: &mut CTX, hasher: &mut StableHasher) {
+        (**self).hash_stable(ctx, hasher);
+    }
+}
+
+impl<CTX, T: HashStable<CTX>> HashStable<CTX> for [T] {
+    fn hash_stable(&self, ctx: &mut CTX, hasher: &mut StableHasher) {
+        self.len().hash_stable(ctx, hasher);
+        for item in self {
+            item.hash_stable(ctx, hasher);
+        }
+    }
+}
+
+impl<CTX, T: HashStable<CTX>> HashStable<CTX> for Vec<T> {
+    #[inline]
+    fn hash_stable(&self, ctx: &mut CTX, hasher: &mut StableHasher) {
+        (&self[..]).hash_stable(ctx, hasher);
+    }
+}
+
+impl<CTX, T: HashStable<CTX>> HashStable<CTX> for Option<T> {
+    fn hash_stable(&self, ctx: &mut CTX, hasher: &mut StableHasher) {
+        match *self {
+            Some(ref value) => {
+                1u8.hash_stable(ctx, hasher);
+                value.hash_stable(ctx, hasher);
+            }
+            None => 0u8.hash_stable(ctx, hasher),
+        }
+    }
+}
+
+impl<CTX, A: HashStable<CTX>, B: HashStable<CTX>> HashStable<CTX> for (A, B) {
+    fn hash_stable(&self, ctx: &mut CTX, hasher: &mut StableHasher) {
+        self.0.hash_stable(ctx, hasher);
+        self.1.hash_stable(ctx, hasher);
+    }
+}
+
+impl<CTX, A, B, C> HashStable<CTX> for (A, B, C)
+    where A: HashStable<CTX>, B: HashStable<CTX>, C: HashStable<CTX>
+{
+    fn hash_stable(&self, ctx: &mut CTX, hasher: &mut StableHasher) {
+        self.0.hash_stable(ctx, hasher);
+        self.1.hash_stable(ctx, hasher);
+        self.2.hash_stable(ctx, hasher);
+    }
+}
+
+/// Hashes the entries of a map or set whose iteration order is arbitrary,
+/// e.g. a `HashMap`. The entries are sorted by the stable key
+/// `to_stable_key` computes for them first, so the result only depends on
+/// the contents of the map.
+pub fn hash_stable_unordered<'a, CTX, K, V, SK, I, F>(ctx: &mut CTX,
+                                                      hasher: &mut StableHasher,
+                                                      entries: I,
+                                                      mut to_stable_key: F)
+    where K: 'a,
+          V: 'a + HashStable<CTX>,
+          SK: Ord + HashStable<CTX>,
+          I: Iterator<Item = (&'a K, &'a V)>,
+          F: FnMut(&mut CTX, &K) -> SK
+{
+    let mut entries = entries.map(|(key, value)| (to_stable_key(ctx, key), value))
+                             .collect::<Vec<_>>();
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+    entries.len().hash_stable(ctx, hasher);
+    for (key, value) in entries {
+        key.hash_stable(ctx, hasher);
+        value.hash_stable(ctx, hasher);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{hash_stable_unordered, HashStable, StableHasher};
+    use std::collections::HashMap;
+    use std::hash::Hasher;
+
+    fn stable_hash<T: HashStable<()>>(value: &T) -> u64 {
+        let mut hasher = StableHasher::new();
+        value.hash_stable(&mut (), &mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn usize_hashes_like_u64() {
+        assert_eq!(stable_hash(&42usize), stable_hash(&42u64));
+        assert_eq!(stable_hash(&-1isize), stable_hash(&-1i64));
+    }
+
+    #[test]
+    fn integers_hash_little_endian() {
+        let mut hasher = StableHasher::new();
+        hasher.write(&[0x78, 0x56, 0x34, 0x12]);
+        assert_eq!(stable_hash(&0x12345678u32), hasher.finish());
+    }
+
+    #[test]
+    fn integers_hash_the_same_on_every_host() {
+        // The expected values were computed on a little-endian 64-bit host.
+        assert_eq!(stable_hash(&0x0123456789abcdefu64), 0xc4a1f0aff7f4be77);
+        assert_eq!(stable_hash(&0x01234567usize), 0xd49d6fb81de4b56b);
+    }
+
+    #[test]
+    fn unordered_entries_ignore_insertion_order() {
+        let hash = |pairs: &[(u32, &'static str)]| {
+            let map = pairs.iter().cloned().collect::<HashMap<_, _>>();
+            let mut hasher = StableHasher::new();
+            hash_stable_unordered(&mut (), &mut hasher, map.iter(), |_, &key| key);
+            hasher.finish()
+        };
+        let forward = (0..100).map(|i| (i, "x")).collect::<Vec<_>>();
+        let backward = forward.iter().rev().cloned().collect::<Vec<_>>();
+        assert_eq!(hash(&forward[..]), hash(&backward[..]));
+        assert!(hash(&forward[..]) != hash(&forward[1..]));
+    }
+}
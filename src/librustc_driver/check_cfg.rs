@@ -0,0 +1,215 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The `unexpected_cfgs` lint: finds the names and values in `#[cfg]` and
+//! `#[cfg_attr]` that are never set, which are usually typos such as
+//! `#[cfg(featur = "foo")]`.
+//!
+//! This runs on the crate as parsed, before anything is stripped, so it
+//! doesn't see the cfgs of code produced by macros or the `cfg!` macro.
+//! The items get their node ids here, and each unexpected cfg is remembered
+//! in the session with the ids of the items around it. Once the rest of the
+//! crate has node ids, `lint_unexpected_cfgs` lints it at the innermost of
+//! those items that wasn't stripped.
+
+use rustc::lint;
+use rustc::session::Session;
+use rustc::util::nodemap::NodeSet;
+use syntax::ast::{self, NodeIdAssigner};
+use syntax::attr::AttrMetaMethods;
+use syntax::fold::{self, Folder};
+use syntax::ptr::P;
+use syntax::util::small_vector::SmallVector;
+use syntax::visit::{self, Visitor};
+
+use std::mem;
+
+/// The names the compiler sets for some target or configuration, if not
+/// necessarily for this one. Only their names are checked, as the values
+/// depend on the target.
+const WELL_KNOWN_NAMES: &'static [&'static str] = &[
+    "debug_assertions",
+    "target_arch",
+    "target_endian",
+    "target_env",
+    "target_family",
+    "target_feature",
+    "target_os",
+    "target_pointer_width",
+    "target_vendor",
+    "test",
+    "unix",
+    "windows",
+];
+
+pub fn check_crate(sess: &Session, krate: ast::Crate) -> ast::Crate {
+    let config = krate.config.clone();
+    let mut checker = CfgChecker {
+        sess: sess,
+        config: &config,
+        owners: vec![],
+    };
+    checker.fold_crate(krate)
+}
+
+/// Lints the unexpected cfgs found by `check_crate`, now that `krate` has
+/// node ids.
+pub fn lint_unexpected_cfgs(sess: &Session, krate: &ast::Crate) {
+    let unexpected_cfgs = mem::replace(&mut *sess.unexpected_cfgs.borrow_mut(), Vec::new());
+    if unexpected_cfgs.is_empty() {
+        return;
+    }
+    let mut collector = ItemCollector { items: NodeSet() };
+    visit::walk_crate(&mut collector, krate);
+    for (owners, cfg_span, msg) in unexpected_cfgs {
+        let id = owners.into_iter()
+                       .rev()
+                       .find(|id| collector.items.contains(id))
+                       .unwrap_or(ast::CRATE_NODE_ID);
+        sess.add_lint(lint::builtin::UNEXPECTED_CFGS, id, cfg_span, msg);
+    }
+}
+
+struct CfgChecker<'a> {
+    sess: &'a Session,
+    config: &'a ast::CrateConfig,
+    /// The ids of the items around the attributes being folded.
+    owners: Vec<ast::NodeId>,
+}
+
+impl<'a> CfgChecker<'a> {
+    fn check_cfg(&self, cfg: &ast::MetaItem) {
+        match cfg.node {
+            ast::MetaList(ref pred, ref mis) if &pred[..] == "any" ||
+                                                &pred[..] == "all" ||
+                                                &pred[..] == "not" => {
+                for mi in mis {
+                    self.check_cfg(mi);
+                }
+            }
+            // Invalid predicates are reported when the crate is configured.
+            ast::MetaList(..) => {}
+            ast::MetaWord(ref name) | ast::MetaNameValue(ref name, _) => {
+                let well_known = WELL_KNOWN_NAMES.contains(&&name[..]);
+                if !well_known && !self.config.iter().any(|mi| mi.name() == *name) {
+                    self.lint(cfg, format!("unexpected `cfg` name: `{}`", name));
+                    return;
+                }
+                if let Some(value) = cfg.value_str() {
+                    let is_set = self.config.iter().any(|mi| {
+                        mi.name() == *name && mi.value_str().as_ref() == Some(&value)
+                    });
+                    if !well_known && !is_set {
+                        self.lint(cfg, format!("unexpected `cfg` value: `{} = \"{}\"`",
+                                               name, value));
+                    }
+                }
+            }
+        }
+    }
+
+    fn lint(&self, cfg: &ast::MetaItem, msg: String) {
+        self.sess.unexpected_cfgs.borrow_mut().push((self.owners.clone(), cfg.span, msg));
+    }
+
+    fn new_owner_id(&self) -> ast::NodeId {
+        let id = self.sess.next_node_id();
+        self.sess.cfg_checked_items.borrow_mut().insert(id);
+        id
+    }
+
+    fn with_owner<T, F>(&mut self, owner: ast::NodeId, f: F) -> T
+        where F: FnOnce(&mut CfgChecker<'a>) -> T
+    {
+        self.owners.push(owner);
+        let result = f(self);
+        self.owners.pop();
+        result
+    }
+}
+
+impl<'a> Folder for CfgChecker<'a> {
+    fn fold_item(&mut self, i: P<ast::Item>) -> SmallVector<P<ast::Item>> {
+        let id = self.new_owner_id();
+        let i = i.map(|i| ast::Item { id: id, ..i });
+        self.with_owner(id, |f| fold::noop_fold_item(i, f))
+    }
+
+    fn fold_foreign_item(&mut self, i: P<ast::ForeignItem>) -> P<ast::ForeignItem> {
+        let id = self.new_owner_id();
+        let i = i.map(|i| ast::ForeignItem { id: id, ..i });
+        self.with_owner(id, |f| fold::noop_fold_foreign_item(i, f))
+    }
+
+    fn fold_trait_item(&mut self, i: P<ast::TraitItem>) -> SmallVector<P<ast::TraitItem>> {
+        let id = self.new_owner_id();
+        let i = i.map(|i| ast::TraitItem { id: id, ..i });
+        self.with_owner(id, |f| fold::noop_fold_trait_item(i, f))
+    }
+
+    fn fold_impl_item(&mut self, i: P<ast::ImplItem>) -> SmallVector<P<ast::ImplItem>> {
+        let id = self.new_owner_id();
+        let i = i.map(|i| ast::ImplItem { id: id, ..i });
+        self.with_owner(id, |f| fold::noop_fold_impl_item(i, f))
+    }
+
+    fn fold_attribute(&mut self, attr: ast::Attribute) -> SmallVector<ast::Attribute> {
+        if attr.check_name("cfg") {
+            if let Some(mis) = attr.meta_item_list() {
+                for mi in mis {
+                    self.check_cfg(mi);
+                }
+            }
+        } else if attr.check_name("cfg_attr") {
+            if let Some(mis) = attr.meta_item_list() {
+                if let Some(cfg) = mis.first() {
+                    self.check_cfg(cfg);
+                }
+            }
+        }
+        fold::noop_fold_attribute(attr, self)
+    }
+
+    fn fold_mac(&mut self, mac: ast::Mac) -> ast::Mac {
+        // Macro invocations aren't expanded yet.
+        fold::noop_fold_mac(mac, self)
+    }
+}
+
+/// Collects the ids of the items of the configured and expanded crate.
+struct ItemCollector {
+    items: NodeSet,
+}
+
+impl<'v> Visitor<'v> for ItemCollector {
+    fn visit_item(&mut self, i: &'v ast::Item) {
+        self.items.insert(i.id);
+        visit::walk_item(self, i);
+    }
+
+    fn visit_foreign_item(&mut self, i: &'v ast::ForeignItem) {
+        self.items.insert(i.id);
+        visit::walk_foreign_item(self, i);
+    }
+
+    fn visit_trait_item(&mut self, ti: &'v ast::TraitItem) {
+        self.items.insert(ti.id);
+        visit::walk_trait_item(self, ti);
+    }
+
+    fn visit_impl_item(&mut self, ii: &'v ast::ImplItem) {
+        self.items.insert(ii.id);
+        visit::walk_impl_item(self, ii);
+    }
+
+    fn visit_mac(&mut self, _mac: &'v ast::Mac) {
+        // The crate is expanded already.
+    }
+}
@@ -18,13 +18,15 @@ use rustc::session::search_paths::PathKind;
 use rustc::lint;
 use rustc::metadata;
 use rustc::metadata::creader::LocalCrateReader;
-use rustc::middle::{stability, ty, reachable};
+use rustc::middle::{module_times, stability, ty, reachable};
 use rustc::middle::dependency_format;
+use rustc::middle::stable_hash;
 use rustc::middle;
 use rustc::plugin::registry::Registry;
 use rustc::plugin;
 use rustc::util::nodemap::NodeMap;
 use rustc::util::common::time;
+use rustc_back::sha2::{Digest, Sha256};
 use rustc_borrowck as borrowck;
 use rustc_resolve as resolve;
 use rustc_trans::back::link;
@@ -35,6 +37,8 @@ use rustc_privacy;
 use rustc_front::hir;
 use rustc_front::lowering::{lower_crate, LoweringContext};
 use super::Compilation;
+use super::check_cfg;
+use super::stats;
 
 use serialize::json;
 
@@ -111,6 +115,11 @@ pub fn compile_input(sess: Session,
         let mut hir_forest = time(sess.time_passes(),
                                   "lowering ast -> hir",
                                   || hir_map::Forest::new(lower_crate(&lcx, &expanded_crate)));
+
+        if sess.opts.debugging_opts.hir_stats {
+            stats::print_hir_stats(hir_forest.krate());
+        }
+
         let arenas = ty::CtxtArenas::new();
         let ast_map = make_map(&sess, &mut hir_forest);
 
@@ -140,7 +149,13 @@ pub fn compile_input(sess: Session,
                                     &arenas,
                                     &id,
                                     control.make_glob_map,
-                                    |tcx, mir_map, analysis| {
+                                    resolve::MakeVisiblePaths::No,
+                                    |tcx, mut mir_map, analysis| {
+
+                                        let output_types = &tcx.sess.opts.output_types;
+                                        if output_types.contains_key(&OutputType::Mir) {
+                                            write_out_mir(tcx, &mut mir_map, &outputs);
+                                        }
 
                                         {
                                             let state =
@@ -182,13 +197,17 @@ pub fn compile_input(sess: Session,
                                     })
     };
 
+    if sess.opts.debugging_opts.input_stats {
+        sess.print_perf_stats();
+    }
+
     let (outputs, trans) = if let Ok(out) = result {
         out
     } else {
         return;
     };
 
-    phase_5_run_llvm_passes(&sess, &trans, &outputs);
+    module_times::time_llvm(&sess, || phase_5_run_llvm_passes(&sess, &trans, &outputs));
 
     controller_entry_point!(after_llvm,
                             sess,
@@ -410,6 +429,10 @@ pub fn phase_1_parse_input(sess: &Session, cfg: ast::CrateConfig, input: &Input)
         println!("Pre-expansion node count:  {}", count_nodes(&krate));
     }
 
+    if sess.opts.debugging_opts.ast_stats {
+        stats::print_ast_stats(&krate, "PRE EXPANSION AST STATS");
+    }
+
     if let Some(ref s) = sess.opts.show_span {
         syntax::show_span::run(sess.diagnostic(), s, &krate);
     }
@@ -448,6 +471,8 @@ pub fn phase_2_configure_and_expand(sess: &Session,
     //
     // baz! should not use this definition unless foo is enabled.
 
+    krate = time(time_passes, "checking cfgs", || check_cfg::check_crate(sess, krate));
+
     let mut feature_gated_cfgs = vec![];
     krate = time(time_passes, "configuration 1", || {
         syntax::config::strip_unconfigured_items(sess.diagnostic(), krate, &mut feature_gated_cfgs)
@@ -455,6 +480,8 @@ pub fn phase_2_configure_and_expand(sess: &Session,
 
     *sess.crate_types.borrow_mut() = collect_crate_types(sess, &krate.attrs);
     *sess.crate_metadata.borrow_mut() = collect_crate_metadata(sess, &krate.attrs);
+    *sess.crate_disambiguator.borrow_mut() =
+        compute_crate_disambiguator(&sess.crate_metadata.borrow());
 
     time(time_passes, "recursion limit", || {
         middle::recursion_limit::update_recursion_limit(sess, &krate);
@@ -522,6 +549,8 @@ pub fn phase_2_configure_and_expand(sess: &Session,
         *sess.plugin_attributes.borrow_mut() = attributes.clone();
     }
 
+    time(time_passes, "extra lints loading", || plugin::lints::load_extra_lints(sess));
+
     // Lint plugins are registered; now we can process command line flags.
     if sess.opts.describe_lints {
         super::describe_lints(&*sess.lint_store.borrow(), true);
@@ -628,6 +657,10 @@ pub fn phase_2_configure_and_expand(sess: &Session,
         println!("Post-expansion node count: {}", count_nodes(&krate));
     }
 
+    if sess.opts.debugging_opts.ast_stats {
+        stats::print_ast_stats(&krate, "POST EXPANSION AST STATS");
+    }
+
     Some(krate)
 }
 
@@ -638,6 +671,11 @@ pub fn assign_node_ids(sess: &Session, krate: ast::Crate) -> ast::Crate {
 
     impl<'a> Folder for NodeIdAssigner<'a> {
         fn new_id(&mut self, old_id: ast::NodeId) -> ast::NodeId {
+            // Items get their ids when their cfgs are checked, before the
+            // crate is configured
+            if self.sess.cfg_checked_items.borrow().contains(&old_id) {
+                return old_id;
+            }
             assert_eq!(old_id, ast::DUMMY_NODE_ID);
             self.sess.next_node_id()
         }
@@ -647,6 +685,8 @@ pub fn assign_node_ids(sess: &Session, krate: ast::Crate) -> ast::Crate {
                      "assigning node ids",
                      || NodeIdAssigner { sess: sess }.fold_crate(krate));
 
+    check_cfg::lint_unexpected_cfgs(sess, &krate);
+
     if sess.opts.debugging_opts.ast_json {
         println!("{}", json::as_json(&krate));
     }
@@ -665,6 +705,31 @@ pub fn make_map<'ast>(sess: &Session,
     map
 }
 
+/// Prints the def-path hash and the fingerprints of every item and of its
+/// type, sorted by path so that the output doesn't depend on the order of
+/// the items. Items without a type of their own, e.g. modules, get a `-`.
+fn print_item_hashes(tcx: &ty::ctxt) {
+    let mut hashes: Vec<_> = tcx.map.krate().items.values().map(|item| {
+        let def_id = tcx.map.local_def_id(item.id);
+        let type_fingerprint = match item.node {
+            hir::ItemStatic(..) | hir::ItemConst(..) | hir::ItemFn(..) | hir::ItemTy(..) |
+            hir::ItemEnum(..) | hir::ItemStruct(..) => {
+                let ty = tcx.lookup_item_type(def_id).ty;
+                format!("{:016x}", stable_hash::type_fingerprint(tcx, ty))
+            }
+            _ => "-".to_string(),
+        };
+        (tcx.map.path_to_string(item.id),
+         tcx.map.def_path_hash(def_id),
+         stable_hash::fingerprint(tcx, item),
+         type_fingerprint)
+    }).collect();
+    hashes.sort();
+    for (path, path_hash, item_hash, type_fingerprint) in hashes {
+        println!("{:016x} {:016x} {:>16} {}", path_hash, item_hash, type_fingerprint, path);
+    }
+}
+
 /// Run the resolution, typechecking, region checking and other
 /// miscellaneous analysis passes on the crate. Return various
 /// structures carrying the results of the analysis.
@@ -673,6 +738,7 @@ pub fn phase_3_run_analysis_passes<'tcx, F, R>(sess: &'tcx Session,
                                                arenas: &'tcx ty::CtxtArenas<'tcx>,
                                                name: &str,
                                                make_glob_map: resolve::MakeGlobMap,
+                                               make_visible_paths: resolve::MakeVisiblePaths,
                                                f: F)
                                                -> R
     where F: for<'a> FnOnce(&'a ty::ctxt<'tcx>, MirMap<'tcx>, ty::CrateAnalysis) -> R
@@ -694,10 +760,13 @@ pub fn phase_3_run_analysis_passes<'tcx, F, R>(sess: &'tcx Session,
         export_map,
         trait_map,
         external_exports,
+        import_sources,
         glob_map,
+        import_map,
+        visible_paths,
     } = time(time_passes,
              "resolution",
-             || resolve::resolve_crate(sess, &ast_map, make_glob_map));
+             || resolve::resolve_crate(sess, &ast_map, make_glob_map, make_visible_paths));
 
     // Discard MTWT tables that aren't required past resolution.
     // FIXME: get rid of uses of MTWT tables in typeck, mir and trans and clear them
@@ -742,6 +811,10 @@ pub fn phase_3_run_analysis_passes<'tcx, F, R>(sess: &'tcx Session,
                                    // passes are timed inside typeck
                                    typeck::check_crate(tcx, trait_map);
 
+                                   if tcx.sess.opts.debugging_opts.print_item_hashes {
+                                       print_item_hashes(tcx);
+                                   }
+
                                    time(time_passes,
                                         "const checking",
                                         || middle::check_const::check_crate(tcx));
@@ -750,7 +823,8 @@ pub fn phase_3_run_analysis_passes<'tcx, F, R>(sess: &'tcx Session,
                                        time(time_passes, "privacy checking", || {
                                            rustc_privacy::check_crate(tcx,
                                                                       &export_map,
-                                                                      external_exports)
+                                                                      external_exports,
+                                                                      import_sources)
                                        });
 
                                    // Do not move this check past lint
@@ -771,13 +845,15 @@ pub fn phase_3_run_analysis_passes<'tcx, F, R>(sess: &'tcx Session,
                                         || middle::check_match::check_crate(tcx));
 
                                    let mir_map = match tcx.sess.opts.unstable_features {
-                                       UnstableFeatures::Disallow => {
+                                       UnstableFeatures::Disallow
+                                           if !tcx.sess.opts.output_types
+                                                  .contains_key(&OutputType::Mir) => {
                                            // use this as a shorthand for beta/stable, and skip
                                            // MIR construction there until known regressions are
                                            // addressed
                                            NodeMap()
                                        }
-                                       UnstableFeatures::Allow | UnstableFeatures::Cheat => {
+                                       _ => {
                                            time(time_passes,
                                                 "MIR dump",
                                                 || mir::mir_map::build_mir_for_crate(tcx))
@@ -841,6 +917,8 @@ pub fn phase_3_run_analysis_passes<'tcx, F, R>(sess: &'tcx Session,
                                          reachable: reachable_map,
                                          name: name,
                                          glob_map: glob_map,
+                                         import_map: import_map,
+                                         visible_paths: visible_paths,
                                      })
                                })
 }
@@ -969,6 +1047,25 @@ fn write_out_deps(sess: &Session, outputs: &OutputFilenames, id: &str) {
     }
 }
 
+/// Writes the MIR of the crate for `--emit=mir`, in the format documented
+/// in `rustc_mir::pretty`.
+fn write_out_mir<'tcx>(tcx: &ty::ctxt<'tcx>,
+                       mir_map: &mut MirMap<'tcx>,
+                       outputs: &OutputFilenames) {
+    // The types are written without their regions, as trans sees them.
+    time(tcx.sess.time_passes(),
+         "erasing regions from MIR",
+         || mir::transform::erase_regions::erase_regions(tcx, mir_map));
+
+    let mir_filename = outputs.path(OutputType::Mir);
+    let result = fs::File::create(&mir_filename).and_then(|mut file| {
+        mir::pretty::write_mir_pretty(tcx, mir_map, &mut file)
+    });
+    if let Err(e) = result {
+        tcx.sess.fatal(&format!("error writing MIR to `{}`: {}", mir_filename.display(), e));
+    }
+}
+
 pub fn collect_crate_types(session: &Session, attrs: &[ast::Attribute]) -> Vec<config::CrateType> {
     // Unconditionally collect crate types from attributes to make them used
     let attr_types: Vec<config::CrateType> =
@@ -1014,6 +1111,18 @@ pub fn collect_crate_types(session: &Session, attrs: &[ast::Attribute]) -> Vec<c
         return vec![config::CrateTypeExecutable];
     }
 
+    // Crate types asked for on the command line have to be supported by the
+    // target; better to say so now than to fail in the linker.
+    for &crate_type in &session.opts.crate_types {
+        if let Some(capability) = link::missing_target_capability(session, crate_type) {
+            session.err(&format!("cannot produce crate type `{}`: target `{}` does not \
+                                  support {}",
+                                 crate_type,
+                                 session.opts.target_triple,
+                                 capability));
+        }
+    }
+
     // Only check command line flags if present. If no types are specified by
     // command line, then reuse the empty `base` Vec to hold the types that
     // will be found in crate attributes.
@@ -1029,15 +1138,19 @@ pub fn collect_crate_types(session: &Session, attrs: &[ast::Attribute]) -> Vec<c
 
     base.into_iter()
         .filter(|crate_type| {
-            let res = !link::invalid_output_for_target(session, *crate_type);
-
-            if !res {
-                session.warn(&format!("dropping unsupported crate type `{}` for target `{}`",
-                                      *crate_type,
-                                      session.opts.target_triple));
+            match link::missing_target_capability(session, *crate_type) {
+                Some(capability) => {
+                    if session.opts.crate_types.is_empty() {
+                        session.warn(&format!("dropping unsupported crate type `{}` for \
+                                               target `{}`, which does not support {}",
+                                              *crate_type,
+                                              session.opts.target_triple,
+                                              capability));
+                    }
+                    false
+                }
+                None => true,
             }
-
-            res
         })
         .collect()
 }
@@ -1046,6 +1159,25 @@ pub fn collect_crate_metadata(session: &Session, _attrs: &[ast::Attribute]) -> V
     session.opts.cg.metadata.clone()
 }
 
+/// Hashes the crate metadata into the crate disambiguator. The values are
+/// sorted first, so that the order of the `-C metadata` flags doesn't
+/// matter.
+pub fn compute_crate_disambiguator(metadata: &[String]) -> String {
+    let mut metadata = metadata.to_vec();
+    metadata.sort();
+    metadata.dedup();
+
+    let mut hasher = Sha256::new();
+    for data in &metadata {
+        // Hash the length too, so that `["ab", "c"]` and `["a", "bc"]`
+        // don't collide.
+        hasher.input_str(&data.len().to_string());
+        hasher.input_str(":");
+        hasher.input_str(data);
+    }
+    hasher.result_str()[..16].to_string()
+}
+
 pub fn build_output_filenames(input: &Input,
                               odir: &Option<PathBuf>,
                               ofile: &Option<PathBuf>,
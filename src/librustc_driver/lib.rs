@@ -26,6 +26,7 @@
       html_root_url = "https://doc.rust-lang.org/nightly/")]
 
 #![feature(box_syntax)]
+#![feature(catch_panic)]
 #![feature(libc)]
 #![feature(quote)]
 #![feature(rustc_diagnostic_macros)]
@@ -66,6 +67,7 @@ use rustc_trans::back::link;
 use rustc_trans::save;
 use rustc::session::{config, Session, build_session};
 use rustc::session::config::{Input, PrintRequest, OutputType};
+use rustc::session::search_paths::PathKind;
 use rustc::lint::Lint;
 use rustc::lint;
 use rustc::metadata;
@@ -81,7 +83,7 @@ use std::str;
 use std::sync::{Arc, Mutex};
 use std::thread;
 
-use rustc::session::early_error;
+use rustc::session::{early_error, EarlyError};
 
 use syntax::ast;
 use syntax::parse;
@@ -94,15 +96,30 @@ pub mod test;
 pub mod driver;
 pub mod pretty;
 pub mod target_features;
+mod check_cfg;
+mod server;
+mod stats;
 
 
 const BUG_REPORT_URL: &'static str = "https://github.com/rust-lang/rust/blob/master/CONTRIBUTING.\
                                       md#bug-reports";
 
 
+/// The exit status of a successful compilation.
+pub const EXIT_SUCCESS: isize = 0;
+
+/// The exit status when the crate has errors.
+pub const EXIT_COMPILE_ERROR: isize = 1;
+
+/// The exit status when the command line is invalid.
+pub const EXIT_USAGE_ERROR: isize = 2;
+
+/// The exit status when the compiler panicked, i.e. hit a bug. This is the
+/// status of any Rust program that panics.
+pub const EXIT_ICE: isize = 101;
+
 pub fn run(args: Vec<String>) -> isize {
-    monitor(move || run_compiler(&args, &mut RustcDefaultCalls));
-    0
+    monitor_status(move || run_compiler(&args, &mut RustcDefaultCalls))
 }
 
 // Parse args and run the compiler. This is the primary entry point for rustc.
@@ -122,6 +139,11 @@ pub fn run_compiler<'a>(args: &[String], callbacks: &mut CompilerCalls<'a>) {
 
     let sopts = config::build_session_options(&matches);
 
+    if sopts.debugging_opts.server {
+        server::serve(&args[0]);
+        return;
+    }
+
     let descriptions = diagnostics_registry();
 
     do_or_return!(callbacks.early_callback(&matches, &descriptions, sopts.color));
@@ -393,6 +415,14 @@ impl<'a> CompilerCalls<'a> for RustcDefaultCalls {
             control.after_analysis.stop = Compilation::Stop;
         }
 
+        // MIR is written out before translation, which needn't run if
+        // nothing else but the dependencies is emitted.
+        if sess.opts.output_types.contains_key(&OutputType::Mir) &&
+           sess.opts.output_types.keys().all(|&i| i == OutputType::Mir ||
+                                                  i == OutputType::DepInfo) {
+            control.after_analysis.stop = Compilation::Stop;
+        }
+
         if !sess.opts.output_types.keys().any(|&i| i == OutputType::Exe) {
             control.after_llvm.stop = Compilation::Stop;
         }
@@ -451,6 +481,15 @@ impl RustcDefaultCalls {
         for req in &sess.opts.prints {
             match *req {
                 PrintRequest::Sysroot => println!("{}", sess.sysroot().display()),
+                PrintRequest::TargetLibdir => {
+                    let filesearch = sess.target_filesearch(PathKind::All);
+                    println!("{}", filesearch.get_lib_path().display());
+                }
+                PrintRequest::CrateDisambiguator => {
+                    let attrs = attrs.as_ref().map_or(&[][..], |attrs| &attrs[..]);
+                    let metadata = driver::collect_crate_metadata(sess, attrs);
+                    println!("{}", driver::compute_crate_disambiguator(&metadata));
+                }
                 PrintRequest::FileNames |
                 PrintRequest::CrateName => {
                     let input = match input {
@@ -832,8 +871,40 @@ fn parse_crate_attrs(sess: &Session, input: &Input) -> Vec<ast::Attribute> {
 /// The diagnostic emitter yielded to the procedure should be used for reporting
 /// errors of the compiler.
 pub fn monitor<F: FnOnce() + Send + 'static>(f: F) {
+    if monitor_status(f) != EXIT_SUCCESS {
+        // Panic so the process returns a failure code, but don't pollute the
+        // output with some unnecessary panic messages, we've already
+        // printed everything that we needed to.
+        io::set_panic(box io::sink());
+        panic!();
+    }
+}
+
+/// Like `monitor`, but returns the status the process should exit with
+/// instead of panicking when the procedure fails: `EXIT_COMPILE_ERROR` after
+/// a fatal diagnostic, `EXIT_USAGE_ERROR` after an `early_error`, and
+/// `EXIT_ICE` after any other panic.
+pub fn monitor_status<F: FnOnce() + Send + 'static>(f: F) -> isize {
     const STACK_SIZE: usize = 8 * 1024 * 1024; // 8MB
 
+    let mut cfg = thread::Builder::new().name("rustc".to_string());
+
+    // FIXME: Hacks on hacks. If the env is trying to override the stack size
+    // then *don't* set it explicitly.
+    if env::var_os("RUST_MIN_STACK").is_none() {
+        cfg = cfg.stack_size(STACK_SIZE);
+    }
+
+    cfg.spawn(move || catch_status(f, io::stdout())).unwrap().join().unwrap()
+}
+
+/// Like `monitor_status`, but runs the procedure on the current thread. What
+/// it leaves in thread-local storage, like the interned strings and the
+/// metadata kept by `-Z server`, is still there for the procedures after it.
+/// The message of a panic that isn't a diagnostic is written to `ice_output`.
+pub fn catch_status<F, W>(f: F, mut ice_output: W) -> isize
+    where F: FnOnce() + Send + 'static, W: Write
+{
     struct Sink(Arc<Mutex<Vec<u8>>>);
     impl Write for Sink {
         fn write(&mut self, data: &[u8]) -> io::Result<usize> {
@@ -847,26 +918,16 @@ pub fn monitor<F: FnOnce() + Send + 'static>(f: F) {
     let data = Arc::new(Mutex::new(Vec::new()));
     let err = Sink(data.clone());
 
-    let mut cfg = thread::Builder::new().name("rustc".to_string());
-
-    // FIXME: Hacks on hacks. If the env is trying to override the stack size
-    // then *don't* set it explicitly.
-    if env::var_os("RUST_MIN_STACK").is_none() {
-        cfg = cfg.stack_size(STACK_SIZE);
-    }
-
-    match cfg.spawn(move || {
-                 io::set_panic(box err);
-                 f()
-             })
-             .unwrap()
-             .join() {
-        Ok(()) => {
-            // fallthrough
-        }
+    io::set_panic(box err);
+    match thread::catch_panic(f) {
+        Ok(()) => EXIT_SUCCESS,
         Err(value) => {
-            // Thread panicked without emitting a fatal diagnostic
-            if !value.is::<diagnostic::FatalError>() {
+            if value.is::<diagnostic::FatalError>() {
+                EXIT_COMPILE_ERROR
+            } else if value.is::<EarlyError>() {
+                EXIT_USAGE_ERROR
+            } else {
+                // Thread panicked without emitting a fatal diagnostic
                 let mut emitter = diagnostic::EmitterWriter::stderr(diagnostic::Auto, None);
 
                 // a .span_bug or .bug call has already printed what
@@ -887,14 +948,10 @@ pub fn monitor<F: FnOnce() + Send + 'static>(f: F) {
                                  diagnostic::Note);
                 }
 
-                println!("{}", str::from_utf8(&data.lock().unwrap()).unwrap());
+                writeln!(ice_output, "{}", str::from_utf8(&data.lock().unwrap()).unwrap())
+                    .unwrap();
+                EXIT_ICE
             }
-
-            // Panic so the process returns a failure code, but don't pollute the
-            // output with some unnecessary panic messages, we've already
-            // printed everything that we needed to.
-            io::set_panic(box io::sink());
-            panic!();
         }
     }
 }
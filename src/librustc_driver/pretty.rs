@@ -197,6 +197,7 @@ impl PpSourceMode {
                                                     arenas,
                                                     id,
                                                     resolve::MakeGlobMap::No,
+                                                    resolve::MakeVisiblePaths::No,
                                                     |tcx, _, _| {
                                                         let annotation = TypedAnnotation {
                                                             tcx: tcx,
@@ -815,6 +816,7 @@ pub fn pretty_print_input(sess: Session,
                                                         &arenas,
                                                         &id,
                                                         resolve::MakeGlobMap::No,
+                                                        resolve::MakeVisiblePaths::No,
                                                         |tcx, _, _| {
                                                             print_flowgraph(variants,
                                                                             tcx,
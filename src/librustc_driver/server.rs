@@ -0,0 +1,57 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! `-Z server`: an experimental mode for build systems compiling many small
+//! crates, in which rustc stays resident and compiles one crate after the
+//! other, instead of starting a process for each of them.
+//!
+//! Every line of stdin is a request: the arguments of a compilation, without
+//! the program name, separated by tabs. The crate is compiled as if rustc
+//! was run with these arguments, with its diagnostics printed to stderr, and
+//! then a line `done <status>` is printed to stdout, with the status the
+//! process would have exited with. The server exits at the end of stdin.
+//!
+//! All the requests are compiled on the same thread, which keeps the
+//! interned strings for the requests after it. The metadata of the crates a
+//! request loads is kept too, and reused by the requests loading the same
+//! files again, unless they were written in between. Every request still
+//! builds a session and a type context of its own, since the types interned
+//! in a type context can't outlive the compilation that made it.
+
+use {catch_status, run_compiler, RustcDefaultCalls};
+
+use rustc::metadata::loader;
+
+use std::io::{self, BufRead, Write};
+
+/// Compiles the crates requested on stdin until it ends. `program` is the
+/// name rustc was run as.
+pub fn serve(program: &str) {
+    loader::keep_metadata();
+
+    let stdin = io::stdin();
+    for line in stdin.lock().lines() {
+        let line = line.unwrap();
+        if line.is_empty() {
+            continue;
+        }
+
+        let mut args = vec![program.to_string()];
+        args.extend(line.split('\t').map(|arg| arg.to_string()));
+        // Only the `done` lines go to stdout, even after an ICE
+        let status = catch_status(move || run_compiler(&args, &mut RustcDefaultCalls),
+                                  io::stderr());
+
+        let stdout = io::stdout();
+        let mut stdout = stdout.lock();
+        writeln!(stdout, "done {}", status).unwrap();
+        stdout.flush().unwrap();
+    }
+}
@@ -0,0 +1,322 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! `-Z ast-stats` and `-Z hir-stats`: the number of nodes of each kind in
+//! the AST or HIR of the crate, and roughly how much memory they take up.
+//!
+//! The sizes are the `size_of` of the node types, so memory owned by a
+//! node through a `Vec` or `P` is only counted if it is a node itself.
+
+use rustc_front::hir;
+use rustc_front::intravisit as hir_visit;
+use std::collections::HashMap;
+use std::mem;
+use syntax::ast::{self, NodeId, Attribute};
+use syntax::codemap::Span;
+use syntax::visit as ast_visit;
+
+struct NodeData {
+    count: usize,
+    size: usize,
+}
+
+struct StatCollector<'k> {
+    /// The HIR crate, to walk into the nested items; `None` for the AST,
+    /// where items are nested in place.
+    krate: Option<&'k hir::Crate>,
+    data: HashMap<&'static str, NodeData>,
+}
+
+pub fn print_ast_stats(krate: &ast::Crate, title: &str) {
+    let mut collector = StatCollector {
+        krate: None,
+        data: HashMap::new(),
+    };
+    ast_visit::walk_crate(&mut collector, krate);
+    collector.print(title);
+}
+
+pub fn print_hir_stats(krate: &hir::Crate) {
+    let mut collector = StatCollector {
+        krate: Some(krate),
+        data: HashMap::new(),
+    };
+    hir_visit::walk_crate(&mut collector, krate);
+    collector.print("HIR STATS");
+}
+
+impl<'k> StatCollector<'k> {
+    fn record<T>(&mut self, label: &'static str, _node: &T) {
+        let entry = self.data.entry(label).or_insert(NodeData {
+            count: 0,
+            size: 0,
+        });
+        entry.count += 1;
+        entry.size = mem::size_of::<T>();
+    }
+
+    fn print(&self, title: &str) {
+        let mut stats: Vec<_> = self.data.iter().collect();
+        stats.sort_by(|&(_, a), &(_, b)| (a.count * a.size).cmp(&(b.count * b.size)));
+
+        let mut total_size = 0;
+
+        println!("\n{}\n", title);
+        println!("{:<18}{:>18}{:>14}{:>14}",
+                 "Name", "Accumulated Size", "Count", "Item Size");
+        println!("----------------------------------------------------------------");
+        for (label, data) in stats {
+            println!("{:<18}{:>18}{:>14}{:>14}",
+                     label, data.count * data.size, data.count, data.size);
+            total_size += data.count * data.size;
+        }
+        println!("----------------------------------------------------------------");
+        println!("{:<18}{:>18}\n", "Total", total_size);
+    }
+}
+
+impl<'v> hir_visit::Visitor<'v> for StatCollector<'v> {
+    fn visit_nested_item(&mut self, id: hir::ItemId) {
+        let item = self.krate.unwrap().item(id.id);
+        hir_visit::Visitor::visit_item(self, item)
+    }
+
+    fn visit_item(&mut self, i: &'v hir::Item) {
+        self.record("Item", i);
+        hir_visit::walk_item(self, i)
+    }
+    fn visit_mod(&mut self, m: &'v hir::Mod, _s: Span, _n: NodeId) {
+        self.record("Mod", m);
+        hir_visit::walk_mod(self, m)
+    }
+    fn visit_foreign_item(&mut self, i: &'v hir::ForeignItem) {
+        self.record("ForeignItem", i);
+        hir_visit::walk_foreign_item(self, i)
+    }
+    fn visit_local(&mut self, l: &'v hir::Local) {
+        self.record("Local", l);
+        hir_visit::walk_local(self, l)
+    }
+    fn visit_block(&mut self, b: &'v hir::Block) {
+        self.record("Block", b);
+        hir_visit::walk_block(self, b)
+    }
+    fn visit_stmt(&mut self, s: &'v hir::Stmt) {
+        self.record("Stmt", s);
+        hir_visit::walk_stmt(self, s)
+    }
+    fn visit_arm(&mut self, a: &'v hir::Arm) {
+        self.record("Arm", a);
+        hir_visit::walk_arm(self, a)
+    }
+    fn visit_pat(&mut self, p: &'v hir::Pat) {
+        self.record("Pat", p);
+        hir_visit::walk_pat(self, p)
+    }
+    fn visit_decl(&mut self, d: &'v hir::Decl) {
+        self.record("Decl", d);
+        hir_visit::walk_decl(self, d)
+    }
+    fn visit_expr(&mut self, ex: &'v hir::Expr) {
+        self.record("Expr", ex);
+        hir_visit::walk_expr(self, ex)
+    }
+    fn visit_ty(&mut self, t: &'v hir::Ty) {
+        self.record("Ty", t);
+        hir_visit::walk_ty(self, t)
+    }
+    fn visit_generics(&mut self, g: &'v hir::Generics) {
+        self.record("Generics", g);
+        hir_visit::walk_generics(self, g)
+    }
+    fn visit_fn(&mut self,
+                fk: hir_visit::FnKind<'v>,
+                fd: &'v hir::FnDecl,
+                b: &'v hir::Block,
+                s: Span,
+                _: NodeId) {
+        self.record("FnDecl", fd);
+        hir_visit::walk_fn(self, fk, fd, b, s)
+    }
+    fn visit_trait_item(&mut self, ti: &'v hir::TraitItem) {
+        self.record("TraitItem", ti);
+        hir_visit::walk_trait_item(self, ti)
+    }
+    fn visit_impl_item(&mut self, ii: &'v hir::ImplItem) {
+        self.record("ImplItem", ii);
+        hir_visit::walk_impl_item(self, ii)
+    }
+    fn visit_ty_param_bound(&mut self, bounds: &'v hir::TyParamBound) {
+        self.record("TyParamBound", bounds);
+        hir_visit::walk_ty_param_bound(self, bounds)
+    }
+    fn visit_struct_field(&mut self, s: &'v hir::StructField) {
+        self.record("StructField", s);
+        hir_visit::walk_struct_field(self, s)
+    }
+    fn visit_variant(&mut self, v: &'v hir::Variant, g: &'v hir::Generics, item_id: NodeId) {
+        self.record("Variant", v);
+        hir_visit::walk_variant(self, v, g, item_id)
+    }
+    fn visit_lifetime(&mut self, lifetime: &'v hir::Lifetime) {
+        self.record("Lifetime", lifetime);
+        hir_visit::walk_lifetime(self, lifetime)
+    }
+    fn visit_lifetime_def(&mut self, lifetime: &'v hir::LifetimeDef) {
+        self.record("LifetimeDef", lifetime);
+        hir_visit::walk_lifetime_def(self, lifetime)
+    }
+    fn visit_explicit_self(&mut self, es: &'v hir::ExplicitSelf) {
+        self.record("ExplicitSelf", es);
+        hir_visit::walk_explicit_self(self, es)
+    }
+    fn visit_path(&mut self, path: &'v hir::Path, _id: NodeId) {
+        self.record("Path", path);
+        hir_visit::walk_path(self, path)
+    }
+    fn visit_path_list_item(&mut self, prefix: &'v hir::Path, item: &'v hir::PathListItem) {
+        self.record("PathListItem", item);
+        hir_visit::walk_path_list_item(self, prefix, item)
+    }
+    fn visit_path_segment(&mut self, path_span: Span, path_segment: &'v hir::PathSegment) {
+        self.record("PathSegment", path_segment);
+        hir_visit::walk_path_segment(self, path_span, path_segment)
+    }
+    fn visit_assoc_type_binding(&mut self, type_binding: &'v hir::TypeBinding) {
+        self.record("TypeBinding", type_binding);
+        hir_visit::walk_assoc_type_binding(self, type_binding)
+    }
+    fn visit_attribute(&mut self, attr: &'v Attribute) {
+        self.record("Attribute", attr);
+    }
+    fn visit_macro_def(&mut self, macro_def: &'v hir::MacroDef) {
+        self.record("MacroDef", macro_def);
+        hir_visit::walk_macro_def(self, macro_def)
+    }
+}
+
+impl<'v> ast_visit::Visitor<'v> for StatCollector<'v> {
+    fn visit_item(&mut self, i: &'v ast::Item) {
+        self.record("Item", i);
+        ast_visit::walk_item(self, i)
+    }
+    fn visit_mod(&mut self, m: &'v ast::Mod, _s: Span, _n: NodeId) {
+        self.record("Mod", m);
+        ast_visit::walk_mod(self, m)
+    }
+    fn visit_foreign_item(&mut self, i: &'v ast::ForeignItem) {
+        self.record("ForeignItem", i);
+        ast_visit::walk_foreign_item(self, i)
+    }
+    fn visit_local(&mut self, l: &'v ast::Local) {
+        self.record("Local", l);
+        ast_visit::walk_local(self, l)
+    }
+    fn visit_block(&mut self, b: &'v ast::Block) {
+        self.record("Block", b);
+        ast_visit::walk_block(self, b)
+    }
+    fn visit_stmt(&mut self, s: &'v ast::Stmt) {
+        self.record("Stmt", s);
+        ast_visit::walk_stmt(self, s)
+    }
+    fn visit_arm(&mut self, a: &'v ast::Arm) {
+        self.record("Arm", a);
+        ast_visit::walk_arm(self, a)
+    }
+    fn visit_pat(&mut self, p: &'v ast::Pat) {
+        self.record("Pat", p);
+        ast_visit::walk_pat(self, p)
+    }
+    fn visit_decl(&mut self, d: &'v ast::Decl) {
+        self.record("Decl", d);
+        ast_visit::walk_decl(self, d)
+    }
+    fn visit_expr(&mut self, ex: &'v ast::Expr) {
+        self.record("Expr", ex);
+        ast_visit::walk_expr(self, ex)
+    }
+    fn visit_ty(&mut self, t: &'v ast::Ty) {
+        self.record("Ty", t);
+        ast_visit::walk_ty(self, t)
+    }
+    fn visit_generics(&mut self, g: &'v ast::Generics) {
+        self.record("Generics", g);
+        ast_visit::walk_generics(self, g)
+    }
+    fn visit_fn(&mut self,
+                fk: ast_visit::FnKind<'v>,
+                fd: &'v ast::FnDecl,
+                b: &'v ast::Block,
+                s: Span,
+                _: NodeId) {
+        self.record("FnDecl", fd);
+        ast_visit::walk_fn(self, fk, fd, b, s)
+    }
+    fn visit_trait_item(&mut self, ti: &'v ast::TraitItem) {
+        self.record("TraitItem", ti);
+        ast_visit::walk_trait_item(self, ti)
+    }
+    fn visit_impl_item(&mut self, ii: &'v ast::ImplItem) {
+        self.record("ImplItem", ii);
+        ast_visit::walk_impl_item(self, ii)
+    }
+    fn visit_ty_param_bound(&mut self, bounds: &'v ast::TyParamBound) {
+        self.record("TyParamBound", bounds);
+        ast_visit::walk_ty_param_bound(self, bounds)
+    }
+    fn visit_struct_field(&mut self, s: &'v ast::StructField) {
+        self.record("StructField", s);
+        ast_visit::walk_struct_field(self, s)
+    }
+    fn visit_variant(&mut self, v: &'v ast::Variant, g: &'v ast::Generics, item_id: NodeId) {
+        self.record("Variant", v);
+        ast_visit::walk_variant(self, v, g, item_id)
+    }
+    fn visit_lifetime(&mut self, lifetime: &'v ast::Lifetime) {
+        self.record("Lifetime", lifetime);
+        ast_visit::walk_lifetime(self, lifetime)
+    }
+    fn visit_lifetime_def(&mut self, lifetime: &'v ast::LifetimeDef) {
+        self.record("LifetimeDef", lifetime);
+        ast_visit::walk_lifetime_def(self, lifetime)
+    }
+    fn visit_explicit_self(&mut self, es: &'v ast::ExplicitSelf) {
+        self.record("ExplicitSelf", es);
+        ast_visit::walk_explicit_self(self, es)
+    }
+    fn visit_mac(&mut self, mac: &'v ast::Mac) {
+        // only reached before expansion
+        self.record("Mac", mac);
+    }
+    fn visit_path(&mut self, path: &'v ast::Path, _id: NodeId) {
+        self.record("Path", path);
+        ast_visit::walk_path(self, path)
+    }
+    fn visit_path_list_item(&mut self, prefix: &'v ast::Path, item: &'v ast::PathListItem) {
+        self.record("PathListItem", item);
+        ast_visit::walk_path_list_item(self, prefix, item)
+    }
+    fn visit_path_segment(&mut self, path_span: Span, path_segment: &'v ast::PathSegment) {
+        self.record("PathSegment", path_segment);
+        ast_visit::walk_path_segment(self, path_span, path_segment)
+    }
+    fn visit_assoc_type_binding(&mut self, type_binding: &'v ast::TypeBinding) {
+        self.record("TypeBinding", type_binding);
+        ast_visit::walk_assoc_type_binding(self, type_binding)
+    }
+    fn visit_attribute(&mut self, attr: &'v Attribute) {
+        self.record("Attribute", attr);
+    }
+    fn visit_macro_def(&mut self, macro_def: &'v ast::MacroDef) {
+        self.record("MacroDef", macro_def);
+        ast_visit::walk_macro_def(self, macro_def)
+    }
+}
@@ -125,7 +125,8 @@ fn test_env<F>(source_string: &str,
     // run just enough stuff to build a tcx:
     let lang_items = lang_items::collect_language_items(&sess, &ast_map);
     let resolve::CrateMap { def_map, freevars, .. } =
-        resolve::resolve_crate(&sess, &ast_map, resolve::MakeGlobMap::No);
+        resolve::resolve_crate(&sess, &ast_map, resolve::MakeGlobMap::No,
+                               resolve::MakeVisiblePaths::No);
     let named_region_map = resolve_lifetime::krate(&sess, krate, &def_map.borrow());
     let region_map = region::resolve_crate(&sess, krate);
     ty::ctxt::create_and_enter(&sess,
@@ -29,7 +29,7 @@
 //! a `pub fn new()`.
 
 use metadata::decoder;
-use middle::{cfg, def, infer, stability, traits};
+use middle::{cfg, def, infer, pat_util, stability, traits};
 use middle::def_id::DefId;
 use middle::subst::Substs;
 use middle::ty::{self, Ty};
@@ -43,10 +43,13 @@ use std::collections::HashSet;
 
 use syntax::{ast};
 use syntax::attr::{self, AttrMetaMethods};
-use syntax::codemap::{self, Span};
+use syntax::codemap::{self, Span, NO_EXPANSION};
+use syntax::parse::token;
+use syntax::ptr::P;
 
+use rustc_back::slice;
 use rustc_front::hir;
-use rustc_front::intravisit::FnKind;
+use rustc_front::intravisit::{self, FnKind, Visitor};
 
 use bad_style::{MethodLateContext, method_context};
 
@@ -586,20 +589,25 @@ pub struct Stability;
 
 impl Stability {
     fn lint(&self, cx: &LateContext, _id: DefId,
-            span: Span, stability: &Option<&attr::Stability>) {
+            span: Span, stability: &Option<&attr::Stability>,
+            deprecation: &Option<attr::Deprecation>) {
         // Deprecated attributes apply in-crate and cross-crate.
-        let (lint, label) = match *stability {
-            Some(&attr::Stability { depr: Some(_), .. }) =>
+        let (lint, label) = match (*stability, deprecation) {
+            (Some(&attr::Stability { depr: Some(_), .. }), _) |
+            (_, &Some(_)) =>
                 (DEPRECATED, "deprecated"),
             _ => return
         };
 
-        output(cx, span, stability, lint, label);
+        output(cx, span, stability, deprecation, lint, label);
 
         fn output(cx: &LateContext, span: Span, stability: &Option<&attr::Stability>,
+                  deprecation: &Option<attr::Deprecation>,
                   lint: &'static Lint, label: &'static str) {
-            let msg = match *stability {
-                Some(&attr::Stability {depr: Some(attr::Deprecation {ref reason, ..}), ..}) => {
+            let msg = match (*stability, deprecation) {
+                (Some(&attr::Stability {depr: Some(attr::RustcDeprecation {ref reason, ..}),
+                                        ..}), _) |
+                (_, &Some(attr::Deprecation {note: Some(ref reason), ..})) => {
                     format!("use of {} item: {}", label, reason)
                 }
                 _ => format!("use of {} item", label)
@@ -619,32 +627,32 @@ impl LintPass for Stability {
 impl LateLintPass for Stability {
     fn check_item(&mut self, cx: &LateContext, item: &hir::Item) {
         stability::check_item(cx.tcx, item, false,
-                              &mut |id, sp, stab|
-                                self.lint(cx, id, sp, &stab));
+                              &mut |id, sp, stab, depr|
+                                self.lint(cx, id, sp, &stab, depr));
     }
 
     fn check_expr(&mut self, cx: &LateContext, e: &hir::Expr) {
         stability::check_expr(cx.tcx, e,
-                              &mut |id, sp, stab|
-                                self.lint(cx, id, sp, &stab));
+                              &mut |id, sp, stab, depr|
+                                self.lint(cx, id, sp, &stab, depr));
     }
 
     fn check_path(&mut self, cx: &LateContext, path: &hir::Path, id: ast::NodeId) {
         stability::check_path(cx.tcx, path, id,
-                              &mut |id, sp, stab|
-                                self.lint(cx, id, sp, &stab));
+                              &mut |id, sp, stab, depr|
+                                self.lint(cx, id, sp, &stab, depr));
     }
 
     fn check_path_list_item(&mut self, cx: &LateContext, item: &hir::PathListItem) {
         stability::check_path_list_item(cx.tcx, item,
-                                         &mut |id, sp, stab|
-                                           self.lint(cx, id, sp, &stab));
+                                         &mut |id, sp, stab, depr|
+                                           self.lint(cx, id, sp, &stab, depr));
     }
 
     fn check_pat(&mut self, cx: &LateContext, pat: &hir::Pat) {
         stability::check_pat(cx.tcx, pat,
-                             &mut |id, sp, stab|
-                                self.lint(cx, id, sp, &stab));
+                             &mut |id, sp, stab, depr|
+                                self.lint(cx, id, sp, &stab, depr));
     }
 }
 
@@ -1163,3 +1171,111 @@ impl LateLintPass for DropWithReprExtern {
         })
     }
 }
+
+declare_lint! {
+    SHADOWED_BINDINGS,
+    Allow,
+    "`let` or pattern bindings that shadow an earlier binding of the same function"
+}
+
+/// Lints for bindings that shadow a binding of the same name that is still in
+/// scope, e.g. the `x` of `let x = 1; let x = "a";`, which easily goes
+/// unnoticed when code is moved around.
+#[derive(Copy, Clone)]
+pub struct ShadowedBindings;
+
+impl LintPass for ShadowedBindings {
+    fn get_lints(&self) -> LintArray {
+        lint_array!(SHADOWED_BINDINGS)
+    }
+}
+
+impl LateLintPass for ShadowedBindings {
+    fn check_fn(&mut self, cx: &LateContext, _: FnKind, decl: &hir::FnDecl,
+                blk: &hir::Block, _: Span, _: ast::NodeId) {
+        let mut visitor = ShadowedBindingsVisitor { cx: cx, scopes: vec![vec![]] };
+        for arg in &decl.inputs {
+            visitor.add_bindings(slice::ref_slice(&arg.pat));
+        }
+        visitor.visit_block(blk);
+    }
+}
+
+struct ShadowedBindingsVisitor<'a, 'tcx: 'a> {
+    cx: &'a LateContext<'a, 'tcx>,
+    /// The bindings in scope, by block or match arm, innermost last.
+    scopes: Vec<Vec<(ast::Name, Span, ast::NodeId)>>,
+}
+
+impl<'a, 'tcx> ShadowedBindingsVisitor<'a, 'tcx> {
+    /// Lints for the bindings of `pats` that shadow one in scope, then
+    /// brings them into the innermost scope. The patterns are the
+    /// alternatives of a single match arm, which may bind the same names.
+    fn add_bindings(&mut self, pats: &[P<hir::Pat>]) {
+        let mut bindings = vec![];
+        for pat in pats {
+            pat_util::pat_bindings(&self.cx.tcx.def_map, pat, |_, id, span, path1| {
+                let name = path1.node;
+                // Skip the bindings the compiler makes up when it desugars
+                // `for` loops and the like, and the ones of macros.
+                if token::intern(&name.as_str()) != name || span.expn_id != NO_EXPANSION {
+                    return;
+                }
+                if !bindings.iter().any(|&(other, _, _)| other == name) {
+                    bindings.push((name, span, id));
+                }
+            });
+        }
+
+        for &(name, span, id) in &bindings {
+            let shadowed = self.scopes.iter().rev().filter_map(|scope| {
+                scope.iter().rev().find(|&&(other, _, _)| other == name)
+            }).next().cloned();
+            if let Some((_, shadowed_span, shadowed_id)) = shadowed {
+                let ty = self.cx.tcx.node_id_to_type(id);
+                let shadowed_ty = self.cx.tcx.node_id_to_type(shadowed_id);
+                self.cx.span_lint_note(SHADOWED_BINDINGS, span,
+                                       &format!("binding `{}: {}` shadows an earlier \
+                                                 binding of the same name", name, ty),
+                                       shadowed_span,
+                                       &format!("the shadowed binding `{}: {}` is here",
+                                                name, shadowed_ty));
+            }
+        }
+        self.scopes.last_mut().unwrap().extend(bindings);
+    }
+}
+
+impl<'a, 'tcx, 'v> Visitor<'v> for ShadowedBindingsVisitor<'a, 'tcx> {
+    fn visit_block(&mut self, b: &hir::Block) {
+        self.scopes.push(vec![]);
+        intravisit::walk_block(self, b);
+        self.scopes.pop();
+    }
+
+    fn visit_local(&mut self, l: &hir::Local) {
+        // The initializer of a `let` still sees the bindings it shadows.
+        if let Some(ref init) = l.init {
+            self.visit_expr(init);
+        }
+        self.add_bindings(slice::ref_slice(&l.pat));
+    }
+
+    fn visit_arm(&mut self, a: &hir::Arm) {
+        self.scopes.push(vec![]);
+        self.add_bindings(&a.pats);
+        if let Some(ref guard) = a.guard {
+            self.visit_expr(guard);
+        }
+        self.visit_expr(&a.body);
+        self.scopes.pop();
+    }
+
+    fn visit_expr(&mut self, e: &hir::Expr) {
+        // Closures are checked as functions of their own.
+        if let hir::ExprClosure(..) = e.node {
+            return;
+        }
+        intravisit::walk_expr(self, e);
+    }
+}
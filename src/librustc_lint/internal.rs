@@ -0,0 +1,133 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Lints for the compiler's own code, in the `rustc_internal` group. They are
+//! allowed by default; `-W rustc_internal` turns them on.
+
+use middle::ty;
+use lint::{LateContext, LintContext, LintArray};
+use lint::{LintPass, LateLintPass};
+
+use syntax::ast;
+
+use rustc_front::hir;
+
+/// Whether `ty` is, or points to, the struct at `path` within its crate,
+/// such as `["session", "Session"]`.
+fn is_struct(cx: &LateContext, ty: ty::Ty, path: &[&str]) -> bool {
+    match ty.sty {
+        ty::TyStruct(def, _) => {
+            let def_path = cx.tcx.def_path(def.did);
+            def_path.len() == path.len() &&
+            def_path.iter().zip(path).all(|(data, &name)| data.data.as_interned_str() == name)
+        }
+        ty::TyRef(_, ty::TypeAndMut { ty, .. }) |
+        ty::TyBox(ty) => is_struct(cx, ty, path),
+        _ => false,
+    }
+}
+
+declare_lint! {
+    pub MISSING_ERROR_CODES,
+    Allow,
+    "errors reported without an error code"
+}
+
+#[derive(Copy, Clone)]
+pub struct MissingErrorCodes;
+
+impl LintPass for MissingErrorCodes {
+    fn get_lints(&self) -> LintArray {
+        lint_array!(MISSING_ERROR_CODES)
+    }
+}
+
+impl LateLintPass for MissingErrorCodes {
+    fn check_expr(&mut self, cx: &LateContext, e: &hir::Expr) {
+        let (method, receiver) = match e.node {
+            hir::ExprMethodCall(ref method, _, ref args) => (method.node, &args[0]),
+            _ => return,
+        };
+        match &*method.as_str() {
+            "span_err" | "err" | "span_fatal" | "fatal" => {}
+            _ => return,
+        }
+        let receiver_ty = cx.tcx.expr_ty(receiver);
+        let is_reporter = [&["session", "Session"],
+                           &["diagnostic", "SpanHandler"],
+                           &["diagnostic", "Handler"]].iter().any(|path| {
+            is_struct(cx, receiver_ty, &path[..])
+        });
+        if is_reporter {
+            cx.span_lint(MISSING_ERROR_CODES, e.span,
+                         &format!("error reported with `{}` has no error code; use \
+                                   `{}!` with a new code instead",
+                                  method, method));
+        }
+    }
+}
+
+declare_lint! {
+    pub NAME_STR_COMPARISONS,
+    Allow,
+    "names compared with string literals rather than as interned names"
+}
+
+#[derive(Copy, Clone)]
+pub struct NameStrComparisons;
+
+impl LintPass for NameStrComparisons {
+    fn get_lints(&self) -> LintArray {
+        lint_array!(NAME_STR_COMPARISONS)
+    }
+}
+
+impl NameStrComparisons {
+    /// Whether `e` is `name.as_str()`, possibly behind `&*`, for a `Name`.
+    fn is_name_as_str(cx: &LateContext, e: &hir::Expr) -> bool {
+        match e.node {
+            hir::ExprAddrOf(_, ref inner) |
+            hir::ExprUnary(hir::UnDeref, ref inner) => Self::is_name_as_str(cx, inner),
+            hir::ExprMethodCall(ref method, _, ref args) => {
+                &*method.node.as_str() == "as_str" &&
+                is_struct(cx, cx.tcx.expr_ty(&args[0]), &["ast", "Name"])
+            }
+            _ => false,
+        }
+    }
+
+    fn is_str_lit(e: &hir::Expr) -> bool {
+        match e.node {
+            hir::ExprLit(ref lit) => match lit.node {
+                ast::LitStr(..) => true,
+                _ => false,
+            },
+            hir::ExprAddrOf(_, ref inner) => Self::is_str_lit(inner),
+            _ => false,
+        }
+    }
+}
+
+impl LateLintPass for NameStrComparisons {
+    fn check_expr(&mut self, cx: &LateContext, e: &hir::Expr) {
+        if let hir::ExprBinary(op, ref l, ref r) = e.node {
+            match op.node {
+                hir::BiEq | hir::BiNe => {}
+                _ => return,
+            }
+            if (Self::is_name_as_str(cx, l) && Self::is_str_lit(r)) ||
+               (Self::is_str_lit(l) && Self::is_name_as_str(cx, r)) {
+                cx.span_lint(NAME_STR_COMPARISONS, e.span,
+                             "comparing a name's string with a literal; compare it \
+                              with an interned name instead");
+            }
+        }
+    }
+}
@@ -60,11 +60,13 @@ use lint::LintId;
 
 mod bad_style;
 mod builtin;
+mod internal;
 mod types;
 mod unused;
 
 use bad_style::*;
 use builtin::*;
+use internal::*;
 use types::*;
 use unused::*;
 
@@ -131,6 +133,9 @@ pub fn register_builtins(store: &mut lint::LintStore, sess: Option<&Session>) {
                  PluginAsLibrary,
                  DropWithReprExtern,
                  MutableTransmutes,
+                 MissingErrorCodes,
+                 NameStrComparisons,
+                 ShadowedBindings,
                  );
 
     add_builtin_with_new!(sess,
@@ -147,6 +152,16 @@ pub fn register_builtins(store: &mut lint::LintStore, sess: Option<&Session>) {
                     UNUSED_MUT, UNREACHABLE_CODE, UNUSED_MUST_USE,
                     UNUSED_UNSAFE, PATH_STATEMENTS, UNUSED_ATTRIBUTES);
 
+    // Allowed by default, for the compiler's own code
+    add_lint_group!(sess, "rustc_internal",
+                    MISSING_ERROR_CODES, NAME_STR_COMPARISONS);
+
+    // Code that is accepted for now, but will become an error
+    store.register_future_incompatible(sess, vec![
+        LintId::of(MODULE_AND_STRUCT_WITH_SAME_NAME),
+        LintId::of(GLOB_AND_EXPLICIT_IMPORT_COLLISIONS),
+    ]);
+
     // We have one lint pass defined specially
     store.register_late_pass(sess, false, box lint::GatherNodeLevels);
 
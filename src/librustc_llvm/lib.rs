@@ -53,6 +53,7 @@ pub use self::AsmDialect::*;
 pub use self::CodeGenOptLevel::*;
 pub use self::RelocMode::*;
 pub use self::CodeGenModel::*;
+pub use self::FloatABIType::*;
 pub use self::DiagnosticKind::*;
 pub use self::CallConv::*;
 pub use self::Visibility::*;
@@ -443,6 +444,14 @@ pub enum CodeGenModel {
     CodeModelLarge = 5,
 }
 
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq)]
+pub enum FloatABIType {
+    FloatABIDefault = 0,
+    FloatABISoft = 1,
+    FloatABIHard = 2,
+}
+
 #[repr(C)]
 #[derive(Copy, Clone)]
 pub enum DiagnosticKind {
@@ -2044,7 +2053,7 @@ extern {
                                        Model: CodeGenModel,
                                        Reloc: RelocMode,
                                        Level: CodeGenOptLevel,
-                                       UseSoftFP: bool,
+                                       FloatABI: FloatABIType,
                                        PositionIndependentExecutable: bool,
                                        FunctionSections: bool,
                                        DataSections: bool) -> TargetMachineRef;
@@ -32,6 +32,8 @@ extern crate syntax;
 pub mod build;
 pub mod mir_map;
 mod hair;
+pub mod lint;
+pub mod pretty;
 pub mod repr;
 mod graphviz;
 pub mod transform;
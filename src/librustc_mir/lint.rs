@@ -0,0 +1,267 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The `unreachable_code` and `unused_assignments` lints, computed
+//! from the MIR control-flow graph. These are only run with
+//! `-Z mir-lints`, in which case the HIR-based versions in typeck and
+//! liveness are disabled.
+//!
+//! MIR statements do not carry node ids, so the lints are attributed
+//! to the enclosing fn (or closure) when determining their level.
+
+use repr::*;
+use visit::{LvalueContext, Visitor};
+use rustc::lint;
+use rustc::middle::ty;
+use syntax::ast;
+use syntax::codemap::{Span, DUMMY_SP};
+
+pub fn check_mir<'tcx>(tcx: &ty::ctxt<'tcx>, fn_id: ast::NodeId, mir: &Mir<'tcx>) {
+    let reachable = reachable_blocks(mir);
+    check_unreachable_code(tcx, fn_id, mir, &reachable);
+    check_dead_assignments(tcx, fn_id, mir, &reachable);
+}
+
+fn reachable_blocks(mir: &Mir) -> Vec<bool> {
+    let mut reachable = vec![false; mir.basic_blocks.len()];
+    let mut stack = vec![START_BLOCK];
+    while let Some(block) = stack.pop() {
+        if reachable[block.index()] {
+            continue;
+        }
+        reachable[block.index()] = true;
+        stack.extend(mir.basic_block_data(block).terminator.successors().iter().cloned());
+    }
+    reachable
+}
+
+///////////////////////////////////////////////////////////////////////////
+// Unreachable code
+
+fn check_unreachable_code<'tcx>(tcx: &ty::ctxt<'tcx>,
+                                fn_id: ast::NodeId,
+                                mir: &Mir<'tcx>,
+                                reachable: &[bool]) {
+    let mut has_predecessors = vec![false; mir.basic_blocks.len()];
+    for block in mir.all_basic_blocks() {
+        for &succ in mir.basic_block_data(block).terminator.successors() {
+            has_predecessors[succ.index()] = true;
+        }
+    }
+
+    // Statements that merely finish off a construct which contains
+    // reachable code (e.g. storing `()` into the result of a block
+    // ending in `return`) are artifacts of lowering, not dead code.
+    let reachable_spans: Vec<Span> =
+        mir.all_basic_blocks()
+           .into_iter()
+           .filter(|block| reachable[block.index()])
+           .flat_map(|block| mir.basic_block_data(block).statements.iter().map(|s| s.span))
+           .filter(|&span| span != DUMMY_SP)
+           .collect();
+    let is_user_code = |span: Span| {
+        span != DUMMY_SP && !reachable_spans.iter().any(|&r| span.contains(r))
+    };
+
+    // Report the first statement of each dead region, starting from the
+    // unreachable blocks that nothing jumps to.
+    let mut visited = vec![false; mir.basic_blocks.len()];
+    for root in mir.all_basic_blocks() {
+        if reachable[root.index()] || has_predecessors[root.index()] {
+            continue;
+        }
+
+        let mut first_span = None;
+        let mut stack = vec![root];
+        while let Some(block) = stack.pop() {
+            if reachable[block.index()] || visited[block.index()] {
+                continue;
+            }
+            visited[block.index()] = true;
+
+            let data = mir.basic_block_data(block);
+            if first_span.is_none() {
+                first_span = data.statements
+                                 .iter()
+                                 .filter(|s| match s.kind {
+                                     StatementKind::Assign(..) => true,
+                                     StatementKind::Drop(..) => false,
+                                 })
+                                 .map(|s| s.span)
+                                 .find(|&span| is_user_code(span));
+            }
+            stack.extend(data.terminator.successors().iter().cloned());
+        }
+
+        if let Some(span) = first_span {
+            tcx.sess.add_lint(lint::builtin::UNREACHABLE_CODE,
+                              fn_id,
+                              span,
+                              "unreachable code".to_string());
+        }
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////
+// Dead assignments
+
+/// The variables read and written by a single statement or terminator.
+struct VarAccesses {
+    /// Variable overwritten as a whole, if any.
+    def: Option<u32>,
+    uses: Vec<u32>,
+}
+
+impl<'tcx> Visitor<'tcx> for VarAccesses {
+    fn visit_lvalue(&mut self, lvalue: &Lvalue<'tcx>, context: LvalueContext) {
+        if let Lvalue::Var(index) = *lvalue {
+            match context {
+                LvalueContext::Store => self.def = Some(index),
+                // drops are not reads as far as the user is concerned
+                LvalueContext::Drop => {}
+                _ => self.uses.push(index),
+            }
+        }
+        self.super_lvalue(lvalue, context);
+    }
+}
+
+/// How each variable is used anywhere in the body.
+struct VarSummary {
+    /// Read at least once; variables that are never read are reported by
+    /// the `unused_variables` lint instead.
+    read: Vec<bool>,
+    /// Borrowed at some point, so may be read through a reference.
+    borrowed: Vec<bool>,
+}
+
+impl<'tcx> Visitor<'tcx> for VarSummary {
+    fn visit_lvalue(&mut self, lvalue: &Lvalue<'tcx>, context: LvalueContext) {
+        if let Some(index) = root_var(lvalue) {
+            match context {
+                LvalueContext::Borrow { .. } | LvalueContext::Slice { .. } => {
+                    self.borrowed[index as usize] = true;
+                }
+                LvalueContext::Store | LvalueContext::Drop | LvalueContext::Projection => {}
+                LvalueContext::Inspect | LvalueContext::Consume => {
+                    self.read[index as usize] = true;
+                }
+            }
+        }
+        self.super_lvalue(lvalue, context);
+    }
+}
+
+fn root_var(lvalue: &Lvalue) -> Option<u32> {
+    match *lvalue {
+        Lvalue::Var(index) => Some(index),
+        Lvalue::Projection(ref proj) => root_var(&proj.base),
+        _ => None,
+    }
+}
+
+fn statement_accesses(block: BasicBlock, statement: &Statement) -> VarAccesses {
+    let mut accesses = VarAccesses { def: None, uses: vec![] };
+    accesses.visit_statement(block, statement);
+    accesses
+}
+
+fn terminator_accesses(block: BasicBlock, terminator: &Terminator) -> VarAccesses {
+    let mut accesses = VarAccesses { def: None, uses: vec![] };
+    accesses.visit_terminator(block, terminator);
+    accesses
+}
+
+fn apply(live: &mut [bool], accesses: &VarAccesses) {
+    if let Some(def) = accesses.def {
+        live[def as usize] = false;
+    }
+    for &var in &accesses.uses {
+        live[var as usize] = true;
+    }
+}
+
+fn check_dead_assignments<'tcx>(tcx: &ty::ctxt<'tcx>,
+                                fn_id: ast::NodeId,
+                                mir: &Mir<'tcx>,
+                                reachable: &[bool]) {
+    let num_vars = mir.var_decls.len();
+    if num_vars == 0 {
+        return;
+    }
+
+    let mut summary = VarSummary {
+        read: vec![false; num_vars],
+        borrowed: vec![false; num_vars],
+    };
+    summary.visit_mir(mir);
+
+    // Backwards liveness of the variables on entry to each block,
+    // iterated to a fixed point.
+    let blocks = mir.all_basic_blocks();
+    let mut live_on_entry = vec![vec![false; num_vars]; blocks.len()];
+    let live_on_exit = |live_on_entry: &[Vec<bool>], block: BasicBlock| {
+        let mut live = vec![false; num_vars];
+        for &succ in mir.basic_block_data(block).terminator.successors() {
+            for (l, &s) in live.iter_mut().zip(&live_on_entry[succ.index()]) {
+                *l = *l || s;
+            }
+        }
+        live
+    };
+
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for &block in blocks.iter().rev() {
+            if !reachable[block.index()] {
+                continue;
+            }
+            let data = mir.basic_block_data(block);
+            let mut live = live_on_exit(&live_on_entry[..], block);
+            apply(&mut live, &terminator_accesses(block, &data.terminator));
+            for statement in data.statements.iter().rev() {
+                apply(&mut live, &statement_accesses(block, statement));
+            }
+            if live != live_on_entry[block.index()] {
+                live_on_entry[block.index()] = live;
+                changed = true;
+            }
+        }
+    }
+
+    for &block in &blocks {
+        if !reachable[block.index()] {
+            continue;
+        }
+        let data = mir.basic_block_data(block);
+        let mut live = live_on_exit(&live_on_entry[..], block);
+        apply(&mut live, &terminator_accesses(block, &data.terminator));
+        for statement in data.statements.iter().rev() {
+            let accesses = statement_accesses(block, statement);
+            if let Some(var) = accesses.def {
+                let decl = &mir.var_decls[var as usize];
+                let index = var as usize;
+                if !live[index] &&
+                   summary.read[index] &&
+                   !summary.borrowed[index] &&
+                   !decl.name.as_str().starts_with("_") &&
+                   statement.span != DUMMY_SP {
+                    tcx.sess.add_lint(lint::builtin::UNUSED_ASSIGNMENTS,
+                                      fn_id,
+                                      statement.span,
+                                      format!("value assigned to `{}` is never read",
+                                              decl.name));
+                }
+            }
+            apply(&mut live, &accesses);
+        }
+    }
+}
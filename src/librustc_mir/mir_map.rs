@@ -22,6 +22,7 @@ extern crate rustc_front;
 
 use build;
 use dot;
+use lint;
 use transform::*;
 use repr::Mir;
 use hair::cx::Cx;
@@ -30,6 +31,7 @@ use std::fs::File;
 use self::rustc::middle::infer;
 use self::rustc::middle::region::CodeExtentData;
 use self::rustc::middle::ty::{self, Ty};
+use self::rustc::session::config;
 use self::rustc::util::common::ErrorReported;
 use self::rustc::util::nodemap::NodeMap;
 use self::rustc_front::hir;
@@ -145,7 +147,16 @@ impl<'a, 'm, 'tcx> Visitor<'tcx> for InnerDump<'a,'m,'tcx> {
 
         match build_mir(Cx::new(&infcx), implicit_arg_tys, id, span, decl, body) {
             Ok(mut mir) => {
+                // lint before the CFG is simplified, since that throws
+                // away the unreachable blocks
+                if self.tcx.sess.opts.debugging_opts.mir_lints {
+                    lint::check_mir(self.tcx, id, &mir);
+                }
+
                 simplify_cfg::SimplifyCfg::new().run_on_mir(&mut mir);
+                if self.tcx.sess.opts.optimize != config::No {
+                    scalar_replacement::ScalarReplacement::new(self.tcx).run_on_mir(&mut mir);
+                }
 
                 let meta_item_list = self.attr
                                          .iter()
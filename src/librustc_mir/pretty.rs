@@ -0,0 +1,376 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The textual MIR written by `--emit=mir`, for tools that analyze MIR
+//! without linking against the compiler.
+//!
+//! Unlike the `Debug` output of the MIR types, which is meant for people
+//! debugging the compiler and changes along with them, this format has a
+//! grammar. The first line of the output gives its version, which goes up
+//! whenever the grammar changes. Version 1:
+//!
+//! ```text
+//! output      := "// rustc-mir 1\n" function*
+//! function    := "\n// " SPAN "\n"
+//!                ("fn" | "closure") " " PATH "(" args ") -> " TYPE " {\n"
+//!                decl* block* "}\n"
+//! args        := (arg (", " arg)*)?
+//! arg         := "arg" N ": " TYPE
+//! decl        := "    let " ("mut ")? "var" N ": " TYPE "; // " IDENT "\n"
+//!              | "    let tmp" N ": " TYPE ";\n"
+//! block       := "\n    bb" N ": {\n" statement* terminator "    }\n"
+//! statement   := "        " (assign | drop) "; // scope " SPAN "\n"
+//! assign      := lvalue " = " rvalue
+//! drop        := ("drop" | "free") " " lvalue
+//! terminator  := "        " (goto | panic | if | switch | switch-int | call
+//!                            | "diverge" | "return") ";\n"
+//! goto        := "goto -> bb" N
+//! panic       := "panic -> bb" N
+//! if          := "if(" operand ") -> [true: bb" N ", false: bb" N "]"
+//! switch      := "switch(" lvalue ") -> [" IDENT ": bb" N (", " IDENT ": bb" N)* "]"
+//! switch-int  := "switchInt(" lvalue ": " TYPE ") -> ["
+//!                (constval ": bb" N ", ")* "otherwise: bb" N "]"
+//! call        := lvalue " = " operand "(" (operand (", " operand)*)? ")"
+//!                " -> [return: bb" N ", unwind: bb" N "]"
+//!
+//! lvalue      := "var" N | "tmp" N | "arg" N | "return" | "static " PATH
+//!              | "(*" lvalue ")" | lvalue "." N | lvalue "[" operand "]"
+//!              | lvalue "[" "-"? N " of " N "]" | "(" lvalue " as " IDENT ")"
+//! operand     := lvalue | "const(" literal ": " TYPE ")"
+//! literal     := PATH | constval
+//! constval    := INT | FLOAT | "true" | "false" | STRING | BYTE-STRING
+//!              | "<struct>" | "<tuple>"
+//! rvalue      := operand | "[" operand "; " operand "]"
+//!              | "&" ("mut " | "uniq ")? lvalue | "len(" lvalue ")"
+//!              | operand " as " TYPE " (" cast-kind ")"
+//!              | BIN-OP "(" operand ", " operand ")" | ("not" | "neg") "(" operand ")"
+//!              | "box " TYPE | "(" operands ")" | "[" operands "]"
+//!              | "adt " PATH "(" operands ")" | "closure@" SPAN "(" operands ")"
+//!              | lvalue "[" N "..-" N "]" | "asm " STRING
+//! operands    := (operand (", " operand)*)?
+//! cast-kind   := "misc" | "reify_fn_pointer" | "unsafe_fn_pointer" | "unsize"
+//! ```
+//!
+//! `BIN-OP` is one of `add`, `sub`, `mul`, `div`, `rem`, `bit_xor`,
+//! `bit_and`, `bit_or`, `shl`, `shr`, `eq`, `lt`, `le`, `ne`, `ge` and `gt`.
+//! `SPAN` is `file:line:col: line:col`, the source the function or
+//! statement comes from. `TYPE` is a Rust type, with regions erased; its
+//! parentheses and brackets are balanced. Strings are quoted and escaped
+//! like Rust string literals. Functions are written in source order, and
+//! the closures of a function after it.
+
+use mir_map::MirMap;
+use repr::*;
+
+use rustc::front::map as hir_map;
+use rustc::middle::const_eval::ConstVal;
+use rustc::middle::ty;
+use std::ascii;
+use std::io::{self, Write};
+use syntax::ast::NodeId;
+use syntax::codemap::{Span, DUMMY_SP};
+
+/// The version of the format, given on the first line of the output.
+pub const MIR_FORMAT_VERSION: u32 = 1;
+
+/// Writes the MIR of every function of `mir_map` to `w`.
+pub fn write_mir_pretty<'tcx, W: Write>(tcx: &ty::ctxt<'tcx>,
+                                        mir_map: &MirMap<'tcx>,
+                                        w: &mut W)
+                                        -> io::Result<()> {
+    try!(writeln!(w, "// rustc-mir {}", MIR_FORMAT_VERSION));
+
+    // Node ids are assigned in source order, and closures get theirs after
+    // the function containing them.
+    let mut ids = mir_map.keys().cloned().collect::<Vec<_>>();
+    ids.sort();
+    for id in ids {
+        try!(write_fn(tcx, id, &mir_map[&id], w));
+    }
+    Ok(())
+}
+
+fn write_fn<'tcx, W: Write>(tcx: &ty::ctxt<'tcx>,
+                            id: NodeId,
+                            mir: &Mir<'tcx>,
+                            w: &mut W)
+                            -> io::Result<()> {
+    try!(writeln!(w, ""));
+    try!(writeln!(w, "// {}", span(tcx, tcx.map.span(id))));
+    let kind = match tcx.map.find(id) {
+        Some(hir_map::NodeExpr(..)) => "closure",
+        _ => "fn",
+    };
+    try!(write!(w, "{} {}(", kind, tcx.map.path_to_string(id)));
+    for (index, arg) in mir.arg_decls.iter().enumerate() {
+        if index > 0 {
+            try!(write!(w, ", "));
+        }
+        try!(write!(w, "arg{}: {}", index, arg.ty));
+    }
+    match mir.return_ty {
+        ty::FnConverging(ty) => try!(writeln!(w, ") -> {} {{", ty)),
+        ty::FnDiverging => try!(writeln!(w, ") -> ! {{")),
+    }
+
+    for (index, var) in mir.var_decls.iter().enumerate() {
+        let mutability = match var.mutability {
+            Mutability::Mut => "mut ",
+            Mutability::Not => "",
+        };
+        try!(writeln!(w, "    let {}var{}: {}; // {}", mutability, index, var.ty, var.name));
+    }
+    for (index, temp) in mir.temp_decls.iter().enumerate() {
+        try!(writeln!(w, "    let tmp{}: {};", index, temp.ty));
+    }
+
+    for bb in mir.all_basic_blocks() {
+        let data = mir.basic_block_data(bb);
+        try!(writeln!(w, ""));
+        try!(writeln!(w, "    bb{}: {{", bb.index()));
+        for statement in &data.statements {
+            try!(writeln!(w,
+                          "        {}; // scope {}",
+                          statement_to_string(tcx, statement),
+                          span(tcx, statement.span)));
+        }
+        try!(writeln!(w, "        {};", terminator_to_string(tcx, &data.terminator)));
+        try!(writeln!(w, "    }}"));
+    }
+    writeln!(w, "}}")
+}
+
+fn span(tcx: &ty::ctxt, sp: Span) -> String {
+    tcx.sess.codemap().span_to_string(sp)
+}
+
+fn statement_to_string<'tcx>(tcx: &ty::ctxt<'tcx>, statement: &Statement<'tcx>) -> String {
+    match statement.kind {
+        StatementKind::Assign(ref lv, ref rv) => {
+            format!("{} = {}", lvalue_to_string(tcx, lv), rvalue_to_string(tcx, rv))
+        }
+        StatementKind::Drop(DropKind::Deep, ref lv) => {
+            format!("drop {}", lvalue_to_string(tcx, lv))
+        }
+        StatementKind::Drop(DropKind::Free, ref lv) => {
+            format!("free {}", lvalue_to_string(tcx, lv))
+        }
+    }
+}
+
+fn terminator_to_string<'tcx>(tcx: &ty::ctxt<'tcx>, terminator: &Terminator<'tcx>) -> String {
+    match *terminator {
+        Terminator::Goto { target } => format!("goto -> bb{}", target.index()),
+        Terminator::Panic { target } => format!("panic -> bb{}", target.index()),
+        Terminator::If { ref cond, targets } => {
+            format!("if({}) -> [true: bb{}, false: bb{}]",
+                    operand_to_string(tcx, cond),
+                    targets[0].index(),
+                    targets[1].index())
+        }
+        Terminator::Switch { ref discr, adt_def, ref targets } => {
+            let targets = adt_def.variants
+                                 .iter()
+                                 .zip(targets)
+                                 .map(|(variant, target)| {
+                                     format!("{}: bb{}", variant.name, target.index())
+                                 })
+                                 .collect::<Vec<_>>();
+            format!("switch({}) -> [{}]", lvalue_to_string(tcx, discr), targets.join(", "))
+        }
+        Terminator::SwitchInt { ref discr, switch_ty, ref values, ref targets } => {
+            let mut cases = values.iter()
+                                  .zip(targets)
+                                  .map(|(value, target)| {
+                                      format!("{}: bb{}", const_val_to_string(tcx, value),
+                                              target.index())
+                                  })
+                                  .collect::<Vec<_>>();
+            cases.push(format!("otherwise: bb{}", targets[values.len()].index()));
+            format!("switchInt({}: {}) -> [{}]",
+                    lvalue_to_string(tcx, discr),
+                    switch_ty,
+                    cases.join(", "))
+        }
+        Terminator::Diverge => "diverge".to_string(),
+        Terminator::Return => "return".to_string(),
+        Terminator::Call { ref data, targets } => {
+            format!("{} = {}({}) -> [return: bb{}, unwind: bb{}]",
+                    lvalue_to_string(tcx, &data.destination),
+                    operand_to_string(tcx, &data.func),
+                    operands_to_string(tcx, &data.args),
+                    targets[0].index(),
+                    targets[1].index())
+        }
+    }
+}
+
+fn lvalue_to_string<'tcx>(tcx: &ty::ctxt<'tcx>, lvalue: &Lvalue<'tcx>) -> String {
+    match *lvalue {
+        Lvalue::Var(index) => format!("var{}", index),
+        Lvalue::Temp(index) => format!("tmp{}", index),
+        Lvalue::Arg(index) => format!("arg{}", index),
+        Lvalue::Static(def_id) => format!("static {}", tcx.item_path_str(def_id)),
+        Lvalue::ReturnPointer => "return".to_string(),
+        Lvalue::Projection(ref data) => {
+            let base = lvalue_to_string(tcx, &data.base);
+            match data.elem {
+                ProjectionElem::Deref => format!("(*{})", base),
+                ProjectionElem::Field(field) => format!("{}.{}", base, field.index()),
+                ProjectionElem::Index(ref index) => {
+                    format!("{}[{}]", base, operand_to_string(tcx, index))
+                }
+                ProjectionElem::ConstantIndex { offset, min_length, from_end } => {
+                    format!("{}[{}{} of {}]",
+                            base,
+                            if from_end { "-" } else { "" },
+                            offset,
+                            min_length)
+                }
+                ProjectionElem::Downcast(adt_def, variant) => {
+                    format!("({} as {})", base, adt_def.variants[variant].name)
+                }
+            }
+        }
+    }
+}
+
+fn operand_to_string<'tcx>(tcx: &ty::ctxt<'tcx>, operand: &Operand<'tcx>) -> String {
+    match *operand {
+        Operand::Consume(ref lv) => lvalue_to_string(tcx, lv),
+        Operand::Constant(ref constant) => {
+            let literal = match constant.literal {
+                Literal::Item { def_id, .. } => tcx.item_path_str(def_id),
+                Literal::Value { ref value } => const_val_to_string(tcx, value),
+            };
+            format!("const({}: {})", literal, constant.ty)
+        }
+    }
+}
+
+fn operands_to_string<'tcx>(tcx: &ty::ctxt<'tcx>, operands: &[Operand<'tcx>]) -> String {
+    operands.iter()
+            .map(|operand| operand_to_string(tcx, operand))
+            .collect::<Vec<_>>()
+            .join(", ")
+}
+
+fn const_val_to_string(tcx: &ty::ctxt, value: &ConstVal) -> String {
+    match *value {
+        ConstVal::Float(f) => f.to_string(),
+        ConstVal::Int(i) => i.to_string(),
+        ConstVal::Uint(u) => u.to_string(),
+        ConstVal::Bool(b) => b.to_string(),
+        ConstVal::Str(ref s) => escape_str(s),
+        ConstVal::ByteStr(ref bytes) => {
+            let escaped = bytes.iter()
+                               .flat_map(|&b| ascii::escape_default(b))
+                               .map(|b| b as char)
+                               .collect::<String>();
+            format!("b\"{}\"", escaped)
+        }
+        ConstVal::Struct(_) => "<struct>".to_string(),
+        ConstVal::Tuple(_) => "<tuple>".to_string(),
+        ConstVal::Function(def_id) => tcx.item_path_str(def_id),
+    }
+}
+
+/// `s` as a string literal.
+fn escape_str(s: &str) -> String {
+    format!("\"{}\"", s.chars().flat_map(|c| c.escape_default()).collect::<String>())
+}
+
+fn rvalue_to_string<'tcx>(tcx: &ty::ctxt<'tcx>, rvalue: &Rvalue<'tcx>) -> String {
+    match *rvalue {
+        Rvalue::Use(ref operand) => operand_to_string(tcx, operand),
+        Rvalue::Repeat(ref operand, ref count) => {
+            format!("[{}; {}]",
+                    operand_to_string(tcx, operand),
+                    operand_to_string(tcx, &Operand::Constant(count.clone())))
+        }
+        Rvalue::Ref(_, kind, ref lv) => {
+            let kind = match kind {
+                BorrowKind::Shared => "",
+                BorrowKind::Unique => "uniq ",
+                BorrowKind::Mut => "mut ",
+            };
+            format!("&{}{}", kind, lvalue_to_string(tcx, lv))
+        }
+        Rvalue::Len(ref lv) => format!("len({})", lvalue_to_string(tcx, lv)),
+        Rvalue::Cast(ref kind, ref operand, ty) => {
+            let kind = match *kind {
+                CastKind::Misc => "misc",
+                CastKind::ReifyFnPointer => "reify_fn_pointer",
+                CastKind::UnsafeFnPointer => "unsafe_fn_pointer",
+                CastKind::Unsize => "unsize",
+            };
+            format!("{} as {} ({})", operand_to_string(tcx, operand), ty, kind)
+        }
+        Rvalue::BinaryOp(op, ref a, ref b) => {
+            format!("{}({}, {})",
+                    bin_op_name(op),
+                    operand_to_string(tcx, a),
+                    operand_to_string(tcx, b))
+        }
+        Rvalue::UnaryOp(op, ref a) => {
+            let op = match op {
+                UnOp::Not => "not",
+                UnOp::Neg => "neg",
+            };
+            format!("{}({})", op, operand_to_string(tcx, a))
+        }
+        Rvalue::Box(ty) => format!("box {}", ty),
+        Rvalue::Aggregate(ref kind, ref operands) => {
+            let operands = operands_to_string(tcx, operands);
+            match *kind {
+                AggregateKind::Vec => format!("[{}]", operands),
+                AggregateKind::Tuple => format!("({})", operands),
+                AggregateKind::Adt(adt_def, variant, _) => {
+                    let path = tcx.item_path_str(adt_def.did);
+                    if adt_def.variants.len() == 1 {
+                        format!("adt {}({})", path, operands)
+                    } else {
+                        format!("adt {}::{}({})", path, adt_def.variants[variant].name, operands)
+                    }
+                }
+                AggregateKind::Closure(def_id, _) => {
+                    format!("closure@{}({})",
+                            span(tcx, tcx.map.def_id_span(def_id, DUMMY_SP)),
+                            operands)
+                }
+            }
+        }
+        Rvalue::Slice { ref input, from_start, from_end } => {
+            format!("{}[{}..-{}]", lvalue_to_string(tcx, input), from_start, from_end)
+        }
+        Rvalue::InlineAsm(asm) => format!("asm {}", escape_str(&asm.asm)),
+    }
+}
+
+fn bin_op_name(op: BinOp) -> &'static str {
+    match op {
+        BinOp::Add => "add",
+        BinOp::Sub => "sub",
+        BinOp::Mul => "mul",
+        BinOp::Div => "div",
+        BinOp::Rem => "rem",
+        BinOp::BitXor => "bit_xor",
+        BinOp::BitAnd => "bit_and",
+        BinOp::BitOr => "bit_or",
+        BinOp::Shl => "shl",
+        BinOp::Shr => "shr",
+        BinOp::Eq => "eq",
+        BinOp::Lt => "lt",
+        BinOp::Le => "le",
+        BinOp::Ne => "ne",
+        BinOp::Ge => "ge",
+        BinOp::Gt => "gt",
+    }
+}
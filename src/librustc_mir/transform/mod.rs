@@ -10,6 +10,7 @@
 
 pub mod simplify_cfg;
 pub mod erase_regions;
+pub mod scalar_replacement;
 mod util;
 
 use repr::Mir;
@@ -0,0 +1,360 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! This pass removes temporaries that only exist because of the way
+//! the MIR is built:
+//!
+//! - Tuples and structs that are built into a temporary and then only
+//!   accessed field by field are split up into one temporary per
+//!   field. Trans has to put aggregates in an alloca, whereas most
+//!   of the field temporaries can be kept as immediates.
+//!
+//! - A temporary that is assigned from an operand and then consumed
+//!   right away by the next statement is replaced by that operand.
+//!
+//! Temporaries that are no longer mentioned anywhere are then
+//! removed, so that trans does not allocate space for them.
+
+use repr::*;
+use rustc::middle::ty::{self, Ty};
+use std::mem;
+use transform::MirPass;
+use visit::{LvalueContext, MutVisitor, Visitor};
+
+pub struct ScalarReplacement<'a, 'tcx: 'a> {
+    tcx: &'a ty::ctxt<'tcx>,
+}
+
+impl<'a, 'tcx> ScalarReplacement<'a, 'tcx> {
+    pub fn new(tcx: &'a ty::ctxt<'tcx>) -> ScalarReplacement<'a, 'tcx> {
+        ScalarReplacement { tcx: tcx }
+    }
+
+    /// The types of the fields of an aggregate of type `ty`, if it can
+    /// be split into its fields. Structs with a destructor of their own
+    /// are kept whole, since dropping them runs that destructor before
+    /// dropping the fields.
+    fn field_tys(&self,
+                 kind: &AggregateKind<'tcx>,
+                 ty: Ty<'tcx>)
+                 -> Option<Vec<Ty<'tcx>>> {
+        match (kind, &ty.sty) {
+            (&AggregateKind::Tuple, &ty::TyTuple(ref tys)) => {
+                Some(tys.clone())
+            }
+            (&AggregateKind::Adt(adt_def, _, substs), _)
+                if adt_def.adt_kind() == ty::AdtKind::Struct && !adt_def.has_dtor() => {
+                Some(adt_def.struct_variant()
+                            .fields
+                            .iter()
+                            .map(|field| field.ty(self.tcx, substs))
+                            .collect())
+            }
+            _ => None,
+        }
+    }
+
+    fn split_aggregates(&self, mir: &mut Mir<'tcx>) {
+        let uses = temp_uses(mir);
+
+        // Find the temporaries that are built from an aggregate and
+        // otherwise only used through their fields.
+        let mut candidates = vec![];
+        for data in &mir.basic_blocks {
+            for statement in &data.statements {
+                if let StatementKind::Assign(Lvalue::Temp(index),
+                                             Rvalue::Aggregate(ref kind, ref operands)) =
+                       statement.kind {
+                    let temp_uses = &uses[index as usize];
+                    if temp_uses.stores != 1 ||
+                       temp_uses.consumes != 0 ||
+                       temp_uses.other != 0 ||
+                       temp_uses.fields == 0 ||
+                       operands.iter().any(|op| mentions_temp(mir, op, index)) {
+                        continue;
+                    }
+                    let ty = mir.temp_decls[index as usize].ty;
+                    if let Some(field_tys) = self.field_tys(kind, ty) {
+                        if field_tys.len() == operands.len() {
+                            candidates.push((index, field_tys));
+                        }
+                    }
+                }
+            }
+        }
+
+        if candidates.is_empty() {
+            return;
+        }
+
+        let mut replacements: Vec<Option<Vec<u32>>> = vec![None; mir.temp_decls.len()];
+        for (index, field_tys) in candidates {
+            let mut field_temps = vec![];
+            for ty in field_tys {
+                field_temps.push(mir.temp_decls.len() as u32);
+                mir.temp_decls.push(TempDecl { ty: ty });
+            }
+            replacements[index as usize] = Some(field_temps);
+        }
+
+        for data in &mut mir.basic_blocks {
+            let statements = mem::replace(&mut data.statements, vec![]);
+            for statement in statements {
+                let span = statement.span;
+                match statement.kind {
+                    StatementKind::Assign(Lvalue::Temp(index),
+                                          Rvalue::Aggregate(kind, operands)) => {
+                        if let Some(ref field_temps) = replacements[index as usize] {
+                            for (&field_temp, operand) in field_temps.iter().zip(operands) {
+                                data.statements.push(Statement {
+                                    span: span,
+                                    kind: StatementKind::Assign(Lvalue::Temp(field_temp),
+                                                                Rvalue::Use(operand)),
+                                });
+                            }
+                        } else {
+                            data.statements.push(Statement {
+                                span: span,
+                                kind: StatementKind::Assign(Lvalue::Temp(index),
+                                                            Rvalue::Aggregate(kind, operands)),
+                            });
+                        }
+                    }
+                    StatementKind::Drop(kind, Lvalue::Temp(index))
+                        if replacements[index as usize].is_some() => {
+                        // The aggregate has no destructor of its own, so
+                        // dropping it is the same as dropping each field.
+                        for &field_temp in replacements[index as usize].as_ref().unwrap() {
+                            data.statements.push(Statement {
+                                span: span,
+                                kind: StatementKind::Drop(kind, Lvalue::Temp(field_temp)),
+                            });
+                        }
+                    }
+                    kind => {
+                        data.statements.push(Statement { span: span, kind: kind });
+                    }
+                }
+            }
+        }
+
+        FieldsToTemps { replacements: replacements }.visit_mir(mir);
+    }
+}
+
+impl<'a, 'tcx> MirPass<'tcx> for ScalarReplacement<'a, 'tcx> {
+    fn run_on_mir(&mut self, mir: &mut Mir<'tcx>) {
+        self.split_aggregates(mir);
+        forward_temps(mir);
+        remove_unused_temps(mir);
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////
+// Temp uses
+
+#[derive(Clone, Default)]
+struct TempUses {
+    /// Assignments to the temporary as a whole.
+    stores: usize,
+    /// Moves or copies of the temporary as a whole.
+    consumes: usize,
+    /// Drops of the temporary as a whole.
+    drops: usize,
+    /// Uses of one of the temporary's fields, e.g. `tmp.0`.
+    fields: usize,
+    /// Any other use, e.g. a borrow or a downcast.
+    other: usize,
+}
+
+impl TempUses {
+    fn is_unused(&self) -> bool {
+        self.stores == 0 && self.consumes == 0 && self.drops == 0 &&
+        self.fields == 0 && self.other == 0
+    }
+
+    /// Assigned once and then moved or copied somewhere else once.
+    fn is_forwarding(&self) -> bool {
+        self.stores == 1 && self.consumes == 1 && self.drops == 0 &&
+        self.fields == 0 && self.other == 0
+    }
+}
+
+struct TempUseCounter {
+    uses: Vec<TempUses>,
+}
+
+impl<'tcx> Visitor<'tcx> for TempUseCounter {
+    fn visit_lvalue(&mut self, lvalue: &Lvalue<'tcx>, context: LvalueContext) {
+        match *lvalue {
+            Lvalue::Temp(index) => {
+                let uses = &mut self.uses[index as usize];
+                match context {
+                    LvalueContext::Store => uses.stores += 1,
+                    LvalueContext::Consume => uses.consumes += 1,
+                    LvalueContext::Drop => uses.drops += 1,
+                    LvalueContext::Inspect |
+                    LvalueContext::Borrow { .. } |
+                    LvalueContext::Slice { .. } |
+                    LvalueContext::Projection => uses.other += 1,
+                }
+            }
+            Lvalue::Projection(ref proj) => {
+                if let (&Lvalue::Temp(index), &ProjectionElem::Field(..)) =
+                       (&proj.base, &proj.elem) {
+                    self.uses[index as usize].fields += 1;
+                    return;
+                }
+            }
+            _ => {}
+        }
+        self.super_lvalue(lvalue, context);
+    }
+}
+
+fn temp_uses(mir: &Mir) -> Vec<TempUses> {
+    let mut counter = TempUseCounter {
+        uses: vec![TempUses::default(); mir.temp_decls.len()],
+    };
+    counter.visit_mir(mir);
+    counter.uses
+}
+
+fn mentions_temp(mir: &Mir, operand: &Operand, index: u32) -> bool {
+    let mut counter = TempUseCounter {
+        uses: vec![TempUses::default(); mir.temp_decls.len()],
+    };
+    counter.visit_operand(operand);
+    !counter.uses[index as usize].is_unused()
+}
+
+///////////////////////////////////////////////////////////////////////////
+// Rewriting
+
+/// Replaces `tmp.i` by the temporary that holds field `i` of `tmp`.
+struct FieldsToTemps {
+    replacements: Vec<Option<Vec<u32>>>,
+}
+
+impl<'tcx> MutVisitor<'tcx> for FieldsToTemps {
+    fn visit_lvalue(&mut self, lvalue: &mut Lvalue<'tcx>, context: LvalueContext) {
+        let field_temp = match *lvalue {
+            Lvalue::Projection(ref proj) => match (&proj.base, &proj.elem) {
+                (&Lvalue::Temp(index), &ProjectionElem::Field(field)) => {
+                    self.replacements[index as usize]
+                        .as_ref()
+                        .map(|field_temps| field_temps[field.index()])
+                }
+                _ => None,
+            },
+            _ => None,
+        };
+        match field_temp {
+            Some(field_temp) => *lvalue = Lvalue::Temp(field_temp),
+            None => self.super_lvalue(lvalue, context),
+        }
+    }
+}
+
+/// Replaces the one use of `temp` as an operand by `operand`.
+struct ForwardTemp<'tcx> {
+    temp: u32,
+    operand: Option<Operand<'tcx>>,
+}
+
+impl<'tcx> MutVisitor<'tcx> for ForwardTemp<'tcx> {
+    fn visit_operand(&mut self, operand: &mut Operand<'tcx>) {
+        if let Operand::Consume(Lvalue::Temp(index)) = *operand {
+            if index == self.temp {
+                if let Some(replacement) = self.operand.take() {
+                    *operand = replacement;
+                }
+                return;
+            }
+        }
+        self.super_operand(operand);
+    }
+}
+
+/// Removes `tmp = op` when `tmp` is only consumed by the statement (or
+/// terminator) that immediately follows, by using `op` there instead.
+/// Nothing can happen in between, so `op` still has the same value.
+fn forward_temps(mir: &mut Mir) {
+    let uses = temp_uses(mir);
+
+    for block in mir.all_basic_blocks() {
+        let data = mir.basic_block_data_mut(block);
+        let mut i = 0;
+        while i < data.statements.len() {
+            let forwarded = match data.statements[i].kind {
+                StatementKind::Assign(Lvalue::Temp(index), Rvalue::Use(ref operand))
+                    if uses[index as usize].is_forwarding() => {
+                    Some((index, operand.clone()))
+                }
+                _ => None,
+            };
+
+            if let Some((index, operand)) = forwarded {
+                let mut forward = ForwardTemp { temp: index, operand: Some(operand) };
+                if i + 1 < data.statements.len() {
+                    forward.visit_statement(block, &mut data.statements[i + 1]);
+                } else {
+                    forward.visit_terminator(block, &mut data.terminator);
+                }
+                if forward.operand.is_none() {
+                    // Don't advance: the next statement may now be
+                    // forwardable into the one after it.
+                    data.statements.remove(i);
+                    continue;
+                }
+            }
+            i += 1;
+        }
+    }
+}
+
+struct RenumberTemps {
+    replacements: Vec<u32>,
+}
+
+impl<'tcx> MutVisitor<'tcx> for RenumberTemps {
+    fn visit_lvalue(&mut self, lvalue: &mut Lvalue<'tcx>, context: LvalueContext) {
+        if let Lvalue::Temp(ref mut index) = *lvalue {
+            *index = self.replacements[*index as usize];
+        }
+        self.super_lvalue(lvalue, context);
+    }
+}
+
+fn remove_unused_temps(mir: &mut Mir) {
+    let uses = temp_uses(mir);
+    if !uses.iter().any(|uses| uses.is_unused()) {
+        return;
+    }
+
+    // `replacements` maps the old temp ids to the new ones
+    let mut replacements = Vec::with_capacity(uses.len());
+    let mut used = 0;
+    for temp_uses in &uses {
+        replacements.push(used);
+        if !temp_uses.is_unused() {
+            used += 1;
+        }
+    }
+
+    let temp_decls = mem::replace(&mut mir.temp_decls, vec![]);
+    mir.temp_decls = temp_decls.into_iter()
+                               .zip(&uses)
+                               .filter(|&(_, temp_uses)| !temp_uses.is_unused())
+                               .map(|(decl, _)| decl)
+                               .collect();
+
+    RenumberTemps { replacements: replacements }.visit_mir(mir);
+}
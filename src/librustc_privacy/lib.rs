@@ -42,7 +42,7 @@ use rustc::middle::def_id::DefId;
 use rustc::middle::privacy::ImportUse::*;
 use rustc::middle::privacy::LastPrivate::*;
 use rustc::middle::privacy::PrivateDep::*;
-use rustc::middle::privacy::{ExternalExports, ExportedItems, PublicItems};
+use rustc::middle::privacy::{ExternalExports, ExportedItems, ImportSources, PublicItems};
 use rustc::middle::ty::{self, Ty};
 use rustc::util::nodemap::{NodeMap, NodeSet};
 use rustc::front::map as ast_map;
@@ -57,7 +57,9 @@ type Context<'a, 'tcx> = (&'a ty::MethodMap<'tcx>, &'a def::ExportMap);
 /// Result of a checking operation - None => no errors were found. Some => an
 /// error and contains the span and message for reporting that error and
 /// optionally the same for a note about the error.
-type CheckResult = Option<(Span, String, Option<(Span, String)>)>;
+/// A privacy error's span and message, followed by its notes and a suggestion
+/// for how to fix it.
+type CheckResult = Option<(Span, String, Vec<(Span, String)>, Option<String>)>;
 
 ////////////////////////////////////////////////////////////////////////////////
 /// The parent visitor, used to determine what's the parent of what (node-wise)
@@ -421,6 +423,8 @@ struct PrivacyVisitor<'a, 'tcx: 'a> {
     in_foreign: bool,
     parents: NodeMap<ast::NodeId>,
     external_exports: ExternalExports,
+    export_map: &'a def::ExportMap,
+    import_sources: ImportSources,
 }
 
 #[derive(Debug)]
@@ -664,13 +668,13 @@ impl<'a, 'tcx> PrivacyVisitor<'a, 'tcx> {
     fn report_error(&self, result: CheckResult) -> bool {
         match result {
             None => true,
-            Some((span, msg, note)) => {
+            Some((span, msg, notes, help)) => {
                 self.tcx.sess.span_err(span, &msg[..]);
-                match note {
-                    Some((span, msg)) => {
-                        self.tcx.sess.span_note(span, &msg[..])
-                    }
-                    None => {},
+                for (span, msg) in notes {
+                    self.tcx.sess.span_note(span, &msg[..]);
+                }
+                if let Some(help) = help {
+                    self.tcx.sess.fileline_help(span, &help[..]);
                 }
                 false
             },
@@ -692,7 +696,7 @@ impl<'a, 'tcx> PrivacyVisitor<'a, 'tcx> {
         debug!("ensure_public: def_privacy={:?}", def_privacy);
         let id = match def_privacy {
             ExternallyDenied => {
-                return Some((span, format!("{} is private", msg), None))
+                return Some((span, format!("{} is private", msg), vec![], None))
             }
             Allowable => return None,
             DisallowedBy(id) => id,
@@ -707,7 +711,9 @@ impl<'a, 'tcx> PrivacyVisitor<'a, 'tcx> {
         let def_id = source_did.unwrap_or(to_check);
         let node_id = self.tcx.map.as_local_node_id(def_id);
         let (err_span, err_msg) = if Some(id) == node_id {
-            return Some((span, format!("{} is private", msg), None));
+            let notes = vec![(self.tcx.map.span(id), format!("{} is defined here", msg))];
+            return Some((span, format!("{} is private", msg), notes,
+                         Some(self.privacy_help(def_id, msg))));
         } else {
             (span, format!("{} is inaccessible", msg))
         };
@@ -722,7 +728,7 @@ impl<'a, 'tcx> PrivacyVisitor<'a, 'tcx> {
                     hir::ItemImpl(_, _, _, _, ref ty, _) => {
                         match ty.node {
                             hir::TyPath(..) => {}
-                            _ => return Some((err_span, err_msg, None)),
+                            _ => return Some((err_span, err_msg, vec![], None)),
                         };
                         let def = self.tcx.def_map.borrow().get(&ty.id).unwrap().full_def();
                         let did = def.def_id();
@@ -736,17 +742,88 @@ impl<'a, 'tcx> PrivacyVisitor<'a, 'tcx> {
                     _ => item
                 }
             }
-            Some(..) | None => return Some((err_span, err_msg, None)),
+            Some(..) | None => return Some((err_span, err_msg, vec![], None)),
         };
         let desc = match item.node {
             hir::ItemMod(..) => "module",
             hir::ItemTrait(..) => "trait",
             hir::ItemStruct(..) => "struct",
             hir::ItemEnum(..) => "enum",
-            _ => return Some((err_span, err_msg, None))
+            _ => return Some((err_span, err_msg, vec![], None))
         };
         let msg = format!("{} `{}` is private", desc, item.name);
-        Some((err_span, err_msg, Some((span, msg))))
+        let help = self.privacy_help(def_id, &format!("{} `{}`", desc, item.name));
+        Some((err_span, err_msg, vec![(span, msg)], Some(help)))
+    }
+
+    /// Suggests using a public re-export of `did` that is accessible from the
+    /// current item, if there is one, or else making `private`, which is
+    /// `did` or one of its parents, public.
+    fn privacy_help(&self, did: DefId, private: &str) -> String {
+        let mut paths = vec![];
+        for (&mod_id, exports) in self.export_map {
+            if mod_id != ast::CRATE_NODE_ID {
+                match self.def_privacy(self.tcx.map.local_def_id(mod_id)) {
+                    Allowable => {}
+                    _ => continue,
+                }
+            }
+            for export in exports.iter().filter(|export| export.def_id == did) {
+                paths.push(if mod_id == ast::CRATE_NODE_ID {
+                    export.name.to_string()
+                } else {
+                    format!("{}::{}", self.tcx.map.path_to_string(mod_id), export.name)
+                });
+            }
+        }
+        // The shortest path, and not whichever comes first in the map.
+        paths.sort_by(|a, b| (a.len(), a).cmp(&(b.len(), b)));
+
+        match paths.into_iter().next() {
+            Some(path) => format!("it is re-exported publicly as `{}`, which can be used \
+                                   instead",
+                                  path),
+            None => format!("consider marking {} as `pub`", private),
+        }
+    }
+
+    /// Notes tracing the item `did` that the import `import_id` refers to
+    /// through the chain of re-exports it is imported from, starting at the
+    /// item, if the import doesn't name the item directly.
+    fn reexport_chain_notes(&self,
+                            import_id: ast::NodeId,
+                            did: DefId,
+                            desc: &str)
+                            -> Vec<(Span, String)> {
+        let mut chain = vec![];
+        let mut id = import_id;
+        while let Some(&source) = self.import_sources.get(&id) {
+            chain.push(source);
+            id = source;
+        }
+        if chain.is_empty() {
+            return vec![];
+        }
+
+        let mut notes = vec![];
+        if let Some(span) = self.tcx.map.span_if_local(did) {
+            notes.push((span, format!("{} is defined here", desc)));
+        }
+        for &reexport in chain.iter().rev() {
+            let span = match self.tcx.map.opt_span(reexport) {
+                Some(span) => span,
+                None => continue,
+            };
+            let module = self.tcx.map.get_module_parent(reexport);
+            let msg = if module == ast::CRATE_NODE_ID {
+                "which is re-exported here, at the crate root".to_string()
+            } else {
+                format!("which is re-exported here, in module `{}`",
+                        self.tcx.map.path_to_string(module))
+            };
+            notes.push((span, msg));
+        }
+        notes
     }
 
     // Checks that a field is in scope.
@@ -802,10 +879,16 @@ impl<'a, 'tcx> PrivacyVisitor<'a, 'tcx> {
             let ck_public = |def: DefId| {
                 debug!("privacy - ck_public {:?}", def);
                 let origdid = path_res.def_id();
-                self.ensure_public(span,
-                                   def,
-                                   Some(origdid),
-                                   &format!("{} `{}`", tyname, last))
+                let msg = format!("{} `{}`", tyname, last);
+                let result = self.ensure_public(span, def, Some(origdid), &msg);
+                result.map(|(err_span, err_msg, mut notes, help)| {
+                    for note in self.reexport_chain_notes(path_id, origdid, &msg) {
+                        if !notes.iter().any(|&(span, _)| span == note.0) {
+                            notes.push(note);
+                        }
+                    }
+                    (err_span, err_msg, notes, help)
+                })
             };
 
             match path_res.last_private {
@@ -1508,7 +1591,8 @@ impl<'a, 'tcx, 'v> Visitor<'v> for VisiblePrivateTypesVisitor<'a, 'tcx> {
 
 pub fn check_crate(tcx: &ty::ctxt,
                    export_map: &def::ExportMap,
-                   external_exports: ExternalExports)
+                   external_exports: ExternalExports,
+                   import_sources: ImportSources)
                    -> (ExportedItems, PublicItems) {
     let krate = tcx.map.krate();
 
@@ -1535,6 +1619,8 @@ pub fn check_crate(tcx: &ty::ctxt,
         tcx: tcx,
         parents: visitor.parents,
         external_exports: external_exports,
+        export_map: export_map,
+        import_sources: import_sources,
     };
     intravisit::walk_crate(&mut visitor, krate);
 
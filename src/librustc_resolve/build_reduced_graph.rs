@@ -31,6 +31,7 @@ use {resolve_error, ResolutionError};
 use self::DuplicateCheckingMode::*;
 use self::NamespaceError::*;
 
+use rustc::lint;
 use rustc::metadata::csearch;
 use rustc::metadata::decoder::{DefLike, DlDef, DlField, DlImpl};
 use rustc::middle::def::*;
@@ -38,8 +39,9 @@ use rustc::middle::def_id::{CRATE_DEF_INDEX, DefId};
 
 use syntax::ast::{Name, NodeId};
 use syntax::attr::AttrMetaMethods;
-use syntax::parse::token::special_idents;
-use syntax::codemap::{Span, DUMMY_SP};
+use syntax::feature_gate::{emit_feature_err, GateIssue};
+use syntax::parse::token::{keywords, special_idents};
+use syntax::codemap::{MultiSpan, Span, DUMMY_SP};
 
 use rustc_front::hir;
 use rustc_front::hir::{Block, Crate, DeclItem};
@@ -205,22 +207,21 @@ impl<'a, 'b:'a, 'tcx:'b> GraphBuilder<'a, 'b, 'tcx> {
                     // Return an error here by looking up the namespace that
                     // had the duplicate.
                     let ns = ns.unwrap();
-                    resolve_error(
-                        self,
-                        sp,
-                        ResolutionError::DuplicateDefinition(
-                            namespace_error_to_string(duplicate_type),
-                            name)
-                    );
-                    {
-                        let r = child.span_for_namespace(ns);
-                        if let Some(sp) = r {
-                            self.session.span_note(sp,
-                                                   &format!("first definition of {} `{}` here",
+                    let mut msp = MultiSpan::new(sp);
+                    msp.set_primary_label(format!("`{}` redefined here", name));
+                    if let Some(first_sp) = child.span_for_namespace(ns) {
+                        msp.push_label(first_sp, "previous definition here".to_string());
+                    }
+                    resolve_error(self,
+                                  msp,
+                                  ResolutionError::DuplicateDefinition(
                                       namespace_error_to_string(duplicate_type),
                                       name));
-                        }
-                    }
+
+                    // Keep the first definition, and let the duplicate be
+                    // defined in bindings that nothing refers to, so that
+                    // resolution can go on and report more errors.
+                    return Rc::new(NameBindings::new());
                 }
                 child
             }
@@ -257,6 +258,29 @@ impl<'a, 'b:'a, 'tcx:'b> GraphBuilder<'a, 'b, 'tcx> {
         ModuleParentLink(Rc::downgrade(parent), name)
     }
 
+    /// Lints the item `id`, a module or a struct, whose name is taken by a
+    /// struct or a module already, which will become an error.
+    fn lint_module_and_struct_with_same_name(&self,
+                                             id: NodeId,
+                                             sp: Span,
+                                             name: Name,
+                                             child: &NameBindings) {
+        let msg = format!("duplicate definition of {} `{}`: a module and a struct have the \
+                           same name",
+                          namespace_error_to_string(TypeError),
+                          name);
+        let lint = lint::builtin::MODULE_AND_STRUCT_WITH_SAME_NAME;
+        match child.span_for_namespace(TypeNS) {
+            Some(first_sp) => {
+                let note = format!("first definition of {} `{}` here",
+                                   namespace_error_to_string(TypeError),
+                                   name);
+                self.session.add_lint_note(lint, id, sp, msg, first_sp, note);
+            }
+            None => self.session.add_lint(lint, id, sp, msg),
+        }
+    }
+
     /// Constructs the reduced graph for one item.
     fn build_reduced_graph_for_item(&mut self, item: &Item, parent: &Rc<Module>) -> Rc<Module> {
         let name = item.name;
@@ -273,7 +297,7 @@ impl<'a, 'b:'a, 'tcx:'b> GraphBuilder<'a, 'b, 'tcx> {
                 // Extract and intern the module part of the path. For
                 // globs and lists, the path is found directly in the AST;
                 // for simple paths we have to munge the path a little.
-                let module_path = match view_path.node {
+                let mut module_path: Vec<Name> = match view_path.node {
                     ViewPathSimple(_, ref full_path) => {
                         full_path.segments
                                  .split_last()
@@ -292,6 +316,7 @@ impl<'a, 'b:'a, 'tcx:'b> GraphBuilder<'a, 'b, 'tcx> {
                                          .collect()
                     }
                 };
+                self.strip_crate_prefix(&mut module_path, view_path.span);
 
                 // Build up the import directives.
                 let shadowable = item.attrs.iter().any(|attr| {
@@ -304,12 +329,37 @@ impl<'a, 'b:'a, 'tcx:'b> GraphBuilder<'a, 'b, 'tcx> {
                 };
 
                 match view_path.node {
-                    ViewPathSimple(binding, ref full_path) => {
-                        let source_name = full_path.segments.last().unwrap().identifier.name;
+                    ViewPathSimple(mut binding, ref full_path) => {
+                        let mut source_name = full_path.segments.last().unwrap().identifier.name;
                         if source_name.as_str() == "mod" || source_name.as_str() == "self" {
                             resolve_error(self,
                                           view_path.span,
                                           ResolutionError::SelfImportsOnlyAllowedWithin);
+
+                            // `use foo::bar::self;` means `use foo::bar;`, so
+                            // suggest that, and import the module instead.
+                            if let Some(module_name) = module_path.pop() {
+                                if binding == source_name {
+                                    binding = module_name;
+                                }
+                                source_name = module_name;
+
+                                let mut module = module_path.clone();
+                                module.push(module_name);
+                                let module = names_to_string(&module);
+                                let suggestion = if binding == module_name {
+                                    module.clone()
+                                } else {
+                                    format!("{} as {}", module, binding)
+                                };
+                                self.session.span_suggestion(view_path.span,
+                                                             "import the module directly:",
+                                                             suggestion);
+                                self.session.fileline_help(view_path.span,
+                                    &format!("to import items of the module along with it, \
+                                              write `use {}::{{self, ...}};`",
+                                             module));
+                            }
                         }
 
                         let subclass = SingleImport(binding, source_name);
@@ -400,6 +450,7 @@ impl<'a, 'b:'a, 'tcx:'b> GraphBuilder<'a, 'b, 'tcx> {
                                                               NormalModuleKind,
                                                               false,
                                                               true));
+                    external_module.extern_crate_id.set(Some(item.id));
                     debug!("(build reduced graph for item) found extern `{}`",
                            module_to_string(&*external_module));
                     self.check_for_conflicts_between_external_crates(&**parent, name, sp);
@@ -417,21 +468,7 @@ impl<'a, 'b:'a, 'tcx:'b> GraphBuilder<'a, 'b, 'tcx> {
                     // check if there's struct of the same name already defined
                     if child.defined_in_namespace(TypeNS) &&
                        child.get_module_if_available().is_none() {
-                        self.session.span_warn(sp,
-                                               &format!("duplicate definition of {} `{}`. \
-                                                         Defining a module and a struct with \
-                                                         the same name will be disallowed soon.",
-                                                        namespace_error_to_string(TypeError),
-                                                        name));
-                        {
-                            let r = child.span_for_namespace(TypeNS);
-                            if let Some(sp) = r {
-                                self.session.span_note(sp,
-                                                       &format!("first definition of {} `{}` here",
-                                          namespace_error_to_string(TypeError),
-                                          name));
-                            }
-                        }
+                        self.lint_module_and_struct_with_same_name(item.id, sp, name, &child);
                     }
                 }
                 let name_bindings = self.add_child(name, parent, ForbidDuplicateModules, sp);
@@ -531,23 +568,10 @@ impl<'a, 'b:'a, 'tcx:'b> GraphBuilder<'a, 'b, 'tcx> {
                     if let Some(child) = child {
                         // check if theres a DefMod
                         if let Some(DefMod(_)) = child.def_for_namespace(TypeNS) {
-                            self.session.span_warn(sp,
-                                                   &format!("duplicate definition of {} `{}`. \
-                                                             Defining a module and a struct \
-                                                             with the same name will be \
-                                                             disallowed soon.",
-                                                            namespace_error_to_string(TypeError),
-                                                            name));
-                            {
-                                let r = child.span_for_namespace(TypeNS);
-                                if let Some(sp) = r {
-                                    self.session
-                                        .span_note(sp,
-                                                   &format!("first definition of {} `{}` here",
-                                                            namespace_error_to_string(TypeError),
-                                                            name));
-                                }
-                            }
+                            self.lint_module_and_struct_with_same_name(item.id,
+                                                                       sp,
+                                                                       name,
+                                                                       &child);
                         }
                     }
                     (ForbidDuplicateTypesAndValues, Some(struct_def.id()))
@@ -986,6 +1010,23 @@ impl<'a, 'b:'a, 'tcx:'b> GraphBuilder<'a, 'b, 'tcx> {
         });
     }
 
+    /// Removes the `crate` that `use crate::...` starts with. The paths of
+    /// imports are relative to the crate root either way, `crate::` only
+    /// says so explicitly.
+    fn strip_crate_prefix(&self, module_path: &mut Vec<Name>, sp: Span) {
+        if module_path.first() != Some(&keywords::Crate.to_name()) {
+            return;
+        }
+        if !self.session.features.borrow().crate_in_paths {
+            emit_feature_err(&self.session.parse_sess.span_diagnostic,
+                             "crate_in_paths",
+                             sp,
+                             GateIssue::Language,
+                             "`crate::` in import paths is experimental");
+        }
+        module_path.remove(0);
+    }
+
     /// Creates and adds an import directive to the given module.
     fn build_import_directive(&mut self,
                               module_: &Module,
@@ -22,10 +22,14 @@ use std::ops::{Deref, DerefMut};
 use Resolver;
 use Namespace::{TypeNS, ValueNS};
 
+use rustc::front::map as hir_map;
 use rustc::lint;
+use rustc::middle::def::{DefMod, PathResolution};
 use rustc::middle::privacy::{DependsOn, LastImport, Used, Unused};
+use rustc::session::config;
+use rustc::util::nodemap::{NodeMap, NodeSet};
 use syntax::ast;
-use syntax::codemap::{Span, DUMMY_SP};
+use syntax::codemap::{mk_sp, BytePos, Span, DUMMY_SP};
 
 use rustc_front::hir;
 use rustc_front::hir::{ViewPathGlob, ViewPathList, ViewPathSimple};
@@ -33,6 +37,9 @@ use rustc_front::intravisit::Visitor;
 
 struct UnusedImportCheckVisitor<'a, 'b: 'a, 'tcx: 'b> {
     resolver: &'a mut Resolver<'b, 'tcx>,
+    // The public glob imports of each local module, e.g. the `pub use
+    // private::*` of a facade, by the id of the module they import.
+    glob_reexports: NodeMap<Vec<ast::NodeId>>,
 }
 
 // Deref and DerefMut impls allow treating UnusedImportCheckVisitor as Resolver.
@@ -58,16 +65,31 @@ impl<'a, 'b, 'tcx> UnusedImportCheckVisitor<'a, 'b, 'tcx> {
     // only check imports and namespaces which are used. In particular, this
     // means that if an import could name either a public or private item, we
     // will check the correct thing, dependent on how the import is used.
-    fn finalize_import(&mut self, id: ast::NodeId, span: Span) {
+    //
+    // `removal_span` is the code to delete to remove an unused import, for
+    // the items of an import list.
+    fn finalize_import(&mut self, id: ast::NodeId, span: Span, removal_span: Option<Span>) {
         debug!("finalizing import uses for {:?}",
                self.session.codemap().span_to_snippet(span));
 
-        if !self.used_imports.contains(&(id, TypeNS)) &&
-           !self.used_imports.contains(&(id, ValueNS)) {
-            self.session.add_lint(lint::builtin::UNUSED_IMPORTS,
-                                  id,
-                                  span,
-                                  "unused import".to_string());
+        if !self.is_used(id) {
+            match removal_span {
+                Some(removal_span) => {
+                    self.session.add_lint_suggestion(lint::builtin::UNUSED_IMPORTS,
+                                                     id,
+                                                     span,
+                                                     "unused import".to_string(),
+                                                     removal_span,
+                                                     "remove it:".to_string(),
+                                                     String::new())
+                }
+                None => {
+                    self.session.add_lint(lint::builtin::UNUSED_IMPORTS,
+                                          id,
+                                          span,
+                                          "unused import".to_string())
+                }
+            }
         }
 
         let mut def_map = self.def_map.borrow_mut();
@@ -109,43 +131,158 @@ impl<'a, 'b, 'tcx> UnusedImportCheckVisitor<'a, 'b, 'tcx> {
             type_used: t_used,
         };
     }
+
+    // Lints the `pub use` item `item` if none of what it re-exports is used
+    // in the crate, and other crates can't use it either because it's in a
+    // private module or the crate is an executable. Re-exports that hide an
+    // item of the prelude are left alone, since removing them would change
+    // what their name refers to.
+    fn check_reexport(&mut self, item: &hir::Item, p: &hir::ViewPath) {
+        if self.is_reachable(item.id) {
+            return;
+        }
+        let ids = match p.node {
+            ViewPathSimple(..) | ViewPathGlob(_) => vec![(item.id, p.span)],
+            ViewPathList(_, ref list) => list.iter().map(|i| (i.node.id(), i.span)).collect(),
+        };
+        for (id, span) in ids {
+            if !self.is_used(id) && !self.prelude_shadowing_reexports.contains(&id) {
+                self.session.add_lint(lint::builtin::UNUSED_REEXPORTS,
+                                      id,
+                                      span,
+                                      "unused re-export, not reachable from the public API \
+                                       of the crate".to_string());
+            }
+        }
+    }
+
+    // Whether other crates can name the item `id`: the crate is a library and
+    // the item is in a module that is public all the way up to the root, or
+    // that a reachable `pub use module::*` re-exports.
+    fn is_reachable(&self, id: ast::NodeId) -> bool {
+        if self.session.crate_types.borrow().iter().all(|&t| t == config::CrateTypeExecutable) {
+            return false;
+        }
+        self.is_reachable_from_root(id, &mut NodeSet())
+    }
+
+    // `seen` holds the private modules already looked at, since glob imports
+    // can import each other's modules.
+    fn is_reachable_from_root(&self, id: ast::NodeId, seen: &mut NodeSet) -> bool {
+        let mut id = id;
+        loop {
+            let parent = self.ast_map.get_parent(id);
+            if parent == ast::CRATE_NODE_ID {
+                return true;
+            }
+            if parent == id {
+                return false;
+            }
+            id = parent;
+            match self.ast_map.find(id) {
+                Some(hir_map::NodeItem(&hir::Item { node: hir::ItemMod(_),
+                                                    vis: hir::Public, .. })) => {}
+                Some(hir_map::NodeItem(&hir::Item { node: hir::ItemMod(_), .. })) => {
+                    if !seen.insert(id) {
+                        return false;
+                    }
+                    return match self.glob_reexports.get(&id) {
+                        Some(globs) => globs.iter().any(|&glob| {
+                            self.is_reachable_from_root(glob, seen)
+                        }),
+                        None => false,
+                    };
+                }
+                // A function the item is in the body of.
+                _ => return false,
+            }
+        }
+    }
+
+    fn is_used(&self, id: ast::NodeId) -> bool {
+        self.used_imports.contains(&(id, TypeNS)) || self.used_imports.contains(&(id, ValueNS))
+    }
+
+    // The code to delete to remove each of the unused items of the import
+    // list `list` of `view_path`, along with the commas separating them from
+    // the rest of the list. The spans of the unused items don't overlap, so a
+    // tool can delete all of them, e.g. `use foo::{A, B, C};` becomes
+    // `use foo::{A};` when `B` and `C` are unused.
+    fn removal_spans(&self, view_path: Span, list: &[hir::PathListItem]) -> Vec<Option<Span>> {
+        let used = list.iter().map(|item| self.is_used(item.node.id())).collect::<Vec<_>>();
+        let mut spans = vec![None; list.len()];
+        for i in 0..list.len() {
+            if used[i] {
+                continue;
+            }
+            spans[i] = Some(if used[i + 1..].iter().any(|&u| u) {
+                // `B, `, up to the next item.
+                mk_sp(list[i].span.lo, list[i + 1].span.lo)
+            } else if used[..i].iter().any(|&u| u) {
+                // `, C`, from the previous item, since no item after this one
+                // is left to take the comma.
+                mk_sp(list[i - 1].span.hi, list[i].span.hi)
+            } else if i + 1 < list.len() {
+                mk_sp(list[i].span.lo, list[i + 1].span.lo)
+            } else {
+                // The last item of a list whose items are all unused, along
+                // with its trailing comma if it has one.
+                let rest = mk_sp(list[i].span.hi, view_path.hi);
+                let comma = match self.session.codemap().span_to_snippet(rest) {
+                    Ok(ref rest) if rest.trim_left().starts_with(",") => {
+                        rest.find(',').map(|pos| BytePos(pos as u32 + 1))
+                    }
+                    _ => None,
+                };
+                mk_sp(list[i].span.lo, list[i].span.hi + comma.unwrap_or(BytePos(0)))
+            });
+        }
+        spans
+    }
 }
 
 impl<'a, 'b, 'v, 'tcx> Visitor<'v> for UnusedImportCheckVisitor<'a, 'b, 'tcx> {
     fn visit_item(&mut self, item: &hir::Item) {
-        // Ignore is_public import statements because there's no way to be sure
-        // whether they're used or not. Also ignore imports with a dummy span
-        // because this means that they were generated in some fashion by the
-        // compiler and we don't need to consider them.
-        if item.vis == hir::Public || item.span == DUMMY_SP {
+        // Ignore imports with a dummy span because this means that they were
+        // generated in some fashion by the compiler and we don't need to
+        // consider them. Public imports are only unused if other crates can't
+        // see them either, see `check_reexport`.
+        if item.span == DUMMY_SP {
+            return;
+        }
+        if item.vis == hir::Public {
+            if let hir::ItemUse(ref p) = item.node {
+                self.check_reexport(item, p);
+            }
             return;
         }
 
         match item.node {
             hir::ItemExternCrate(_) => {
-                if let Some(crate_num) = self.session.cstore.find_extern_mod_stmt_cnum(item.id) {
-                    if !self.used_crates.contains(&crate_num) {
-                        self.session.add_lint(lint::builtin::UNUSED_EXTERN_CRATES,
-                                              item.id,
-                                              item.span,
-                                              "unused extern crate".to_string());
-                    }
+                // The crate is used if a path or an import, possibly one a
+                // macro expanded to, was resolved through this item.
+                if self.session.cstore.find_extern_mod_stmt_cnum(item.id).is_some() &&
+                   !self.used_extern_crates.contains(&item.id) {
+                    self.session.add_lint(lint::builtin::UNUSED_EXTERN_CRATES,
+                                          item.id,
+                                          item.span,
+                                          "unused extern crate".to_string());
                 }
             }
             hir::ItemUse(ref p) => {
                 match p.node {
                     ViewPathSimple(_, _) => {
-                        self.finalize_import(item.id, p.span)
+                        self.finalize_import(item.id, p.span, None)
                     }
 
                     ViewPathList(_, ref list) => {
-                        for i in list {
-                            self.finalize_import(i.node.id(), i.span);
+                        let removal_spans = self.removal_spans(p.span, list);
+                        for (i, removal_span) in list.iter().zip(removal_spans) {
+                            self.finalize_import(i.node.id(), i.span, removal_span);
                         }
                     }
                     ViewPathGlob(_) => {
-                        if !self.used_imports.contains(&(item.id, TypeNS)) &&
-                           !self.used_imports.contains(&(item.id, ValueNS)) {
+                        if !self.is_used(item.id) {
                             self.session
                                 .add_lint(lint::builtin::UNUSED_IMPORTS,
                                           item.id,
@@ -160,7 +297,45 @@ impl<'a, 'b, 'v, 'tcx> Visitor<'v> for UnusedImportCheckVisitor<'a, 'b, 'tcx> {
     }
 }
 
+// Finds the public glob imports of local modules, for `is_reachable`.
+struct GlobReexportCollector<'a, 'b: 'a, 'tcx: 'b> {
+    resolver: &'a Resolver<'b, 'tcx>,
+    glob_reexports: NodeMap<Vec<ast::NodeId>>,
+}
+
+impl<'a, 'b, 'v, 'tcx> Visitor<'v> for GlobReexportCollector<'a, 'b, 'tcx> {
+    fn visit_item(&mut self, item: &hir::Item) {
+        match item.node {
+            hir::ItemUse(ref p) if item.vis == hir::Public => {
+                if let ViewPathGlob(_) = p.node {
+                    let module = match self.resolver.def_map.borrow().get(&item.id) {
+                        Some(&PathResolution { base_def: DefMod(did), .. }) => {
+                            self.resolver.ast_map.as_local_node_id(did)
+                        }
+                        _ => None,
+                    };
+                    if let Some(module) = module {
+                        self.glob_reexports.entry(module).or_insert(vec![]).push(item.id);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
 pub fn check_crate(resolver: &mut Resolver, krate: &hir::Crate) {
-    let mut visitor = UnusedImportCheckVisitor { resolver: resolver };
+    let glob_reexports = {
+        let mut collector = GlobReexportCollector {
+            resolver: resolver,
+            glob_reexports: NodeMap(),
+        };
+        krate.visit_all_items(&mut collector);
+        collector.glob_reexports
+    };
+    let mut visitor = UnusedImportCheckVisitor {
+        resolver: resolver,
+        glob_reexports: glob_reexports,
+    };
     krate.visit_all_items(&mut visitor);
 }
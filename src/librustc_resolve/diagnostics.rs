@@ -899,6 +899,40 @@ trait Foo {}
 
 impl Foo for i32 {}
 ```
+"##,
+
+E0518: r##"
+Imports were found to depend on each other in a cycle, so none of them can be
+resolved.
+
+An example of this error:
+
+```
+mod a {
+    pub use b::Foo; // error, `b::Foo` is imported from `a::Foo`
+}
+
+mod b {
+    pub use a::Foo;
+}
+```
+
+To fix this error, have one of the imports refer to the item `Foo` itself
+rather than to a re-export of it:
+
+```
+mod a {
+    pub use c::Foo;
+}
+
+mod b {
+    pub use a::Foo;
+}
+
+mod c {
+    pub struct Foo;
+}
+```
 "##
 
 }
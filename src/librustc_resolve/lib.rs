@@ -63,18 +63,19 @@ use rustc::middle::def_id::DefId;
 use rustc::middle::pat_util::pat_bindings_hygienic;
 use rustc::middle::privacy::*;
 use rustc::middle::subst::{ParamSpace, FnSpace, TypeSpace};
-use rustc::middle::ty::{Freevar, FreevarMap, TraitMap, GlobMap};
-use rustc::util::nodemap::{NodeMap, DefIdSet, FnvHashMap};
+use rustc::middle::ty::{Freevar, FreevarMap, TraitMap, GlobMap, ImportMap, VisiblePaths};
+use rustc::util::nodemap::{NodeMap, NodeSet, DefIdMap, DefIdSet, FnvHashMap};
 use rustc::util::lev_distance::lev_distance;
 
 use syntax::ast;
-use syntax::ast::{CRATE_NODE_ID, Ident, Name, NodeId, CrateNum, TyIs, TyI8, TyI16, TyI32, TyI64};
+use syntax::ast::{CRATE_NODE_ID, Ident, Name, NodeId, TyIs, TyI8, TyI16, TyI32, TyI64};
 use syntax::ast::{TyUs, TyU8, TyU16, TyU32, TyU64, TyF64, TyF32};
 use syntax::attr::AttrMetaMethods;
 use syntax::ext::mtwt;
+use syntax::feature_gate::{emit_feature_err, GateIssue};
 use syntax::parse::token::{self, special_names, special_idents};
 use syntax::ptr::P;
-use syntax::codemap::{self, Span, Pos};
+use syntax::codemap::{self, MultiSpan, Span, Pos};
 
 use rustc_front::intravisit::{self, FnKind, Visitor};
 use rustc_front::hir;
@@ -94,12 +95,11 @@ use rustc_front::hir::{TraitRef, Ty, TyBool, TyChar, TyFloat, TyInt};
 use rustc_front::hir::{TyRptr, TyStr, TyUint, TyPath, TyPtr};
 use rustc_front::util::walk_pat;
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::cell::{Cell, RefCell};
 use std::fmt;
 use std::mem::replace;
 use std::rc::{Rc, Weak};
-use std::usize;
 
 use resolve_imports::{Target, ImportDirective, ImportResolution};
 use resolve_imports::Shadowable;
@@ -193,20 +193,39 @@ pub enum ResolutionError<'a> {
     SelfImportOnlyInImportListWithNonEmptyPrefix,
     /// error E0432: unresolved import
     UnresolvedImport(Option<(&'a str, &'a str)>),
+    /// error E0432: unresolved module path, shared by several imports
+    UnresolvedImportPath(&'a str, usize, &'a str),
     /// error E0433: failed to resolve
     FailedToResolve(&'a str),
+    /// error E0518: imports that wait for each other
+    ImportCycle(&'a str),
     /// error E0434: can't capture dynamic environment in a fn item
     CannotCaptureDynamicEnvironmentInFnItem,
     /// error E0435: attempt to use a non-constant value in a constant
     AttemptToUseNonConstantValueInConstant,
 }
 
-fn resolve_error<'b, 'a: 'b, 'tcx: 'a>(resolver: &'b Resolver<'a, 'tcx>,
-                                       span: syntax::codemap::Span,
-                                       resolution_error: ResolutionError<'b>) {
+/// Reports `resolution_error` at `span`, which can carry labeled secondary
+/// spans. The errors that support them report the labels as part of the
+/// error, the others as notes following it.
+fn resolve_error<'b, 'a: 'b, 'tcx: 'a, S>(resolver: &'b Resolver<'a, 'tcx>,
+                                          span: S,
+                                          resolution_error: ResolutionError<'b>)
+    where S: Into<MultiSpan>
+{
     if !resolver.emit_errors {
         return;
     }
+    let msp = span.into();
+    let span = msp.primary;
+    let searched_modules = replace(&mut *resolver.searched_modules.borrow_mut(), Vec::new());
+    let is_lookup_failure = match resolution_error {
+        ResolutionError::UndeclaredTraitName(..) |
+        ResolutionError::UseOfUndeclared(..) |
+        ResolutionError::UnresolvedName(..) |
+        ResolutionError::FailedToResolve(..) => true,
+        _ => false,
+    };
     match resolution_error {
         ResolutionError::TypeParametersFromOuterFunction => {
             span_err!(resolver.session,
@@ -424,12 +443,13 @@ fn resolve_error<'b, 'a: 'b, 'tcx: 'a>(resolver: &'b Resolver<'a, 'tcx>,
                       descr);
         }
         ResolutionError::DuplicateDefinition(namespace, name) => {
-            span_err!(resolver.session,
-                      span,
-                      E0428,
-                      "duplicate definition of {} `{}`",
-                      namespace,
-                      name);
+            span_err_multi!(resolver.session,
+                            msp,
+                            E0428,
+                            "duplicate definition of {} `{}`",
+                            namespace,
+                            name);
+            return;
         }
         ResolutionError::SelfImportsOnlyAllowedWithin => {
             span_err!(resolver.session,
@@ -457,6 +477,20 @@ fn resolve_error<'b, 'a: 'b, 'tcx: 'a>(resolver: &'b Resolver<'a, 'tcx>,
             };
             span_err!(resolver.session, span, E0432, "{}", msg);
         }
+        ResolutionError::UnresolvedImportPath(path, count, help) => {
+            span_err_multi!(resolver.session,
+                            msp,
+                            E0432,
+                            "unresolved import path `{}` in {} imports{}",
+                            path,
+                            count,
+                            help);
+            return;
+        }
+        ResolutionError::ImportCycle(cycle) => {
+            span_err_multi!(resolver.session, msp, E0518, "import cycle: {}", cycle);
+            return;
+        }
         ResolutionError::FailedToResolve(msg) => {
             span_err!(resolver.session, span, E0433, "failed to resolve. {}", msg);
         }
@@ -475,6 +509,46 @@ fn resolve_error<'b, 'a: 'b, 'tcx: 'a>(resolver: &'b Resolver<'a, 'tcx>,
                       "attempt to use a non-constant value in a constant");
         }
     }
+
+    for (sp, label) in msp.labels {
+        resolver.session.span_note(sp, &label);
+    }
+
+    if is_lookup_failure {
+        for searched in &searched_modules {
+            resolver.session.fileline_note(span, &searched.to_string());
+        }
+    }
+}
+
+/// A module that was searched for a name that wasn't found in it, which is
+/// noted with `-Z verbose-resolve-failures` when the name fails to resolve.
+struct SearchedModule {
+    module: String,
+    name: Name,
+    namespace: Namespace,
+    /// Whether the name is defined in the other namespace of the module.
+    in_other_namespace: bool,
+    /// Whether a private import of the name was skipped, because the module
+    /// was searched from outside of it.
+    private_import_skipped: bool,
+}
+
+impl fmt::Display for SearchedModule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (kind, other_kind) = match self.namespace {
+            TypeNS => ("type or module", "value"),
+            ValueNS => ("value", "type or module"),
+        };
+        try!(write!(f, "searched {} for the {} `{}`", self.module, kind, self.name));
+        if self.in_other_namespace {
+            try!(write!(f, ", which only defines it as a {}", other_kind));
+        }
+        if self.private_import_skipped {
+            try!(write!(f, ", skipping a private import of it"));
+        }
+        Ok(())
+    }
 }
 
 #[derive(Copy, Clone)]
@@ -795,6 +869,10 @@ pub struct Module {
     // `extern crate`.
     external_module_children: RefCell<HashMap<Name, Rc<Module>>>,
 
+    // The `extern crate` item this node is the root of the crate of, if it
+    // is one of the external module children of a module.
+    extern_crate_id: Cell<Option<NodeId>>,
+
     // The anonymous children of this node. Anonymous children are pseudo-
     // modules that are implicitly created around items contained within
     // blocks.
@@ -847,6 +925,7 @@ impl Module {
             children: RefCell::new(HashMap::new()),
             imports: RefCell::new(Vec::new()),
             external_module_children: RefCell::new(HashMap::new()),
+            extern_crate_id: Cell::new(None),
             anonymous_children: RefCell::new(NodeMap()),
             import_resolutions: RefCell::new(HashMap::new()),
             glob_count: Cell::new(0),
@@ -865,6 +944,32 @@ impl Module {
             self.imports.borrow().len() == self.resolved_import_count.get()
         }
     }
+
+    /// The modules nested in this one: the named ones by name, then the
+    /// anonymous ones in the order of their blocks. Walking the module tree
+    /// in this order rather than in the order of the hashmaps keeps the
+    /// errors reported on the way in the same order from one run to the next.
+    fn submodules(&self) -> Vec<Rc<Module>> {
+        let mut named = self.children
+                            .borrow()
+                            .iter()
+                            .filter_map(|(&name, bindings)| {
+                                bindings.get_module_if_available().map(|module| (name, module))
+                            })
+                            .collect::<Vec<_>>();
+        sort_by_name(&mut named);
+        let mut anonymous = self.anonymous_children
+                                .borrow()
+                                .iter()
+                                .map(|(&id, module)| (id, module.clone()))
+                                .collect::<Vec<_>>();
+        anonymous.sort_by(|a, b| a.0.cmp(&b.0));
+
+        named.into_iter()
+             .chain(anonymous.into_iter())
+             .map(|(_, module)| module)
+             .collect()
+    }
 }
 
 impl Module {
@@ -1205,6 +1310,25 @@ impl PrimitiveTypeTable {
     }
 }
 
+/// The paths items can be imported with, see `build_import_candidates`.
+struct ImportCandidates {
+    // The paths of the items, by name and namespace, shortest first.
+    paths: HashMap<(Name, Namespace), Vec<ImportCandidate>>,
+    // The paths of the traits defining an associated item, by the name of
+    // the item, shortest first.
+    traits_by_item: HashMap<Name, Vec<ImportCandidate>>,
+}
+
+/// A path an item can be imported with.
+#[derive(Clone)]
+struct ImportCandidate {
+    def_id: DefId,
+    path: Vec<Name>,
+    // The module the path goes through a private item of, if any. The path
+    // can only be used from that module and the modules inside it.
+    private_to: Option<DefId>,
+}
+
 /// The main resolver class.
 pub struct Resolver<'a, 'tcx: 'a> {
     session: &'a Session,
@@ -1248,19 +1372,38 @@ pub struct Resolver<'a, 'tcx: 'a> {
     export_map: ExportMap,
     trait_map: TraitMap,
     external_exports: ExternalExports,
+    import_sources: ImportSources,
 
     // Whether or not to print error messages. Can be set to true
     // when getting additional info for error message suggestions,
     // so as to avoid printing duplicate errors
     emit_errors: bool,
 
+    // The modules the last path resolution searched without finding the name
+    // it looked for, with `-Z verbose-resolve-failures`.
+    searched_modules: RefCell<Vec<SearchedModule>>,
+
     make_glob_map: bool,
     // Maps imports to the names of items actually imported (this actually maps
     // all imports, but only glob imports are actually interesting).
     glob_map: GlobMap,
 
     used_imports: HashSet<(NodeId, Namespace)>,
-    used_crates: HashSet<CrateNum>,
+    // The `extern crate` items that a path or an import was resolved
+    // through.
+    used_extern_crates: NodeSet,
+
+    // The `pub use` re-exports that hide an item of the prelude.
+    prelude_shadowing_reexports: NodeSet,
+
+    // The import each name of a glob import comes from, if it's a re-export
+    // of the module the glob imports from, by glob import, name and
+    // namespace.
+    glob_sources: HashMap<(NodeId, Name, Namespace), NodeId>,
+
+    // The paths that items can be imported with. Only built, by
+    // `import_candidates`, once an unresolved name needs it.
+    import_candidates: Option<ImportCandidates>,
 
     // Callback function for intercepting walks
     callback: Option<Box<Fn(hir_map::Node, &mut bool) -> bool>>,
@@ -1323,13 +1466,19 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
             export_map: NodeMap(),
             trait_map: NodeMap(),
             used_imports: HashSet::new(),
-            used_crates: HashSet::new(),
+            used_extern_crates: NodeSet(),
+            prelude_shadowing_reexports: NodeSet(),
+            glob_sources: HashMap::new(),
             external_exports: DefIdSet(),
+            import_sources: NodeMap(),
 
             emit_errors: true,
+            searched_modules: RefCell::new(Vec::new()),
             make_glob_map: make_glob_map == MakeGlobMap::Yes,
             glob_map: HashMap::new(),
 
+            import_candidates: None,
+
             callback: None,
             resolved: false,
         }
@@ -1350,11 +1499,28 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
         self.glob_map.insert(import_id, new_set);
     }
 
-    fn get_trait_name(&self, did: DefId) -> Name {
-        if let Some(node_id) = self.ast_map.as_local_node_id(did) {
-            self.ast_map.expect_item(node_id).name
-        } else {
-            csearch::get_trait_name(&self.session.cstore, did)
+    /// Records that `name` was resolved in `namespace` through the import
+    /// `id`, and through the re-exports it has the name from if it's a glob
+    /// import.
+    fn record_use(&mut self, id: NodeId, name: Name, namespace: Namespace) {
+        let mut id = id;
+        let mut seen = vec![];
+        while !seen.contains(&id) {
+            seen.push(id);
+            self.used_imports.insert((id, namespace));
+            self.record_import_use(id, name);
+            match self.glob_sources.get(&(id, name, namespace)) {
+                Some(&source) => id = source,
+                None => break,
+            }
+        }
+    }
+
+    /// Records that a path or an import was resolved through `module`, for
+    /// the `unused_extern_crates` lint, if it's the root of an `extern crate`.
+    fn record_extern_crate_use(&mut self, module: &Module) {
+        if let Some(id) = module.extern_crate_id.get() {
+            self.used_extern_crates.insert(id);
         }
     }
 
@@ -1486,11 +1652,6 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
                                 Some(ref module_def) => {
                                     search_module = module_def.clone();
 
-                                    // track extern crates for unused_extern_crate lint
-                                    if let Some(did) = module_def.def_id.get() {
-                                        self.used_crates.insert(did.krate);
-                                    }
-
                                     // Keep track of the closest
                                     // private module used when
                                     // resolving this import chain.
@@ -1656,13 +1817,7 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
                 }
                 Some(target) => {
                     debug!("(resolving item in lexical scope) using import resolution");
-                    // track used imports and extern crates as well
-                    let id = import_resolution.id(namespace);
-                    self.used_imports.insert((id, namespace));
-                    self.record_import_use(id, name);
-                    if let Some(DefId{krate: kid, ..}) = target.target_module.def_id.get() {
-                        self.used_crates.insert(kid);
-                    }
+                    self.record_use(import_resolution.id(namespace), name, namespace);
                     return Success((target, false));
                 }
             }
@@ -1673,6 +1828,7 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
             // FIXME (21114): In principle unclear `child` *has* to be lifted.
             let child = module_.external_module_children.borrow().get(&name).cloned();
             if let Some(module) = child {
+                self.record_extern_crate_use(&module);
                 let name_bindings = Rc::new(Resolver::create_name_bindings_from_module(module));
                 debug!("lower name bindings succeeded");
                 return Success((Target::new(module_, name_bindings, Shadowable::Never),
@@ -1680,6 +1836,8 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
             }
         }
 
+        self.record_searched_module(&module_, name, namespace, true);
+
         // Finally, proceed up the scope chain looking for parent modules.
         let mut search_module = module_;
         loop {
@@ -1909,13 +2067,7 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
                     }
                     Some(target) => {
                         debug!("(resolving name in module) resolved to import");
-                        // track used imports and extern crates as well
-                        let id = import_resolution.id(namespace);
-                        self.used_imports.insert((id, namespace));
-                        self.record_import_use(id, name);
-                        if let Some(DefId{krate: kid, ..}) = target.target_module.def_id.get() {
-                            self.used_crates.insert(kid);
-                        }
+                        self.record_use(import_resolution.id(namespace), name, namespace);
                         return Success((target, true));
                     }
                 }
@@ -1928,6 +2080,7 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
             // FIXME (21114): In principle unclear `child` *has* to be lifted.
             let child = module_.external_module_children.borrow().get(&name).cloned();
             if let Some(module) = child {
+                self.record_extern_crate_use(&module);
                 let name_bindings = Rc::new(Resolver::create_name_bindings_from_module(module));
                 return Success((Target::new(module_, name_bindings, Shadowable::Never),
                                 false));
@@ -1936,14 +2089,58 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
 
         // We're out of luck.
         debug!("(resolving name in module) failed to resolve `{}`", name);
+        self.record_searched_module(&module_, name, namespace, allow_private_imports);
         return Failed(None);
     }
 
-    fn report_unresolved_imports(&mut self, module_: Rc<Module>) {
+    /// Records that `name` wasn't found in `module_` when
+    /// `-Z verbose-resolve-failures` is on.
+    fn record_searched_module(&self,
+                              module_: &Module,
+                              name: Name,
+                              namespace: Namespace,
+                              allow_private_imports: bool) {
+        if !self.session.opts.debugging_opts.verbose_resolve_failures {
+            return;
+        }
+
+        let other_namespace = match namespace {
+            TypeNS => ValueNS,
+            ValueNS => TypeNS,
+        };
+        let mut in_other_namespace = match module_.children.borrow().get(&name) {
+            Some(name_bindings) => name_bindings.defined_in_namespace(other_namespace),
+            None => false,
+        };
+        if other_namespace == TypeNS &&
+           module_.external_module_children.borrow().contains_key(&name) {
+            in_other_namespace = true;
+        }
+        let mut private_import_skipped = false;
+        if let Some(import_resolution) = module_.import_resolutions.borrow().get(&name) {
+            if allow_private_imports || import_resolution.is_public {
+                if import_resolution.target_for_namespace(other_namespace).is_some() {
+                    in_other_namespace = true;
+                }
+            } else if import_resolution.target_for_namespace(namespace).is_some() {
+                private_import_skipped = true;
+            }
+        }
+
+        self.searched_modules.borrow_mut().push(SearchedModule {
+            module: searched_module_to_string(module_),
+            name: name,
+            namespace: namespace,
+            in_other_namespace: in_other_namespace,
+            private_import_skipped: private_import_skipped,
+        });
+    }
+
+    fn report_unresolved_imports(&mut self, module_: Rc<Module>, in_cycle: &NodeSet) {
         let index = module_.resolved_import_count.get();
         let imports = module_.imports.borrow();
         let import_count = imports.len();
-        if index != import_count {
+        if index != import_count && !in_cycle.contains(&(*imports)[index].id) {
             resolve_error(self,
                           (*imports)[index].span,
                           ResolutionError::UnresolvedImport(None));
@@ -1952,19 +2149,8 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
         // Descend into children and anonymous children.
         build_reduced_graph::populate_module_if_necessary(self, &module_);
 
-        for (_, child_node) in module_.children.borrow().iter() {
-            match child_node.get_module_if_available() {
-                None => {
-                    // Continue.
-                }
-                Some(child_module) => {
-                    self.report_unresolved_imports(child_module);
-                }
-            }
-        }
-
-        for (_, module_) in module_.anonymous_children.borrow().iter() {
-            self.report_unresolved_imports(module_.clone());
+        for child_module in module_.submodules() {
+            self.report_unresolved_imports(child_module, in_cycle);
         }
     }
 
@@ -2026,6 +2212,19 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
         self.current_module = orig_module;
     }
 
+    /// Whether the module `name` of the current module is the one the item
+    /// `id` defines, rather than an earlier definition of the same name.
+    fn is_module_defined_by(&self, name: Name, id: NodeId) -> bool {
+        let def_id = self.ast_map.local_def_id(id);
+        match self.current_module.children.borrow().get(&name) {
+            Some(name_bindings) => {
+                name_bindings.get_module_if_available()
+                             .map_or(false, |module_| module_.def_id.get() == Some(def_id))
+            }
+            None => false,
+        }
+    }
+
     /// Searches the current set of local scopes for labels.
     /// Stops after meeting a closure.
     fn search_label(&self, name: Name) -> Option<DefLike> {
@@ -2050,6 +2249,9 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
     fn resolve_crate(&mut self, krate: &hir::Crate) {
         debug!("(resolving crate) starting");
 
+        // Forget the modules searched while resolving imports.
+        self.searched_modules.borrow_mut().clear();
+
         intravisit::walk_crate(self, krate);
     }
 
@@ -2141,6 +2343,11 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
                 });
             }
 
+            ItemMod(_) if !self.is_module_defined_by(name, item.id) => {
+                // Only the first definition of a module defined twice is in
+                // the graph, which the contents of this one don't belong to.
+            }
+
             ItemMod(_) | ItemForeignMod(_) => {
                 self.with_scope(Some(name), |this| {
                     intravisit::walk_item(this, item);
@@ -2671,6 +2878,10 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
                                                                     &*path_names_to_string(path,
                                                                                            0))
                                          );
+                            if maybe_qself.is_none() && path.segments.len() == 1 {
+                                let name = path.segments[0].identifier.name;
+                                self.suggest_imports(ty.span, name, TypeNS);
+                            }
                         }
                     }
                 }
@@ -2923,7 +3134,11 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
                 }
 
                 PatStruct(ref path, _, _) => {
-                    match self.resolve_path(pat_id, path, 0, TypeNS, false) {
+                    let resolution = match self.resolve_self_struct(pat_id, path, TypeNS) {
+                        Some(resolution) => Some(resolution),
+                        None => self.resolve_path(pat_id, path, 0, TypeNS, false),
+                    };
+                    match resolution {
                         Some(definition) => {
                             self.record_def(pattern.id, definition);
                         }
@@ -2956,6 +3171,7 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
                                        name: Name,
                                        span: Span)
                                        -> BareIdentifierPatternResolution {
+        self.searched_modules.borrow_mut().clear();
         let module = self.current_module.clone();
         match self.resolve_item_in_lexical_scope(module, name, ValueNS) {
             Success((target, _)) => {
@@ -3042,6 +3258,10 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
                 resolution = this.resolve_path(id, path, depth, TypeNS, true);
             });
         }
+        if resolution.is_none() && maybe_qself.is_none() && namespace == ValueNS {
+            // `Self(..)` or a unit-like `Self` inside an impl.
+            resolution = self.resolve_self_struct(id, path, ValueNS);
+        }
         if let Some(DefMod(_)) = resolution.map(|r| r.base_def) {
             // A module is not a valid type or value.
             resolution = None;
@@ -3049,6 +3269,65 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
         ResolveAttempt(resolution)
     }
 
+    /// Resolves a path consisting only of `Self`, used as a struct
+    /// literal, a struct pattern or a constructor inside an impl, to
+    /// the struct named by the self type of that impl. In the type
+    /// namespace this is the struct itself; in the value namespace it
+    /// is the constructor of a tuple-like or unit-like struct.
+    fn resolve_self_struct(&mut self,
+                           id: NodeId,
+                           path: &Path,
+                           namespace: Namespace)
+                           -> Option<PathResolution> {
+        if path.global || path.segments.len() != 1 ||
+           path.segments[0].identifier.name != special_idents::type_self.name {
+            return None;
+        }
+
+        let self_ty_id = match self.with_no_errors(|this| {
+            this.resolve_path(id, path, 0, TypeNS, true)
+        }) {
+            Some(PathResolution { base_def: DefSelfTy(_, Some((_, self_ty_id))), .. }) => {
+                self_ty_id
+            }
+            _ => return None,
+        };
+
+        let struct_did = match self.def_map.borrow().get(&self_ty_id) {
+            Some(&PathResolution { base_def: DefTy(did, false), depth: 0, .. }) => did,
+            _ => return None,
+        };
+
+        let (def, what) = match namespace {
+            TypeNS => (DefTy(struct_did, false), "`Self` as a struct name is experimental"),
+            ValueNS => {
+                let node_id = match self.ast_map.as_local_node_id(struct_did) {
+                    Some(node_id) => node_id,
+                    None => return None,
+                };
+                match self.ast_map.find(node_id) {
+                    Some(hir_map::NodeItem(&Item { node: ItemStruct(ref struct_def, _), .. }))
+                            if !struct_def.is_struct() => {
+                        (DefStruct(self.ast_map.local_def_id(struct_def.id())),
+                         "`Self` as a struct constructor is experimental")
+                    }
+                    _ => return None,
+                }
+            }
+        };
+
+        if self.emit_errors && !self.session.features.borrow().self_struct_ctor {
+            emit_feature_err(&self.session.parse_sess.span_diagnostic,
+                             "self_struct_ctor",
+                             path.span,
+                             GateIssue::Language,
+                             what);
+        }
+
+        debug!("(resolving path) resolved `Self` to {:?}", def);
+        Some(PathResolution::new(def, LastMod(AllPublic), 0))
+    }
+
     /// If `check_ribs` is true, checks the local definitions first; i.e.
     /// doesn't skip straight to the containing module.
     /// Skips `path_depth` trailing segments, which is also reflected in the
@@ -3060,8 +3339,12 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
                         namespace: Namespace,
                         check_ribs: bool)
                         -> Option<PathResolution> {
+        let path_resolutions = &self.session.perf_stats.path_resolutions;
+        path_resolutions.set(path_resolutions.get() + 1);
+
         let span = path.span;
         let segments = &path.segments[..path.segments.len() - path_depth];
+        self.searched_modules.borrow_mut().clear();
 
         let mk_res = |(def, lp)| PathResolution::new(def, lp, path_depth);
 
@@ -3082,6 +3365,9 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
                                   });
         }
 
+        // The unqualified lookup only checks whether the qualification is
+        // needed, it isn't where a qualified path is searched for.
+        self.searched_modules.borrow_mut().clear();
         let def = self.resolve_module_relative_path(span, segments, namespace);
         match (def, unqualified_def) {
             (Some((ref d, _)), Some(ref ud)) if *d == ud.def => {
@@ -3245,16 +3531,7 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
                     match target.bindings.def_for_namespace(namespace) {
                         Some(def) => {
                             // Found it.
-                            let id = import_resolution.id(namespace);
-                            // track imports and extern crates as well
-                            self.used_imports.insert((id, namespace));
-                            self.record_import_use(id, name);
-                            match target.target_module.def_id.get() {
-                                Some(DefId{krate: kid, ..}) => {
-                                    self.used_crates.insert(kid);
-                                }
-                                _ => {}
-                            }
+                            self.record_use(import_resolution.id(namespace), name, namespace);
                             return ImportNameDefinition(def, LastMod(AllPublic));
                         }
                         None => {
@@ -3274,8 +3551,7 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
                                                    .get(&name)
                                                    .cloned() {
                 if let Some(def_id) = module.def_id.get() {
-                    // track used crates
-                    self.used_crates.insert(def_id.krate);
+                    self.record_extern_crate_use(&module);
                     let lp = if module.is_public {
                         LastMod(AllPublic)
                     } else {
@@ -3286,6 +3562,7 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
             }
         }
 
+        self.record_searched_module(&containing_module, name, namespace, false);
         return NoNameDefinition;
     }
 
@@ -3311,16 +3588,19 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
                                        span,
                                        PathSearch) {
             Failed(err) => {
-                let (span, msg) = match err {
-                    Some((span, msg)) => (span, msg),
+                let (span, msg, undeclared) = match err {
+                    Some((span, msg)) => (span, msg, false),
                     None => {
                         let msg = format!("Use of undeclared type or module `{}`",
                                           names_to_string(&module_path));
-                        (span, msg)
+                        (span, msg, true)
                     }
                 };
 
                 resolve_error(self, span, ResolutionError::FailedToResolve(&*msg));
+                if undeclared && module_path.len() == 1 {
+                    self.suggest_imports(span, module_path[0], TypeNS);
+                }
                 return None;
             }
             Indeterminate => panic!("indeterminate unexpected"),
@@ -3342,9 +3622,6 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
                 (def, last_private.or(lp))
             }
         };
-        if let Some(DefId{krate: kid, ..}) = containing_module.def_id.get() {
-            self.used_crates.insert(kid);
-        }
         return Some(def);
     }
 
@@ -3490,9 +3767,13 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
     fn with_no_errors<T, F>(&mut self, f: F) -> T
         where F: FnOnce(&mut Resolver) -> T
     {
+        // The lookups made for suggestions mustn't be mistaken for the ones
+        // of the failure being reported.
+        let searched_modules = replace(&mut *self.searched_modules.borrow_mut(), Vec::new());
         self.emit_errors = false;
         let rs = f(self);
         self.emit_errors = true;
+        *self.searched_modules.borrow_mut() = searched_modules;
         rs
     }
 
@@ -3616,38 +3897,263 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
         NoSuggestion
     }
 
+    /// Walks the module graph, including the modules of external crates,
+    /// and records the shortest path to each item. Items of external crates
+    /// have to be public, and so do the modules on the way to them.
+    fn build_import_candidates(&mut self) -> ImportCandidates {
+        let mut candidates: HashMap<_, Vec<_>> = HashMap::new();
+        let mut trait_paths = HashMap::new();
+        let mut seen_defs = HashSet::new();
+        let mut seen_modules = HashSet::new();
+        let mut worklist = VecDeque::new();
+        worklist.push_back((self.graph_root.get_module(), vec![], false, None));
+
+        // `private_to` is the innermost module the path so far goes through
+        // a private item of. Only the local crate's private items are taken.
+        while let Some((module_, path, in_extern, private_to)) = worklist.pop_front() {
+            build_reduced_graph::populate_module_if_necessary(self, &module_);
+
+            // Sorted, so that the suggestions don't depend on hashmap order.
+            let mut children = module_.children
+                                      .borrow()
+                                      .iter()
+                                      .map(|(&name, bindings)| (name, bindings.clone()))
+                                      .collect::<Vec<_>>();
+            children.sort_by(|a, b| a.0.as_str().cmp(&b.0.as_str()));
+
+            for (name, bindings) in children {
+                let mut child_path = path.clone();
+                child_path.push(name);
+
+                for &namespace in &[TypeNS, ValueNS] {
+                    if !bindings.defined_in_namespace(namespace) ||
+                       (in_extern && !bindings.defined_in_public_namespace(namespace)) {
+                        continue;
+                    }
+                    let def_id = match bindings.def_for_namespace(namespace) {
+                        Some(DefPrimTy(..)) | Some(DefSelfTy(..)) | Some(DefLabel(..)) |
+                        None => continue,
+                        Some(def) => def.def_id(),
+                    };
+                    let private_to = if bindings.defined_in_public_namespace(namespace) {
+                        private_to
+                    } else {
+                        module_.def_id.get()
+                    };
+                    if seen_defs.insert((def_id, namespace, private_to)) {
+                        let candidate = ImportCandidate {
+                            def_id: def_id,
+                            path: child_path.clone(),
+                            private_to: private_to,
+                        };
+                        if let Some(DefTrait(_)) = bindings.def_for_namespace(namespace) {
+                            trait_paths.entry(def_id).or_insert(vec![]).push(candidate.clone());
+                        }
+                        candidates.entry((name, namespace)).or_insert(vec![]).push(candidate);
+                    }
+                }
+
+                // Trait and type modules only hold associated items, which
+                // can't be imported.
+                if let Some(child) = bindings.get_module_if_available() {
+                    let is_searchable = match child.kind.get() {
+                        NormalModuleKind | EnumModuleKind => true,
+                        _ => false,
+                    };
+                    let private_to = if child.is_public {
+                        private_to
+                    } else {
+                        module_.def_id.get()
+                    };
+                    if is_searchable && (!in_extern || child.is_public) &&
+                       child.def_id.get().map_or(true, |did| {
+                           seen_modules.insert((did, private_to))
+                       }) {
+                        worklist.push_back((child, child_path, in_extern, private_to));
+                    }
+                }
+            }
+
+            let mut crates = module_.external_module_children
+                                    .borrow()
+                                    .iter()
+                                    .map(|(&name, module_)| (name, module_.clone()))
+                                    .collect::<Vec<_>>();
+            crates.sort_by(|a, b| a.0.as_str().cmp(&b.0.as_str()));
+            for (name, child) in crates {
+                if child.def_id.get().map_or(true, |did| seen_modules.insert((did, private_to))) {
+                    let mut child_path = path.clone();
+                    child_path.push(name);
+                    worklist.push_back((child, child_path, true, private_to));
+                }
+            }
+        }
+
+        // Every module that was walked has been populated, so the items of
+        // the traits of external crates are in `trait_item_map` by now.
+        let mut traits_by_item: HashMap<_, Vec<_>> = HashMap::new();
+        for &(name, trait_did) in self.trait_item_map.keys() {
+            if let Some(paths) = trait_paths.get(&trait_did) {
+                traits_by_item.entry(name).or_insert(vec![]).extend(paths.iter().cloned());
+            }
+        }
+        for paths in traits_by_item.values_mut() {
+            paths.sort_by(|a, b| {
+                (a.path.len(), names_to_string(&a.path))
+                    .cmp(&(b.path.len(), names_to_string(&b.path)))
+            });
+        }
+
+        ImportCandidates {
+            paths: candidates,
+            traits_by_item: traits_by_item,
+        }
+    }
+
+    /// The paths that an item called `name` in `namespace` can be imported
+    /// with, shortest first.
+    fn import_candidates(&mut self, name: Name, namespace: Namespace) -> Vec<Vec<Name>> {
+        if self.import_candidates.is_none() {
+            self.import_candidates = Some(self.build_import_candidates());
+        }
+        let candidates = self.import_candidates.as_ref().unwrap();
+        match candidates.paths.get(&(name, namespace)) {
+            Some(paths) => self.visible_candidates(paths),
+            None => vec![],
+        }
+    }
+
+    /// The paths that the traits defining an associated item called `name`
+    /// can be imported with, shortest first.
+    fn trait_candidates(&mut self, name: Name) -> Vec<Vec<Name>> {
+        if self.import_candidates.is_none() {
+            self.import_candidates = Some(self.build_import_candidates());
+        }
+        let candidates = self.import_candidates.as_ref().unwrap();
+        match candidates.traits_by_item.get(&name) {
+            Some(paths) => self.visible_candidates(paths),
+            None => vec![],
+        }
+    }
+
+    /// The paths of `candidates` that can be used from the current module,
+    /// keeping only the first one for each item.
+    fn visible_candidates(&self, candidates: &[ImportCandidate]) -> Vec<Vec<Name>> {
+        let mut seen_defs = HashSet::new();
+        candidates.iter()
+                  .filter(|candidate| {
+                      candidate.private_to.map_or(true, |did| self.current_module_is_in(did))
+                  })
+                  .filter(|candidate| seen_defs.insert(candidate.def_id))
+                  .map(|candidate| candidate.path.clone())
+                  .collect()
+    }
+
+    /// Whether the current module is the module `module_did` or inside it.
+    fn current_module_is_in(&self, module_did: DefId) -> bool {
+        let mut module_ = self.current_module.clone();
+        loop {
+            if module_.def_id.get() == Some(module_did) {
+                return true;
+            }
+            module_ = match module_.parent_link.clone() {
+                NoParentLink => return false,
+                ModuleParentLink(parent, _) | BlockParentLink(parent, _) => {
+                    parent.upgrade().unwrap()
+                }
+            };
+        }
+    }
+
+    /// Suggests `use` statements for the unresolved name `name`.
+    fn suggest_imports(&mut self, span: Span, name: Name, namespace: Namespace) {
+        // Don't load every external crate's modules just to drop the result.
+        if !self.emit_errors {
+            return;
+        }
+
+        // Limit the number of suggestions, a name can be common.
+        const MAX_SUGGESTIONS: usize = 4;
+
+        let candidates = self.import_candidates(name, namespace);
+        for path in candidates.iter().take(MAX_SUGGESTIONS) {
+            self.session.fileline_help(span,
+                                       &format!("you can import it with `use {};`",
+                                                names_to_string(path)));
+        }
+        if candidates.len() > MAX_SUGGESTIONS {
+            self.session.fileline_help(span,
+                                       &format!("and {} other candidates",
+                                                candidates.len() - MAX_SUGGESTIONS));
+        }
+    }
+
+    /// Suggests importing the traits that define an associated item called
+    /// `name`, for an unresolved name that is meant to be such an item.
+    fn suggest_trait_imports(&mut self, span: Span, name: Name) {
+        if !self.emit_errors {
+            return;
+        }
+
+        const MAX_SUGGESTIONS: usize = 4;
+
+        let candidates = self.trait_candidates(name);
+        if candidates.is_empty() {
+            return;
+        }
+        let msg = format!("items from traits can only be used through the trait; the following \
+                           {traits_define} an item `{name}`, perhaps you need to import \
+                           {one_of_them}:",
+                          traits_define = if candidates.len() == 1 {
+                              "trait defines"
+                          } else {
+                              "traits define"
+                          },
+                          name = name,
+                          one_of_them = if candidates.len() == 1 { "it" } else { "one of them" });
+        self.session.fileline_help(span, &msg);
+        for (i, path) in candidates.iter().take(MAX_SUGGESTIONS).enumerate() {
+            self.session.fileline_help(span,
+                                       &format!("candidate #{}: `use {};` and write `{}::{}`",
+                                                i + 1,
+                                                names_to_string(path),
+                                                path.last().unwrap(),
+                                                name));
+        }
+        if candidates.len() > MAX_SUGGESTIONS {
+            self.session.fileline_help(span,
+                                       &format!("and {} other candidates",
+                                                candidates.len() - MAX_SUGGESTIONS));
+        }
+    }
+
     fn find_best_match_for_name(&mut self, name: &str) -> Option<String> {
         let mut maybes: Vec<token::InternedString> = Vec::new();
-        let mut values: Vec<usize> = Vec::new();
 
         for rib in self.value_ribs.iter().rev() {
             for (&k, _) in &rib.bindings {
                 maybes.push(k.as_str());
-                values.push(usize::MAX);
             }
         }
 
-        let mut smallest = 0;
-        for (i, other) in maybes.iter().enumerate() {
-            values[i] = lev_distance(name, &other);
+        find_best_match(name, &maybes)
+    }
+
+    /// Like `find_best_match_for_name`, for the labels a `break` or
+    /// `continue` at this point could refer to.
+    fn find_best_match_for_label(&self, name: &str) -> Option<String> {
+        let mut maybes: Vec<token::InternedString> = Vec::new();
 
-            if values[i] <= values[smallest] {
-                smallest = i;
+        for rib in self.label_ribs.iter().rev() {
+            if let NormalRibKind = rib.kind {
+                maybes.extend(rib.bindings.keys().map(|k| k.as_str()));
+            } else {
+                // Labels aren't resolved across function boundaries.
+                break;
             }
         }
 
-        // As a loose rule to avoid obviously incorrect suggestions, clamp the
-        // maximum edit distance we will accept for a suggestion to one third of
-        // the typo'd name's length.
-        let max_distance = std::cmp::max(name.len(), 3) / 3;
-
-        if !values.is_empty() && values[smallest] <= max_distance && name != &maybes[smallest][..] {
-
-            Some(maybes[smallest].to_string())
-
-        } else {
-            None
-        }
+        find_best_match(name, &maybes)
     }
 
     fn resolve_expr(&mut self, expr: &Expr) {
@@ -3776,6 +4282,11 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
                                 resolve_error(self,
                                               expr.span,
                                               ResolutionError::UnresolvedName(&*path_name, &*msg));
+                                if msg.is_empty() && maybe_qself.is_none() &&
+                                   path.segments.len() == 1 {
+                                    self.suggest_imports(expr.span, last_name, ValueNS);
+                                    self.suggest_trait_imports(expr.span, last_name);
+                                }
                             }
                         }
                     }
@@ -3788,7 +4299,11 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
                 // Resolve the path to the structure it goes to. We don't
                 // check to ensure that the path is actually a structure; that
                 // is checked later during typeck.
-                match self.resolve_path(expr.id, path, 0, TypeNS, false) {
+                let resolution = match self.resolve_self_struct(expr.id, path, TypeNS) {
+                    Some(resolution) => Some(resolution),
+                    None => self.resolve_path(expr.id, path, 0, TypeNS, false),
+                };
+                match resolution {
                     Some(definition) => self.record_def(expr.id, definition),
                     None => {
                         debug!("(resolving expression) didn't find struct def",);
@@ -3822,9 +4337,16 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
                 let renamed = mtwt::resolve(label.node);
                 match self.search_label(renamed) {
                     None => {
+                        let label_name = label.node.name.as_str();
                         resolve_error(self,
                                       label.span,
-                                      ResolutionError::UndeclaredLabel(&label.node.name.as_str()))
+                                      ResolutionError::UndeclaredLabel(&label_name));
+                        if self.emit_errors {
+                            if let Some(best) = self.find_best_match_for_label(&label_name) {
+                                self.session.span_help(label.span,
+                                                       &format!("did you mean `{}`?", best));
+                            }
+                        }
                     }
                     Some(DlDef(def @ DefLabel(_))) => {
                         // Since this def is a label, it is never read.
@@ -3912,7 +4434,7 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
             }
 
             // Look for imports.
-            for (_, import) in search_module.import_resolutions.borrow().iter() {
+            for (&import_name, import) in search_module.import_resolutions.borrow().iter() {
                 let target = match import.target_for_namespace(TypeNS) {
                     None => continue,
                     Some(target) => target,
@@ -3923,13 +4445,9 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
                 };
                 if self.trait_item_map.contains_key(&(name, did)) {
                     add_trait_info(&mut found_traits, did, name);
-                    let id = import.type_id;
-                    self.used_imports.insert((id, TypeNS));
-                    let trait_name = self.get_trait_name(did);
-                    self.record_import_use(id, trait_name);
-                    if let Some(DefId{krate: kid, ..}) = target.target_module.def_id.get() {
-                        self.used_crates.insert(kid);
-                    }
+                    // The name the trait is imported as here, which is the
+                    // one `glob_sources` knows it by if it was renamed.
+                    self.record_use(import.type_id, import_name, TypeNS);
                 }
             }
 
@@ -3975,6 +4493,137 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
         }
     }
 
+    /// Builds the map from every definition imported into this crate to the
+    /// `use` directives importing it, for tools that need to find all the
+    /// imports of an item. Only meaningful once imports are resolved.
+    pub fn import_map(&self) -> ImportMap {
+        let mut import_map = DefIdMap();
+        self.add_imports_for_module_subtree(&mut import_map, &self.graph_root.get_module());
+        for ids in import_map.values_mut() {
+            ids.sort();
+            ids.dedup();
+        }
+        import_map
+    }
+
+    fn add_imports_for_module_subtree(&self, import_map: &mut ImportMap, module_: &Rc<Module>) {
+        // Modules of other crates don't contain any `use` directive of ours.
+        match module_.def_id.get() {
+            Some(def_id) if !def_id.is_local() => return,
+            _ => {}
+        }
+
+        for (_, import_resolution) in module_.import_resolutions.borrow().iter() {
+            for &ns in &[TypeNS, ValueNS] {
+                let target = match import_resolution.target_for_namespace(ns) {
+                    Some(target) => target,
+                    None => continue,
+                };
+                let def_id = match target.bindings.def_for_namespace(ns) {
+                    Some(DefPrimTy(..)) | Some(DefSelfTy(..)) | Some(DefLabel(..)) | None => {
+                        continue
+                    }
+                    Some(def) => def.def_id(),
+                };
+                import_map.entry(def_id).or_insert(vec![]).push(import_resolution.id(ns));
+            }
+        }
+
+        for (_, child_name_bindings) in module_.children.borrow().iter() {
+            if let Some(child_module) = child_name_bindings.get_module_if_available() {
+                self.add_imports_for_module_subtree(import_map, &child_module);
+            }
+        }
+
+        for (_, child_module) in module_.anonymous_children.borrow().iter() {
+            self.add_imports_for_module_subtree(import_map, child_module);
+        }
+    }
+
+    /// Builds the paths that the items of other crates are documented at,
+    /// for rustdoc, which needs them to link to the items. Documentation is
+    /// generated where an item is defined if all the modules on the way are
+    /// public, and where it is re-exported otherwise, so only the items that
+    /// aren't publicly reachable where they are defined get a path, the
+    /// shortest one. It goes through the public re-exports of the crates
+    /// this crate depends on directly.
+    pub fn visible_paths(&mut self) -> VisiblePaths {
+        let mut paths = DefIdMap();
+        let mut defined_here = DefIdSet();
+        let mut seen_modules = HashSet::new();
+        let mut worklist = VecDeque::new();
+        worklist.push_back((self.graph_root.get_module(), vec![], false, false));
+
+        // `in_extern` is set once the walk has entered another crate, and
+        // `canonical` while it only took the paths the modules are defined at.
+        while let Some((module_, path, in_extern, canonical)) = worklist.pop_front() {
+            build_reduced_graph::populate_module_if_necessary(self, &module_);
+
+            // Sorted, so that the paths don't depend on hashmap order.
+            let mut children = module_.children
+                                      .borrow()
+                                      .iter()
+                                      .map(|(&name, bindings)| (name, bindings.clone()))
+                                      .collect::<Vec<_>>();
+            children.sort_by(|a, b| a.0.as_str().cmp(&b.0.as_str()));
+
+            for (name, bindings) in children {
+                let mut child_path = path.clone();
+                child_path.push(name);
+                let mut child_canonical = false;
+
+                for &namespace in &[TypeNS, ValueNS] {
+                    if !in_extern || !bindings.defined_in_public_namespace(namespace) {
+                        continue;
+                    }
+                    let def_id = match bindings.def_for_namespace(namespace) {
+                        Some(DefPrimTy(..)) | Some(DefSelfTy(..)) | Some(DefLabel(..)) |
+                        None => continue,
+                        Some(def) => def.def_id(),
+                    };
+                    if canonical && csearch::get_def_parent(&self.session.cstore, def_id) ==
+                                    module_.def_id.get() {
+                        defined_here.insert(def_id);
+                        child_canonical = true;
+                    }
+                    if !paths.contains_key(&def_id) {
+                        paths.insert(def_id, child_path.clone());
+                    }
+                }
+
+                // Associated items are documented with their trait or type.
+                if let Some(child) = bindings.get_module_if_available() {
+                    let is_searchable = match child.kind.get() {
+                        NormalModuleKind | EnumModuleKind => true,
+                        _ => false,
+                    };
+                    if is_searchable && (!in_extern || child.is_public) &&
+                       child.def_id.get().map_or(true, |did| {
+                           seen_modules.insert((did, child_canonical))
+                       }) {
+                        worklist.push_back((child, child_path, in_extern, child_canonical));
+                    }
+                }
+            }
+
+            let crates = module_.external_module_children
+                                .borrow()
+                                .values()
+                                .cloned()
+                                .collect::<Vec<_>>();
+            for child in crates {
+                let did = child.def_id.get().unwrap();
+                if in_extern || !seen_modules.insert((did, true)) {
+                    continue;
+                }
+                let name = self.session.cstore.get_crate_data(did.krate).name();
+                worklist.push_back((child, vec![token::intern(&name)], true, true));
+            }
+        }
+
+        paths.into_iter().filter(|&(ref def_id, _)| !defined_here.contains(def_id)).collect()
+    }
+
     //
     // Diagnostics
     //
@@ -4037,6 +4686,40 @@ fn names_to_string(names: &[Name]) -> String {
     result
 }
 
+/// Sorts `entries` by the string of their name, which, unlike the order of
+/// a hashmap keyed by names, doesn't change from one run to the next.
+fn sort_by_name<T>(entries: &mut [(Name, T)]) {
+    entries.sort_by(|a, b| a.0.as_str().cmp(&b.0.as_str()));
+}
+
+/// The name among `maybes` closest to `name`, if it is close enough to be
+/// suggested in its stead.
+fn find_best_match(name: &str, maybes: &[token::InternedString]) -> Option<String> {
+    let mut values: Vec<usize> = Vec::new();
+
+    let mut smallest = 0;
+    for (i, other) in maybes.iter().enumerate() {
+        values.push(lev_distance(name, &other));
+
+        if values[i] <= values[smallest] {
+            smallest = i;
+        }
+    }
+
+    // As a loose rule to avoid obviously incorrect suggestions, clamp the
+    // maximum edit distance we will accept for a suggestion to one third of
+    // the typo'd name's length.
+    let max_distance = std::cmp::max(name.len(), 3) / 3;
+
+    if !values.is_empty() && values[smallest] <= max_distance && name != &maybes[smallest][..] {
+
+        Some(maybes[smallest].to_string())
+
+    } else {
+        None
+    }
+}
+
 fn path_names_to_string(path: &Path, depth: usize) -> String {
     let names: Vec<ast::Name> = path.segments[..path.segments.len() - depth]
                                     .iter()
@@ -4071,6 +4754,17 @@ fn module_to_string(module: &Module) -> String {
     names_to_string(&names.into_iter().rev().collect::<Vec<ast::Name>>())
 }
 
+/// How a searched module is named in the notes of
+/// `-Z verbose-resolve-failures`.
+fn searched_module_to_string(module: &Module) -> String {
+    match module.parent_link {
+        NoParentLink => "the crate root".to_string(),
+        ModuleParentLink(..) => format!("`{}`", module_to_string(module)),
+        BlockParentLink(ref parent, _) => {
+            format!("a block in {}", searched_module_to_string(&*parent.upgrade().unwrap()))
+        }
+    }
+}
 
 pub struct CrateMap {
     pub def_map: RefCell<DefMap>,
@@ -4078,19 +4772,32 @@ pub struct CrateMap {
     pub export_map: ExportMap,
     pub trait_map: TraitMap,
     pub external_exports: ExternalExports,
+    pub import_sources: ImportSources,
     pub glob_map: Option<GlobMap>,
+    pub import_map: Option<ImportMap>,
+    pub visible_paths: Option<VisiblePaths>,
 }
 
+/// Whether to build the maps only tools need: the glob map and the import map.
 #[derive(PartialEq,Copy, Clone)]
 pub enum MakeGlobMap {
     Yes,
     No,
 }
 
+/// Whether to build the visible paths of the items of other crates, which
+/// only rustdoc needs.
+#[derive(PartialEq,Copy, Clone)]
+pub enum MakeVisiblePaths {
+    Yes,
+    No,
+}
+
 /// Entry point to crate resolution.
 pub fn resolve_crate<'a, 'tcx>(session: &'a Session,
                                ast_map: &'a hir_map::Map<'tcx>,
-                               make_glob_map: MakeGlobMap)
+                               make_glob_map: MakeGlobMap,
+                               make_visible_paths: MakeVisiblePaths)
                                -> CrateMap {
     let krate = ast_map.krate();
     let mut resolver = create_resolver(session, ast_map, krate, make_glob_map, None);
@@ -4100,17 +4807,31 @@ pub fn resolve_crate<'a, 'tcx>(session: &'a Session,
 
     check_unused::check_crate(&mut resolver, krate);
 
+    let import_map = if resolver.make_glob_map {
+        Some(resolver.import_map())
+    } else {
+        None
+    };
+    let visible_paths = if make_visible_paths == MakeVisiblePaths::Yes {
+        Some(resolver.visible_paths())
+    } else {
+        None
+    };
+
     CrateMap {
         def_map: resolver.def_map,
         freevars: resolver.freevars,
         export_map: resolver.export_map,
         trait_map: resolver.trait_map,
         external_exports: resolver.external_exports,
+        import_sources: resolver.import_sources,
         glob_map: if resolver.make_glob_map {
             Some(resolver.glob_map)
         } else {
             None
         },
+        import_map: import_map,
+        visible_paths: visible_paths,
     }
 }
 
@@ -4132,8 +4853,8 @@ pub fn create_resolver<'a, 'tcx>(session: &'a Session,
 
     resolver.callback = callback;
 
+    // Duplicate definitions don't stop resolution, the first one is used.
     build_reduced_graph::build_reduced_graph(&mut resolver, krate);
-    session.abort_if_errors();
 
     resolve_imports::resolve_imports(&mut resolver);
     session.abort_if_errors();
@@ -18,22 +18,28 @@ use NameBindings;
 use NamespaceResult::{BoundResult, UnboundResult, UnknownResult};
 use NamespaceResult;
 use NameSearchType;
+use ParentLink::NoParentLink;
 use ResolveResult;
 use Resolver;
 use UseLexicalScopeFlag;
 use {names_to_string, module_to_string};
-use {resolve_error, ResolutionError};
+use {resolve_error, sort_by_name, ResolutionError};
 
 use build_reduced_graph;
 
+use rustc::front::map as hir_map;
+use rustc::lint;
+use rustc::metadata::loader;
 use rustc::middle::def::*;
-use rustc::middle::def_id::DefId;
 use rustc::middle::privacy::*;
+use rustc::util::nodemap::NodeSet;
 
+use rustc_front::hir;
 use syntax::ast::{NodeId, Name};
 use syntax::attr::AttrMetaMethods;
-use syntax::codemap::Span;
+use syntax::codemap::{MultiSpan, Span, DUMMY_SP};
 
+use std::iter::repeat;
 use std::mem::replace;
 use std::rc::Rc;
 
@@ -185,6 +191,23 @@ struct ImportResolvingError {
     span: Span,
     path: String,
     help: String,
+    note: Option<String>,
+    // A crate that could be declared with `extern crate` to fix the import.
+    extern_crate: Option<Name>,
+    // The shortest prefix of the module path of the import that fails to
+    // resolve, with `-Z group-import-errors`.
+    missing_module: Option<String>,
+}
+
+/// A single import that is left unresolved when import resolution stops
+/// making progress.
+struct PendingImport {
+    module: Rc<Module>,
+    span: Span,
+    id: NodeId,
+    module_path: Vec<Name>,
+    target: Name,
+    source: Name,
 }
 
 struct ImportResolver<'a, 'b: 'a, 'tcx: 'b> {
@@ -211,7 +234,7 @@ impl<'a, 'b:'a, 'tcx:'b> ImportResolver<'a, 'b, 'tcx> {
                    self.resolver.unresolved_imports);
 
             let module_root = self.resolver.graph_root.get_module();
-            let errors = self.resolve_imports_for_module_subtree(module_root.clone());
+            let mut errors = self.resolve_imports_for_module_subtree(module_root.clone());
 
             if self.resolver.unresolved_imports == 0 {
                 debug!("(resolving imports) success");
@@ -221,17 +244,27 @@ impl<'a, 'b:'a, 'tcx:'b> ImportResolver<'a, 'b, 'tcx> {
             if self.resolver.unresolved_imports == prev_unresolved_imports {
                 // resolving failed
                 if errors.len() > 0 {
-                    for e in errors {
-                        resolve_error(self.resolver,
-                                      e.span,
-                                      ResolutionError::UnresolvedImport(Some((&e.path, &e.help))));
+                    // Report the errors in the order of the source. Byte
+                    // positions are global to the codemap, so this sorts
+                    // them by file first.
+                    errors.sort_by(|a, b| {
+                        (a.span.lo.0, a.span.hi.0).cmp(&(b.span.lo.0, b.span.hi.0))
+                    });
+                    if self.resolver.session.opts.debugging_opts.group_import_errors {
+                        self.report_grouped_import_errors(errors);
+                    } else {
+                        for e in &errors {
+                            self.report_import_error(e);
+                        }
                     }
                 } else {
                     // Report unresolved imports only if no hard error was already reported
                     // to avoid generating multiple errors on the same import.
                     // Imports that are still indeterminate at this point are actually blocked
                     // by errored imports, so there is no point reporting them.
-                    self.resolver.report_unresolved_imports(module_root);
+                    // Those blocked by each other are reported as a cycle instead.
+                    let in_cycle = self.report_import_cycles(module_root.clone());
+                    self.resolver.report_unresolved_imports(module_root, &in_cycle);
                 }
                 break;
             }
@@ -241,6 +274,169 @@ impl<'a, 'b:'a, 'tcx:'b> ImportResolver<'a, 'b, 'tcx> {
         }
     }
 
+    fn report_import_error(&self, e: &ImportResolvingError) {
+        resolve_error(self.resolver,
+                      e.span,
+                      ResolutionError::UnresolvedImport(Some((&e.path, &e.help))));
+        if let Some(ref note) = e.note {
+            self.resolver.session.fileline_note(e.span, note);
+        }
+        if let Some(name) = e.extern_crate {
+            self.resolver.session.fileline_help(e.span,
+                                                &format!("add `extern crate {};`", name));
+        }
+    }
+
+    /// Reports the imports that fail because of the same missing module as
+    /// one error, at the first of them, which lists the others. The groups
+    /// are reported in the order of their first import.
+    fn report_grouped_import_errors(&self, errors: Vec<ImportResolvingError>) {
+        let mut groups: Vec<Vec<ImportResolvingError>> = Vec::new();
+        for e in errors {
+            let index = match e.missing_module {
+                Some(ref missing) => {
+                    groups.iter().position(|group| {
+                        group[0].missing_module.as_ref() == Some(missing)
+                    })
+                }
+                None => None,
+            };
+            match index {
+                Some(index) => groups[index].push(e),
+                None => groups.push(vec![e]),
+            }
+        }
+
+        for group in groups {
+            if group.len() == 1 {
+                self.report_import_error(&group[0]);
+                continue;
+            }
+
+            let first = &group[0];
+            let mut msp = MultiSpan::new(first.span);
+            for e in &group[1..] {
+                msp.push_label(e.span, format!("`{}` is imported here", e.path));
+            }
+            let missing = first.missing_module.as_ref().unwrap();
+            resolve_error(self.resolver,
+                          msp,
+                          ResolutionError::UnresolvedImportPath(missing, group.len(), &first.help));
+            if let Some(name) = first.extern_crate {
+                self.resolver.session.fileline_help(first.span,
+                                                    &format!("add `extern crate {};`", name));
+            }
+        }
+    }
+
+    /// Reports the cycles among the single imports left unresolved, e.g.
+    /// `pub use b::X;` in `a` and `pub use a::X;` in `b`, and returns the ids
+    /// of the imports in them. Each of these imports waits for the next one
+    /// in its cycle, so none of them can ever be resolved.
+    fn report_import_cycles(&mut self, module_root: Rc<Module>) -> NodeSet {
+        let mut pending = Vec::new();
+        self.collect_pending_imports(module_root, &mut pending);
+
+        // The import each pending import waits for: the one of its source
+        // name in the module its path leads to.
+        let mut next = Vec::with_capacity(pending.len());
+        for import in &pending {
+            let containing_module =
+                match self.resolver.resolve_module_path(import.module.clone(),
+                                                        &import.module_path[..],
+                                                        UseLexicalScopeFlag::DontUseLexicalScope,
+                                                        import.span,
+                                                        NameSearchType::ImportSearch) {
+                    ResolveResult::Success((module, _)) => module,
+                    _ => {
+                        next.push(None);
+                        continue;
+                    }
+                };
+            next.push(pending.iter().position(|other| {
+                other.target == import.source &&
+                &*other.module as *const Module == &*containing_module as *const Module
+            }));
+        }
+
+        // Every import waits for at most one other, so following them from
+        // any import either comes to an end or runs into a cycle.
+        let mut in_cycle = NodeSet();
+        let mut visited = vec![false; pending.len()];
+        for start in 0..pending.len() {
+            let mut path = Vec::new();
+            let mut current = Some(start);
+            while let Some(index) = current {
+                if visited[index] {
+                    if let Some(pos) = path.iter().position(|&i| i == index) {
+                        self.report_import_cycle(&pending, &path[pos..]);
+                        in_cycle.extend(path[pos..].iter().map(|&i| pending[i].id));
+                    }
+                    break;
+                }
+                visited[index] = true;
+                path.push(index);
+                current = next[index];
+            }
+        }
+        in_cycle
+    }
+
+    /// Reports the imports `cycle` of `pending` at the first of them in the
+    /// source, with a label at each of the others.
+    fn report_import_cycle(&self, pending: &[PendingImport], cycle: &[usize]) {
+        let mut first = 0;
+        for (pos, &index) in cycle.iter().enumerate() {
+            if pending[index].span.lo < pending[cycle[first]].span.lo {
+                first = pos;
+            }
+        }
+        let cycle = cycle[first..].iter().chain(&cycle[..first]).map(|&i| &pending[i]);
+        let cycle = cycle.collect::<Vec<_>>();
+
+        let path = |import: &PendingImport| {
+            format!("`{}::{}`", names_to_string(&import.module_path[..]), import.source)
+        };
+        let mut paths = cycle.iter().map(|&import| path(import)).collect::<Vec<_>>();
+        paths.push(path(cycle[0]));
+
+        let mut msp = MultiSpan::new(cycle[0].span);
+        for &import in &cycle[1..] {
+            msp.push_label(import.span, format!("{} is imported here", path(import)));
+        }
+        resolve_error(self.resolver,
+                      msp,
+                      ResolutionError::ImportCycle(&paths.join(" -> ")));
+    }
+
+    /// Collects the single imports left unresolved in the given module and
+    /// all of its submodules.
+    fn collect_pending_imports(&mut self, module_: Rc<Module>, pending: &mut Vec<PendingImport>) {
+        {
+            let imports = module_.imports.borrow();
+            for directive in &imports[module_.resolved_import_count.get()..] {
+                match directive.subclass {
+                    SingleImport(target, source) if !directive.module_path.is_empty() => {
+                        pending.push(PendingImport {
+                            module: module_.clone(),
+                            span: directive.span,
+                            id: directive.id,
+                            module_path: directive.module_path.clone(),
+                            target: target,
+                            source: source,
+                        });
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        build_reduced_graph::populate_module_if_necessary(self.resolver, &module_);
+        for child_module in module_.submodules() {
+            self.collect_pending_imports(child_module, pending);
+        }
+    }
+
     /// Attempts to resolve imports for the given module and all of its
     /// submodules.
     fn resolve_imports_for_module_subtree(&mut self,
@@ -254,19 +450,8 @@ impl<'a, 'b:'a, 'tcx:'b> ImportResolver<'a, 'b, 'tcx> {
         self.resolver.current_module = orig_module;
 
         build_reduced_graph::populate_module_if_necessary(self.resolver, &module_);
-        for (_, child_node) in module_.children.borrow().iter() {
-            match child_node.get_module_if_available() {
-                None => {
-                    // Nothing to do.
-                }
-                Some(child_module) => {
-                    errors.extend(self.resolve_imports_for_module_subtree(child_module));
-                }
-            }
-        }
-
-        for (_, child_module) in module_.anonymous_children.borrow().iter() {
-            errors.extend(self.resolve_imports_for_module_subtree(child_module.clone()));
+        for child_module in module_.submodules() {
+            errors.extend(self.resolve_imports_for_module_subtree(child_module));
         }
 
         errors
@@ -282,6 +467,7 @@ impl<'a, 'b:'a, 'tcx:'b> ImportResolver<'a, 'b, 'tcx> {
             return errors;
         }
 
+        let group_errors = self.resolver.session.opts.debugging_opts.group_import_errors;
         let mut imports = module.imports.borrow_mut();
         let import_count = imports.len();
         let mut indeterminate_imports = Vec::new();
@@ -290,15 +476,35 @@ impl<'a, 'b:'a, 'tcx:'b> ImportResolver<'a, 'b, 'tcx> {
             match self.resolve_import_for_module(module.clone(), &imports[import_index]) {
                 ResolveResult::Failed(err) => {
                     let import_directive = &imports[import_index];
+                    let note = self.suggest_import_path(module.clone(), import_directive)
+                                   .or_else(|| self.suggest_crate_path(import_directive));
+                    let extern_crate = if note.is_none() {
+                        self.suggest_extern_crate(import_directive)
+                    } else {
+                        None
+                    };
                     let (span, help) = match err {
+                        // The suggestions replace the guess of the resolver,
+                        // e.g. a missing `extern crate`.
+                        Some((span, _)) if note.is_some() || extern_crate.is_some() => {
+                            (span, String::new())
+                        }
                         Some((span, msg)) => (span, format!(". {}", msg)),
                         None => (import_directive.span, String::new()),
                     };
+                    let missing_module = if group_errors {
+                        self.missing_module_prefix(module.clone(), import_directive)
+                    } else {
+                        None
+                    };
                     errors.push(ImportResolvingError {
                         span: span,
                         path: import_path_to_string(&import_directive.module_path,
                                                     import_directive.subclass),
                         help: help,
+                        note: note,
+                        extern_crate: extern_crate,
+                        missing_module: missing_module,
                     });
                 }
                 ResolveResult::Indeterminate => {}
@@ -319,6 +525,157 @@ impl<'a, 'b:'a, 'tcx:'b> ImportResolver<'a, 'b, 'tcx> {
         errors
     }
 
+    /// The shortest prefix of the module path of a failed import that
+    /// doesn't resolve to a module, if the import failed because of its
+    /// module path rather than because of the name it imports.
+    fn missing_module_prefix(&mut self,
+                             module_: Rc<Module>,
+                             import_directive: &ImportDirective)
+                             -> Option<String> {
+        let module_path = &import_directive.module_path;
+        for len in 1..module_path.len() + 1 {
+            match self.resolver.resolve_module_path(module_.clone(),
+                                                    &module_path[..len],
+                                                    UseLexicalScopeFlag::DontUseLexicalScope,
+                                                    import_directive.span,
+                                                    NameSearchType::ImportSearch) {
+                ResolveResult::Failed(_) => return Some(names_to_string(&module_path[..len])),
+                ResolveResult::Indeterminate => return None,
+                ResolveResult::Success(_) => {}
+            }
+        }
+        None
+    }
+
+    /// The first name of the path of an import which is relative to the
+    /// crate root, if it isn't in scope there, and whether it has to be a
+    /// module.
+    fn unresolved_root_name(&self, import_directive: &ImportDirective) -> Option<(Name, bool)> {
+        // The first segment has to name a module, unless it is the only one.
+        let (head, needs_module) = match (import_directive.module_path.first(),
+                                          import_directive.subclass) {
+            (Some(&head), _) => (head, true),
+            (None, SingleImport(_, source)) => (source, false),
+            (None, GlobImport) => return None,
+        };
+        match &*head.as_str() {
+            "self" | "super" => return None,
+            _ => {}
+        }
+
+        let root = self.resolver.graph_root.get_module();
+        if root.children.borrow().contains_key(&head) ||
+           root.external_module_children.borrow().contains_key(&head) ||
+           root.import_resolutions.borrow().contains_key(&head) {
+            return None;
+        }
+        Some((head, needs_module))
+    }
+
+    /// Suggests another path for a failed import whose path is relative to the
+    /// crate root, when its first segment names an item of the module of the
+    /// import, or of one of the modules containing it, but not of the crate
+    /// root. The path is then prefixed with `self::` or enough `super::`s.
+    fn suggest_import_path(&mut self,
+                           module_: Rc<Module>,
+                           import_directive: &ImportDirective)
+                           -> Option<String> {
+        let (head, needs_module) = match self.unresolved_root_name(import_directive) {
+            Some(name) => name,
+            None => return None,
+        };
+
+        // `self` and `super` skip blocks, traits and the like.
+        let mut search_module = self.resolver.get_nearest_normal_module_parent_or_self(module_);
+        let mut supers = 0;
+        loop {
+            if let NoParentLink = search_module.parent_link {
+                return None;
+            }
+            let found = match search_module.children.borrow().get(&head) {
+                Some(bindings) => !needs_module || bindings.get_module_if_available().is_some(),
+                None => false,
+            };
+            if found {
+                break;
+            }
+            search_module = match self.resolver.get_nearest_normal_module_parent(search_module) {
+                Some(parent) => parent,
+                None => return None,
+            };
+            supers += 1;
+        }
+
+        let prefix = if supers == 0 {
+            "self::".to_string()
+        } else {
+            repeat("super::").take(supers).collect::<String>()
+        };
+        Some(format!("did you mean `use {}{};`?",
+                     prefix,
+                     import_path_to_string(&import_directive.module_path,
+                                           import_directive.subclass)))
+    }
+
+    /// Suggests starting a failed import at the crate root, when its path
+    /// starts with `super::`s and the rest of it starts with an item of the
+    /// crate root, which is easier than counting the `super`s right. The
+    /// path is prefixed with `crate::` if that is enabled.
+    fn suggest_crate_path(&self, import_directive: &ImportDirective) -> Option<String> {
+        let module_path = &import_directive.module_path;
+        if module_path.first().map_or(true, |name| name.as_str() != "super") {
+            return None;
+        }
+        let supers = module_path.iter().take_while(|name| name.as_str() == "super").count();
+        let rest = &module_path[supers..];
+
+        // The first segment has to name a module, unless it is the only one.
+        let (head, needs_module) = match (rest.first(), import_directive.subclass) {
+            (Some(&head), _) => (head, true),
+            (None, SingleImport(_, source)) => (source, false),
+            (None, GlobImport) => return None,
+        };
+        let root = self.resolver.graph_root.get_module();
+        let found = match root.children.borrow().get(&head) {
+            Some(bindings) => !needs_module || bindings.get_module_if_available().is_some(),
+            None => root.external_module_children.borrow().contains_key(&head),
+        };
+        if !found {
+            return None;
+        }
+
+        let prefix = if self.resolver.session.features.borrow().crate_in_paths {
+            "crate::"
+        } else {
+            ""
+        };
+        Some(format!("did you mean `use {}{};`?",
+                     prefix,
+                     import_path_to_string(rest, import_directive.subclass)))
+    }
+
+    /// Suggests declaring the crate a failed import starts with, when the
+    /// crate isn't declared but could be: it is passed with `--extern`, it is
+    /// already loaded as a dependency of another crate, or there is a library
+    /// named after it in the library search paths or the sysroot.
+    fn suggest_extern_crate(&self, import_directive: &ImportDirective) -> Option<Name> {
+        let name = match self.unresolved_root_name(import_directive) {
+            Some((name, _)) => name,
+            None => return None,
+        };
+
+        let session = self.resolver.session;
+        let name_str = name.as_str();
+        let mut loaded = false;
+        session.cstore.iter_crate_data(|_, data| loaded = loaded || data.name() == &*name_str);
+        if loaded || session.opts.externs.contains_key(&*name_str) ||
+           loader::library_exists(session, &name_str) {
+            Some(name)
+        } else {
+            None
+        }
+    }
+
     /// Attempts to resolve the given import. The return value indicates
     /// failure if we're certain the name does not exist, indeterminate if we
     /// don't know whether the name exists at the moment due to other
@@ -328,6 +685,9 @@ impl<'a, 'b:'a, 'tcx:'b> ImportResolver<'a, 'b, 'tcx> {
                                  module_: Rc<Module>,
                                  import_directive: &ImportDirective)
                                  -> ResolveResult<()> {
+        let import_resolutions = &self.resolver.session.perf_stats.import_resolutions;
+        import_resolutions.set(import_resolutions.get() + 1);
+
         let mut resolution_result = ResolveResult::Failed(None);
         let module_path = &import_directive.module_path;
 
@@ -417,6 +777,16 @@ impl<'a, 'b:'a, 'tcx:'b> ImportResolver<'a, 'b, 'tcx> {
         return resolution_result;
     }
 
+    /// Points at where `name`, which couldn't be imported, is defined.
+    fn note_definition(&self, bindings: &NameBindings, namespace: Namespace, name: Name) {
+        match bindings.span_for_namespace(namespace) {
+            Some(span) if span != DUMMY_SP => {
+                self.resolver.session.span_note(span, &format!("`{}` is defined here", name));
+            }
+            _ => {}
+        }
+    }
+
     fn resolve_single_import(&mut self,
                              module_: &Module,
                              target_module: Rc<Module>,
@@ -470,6 +840,7 @@ impl<'a, 'b:'a, 'tcx:'b> ImportResolver<'a, 'b, 'tcx> {
                                                source);
                         span_err!(self.resolver.session, directive.span, E0364, "{}", &msg);
                         self.resolver.session.span_note(directive.span, &note_msg);
+                        self.note_definition(&child_name_bindings, ValueNS, source);
                         pub_err = true;
                     }
                 }
@@ -483,6 +854,7 @@ impl<'a, 'b:'a, 'tcx:'b> ImportResolver<'a, 'b, 'tcx> {
                                                source);
                         span_err!(self.resolver.session, directive.span, E0365, "{}", &msg);
                         self.resolver.session.span_note(directive.span, &note_msg);
+                        self.note_definition(&child_name_bindings, TypeNS, source);
                     }
                 }
             }
@@ -525,7 +897,8 @@ impl<'a, 'b:'a, 'tcx:'b> ImportResolver<'a, 'b, 'tcx> {
                         fn get_binding(this: &mut Resolver,
                                        import_resolution: &ImportResolution,
                                        namespace: Namespace,
-                                       source: Name)
+                                       source: Name,
+                                       directive_id: NodeId)
                                        -> NamespaceResult {
 
                             // Import resolutions must be declared with "pub"
@@ -546,15 +919,9 @@ impl<'a, 'b:'a, 'tcx:'b> ImportResolver<'a, 'b, 'tcx> {
                                     debug!("(resolving single import) found import in ns {:?}",
                                            namespace);
                                     let id = import_resolution.id(namespace);
-                                    // track used imports and extern crates as well
-                                    this.used_imports.insert((id, namespace));
-                                    this.record_import_use(id, source);
-                                    match target_module.def_id.get() {
-                                        Some(DefId{krate: kid, ..}) => {
-                                            this.used_crates.insert(kid);
-                                        }
-                                        _ => {}
-                                    }
+                                    this.record_use(id, source, namespace);
+                                    // Remember the re-export the import goes through.
+                                    this.import_sources.entry(directive_id).or_insert(id);
                                     return BoundResult(target_module, bindings);
                                 }
                             }
@@ -566,14 +933,16 @@ impl<'a, 'b:'a, 'tcx:'b> ImportResolver<'a, 'b, 'tcx> {
                             value_result = get_binding(self.resolver,
                                                        import_resolution,
                                                        ValueNS,
-                                                       source);
+                                                       source,
+                                                       directive.id);
                             value_used_reexport = import_resolution.is_public;
                         }
                         if type_result.is_unknown() {
                             type_result = get_binding(self.resolver,
                                                       import_resolution,
                                                       TypeNS,
-                                                      source);
+                                                      source,
+                                                      directive.id);
                             type_used_reexport = import_resolution.is_public;
                         }
 
@@ -623,13 +992,8 @@ impl<'a, 'b:'a, 'tcx:'b> ImportResolver<'a, 'b, 'tcx> {
                     None => {} // Continue.
                     Some(module) => {
                         debug!("(resolving single import) found external module");
-                        // track the module as used.
-                        match module.def_id.get() {
-                            Some(DefId{krate: kid, ..}) => {
-                                self.resolver.used_crates.insert(kid);
-                            }
-                            _ => {}
-                        }
+                        // track the `extern crate` as used.
+                        self.resolver.record_extern_crate_use(&module);
                         let name_bindings =
                             Rc::new(Resolver::create_name_bindings_from_module(module));
                         type_result = BoundResult(target_module.clone(), name_bindings);
@@ -643,6 +1007,12 @@ impl<'a, 'b:'a, 'tcx:'b> ImportResolver<'a, 'b, 'tcx> {
         let mut import_resolutions = module_.import_resolutions.borrow_mut();
         let import_resolution = import_resolutions.get_mut(&target).unwrap();
 
+        self.lint_prelude_shadowing(import_resolution,
+                                    directive,
+                                    target,
+                                    &value_result,
+                                    &type_result);
+
         {
             let mut check_and_write_import = |namespace, result: &_, used_public: &mut bool| {
                 let namespace_name = match namespace {
@@ -657,8 +1027,10 @@ impl<'a, 'b:'a, 'tcx:'b> ImportResolver<'a, 'b, 'tcx> {
                                name_bindings.def_for_namespace(namespace));
                         self.check_for_conflicting_import(&import_resolution,
                                                           directive.span,
+                                                          directive.id,
                                                           target,
-                                                          namespace);
+                                                          namespace,
+                                                          name_bindings);
 
                         self.check_that_import_is_importable(&**name_bindings,
                                                              directive.span,
@@ -684,9 +1056,11 @@ impl<'a, 'b:'a, 'tcx:'b> ImportResolver<'a, 'b, 'tcx> {
             check_and_write_import(TypeNS, &type_result, &mut type_used_public);
         }
 
+        self.check_for_glob_and_explicit_import_collision(import_resolution, directive, target);
+
         self.check_for_conflicts_between_imports_and_items(module_,
                                                            import_resolution,
-                                                           directive.span,
+                                                           directive,
                                                            target);
 
         if value_result.is_unbound() && type_result.is_unbound() {
@@ -788,7 +1162,15 @@ impl<'a, 'b:'a, 'tcx:'b> ImportResolver<'a, 'b, 'tcx> {
                                                "Cannot glob-import a module into itself.".into())));
         }
 
-        for (name, target_import_resolution) in import_resolutions.iter() {
+        // Merge the names in a fixed order, so that conflicts are reported
+        // in the same order every time.
+        let mut target_resolutions = import_resolutions.iter()
+                                                       .map(|(&name, resolution)| {
+                                                           (name, resolution)
+                                                       })
+                                                       .collect::<Vec<_>>();
+        sort_by_name(&mut target_resolutions);
+        for &(ref name, target_import_resolution) in &target_resolutions {
             debug!("(resolving glob import) writing module resolution {} into `{}`",
                    *name,
                    module_to_string(module_));
@@ -798,6 +1180,15 @@ impl<'a, 'b:'a, 'tcx:'b> ImportResolver<'a, 'b, 'tcx> {
                 continue;
             }
 
+            // Uses of the name through this glob import are uses of the
+            // re-export it comes from as well.
+            for &namespace in &[ValueNS, TypeNS] {
+                if target_import_resolution.target_for_namespace(namespace).is_some() {
+                    let source = target_import_resolution.id(namespace);
+                    self.resolver.glob_sources.insert((id, *name, namespace), source);
+                }
+            }
+
             // Here we merge two import resolutions.
             let mut import_resolutions = module_.import_resolutions.borrow_mut();
             match import_resolutions.get_mut(name) {
@@ -812,9 +1203,13 @@ impl<'a, 'b:'a, 'tcx:'b> ImportResolver<'a, 'b, 'tcx> {
                         Some(ref value_target) => {
                             self.check_for_conflicting_import(&dest_import_resolution,
                                                               import_directive.span,
+                                                              id,
                                                               *name,
-                                                              ValueNS);
-                            dest_import_resolution.value_target = Some(value_target.clone());
+                                                              ValueNS,
+                                                              &value_target.bindings);
+                            dest_import_resolution.set_target_and_id(ValueNS,
+                                                                     Some(value_target.clone()),
+                                                                     id);
                         }
                     }
                     match target_import_resolution.type_target {
@@ -824,12 +1219,19 @@ impl<'a, 'b:'a, 'tcx:'b> ImportResolver<'a, 'b, 'tcx> {
                         Some(ref type_target) => {
                             self.check_for_conflicting_import(&dest_import_resolution,
                                                               import_directive.span,
+                                                              id,
                                                               *name,
-                                                              TypeNS);
-                            dest_import_resolution.type_target = Some(type_target.clone());
+                                                              TypeNS,
+                                                              &type_target.bindings);
+                            dest_import_resolution.set_target_and_id(TypeNS,
+                                                                     Some(type_target.clone()),
+                                                                     id);
                         }
                     }
                     dest_import_resolution.is_public = is_public;
+                    self.check_for_glob_and_explicit_import_collision(&dest_import_resolution,
+                                                                      import_directive,
+                                                                      *name);
                     continue;
                 }
                 None => {}
@@ -846,17 +1248,34 @@ impl<'a, 'b:'a, 'tcx:'b> ImportResolver<'a, 'b, 'tcx> {
         // Add all children from the containing module.
         build_reduced_graph::populate_module_if_necessary(self.resolver, &target_module);
 
-        for (&name, name_bindings) in target_module.children.borrow().iter() {
+        let mut children = target_module.children
+                                        .borrow()
+                                        .iter()
+                                        .map(|(&name, name_bindings)| (name, name_bindings.clone()))
+                                        .collect::<Vec<_>>();
+        sort_by_name(&mut children);
+        for (name, name_bindings) in children {
             self.merge_import_resolution(module_,
                                          target_module.clone(),
                                          import_directive,
                                          name,
-                                         name_bindings.clone());
+                                         name_bindings);
 
         }
 
         // Add external module children from the containing module.
-        for (&name, module) in target_module.external_module_children.borrow().iter() {
+        let mut external_module_children = target_module.external_module_children
+                                                        .borrow()
+                                                        .iter()
+                                                        .map(|(&name, module)| {
+                                                            (name, module.clone())
+                                                        })
+                                                        .collect::<Vec<_>>();
+        sort_by_name(&mut external_module_children);
+        for (name, module) in external_module_children {
+            // Whether the glob import ends up being used for the crate isn't
+            // known, take it as used rather than report it wrongly.
+            self.resolver.record_extern_crate_use(&module);
             let name_bindings = Rc::new(Resolver::create_name_bindings_from_module(module.clone()));
             self.merge_import_resolution(module_,
                                          target_module.clone(),
@@ -920,6 +1339,16 @@ impl<'a, 'b:'a, 'tcx:'b> ImportResolver<'a, 'b, 'tcx> {
                                   E0251,
                                   "{}",
                                   msg);
+                        let use_id = dest_import_resolution.id(namespace);
+                        let item = self.resolver.ast_map.expect_item(use_id);
+                        span_note!(self.resolver.session,
+                                   item.span,
+                                   "previous import of `{}` here",
+                                   name);
+                        self.note_conflicting_definitions(dest_import_resolution,
+                                                          &name_bindings,
+                                                          name,
+                                                          namespace);
                     } else {
                         let target = Target::new(containing_module.clone(),
                                                  name_bindings.clone(),
@@ -934,9 +1363,12 @@ impl<'a, 'b:'a, 'tcx:'b> ImportResolver<'a, 'b, 'tcx> {
 
         dest_import_resolution.is_public = is_public;
 
+        self.check_for_glob_and_explicit_import_collision(dest_import_resolution,
+                                                          import_directive,
+                                                          name);
         self.check_for_conflicts_between_imports_and_items(module_,
                                                            dest_import_resolution,
-                                                           import_directive.span,
+                                                           import_directive,
                                                            name);
     }
 
@@ -944,8 +1376,10 @@ impl<'a, 'b:'a, 'tcx:'b> ImportResolver<'a, 'b, 'tcx> {
     fn check_for_conflicting_import(&mut self,
                                     import_resolution: &ImportResolution,
                                     import_span: Span,
+                                    import_id: NodeId,
                                     name: Name,
-                                    namespace: Namespace) {
+                                    namespace: Namespace,
+                                    new_bindings: &NameBindings) {
         let target = import_resolution.target_for_namespace(namespace);
         debug!("check_for_conflicting_import: {}; target exists: {}",
                name,
@@ -982,11 +1416,191 @@ impl<'a, 'b:'a, 'tcx:'b> ImportResolver<'a, 'b, 'tcx> {
                            item.span,
                            "previous import of `{}` here",
                            name);
+                if self.is_glob_import(use_id) || self.is_glob_import(import_id) {
+                    self.note_conflicting_definitions(import_resolution,
+                                                      new_bindings,
+                                                      name,
+                                                      namespace);
+                }
             }
             Some(_) | None => {}
         }
     }
 
+    /// Notes where the two conflicting items named `name` are defined: the
+    /// one `import_resolution` already refers to and the one `new_bindings`
+    /// would import. With glob imports, this is the only place they show up.
+    fn note_conflicting_definitions(&self,
+                                    import_resolution: &ImportResolution,
+                                    new_bindings: &NameBindings,
+                                    name: Name,
+                                    namespace: Namespace) {
+        if let Some(target) = import_resolution.target_for_namespace(namespace) {
+            self.note_conflicting_definition(&target.bindings, name, namespace, "first");
+        }
+        self.note_conflicting_definition(new_bindings, name, namespace, "second");
+    }
+
+    fn note_conflicting_definition(&self,
+                                   name_bindings: &NameBindings,
+                                   name: Name,
+                                   namespace: Namespace,
+                                   which: &str) {
+        match name_bindings.span_for_namespace(namespace) {
+            Some(span) if span != DUMMY_SP => {
+                span_note!(self.resolver.session,
+                           span,
+                           "the {} `{}` is defined here",
+                           which,
+                           name);
+            }
+            _ => {
+                let did = match name_bindings.def_for_namespace(namespace) {
+                    Some(DefPrimTy(..)) | Some(DefSelfTy(..)) | Some(DefLabel(..)) | None => return,
+                    Some(def) => def.def_id(),
+                };
+                if !did.is_local() {
+                    let crate_name = self.resolver.session.cstore.get_crate_data(did.krate).name();
+                    self.resolver.session.note(&format!("the {} `{}` is defined in the crate `{}`",
+                                                        which,
+                                                        name,
+                                                        crate_name));
+                }
+            }
+        }
+    }
+
+    /// Whether `id` is the id of a glob import.
+    fn is_glob_import(&self, id: NodeId) -> bool {
+        match self.resolver.ast_map.find(id) {
+            Some(hir_map::NodeItem(item)) => {
+                match item.node {
+                    hir::ItemUse(ref view_path) => {
+                        match view_path.node {
+                            hir::ViewPathGlob(..) => true,
+                            _ => false,
+                        }
+                    }
+                    _ => false,
+                }
+            }
+            _ => false,
+        }
+    }
+
+    /// Lints `directive` if, along with an earlier import, it imports `name`
+    /// as a type by a glob import and as a value by an explicit import, or
+    /// the other way around. The imports don't conflict, but the name refers
+    /// to two unrelated items.
+    fn check_for_glob_and_explicit_import_collision(&mut self,
+                                                    import_resolution: &ImportResolution,
+                                                    directive: &ImportDirective,
+                                                    name: Name) {
+        // Shadowable imports, i.e. the prelude, are meant to be overridden.
+        match (&import_resolution.value_target, &import_resolution.type_target) {
+            (&Some(ref value), &Some(ref type_)) if value.shadowable == Shadowable::Never &&
+                                                    type_.shadowable == Shadowable::Never => {}
+            _ => return,
+        }
+        let (value_id, type_id) = (import_resolution.value_id, import_resolution.type_id);
+        if value_id == type_id {
+            return;
+        }
+        let (glob_id, glob_ns, explicit_id, explicit_ns) =
+            match (self.is_glob_import(value_id), self.is_glob_import(type_id)) {
+                (true, false) => (value_id, "value", type_id, "type"),
+                (false, true) => (type_id, "type", value_id, "value"),
+                _ => return,
+            };
+
+        let msg = format!("`{}` is imported as a {} by a glob import and as a {} by an \
+                           explicit import",
+                          name,
+                          glob_ns,
+                          explicit_ns);
+        // Point at whichever of the two imports the lint isn't reported at.
+        let other_id = if directive.id == glob_id { explicit_id } else { glob_id };
+        let other_ns = if other_id == glob_id { glob_ns } else { explicit_ns };
+        let note = format!("the {} `{}` is imported here", other_ns, name);
+        self.resolver.session.add_lint_note(lint::builtin::GLOB_AND_EXPLICIT_IMPORT_COLLISIONS,
+                                            directive.id,
+                                            directive.span,
+                                            msg,
+                                            self.resolver.ast_map.span(other_id),
+                                            note);
+    }
+
+    /// Lints the single import `directive` of `name` if it hides an item of
+    /// the prelude, which `import_resolution` refers to until the import is
+    /// written in. Public re-exports are left alone, they are part of the
+    /// API of their module, but they are remembered for the
+    /// `unused_reexports` lint.
+    fn lint_prelude_shadowing(&mut self,
+                              import_resolution: &ImportResolution,
+                              directive: &ImportDirective,
+                              name: Name,
+                              value_result: &NamespaceResult,
+                              type_result: &NamespaceResult) {
+        if directive.shadowable == Shadowable::Always {
+            return;
+        }
+        for &(namespace, result) in &[(TypeNS, type_result), (ValueNS, value_result)] {
+            let name_bindings = match *result {
+                BoundResult(_, ref name_bindings) => name_bindings,
+                UnboundResult | UnknownResult => continue,
+            };
+            let prelude = match import_resolution.target_for_namespace(namespace) {
+                Some(target) => {
+                    if target.shadowable != Shadowable::Always {
+                        continue;
+                    }
+                    target.bindings
+                }
+                None => continue,
+            };
+            // Importing the item of the prelude itself hides nothing.
+            let prelude_def = prelude.def_for_namespace(namespace);
+            if prelude_def.is_none() || prelude_def == name_bindings.def_for_namespace(namespace) {
+                continue;
+            }
+            if directive.is_public {
+                self.resolver.prelude_shadowing_reexports.insert(directive.id);
+                return;
+            }
+
+            let msg = format!("the import of `{}` shadows the `{}` of the prelude", name, name);
+            let (note_sp, note) = match prelude.span_for_namespace(namespace) {
+                Some(span) if span != DUMMY_SP => {
+                    (span, format!("the `{}` of the prelude is defined here", name))
+                }
+                _ => {
+                    let did = prelude_def.unwrap().def_id();
+                    let note = if did.is_local() {
+                        format!("rename the import with `as` to keep using the `{}` of the \
+                                 prelude",
+                                name)
+                    } else {
+                        let crate_data = self.resolver.session.cstore.get_crate_data(did.krate);
+                        format!("the `{}` of the prelude is defined in the crate `{}`, rename \
+                                 the import with `as` to keep using both",
+                                name,
+                                crate_data.name())
+                    };
+                    (directive.span, note)
+                }
+            };
+            self.resolver.session.add_lint_note(lint::builtin::IMPORTS_SHADOWING_PRELUDE,
+                                                directive.id,
+                                                directive.span,
+                                                msg,
+                                                note_sp,
+                                                note);
+            // One warning per import, even if it shadows the prelude in both
+            // namespaces.
+            return;
+        }
+    }
+
     /// Checks that an import is actually importable
     fn check_that_import_is_importable(&mut self,
                                        name_bindings: &NameBindings,
@@ -1003,8 +1617,9 @@ impl<'a, 'b:'a, 'tcx:'b> ImportResolver<'a, 'b, 'tcx> {
     fn check_for_conflicts_between_imports_and_items(&mut self,
                                                      module: &Module,
                                                      import_resolution: &ImportResolution,
-                                                     import_span: Span,
+                                                     import_directive: &ImportDirective,
                                                      name: Name) {
+        let import_span = import_directive.span;
         // First, check for conflicts between imports and `extern crate`s.
         if module.external_module_children
                  .borrow()
@@ -1041,6 +1656,8 @@ impl<'a, 'b:'a, 'tcx:'b> ImportResolver<'a, 'b, 'tcx> {
                     if let Some(span) = value.value_span {
                         self.resolver.session.span_note(span, "conflicting value here");
                     }
+                    let same_item = target.bindings.def_for_namespace(ValueNS) == Some(value.def);
+                    self.suggest_import_rename(import_directive, same_item);
                 }
             }
             Some(_) | None => {}
@@ -1065,11 +1682,68 @@ impl<'a, 'b:'a, 'tcx:'b> ImportResolver<'a, 'b, 'tcx> {
                     if let Some(span) = ty.type_span {
                         self.resolver.session.span_note(span, note);
                     }
+                    let same_item = target.bindings.def_for_namespace(TypeNS).is_some() &&
+                                    target.bindings.def_for_namespace(TypeNS) ==
+                                    name_bindings.def_for_namespace(TypeNS);
+                    self.suggest_import_rename(import_directive, same_item);
                 }
             }
             Some(_) | None => {}
         }
     }
+
+    /// Suggests renaming a single import that conflicts with an item of its
+    /// module, or removing it if it imports that very item. The new name is
+    /// made from the name of the module it is imported from, e.g.
+    /// `use foo::Bar as FooBar;`.
+    fn suggest_import_rename(&self, import_directive: &ImportDirective, same_item: bool) {
+        let source = match import_directive.subclass {
+            SingleImport(_, source) => source,
+            GlobImport => return,
+        };
+        let session = self.resolver.session;
+        let span = import_directive.span;
+        if same_item {
+            session.fileline_help(span,
+                                  &format!("`{}` is already defined in this module, the import \
+                                            can be removed",
+                                           source));
+            return;
+        }
+
+        let module = import_directive.module_path.iter().rev().find(|name| {
+            match &*name.as_str() {
+                "self" | "super" => false,
+                _ => true,
+            }
+        });
+        let (module, snippet) = match (module, session.codemap().span_to_snippet(span)) {
+            (Some(module), Ok(snippet)) => (module.as_str(), snippet),
+            _ => return,
+        };
+        // Replace the name it is already imported as, if any.
+        let path = snippet.split(" as ").next().unwrap().trim_right();
+        let rename = renamed_import(&module, &source.as_str());
+        session.span_suggestion(span, "rename the import:", format!("{} as {}", path, rename));
+    }
+}
+
+/// A name for the import of `name` from `module` that follows the case of
+/// `name`: `FooBar` for a type `Bar` of a module `foo`, `foo_bar` for a
+/// function `bar`, `FOO_BAR` for a constant `BAR`.
+fn renamed_import(module: &str, name: &str) -> String {
+    if name.chars().all(|c| c.is_uppercase() || c.is_numeric() || c == '_') {
+        format!("{}_{}", module.to_uppercase(), name)
+    } else if name.chars().next().map_or(false, |c| c.is_uppercase()) {
+        let camel_module = module.split('_').filter(|word| !word.is_empty()).map(|word| {
+            let mut chars = word.chars();
+            let first = chars.next().unwrap();
+            first.to_uppercase().chain(chars).collect::<String>()
+        }).collect::<String>();
+        format!("{}{}", camel_module, name)
+    } else {
+        format!("{}_{}", module, name)
+    }
 }
 
 fn import_path_to_string(names: &[Name], subclass: ImportDirectiveSubclass) -> String {
@@ -206,18 +206,53 @@ fn symbol_hash<'tcx>(tcx: &ty::ctxt<'tcx>,
                      t: Ty<'tcx>,
                      link_meta: &LinkMeta)
                      -> String {
+    def_symbol_hash(tcx, symbol_hasher, None, t, &[], link_meta)
+}
+
+// Like `symbol_hash`, but also hashes the `DefPath` of the item being named,
+// the crate defining it and the types it is instantiated with. Everything
+// that goes into the hash is independent of node ids, interned type addresses
+// and the order of items in the crate, so symbol names only change when the
+// item itself does. For the same reason the SVH, which changes with any edit
+// to the crate, is only hashed into the symbols without a def path.
+fn def_symbol_hash<'tcx>(tcx: &ty::ctxt<'tcx>,
+                         symbol_hasher: &mut Sha256,
+                         def_path: Option<(ast::CrateNum, &DefPath)>,
+                         t: Ty<'tcx>,
+                         substs: &[Ty<'tcx>],
+                         link_meta: &LinkMeta)
+                         -> String {
     // NB: do *not* use abbrevs here as we want the symbol names
     // to be independent of one another in the crate.
 
     symbol_hasher.reset();
     symbol_hasher.input_str(&link_meta.crate_name);
     symbol_hasher.input_str("-");
-    symbol_hasher.input_str(link_meta.crate_hash.as_str());
-    for meta in tcx.sess.crate_metadata.borrow().iter() {
-        symbol_hasher.input_str(&meta[..]);
+    if def_path.is_none() {
+        symbol_hasher.input_str(link_meta.crate_hash.as_str());
     }
+    symbol_hasher.input_str(&tcx.sess.crate_disambiguator.borrow());
     symbol_hasher.input_str("-");
-    symbol_hasher.input(&encoder::encoded_ty(tcx, t));
+    if let Some((krate, def_path)) = def_path {
+        // Items of different upstream crates can have the same path, and are
+        // both instantiated in this crate.
+        if krate != ast::LOCAL_CRATE {
+            let cdata = tcx.sess.cstore.get_crate_data(krate);
+            symbol_hasher.input_str(&cdata.name());
+            symbol_hasher.input_str(&cdata.disambiguator());
+            symbol_hasher.input_str("-");
+        }
+        for elem in def_path {
+            symbol_hasher.input_str(&elem.data.as_interned_str());
+            symbol_hasher.input_str(&format!("[{}]::", elem.disambiguator));
+        }
+        symbol_hasher.input_str("-");
+    }
+    symbol_hasher.input(&encoder::stable_encoded_ty(tcx, t));
+    for &ty in substs {
+        symbol_hasher.input_str(",");
+        symbol_hasher.input(&encoder::stable_encoded_ty(tcx, ty));
+    }
     // Prefix with 'h' so that it never blends into adjacent digits
     let mut hash = String::from("h");
     hash.push_str(&truncated_hash_result(symbol_hasher));
@@ -330,30 +365,31 @@ pub fn exported_name(path: DefPath, hash: &str) -> String {
 }
 
 pub fn mangle_exported_name<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>, path: DefPath,
-                                      t: Ty<'tcx>, id: ast::NodeId) -> String {
-    let mut hash = get_symbol_hash(ccx, t);
-
+                                      t: Ty<'tcx>) -> String {
     // Paths can be completely identical for different nodes,
-    // e.g. `fn foo() { { fn a() {} } { fn a() {} } }`, so we
-    // generate unique characters from the node id. For now
-    // hopefully 3 characters is enough to avoid collisions.
-    const EXTRA_CHARS: &'static str =
-        "abcdefghijklmnopqrstuvwxyz\
-         ABCDEFGHIJKLMNOPQRSTUVWXYZ\
-         0123456789";
-    let id = id as usize;
-    let extra1 = id % EXTRA_CHARS.len();
-    let id = id / EXTRA_CHARS.len();
-    let extra2 = id % EXTRA_CHARS.len();
-    let id = id / EXTRA_CHARS.len();
-    let extra3 = id % EXTRA_CHARS.len();
-    hash.push(EXTRA_CHARS.as_bytes()[extra1] as char);
-    hash.push(EXTRA_CHARS.as_bytes()[extra2] as char);
-    hash.push(EXTRA_CHARS.as_bytes()[extra3] as char);
-
+    // e.g. `fn foo() { { fn a() {} } { fn a() {} } }`; their
+    // disambiguators, which go into the hash, are not.
+    let hash = mangle_hash(ccx, ast::LOCAL_CRATE, &path, t, &[]);
     exported_name(path, &hash[..])
 }
 
+/// The hash suffix for the symbol of `path`, an item of the crate `krate`,
+/// at type `t`, instantiated with the type parameters `substs`.
+pub fn mangle_hash<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>,
+                             krate: ast::CrateNum,
+                             path: &DefPath,
+                             t: Ty<'tcx>,
+                             substs: &[Ty<'tcx>])
+                             -> String {
+    let mut symbol_hasher = ccx.symbol_hasher().borrow_mut();
+    def_symbol_hash(ccx.tcx(),
+                    &mut *symbol_hasher,
+                    Some((krate, path)),
+                    t,
+                    substs,
+                    ccx.link_meta())
+}
+
 pub fn mangle_internal_name_by_type_and_seq<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>,
                                                       t: Ty<'tcx>,
                                                       name: &str) -> String {
@@ -458,11 +494,19 @@ pub fn default_output_for_target(sess: &Session) -> config::CrateType {
 /// Checks if target supports crate_type as output
 pub fn invalid_output_for_target(sess: &Session,
                                  crate_type: config::CrateType) -> bool {
-    match (sess.target.target.options.dynamic_linking,
-           sess.target.target.options.executables, crate_type) {
-        (false, _, config::CrateTypeDylib) => true,
-        (_, false, config::CrateTypeExecutable) => true,
-        _ => false
+    missing_target_capability(sess, crate_type).is_some()
+}
+
+/// The capability the target lacks to produce `crate_type`, if any, e.g.
+/// "dynamic linking" for a dylib.
+pub fn missing_target_capability(sess: &Session,
+                                 crate_type: config::CrateType) -> Option<&'static str> {
+    let options = &sess.target.target.options;
+    match crate_type {
+        config::CrateTypeDylib if !options.dynamic_linking => Some("dynamic linking"),
+        config::CrateTypeExecutable if !options.executables => Some("executables"),
+        config::CrateTypeStaticlib if !options.staticlibs => Some("static libraries"),
+        _ => None,
     }
 }
 
@@ -625,7 +669,8 @@ fn link_rlib<'a>(sess: &'a Session,
 
     for &(ref l, kind) in sess.cstore.get_used_libraries().borrow().iter() {
         match kind {
-            cstore::NativeStatic => ab.add_native_library(&l),
+            cstore::NativeStatic |
+            cstore::NativeStaticNoWholeArchive => ab.add_native_library(&l),
             cstore::NativeFramework | cstore::NativeUnknown => {}
         }
     }
@@ -826,7 +871,8 @@ fn link_staticlib(sess: &Session, objects: &[PathBuf], out_filename: &Path,
 
     for &(kind, ref lib) in &all_native_libs {
         let name = match kind {
-            cstore::NativeStatic => "static library",
+            cstore::NativeStatic |
+            cstore::NativeStaticNoWholeArchive => "static library",
             cstore::NativeUnknown => "library",
             cstore::NativeFramework => "framework",
         };
@@ -1091,12 +1137,8 @@ fn add_local_native_libraries(cmd: &mut Linker, sess: &Session) {
     let libs = sess.cstore.get_used_libraries();
     let libs = libs.borrow();
 
-    let staticlibs = libs.iter().filter_map(|&(ref l, kind)| {
-        if kind == cstore::NativeStatic {Some(l)} else {None}
-    });
-    let others = libs.iter().filter(|&&(_, kind)| {
-        kind != cstore::NativeStatic
-    });
+    let staticlibs = libs.iter().filter(|&&(_, kind)| kind.is_static());
+    let others = libs.iter().filter(|&&(_, kind)| !kind.is_static());
 
     // Some platforms take hints about whether a library is static or dynamic.
     // For those that support this, we ensure we pass the option if the library
@@ -1105,14 +1147,19 @@ fn add_local_native_libraries(cmd: &mut Linker, sess: &Session) {
     cmd.hint_static();
 
     let search_path = archive_search_paths(sess);
-    for l in staticlibs {
+    for &(ref l, kind) in staticlibs {
         // Here we explicitly ask that the entire archive is included into the
         // result artifact. For more details see #15460, but the gist is that
         // the linker will strip away any unused objects in the archive if we
         // don't otherwise explicitly reference them. This can occur for
         // libraries which are just providing bindings, libraries with generic
-        // functions, etc.
-        cmd.link_whole_staticlib(l, &search_path);
+        // functions, etc. Crates which know better can opt out with the
+        // `-whole-archive` modifier.
+        if kind == cstore::NativeStaticNoWholeArchive {
+            cmd.link_staticlib(l);
+        } else {
+            cmd.link_whole_staticlib(l, &search_path);
+        }
     }
 
     cmd.hint_dynamic();
@@ -1121,7 +1168,8 @@ fn add_local_native_libraries(cmd: &mut Linker, sess: &Session) {
         match kind {
             cstore::NativeUnknown => cmd.link_dylib(l),
             cstore::NativeFramework => cmd.link_framework(l),
-            cstore::NativeStatic => unreachable!(),
+            cstore::NativeStatic |
+            cstore::NativeStaticNoWholeArchive => unreachable!(),
         }
     }
 }
@@ -1302,7 +1350,7 @@ fn add_upstream_native_libraries(cmd: &mut Linker, sess: &Session) {
             match kind {
                 cstore::NativeUnknown => cmd.link_dylib(lib),
                 cstore::NativeFramework => cmd.link_framework(lib),
-                cstore::NativeStatic => {
+                cstore::NativeStatic | cstore::NativeStaticNoWholeArchive => {
                     sess.bug("statics shouldn't be propagated");
                 }
             }
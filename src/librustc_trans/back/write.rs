@@ -164,16 +164,31 @@ pub fn create_target_machine(sess: &Session) -> TargetMachineRef {
         "dynamic-no-pic" => llvm::RelocDynamicNoPic,
         _ => {
             sess.err(&format!("{:?} is not a valid relocation mode",
-                             sess.opts
-                                 .cg
-                                 .relocation_model));
+                             reloc_model_arg));
             sess.abort_if_errors();
             unreachable!();
         }
     };
 
     let opt_level = get_llvm_opt_level(sess.opts.optimize);
-    let use_softfp = sess.opts.cg.soft_float;
+
+    // `-C soft-float` takes precedence over the target's float ABI.
+    let float_abi_arg = if sess.opts.cg.soft_float {
+        "soft"
+    } else {
+        &sess.target.target.options.float_abi[..]
+    };
+    let float_abi = match float_abi_arg {
+        "default" => llvm::FloatABIDefault,
+        "soft" => llvm::FloatABISoft,
+        "hard" => llvm::FloatABIHard,
+        _ => {
+            sess.err(&format!("{:?} is not a valid float ABI",
+                             float_abi_arg));
+            sess.abort_if_errors();
+            unreachable!();
+        }
+    };
 
     let any_library = sess.crate_types.borrow().iter().any(|ty| {
         *ty != config::CrateTypeExecutable
@@ -195,9 +210,7 @@ pub fn create_target_machine(sess: &Session) -> TargetMachineRef {
         "large" => llvm::CodeModelLarge,
         _ => {
             sess.err(&format!("{:?} is not a valid code model",
-                             sess.opts
-                                 .cg
-                                 .code_model));
+                             code_model_arg));
             sess.abort_if_errors();
             unreachable!();
         }
@@ -218,7 +231,7 @@ pub fn create_target_machine(sess: &Session) -> TargetMachineRef {
             code_model,
             reloc_model,
             opt_level,
-            use_softfp,
+            float_abi,
             !any_library && reloc_model == llvm::RelocPIC,
             ffunction_sections,
             fdata_sections,
@@ -631,7 +644,7 @@ pub fn run_passes(sess: &Session,
                 modules_config.emit_obj = true;
                 metadata_config.emit_obj = true;
             },
-            OutputType::DepInfo => {}
+            OutputType::DepInfo | OutputType::Mir => {}
         }
     }
 
@@ -735,7 +748,8 @@ pub fn run_passes(sess: &Session,
                 copy_if_one_unit("0.o", OutputType::Object, true);
             }
             OutputType::Exe |
-            OutputType::DepInfo => {}
+            OutputType::DepInfo |
+            OutputType::Mir => {}
         }
     }
     let user_wants_bitcode = user_wants_bitcode;
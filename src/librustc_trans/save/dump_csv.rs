@@ -126,6 +126,19 @@ impl <'l, 'tcx> DumpCsvVisitor<'l, 'tcx> {
         self.fmt.recorder.record("end_external_crates\n");
     }
 
+    // Dump, for every item imported into this crate, the `use` items importing it.
+    pub fn dump_import_map(&mut self, krate: &ast::Crate) {
+        let analysis = self.analysis;
+        let import_map = analysis.import_map.as_ref().unwrap();
+        let mut imports = import_map.iter().collect::<Vec<_>>();
+        imports.sort_by(|a, b| a.0.cmp(b.0));
+        for (&def_id, import_ids) in imports {
+            for &import_id in import_ids {
+                self.fmt.import_str(krate.span, def_id, import_id);
+            }
+        }
+    }
+
     // Return all non-empty prefixes of a path.
     // For each prefix, we return the span for the last segment in the prefix and
     // a str representation of the entire prefix.
@@ -720,6 +720,7 @@ pub fn process_crate<'l, 'tcx>(tcx: &'l ty::ctxt<'tcx>,
     }
 
     assert!(analysis.glob_map.is_some());
+    assert!(analysis.import_map.is_some());
 
     info!("Dumping crate {}", cratename);
 
@@ -760,6 +761,7 @@ pub fn process_crate<'l, 'tcx>(tcx: &'l ty::ctxt<'tcx>,
 
     visitor.dump_crate_info(cratename, krate);
     visit::walk_crate(&mut visitor, krate);
+    visitor.dump_import_map(krate);
 }
 
 // Utility functions for the module.
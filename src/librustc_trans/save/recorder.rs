@@ -96,6 +96,7 @@ pub enum Row {
     VarRef,
     TypeRef,
     FnRef,
+    Import,
 }
 
 impl<'a, 'tcx: 'a> FmtStrs<'a, 'tcx> {
@@ -181,6 +182,7 @@ impl<'a, 'tcx: 'a> FmtStrs<'a, 'tcx> {
                          true,
                          true),
             UseGlob => ("use_glob", vec!("id", "value", "scopeid"), true, true),
+            Import => ("import", vec!("refid", "refidcrate", "importid"), false, false),
             ExternCrate => ("extern_crate",
                             vec!("id", "name", "location", "crate", "scopeid"),
                             true,
@@ -585,6 +587,14 @@ impl<'a, 'tcx: 'a> FmtStrs<'a, 'tcx> {
         self.check_and_record(UseGlob, span, sub_span, svec!(id, values, parent));
     }
 
+    // `import_id` is the `use_alias` or `use_glob` importing the item `id`.
+    pub fn import_str(&mut self, span: Span, id: DefId, import_id: NodeId) {
+        let import_id = self.normalize_node_id(import_id);
+        self.record_without_span(Import,
+                                 svec!(id.index.as_usize(), id.krate, import_id),
+                                 span);
+    }
+
     pub fn extern_crate_str(&mut self,
                             span: Span,
                             sub_span: Option<Span>,
@@ -287,7 +287,7 @@ impl<'a, 'tcx> Opt<'a, 'tcx> {
                 let expr = consts::const_expr(ccx, &*lit_expr, bcx.fcx.param_substs, None, Yes);
                 let llval = match expr {
                     Ok((llval, _)) => llval,
-                    Err(err) => bcx.ccx().sess().span_fatal(lit_expr.span, &err.description()),
+                    Err(err) => err.span_fatal(bcx.ccx().sess(), lit_expr.span),
                 };
                 let lit_datum = immediate_rvalue(llval, lit_ty);
                 let lit_datum = unpack_datum!(bcx, lit_datum.to_appropriate_datum(bcx));
@@ -296,11 +296,11 @@ impl<'a, 'tcx> Opt<'a, 'tcx> {
             ConstantRange(ConstantExpr(ref l1), ConstantExpr(ref l2), _) => {
                 let l1 = match consts::const_expr(ccx, &**l1, bcx.fcx.param_substs, None, Yes) {
                     Ok((l1, _)) => l1,
-                    Err(err) => bcx.ccx().sess().span_fatal(l1.span, &err.description()),
+                    Err(err) => err.span_fatal(bcx.ccx().sess(), l1.span),
                 };
                 let l2 = match consts::const_expr(ccx, &**l2, bcx.fcx.param_substs, None, Yes) {
                     Ok((l2, _)) => l2,
-                    Err(err) => bcx.ccx().sess().span_fatal(l2.span, &err.description()),
+                    Err(err) => err.span_fatal(bcx.ccx().sess(), l2.span),
                 };
                 RangeResult(Result::new(bcx, l1), Result::new(bcx, l2))
             }
@@ -40,6 +40,7 @@ use middle::cfg;
 use middle::def_id::DefId;
 use middle::infer;
 use middle::lang_items::{LangItem, ExchangeMallocFnLangItem, StartFnLangItem};
+use middle::module_times::{self, Pass};
 use middle::weak_lang_items;
 use middle::pat_util::simple_name;
 use middle::subst::Substs;
@@ -56,9 +57,11 @@ use trans::builder::{Builder, noname};
 use trans::callee;
 use trans::cleanup::{self, CleanupMethods, DropHint};
 use trans::closure;
+use trans::collector::{self, TransItemCollectionMode};
 use trans::common::{Block, C_bool, C_bytes_in_context, C_i32, C_int, C_uint, C_integral};
 use trans::common::{C_null, C_struct_in_context, C_u64, C_u8, C_undef};
 use trans::common::{CrateContext, DropFlagHintsMap, Field, FunctionContext};
+#[cfg_attr(not(stage0), allow(imports_shadowing_prelude))] // `trans::common::Result`
 use trans::common::{Result, NodeIdAndSpan, VariantInfo};
 use trans::common::{node_id_type, return_type_is_void};
 use trans::common::{type_is_immediate, type_is_zero_size, val_ty};
@@ -78,6 +81,7 @@ use trans::machine::{llsize_of, llsize_of_real};
 use trans::meth;
 use trans::mir;
 use trans::monomorphize;
+use trans::symbol_names_test;
 use trans::tvec;
 use trans::type_::Type;
 use trans::type_of;
@@ -2393,7 +2397,7 @@ pub fn trans_item(ccx: &CrateContext, item: &hir::Item) {
         hir::ItemStatic(_, m, ref expr) => {
             let g = match consts::trans_static(ccx, m, expr, item.id, &item.attrs) {
                 Ok(g) => g,
-                Err(err) => ccx.tcx().sess.span_fatal(expr.span, &err.description()),
+                Err(err) => err.span_fatal(ccx.sess(), expr.span),
             };
             set_global_section(ccx, g, item);
             update_linkage(ccx, g, Some(item.id), OriginalTranslation);
@@ -2569,7 +2573,7 @@ fn exported_name<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>,
                     Some(name) => name.to_string(),
                     None => {
                         // Usual name mangling
-                        mangle_exported_name(ccx, path, ty, id)
+                        mangle_exported_name(ccx, path, ty)
                     }
                 }
             }
@@ -2808,6 +2812,47 @@ pub fn write_metadata(cx: &SharedCrateContext, krate: &hir::Crate, reachable: &N
     return metadata;
 }
 
+/// Counts the LLVM instructions translated for the items of each module, for
+/// `-Z module-times`.
+fn record_module_llvm_insns(shared_ccx: &SharedCrateContext) {
+    let tcx = shared_ccx.tcx();
+    for ccx in shared_ccx.iter() {
+        let mut items = HashMap::new();
+        for (&id, &llfn) in ccx.item_vals().borrow().iter() {
+            items.insert(llfn, id);
+        }
+        let monomorphized = ccx.monomorphized().borrow();
+        let closure_vals = ccx.closure_vals().borrow();
+        for (mono_id, &llfn) in monomorphized.iter().chain(closure_vals.iter()) {
+            if let Some(id) = tcx.map.as_local_node_id(mono_id.def) {
+                items.insert(llfn, id);
+            }
+        }
+
+        unsafe {
+            let mut llfn = llvm::LLVMGetFirstFunction(ccx.llmod());
+            while !llfn.is_null() {
+                let mut insns = 0;
+                let mut llbb = llvm::LLVMGetFirstBasicBlock(llfn);
+                while !llbb.is_null() {
+                    let mut llinst = llvm::LLVMGetFirstInstruction(llbb);
+                    while !llinst.is_null() {
+                        insns += 1;
+                        llinst = llvm::LLVMGetNextInstruction(llinst);
+                    }
+                    llbb = llvm::LLVMGetNextBasicBlock(llbb);
+                }
+
+                match items.get(&llfn) {
+                    Some(&id) => module_times::record_llvm_insns(tcx, id, insns),
+                    None => module_times::record_other_llvm_insns(tcx, insns),
+                }
+                llfn = llvm::LLVMGetNextFunction(llfn);
+            }
+        }
+    }
+}
+
 /// Find any symbols that are defined in one compilation unit, but not declared
 /// in any other compilation unit.  Give these symbols internal linkage.
 fn internalize_symbols(cx: &SharedCrateContext, reachable: &HashSet<&str>) {
@@ -3030,6 +3075,16 @@ pub fn trans_crate<'tcx>(tcx: &ty::ctxt<'tcx>,
         // First, verify intrinsics.
         intrinsic::check_intrinsics(&ccx);
 
+        if let Some(ref mode) = tcx.sess.opts.debugging_opts.print_trans_items {
+            let mode = match &mode[..] {
+                "eager" => TransItemCollectionMode::Eager,
+                "lazy" => TransItemCollectionMode::Lazy,
+                _ => tcx.sess.fatal(&format!("unknown translation item collection mode `{}`, \
+                                              expected `lazy` or `eager`", mode)),
+            };
+            collector::print_translation_items(&ccx, mode);
+        }
+
         // Next, translate all items. See `TransModVisitor` for
         // details on why we walk in this particular way.
         {
@@ -3037,6 +3092,12 @@ pub fn trans_crate<'tcx>(tcx: &ty::ctxt<'tcx>,
             intravisit::walk_mod(&mut TransItemsWithinModVisitor { ccx: &ccx }, &krate.module);
             krate.visit_all_items(&mut TransModVisitor { ccx: &ccx });
         }
+
+        symbol_names_test::report_symbol_names(&ccx);
+    }
+
+    if tcx.sess.module_times() {
+        record_module_llvm_insns(&shared_ccx);
     }
 
     for ccx in shared_ccx.iter() {
@@ -3181,7 +3242,9 @@ impl<'a, 'tcx, 'v> Visitor<'v> for TransItemsWithinModVisitor<'a, 'tcx> {
                 // skip modules, they will be uncovered by the TransModVisitor
             }
             _ => {
-                trans_item(self.ccx, i);
+                module_times::time_item(self.ccx.tcx(), Pass::Trans, i.id, || {
+                    trans_item(self.ccx, i)
+                });
                 intravisit::walk_item(self, i);
             }
         }
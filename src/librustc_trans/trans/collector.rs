@@ -0,0 +1,505 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Translation Item Collection
+//! ===========================
+//!
+//! This module computes the set of "translation items" of a crate: the
+//! monomorphized functions, drop glue and statics that translation will
+//! have to produce. It is exposed through `-Z print-trans-items`, which
+//! makes the set testable independently of LLVM (see the `codegen-units`
+//! test suite).
+//!
+//! The collector starts from a set of roots and follows the MIR of each
+//! function it finds to its "neighbors": the functions it calls or takes
+//! the address of (with the caller's substitutions applied), the
+//! closures it creates, the values it drops, and the methods of the
+//! vtables it creates through unsizing. Calls of trait methods are
+//! resolved to the implementing method where the impl is known.
+//!
+//! There are two modes. In `lazy` mode the roots are the items that
+//! must be translated regardless of their uses: exported functions and
+//! the entry point. In `eager` mode every non-generic function, and the
+//! drop glue of every non-generic type with a destructor, is a root.
+//! Statics are roots in both modes.
+//!
+//! Only functions with MIR in this crate can be walked. Generic functions
+//! from other crates are recorded, but what they reference in turn is not.
+//!
+//! Translation itself does not use the collected set yet: codegen units
+//! are still formed by `trans::base`, which walks the HIR and assigns
+//! each module to the next unit, instantiating generic functions on
+//! demand wherever they are used.
+
+use rustc::front::map as hir_map;
+use rustc_front::hir;
+use rustc_front::intravisit as hir_visit;
+
+use metadata::csearch;
+use middle::def_id::DefId;
+use middle::subst::{self, Substs, VecPerParamSpace};
+use middle::traits;
+use middle::ty::{self, Ty};
+use rustc_mir::repr as mir;
+use rustc_mir::visit as mir_visit;
+use rustc_mir::visit::Visitor as MirVisitor;
+
+use syntax::ast::NodeId;
+use syntax::codemap::DUMMY_SP;
+use trans::common::{fulfill_obligation, type_needs_drop};
+use trans::context::CrateContext;
+use trans::monomorphize;
+use util::nodemap::FnvHashSet;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum TransItemCollectionMode {
+    Eager,
+    Lazy,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum TransItem<'tcx> {
+    DropGlue(Ty<'tcx>),
+    Fn {
+        def_id: DefId,
+        substs: &'tcx Substs<'tcx>,
+    },
+    Static(NodeId),
+}
+
+impl<'tcx> TransItem<'tcx> {
+    /// A description of the item that is independent of node ids and of
+    /// the order in which items were found, used by `-Z print-trans-items`.
+    pub fn to_string(&self, tcx: &ty::ctxt<'tcx>) -> String {
+        match *self {
+            TransItem::DropGlue(ty) => format!("drop-glue {}", ty),
+            TransItem::Fn { def_id, substs } => {
+                let mut s = format!("fn {}", fn_path_str(tcx, def_id));
+                if !substs.types.is_empty() {
+                    let types: Vec<String> = substs.types
+                                                   .iter()
+                                                   .map(|t| t.to_string())
+                                                   .collect();
+                    s.push_str(&format!("<{}>", types.join(", ")));
+                }
+                s
+            }
+            TransItem::Static(node_id) => {
+                format!("static {}", tcx.item_path_str(tcx.map.local_def_id(node_id)))
+            }
+        }
+    }
+}
+
+/// Like `item_path_str`, but distinguishes methods of different impls,
+/// which all share the (empty) name of their impl.
+fn fn_path_str(tcx: &ty::ctxt, def_id: DefId) -> String {
+    match tcx.impl_of_method(def_id) {
+        Some(impl_def_id) => {
+            let self_ty = tcx.lookup_item_type(impl_def_id).ty;
+            let name = tcx.item_name(def_id);
+            match tcx.impl_trait_ref(impl_def_id) {
+                Some(trait_ref) => {
+                    format!("<{} as {}>::{}",
+                            self_ty,
+                            tcx.item_path_str(trait_ref.def_id),
+                            name)
+                }
+                None => format!("{}::{}", self_ty, name),
+            }
+        }
+        None => tcx.item_path_str(def_id),
+    }
+}
+
+pub fn collect_crate_translation_items<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>,
+                                                 mode: TransItemCollectionMode)
+                                                 -> FnvHashSet<TransItem<'tcx>> {
+    let mut roots = Vec::new();
+    {
+        let mut visitor = RootCollector {
+            ccx: ccx,
+            mode: mode,
+            output: &mut roots,
+        };
+        ccx.tcx().map.krate().visit_all_items(&mut visitor);
+    }
+
+    let mut visited = FnvHashSet();
+    for root in roots {
+        collect_items_rec(ccx, root, &mut visited);
+    }
+    visited
+}
+
+/// Implements `-Z print-trans-items`, printing the items in a
+/// deterministic order.
+pub fn print_translation_items<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>,
+                                         mode: TransItemCollectionMode) {
+    let items = collect_crate_translation_items(ccx, mode);
+    let mut lines: Vec<String> = items.iter()
+                                      .map(|item| item.to_string(ccx.tcx()))
+                                      .collect();
+    lines.sort();
+    lines.dedup();
+    for line in lines {
+        println!("TRANS_ITEM {}", line);
+    }
+}
+
+fn collect_items_rec<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>,
+                               item: TransItem<'tcx>,
+                               visited: &mut FnvHashSet<TransItem<'tcx>>) {
+    if !visited.insert(item) {
+        return;
+    }
+    debug!("collect_items_rec({})", item.to_string(ccx.tcx()));
+
+    let mut neighbors = Vec::new();
+    match item {
+        TransItem::Static(_) => {
+            // static initializers are constant expressions and do not
+            // reference any translation items of their own
+        }
+        TransItem::DropGlue(ty) => {
+            find_drop_glue_neighbors(ccx, ty, &mut neighbors);
+        }
+        TransItem::Fn { def_id, substs } => {
+            let mir = ccx.tcx().map.as_local_node_id(def_id)
+                                   .and_then(|node_id| ccx.mir_map().get(&node_id));
+            if let Some(mir) = mir {
+                let mut visitor = MirNeighborCollector {
+                    ccx: ccx,
+                    mir: mir,
+                    param_substs: substs,
+                    output: &mut neighbors,
+                };
+                visitor.visit_mir(mir);
+            }
+        }
+    }
+
+    for neighbor in neighbors {
+        collect_items_rec(ccx, neighbor, visited);
+    }
+}
+
+struct MirNeighborCollector<'a, 'b: 'a, 'tcx: 'b> {
+    ccx: &'a CrateContext<'b, 'tcx>,
+    mir: &'a mir::Mir<'tcx>,
+    param_substs: &'tcx Substs<'tcx>,
+    output: &'a mut Vec<TransItem<'tcx>>,
+}
+
+impl<'a, 'b, 'tcx> MirNeighborCollector<'a, 'b, 'tcx> {
+    fn monomorphize<T>(&self, value: &T) -> T
+        where T: ty::fold::TypeFoldable<'tcx> + ty::HasTypeFlags
+    {
+        monomorphize::apply_param_substs(self.ccx.tcx(), self.param_substs, value)
+    }
+}
+
+impl<'a, 'b, 'tcx> MirVisitor<'tcx> for MirNeighborCollector<'a, 'b, 'tcx> {
+    fn visit_statement(&mut self, block: mir::BasicBlock, statement: &mir::Statement<'tcx>) {
+        if let mir::StatementKind::Drop(_, ref lvalue) = statement.kind {
+            let ty = self.mir.lvalue_ty(self.ccx.tcx(), lvalue).to_ty(self.ccx.tcx());
+            let ty = self.monomorphize(&ty);
+            if type_needs_drop(self.ccx.tcx(), ty) {
+                self.output.push(TransItem::DropGlue(ty));
+            }
+        }
+        self.super_statement(block, statement);
+    }
+
+    fn visit_rvalue(&mut self, rvalue: &mir::Rvalue<'tcx>) {
+        match *rvalue {
+            mir::Rvalue::Aggregate(mir::AggregateKind::Closure(def_id, substs), _) => {
+                let substs = self.monomorphize(substs.func_substs);
+                self.output.push(TransItem::Fn {
+                    def_id: def_id,
+                    substs: self.ccx.tcx().mk_substs(substs),
+                });
+            }
+            mir::Rvalue::Cast(mir::CastKind::Unsize, ref operand, target_ty) => {
+                let source_ty = self.mir.operand_ty(self.ccx.tcx(), operand);
+                let source_ty = self.monomorphize(&source_ty);
+                let target_ty = self.monomorphize(&target_ty);
+                find_vtable_neighbors(self.ccx, source_ty, target_ty, self.output);
+            }
+            _ => {}
+        }
+        self.super_rvalue(rvalue);
+    }
+
+    fn visit_constant(&mut self, constant: &mir::Constant<'tcx>) {
+        if let mir::Literal::Item { def_id, substs } = constant.literal {
+            // only fn items are translation items; constants and
+            // struct/variant constructors are translated in place
+            if let ty::TyBareFn(Some(_), _) = constant.ty.sty {
+                if !is_constructor(self.ccx, def_id) {
+                    let substs = self.monomorphize(substs);
+                    if let Some(item) = resolve_fn_item(self.ccx, def_id, substs) {
+                        self.output.push(item);
+                    }
+                }
+            }
+        }
+        self.super_constant(constant);
+    }
+
+    fn visit_lvalue(&mut self, lvalue: &mir::Lvalue<'tcx>, context: mir_visit::LvalueContext) {
+        if let mir::Lvalue::Static(def_id) = *lvalue {
+            if let Some(node_id) = self.ccx.tcx().map.as_local_node_id(def_id) {
+                self.output.push(TransItem::Static(node_id));
+            }
+        }
+        self.super_lvalue(lvalue, context);
+    }
+}
+
+fn is_constructor(ccx: &CrateContext, def_id: DefId) -> bool {
+    match ccx.tcx().map.as_local_node_id(def_id) {
+        Some(node_id) => {
+            match ccx.tcx().map.find(node_id) {
+                Some(hir_map::NodeVariant(..)) | Some(hir_map::NodeStructCtor(..)) => true,
+                _ => false,
+            }
+        }
+        None => {
+            csearch::get_tuple_struct_definition_if_ctor(&ccx.sess().cstore, def_id).is_some()
+        }
+    }
+}
+
+/// Maps a reference to a fn or method to the item that will be
+/// translated for it, resolving trait methods to the implementing
+/// method when the impl is statically known.
+fn resolve_fn_item<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>,
+                             def_id: DefId,
+                             substs: Substs<'tcx>)
+                             -> Option<TransItem<'tcx>> {
+    let tcx = ccx.tcx();
+    let is_trait_method = tcx.trait_of_item(def_id).is_some() && {
+        match tcx.impl_or_trait_item(def_id).container() {
+            ty::TraitContainer(_) => true,
+            ty::ImplContainer(_) => false,
+        }
+    };
+    if !is_trait_method {
+        return Some(TransItem::Fn {
+            def_id: def_id,
+            substs: tcx.mk_substs(substs),
+        });
+    }
+    let trait_id = tcx.trait_of_item(def_id).unwrap();
+
+    // Split the substitutions into those of the trait reference and
+    // those of the method itself, as `trans_static_method_callee` does.
+    let subst::SeparateVecsPerParamSpace {
+        types: rcvr_type,
+        selfs: rcvr_self,
+        fns: rcvr_method
+    } = substs.types.split();
+    let trait_substs = Substs::erased(VecPerParamSpace::new(rcvr_type, rcvr_self, Vec::new()));
+    let trait_ref = ty::Binder(ty::TraitRef::new(trait_id, tcx.mk_substs(trait_substs)));
+
+    match fulfill_obligation(ccx, DUMMY_SP, trait_ref) {
+        traits::VtableImpl(traits::VtableImplData { impl_def_id, substs: impl_substs, .. }) => {
+            let subst::SeparateVecsPerParamSpace {
+                types: impl_type,
+                selfs: impl_self,
+                fns: _
+            } = impl_substs.types.split();
+            let callee_substs =
+                Substs::erased(VecPerParamSpace::new(impl_type, impl_self, rcvr_method));
+            let name = tcx.item_name(def_id);
+            let mth = tcx.get_impl_method(impl_def_id, callee_substs, name);
+            Some(TransItem::Fn {
+                def_id: mth.method.def_id,
+                substs: tcx.mk_substs(mth.substs),
+            })
+        }
+        // dispatched dynamically, or to compiler-generated code
+        _ => None,
+    }
+}
+
+fn find_drop_glue_neighbors<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>,
+                                      ty: Ty<'tcx>,
+                                      output: &mut Vec<TransItem<'tcx>>) {
+    let tcx = ccx.tcx();
+    let push_glue = |ty: Ty<'tcx>, output: &mut Vec<TransItem<'tcx>>| {
+        if type_needs_drop(tcx, ty) {
+            output.push(TransItem::DropGlue(ty));
+        }
+    };
+
+    match ty.sty {
+        ty::TyStruct(def, substs) | ty::TyEnum(def, substs) => {
+            if def.has_dtor() {
+                let trait_ref = ty::Binder(ty::TraitRef {
+                    def_id: tcx.lang_items.drop_trait().unwrap(),
+                    substs: tcx.mk_substs(Substs::trans_empty().with_self_ty(ty))
+                });
+                if let traits::VtableImpl(data) = fulfill_obligation(ccx, DUMMY_SP, trait_ref) {
+                    output.push(TransItem::Fn {
+                        def_id: def.destructor().unwrap(),
+                        substs: tcx.mk_substs(data.substs),
+                    });
+                }
+            }
+            for field in def.all_fields() {
+                push_glue(monomorphize::field_ty(tcx, substs, field), output);
+            }
+        }
+        ty::TyBox(inner) => push_glue(inner, output),
+        ty::TyArray(elem, _) | ty::TySlice(elem) => push_glue(elem, output),
+        ty::TyTuple(ref tys) => {
+            for &ty in tys {
+                push_glue(ty, output);
+            }
+        }
+        ty::TyClosure(_, ref substs) => {
+            for &ty in &substs.upvar_tys {
+                push_glue(ty, output);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Unsizing a pointer to a concrete type into a trait object creates a
+/// vtable, whose methods are translation items.
+fn find_vtable_neighbors<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>,
+                                   source_ty: Ty<'tcx>,
+                                   target_ty: Ty<'tcx>,
+                                   output: &mut Vec<TransItem<'tcx>>) {
+    let tcx = ccx.tcx();
+    let (source, target) = match (pointee_ty(source_ty), pointee_ty(target_ty)) {
+        (Some(source), Some(target)) => (source, target),
+        _ => return,
+    };
+    let trait_ty = match target.sty {
+        ty::TyTrait(ref trait_ty) => trait_ty,
+        _ => return,
+    };
+    if let ty::TyTrait(..) = source.sty {
+        // upcasting an existing object reuses its vtable
+        return;
+    }
+
+    if type_needs_drop(tcx, source) {
+        output.push(TransItem::DropGlue(source));
+    }
+
+    let trait_ref = trait_ty.principal_trait_ref_with_self_ty(tcx, source);
+    let trait_id = trait_ref.def_id();
+    let impl_data = match fulfill_obligation(ccx, DUMMY_SP, trait_ref) {
+        traits::VtableImpl(data) => data,
+        _ => return,
+    };
+
+    for item_def_id in tcx.trait_item_def_ids(trait_id).iter() {
+        let method_def_id = match *item_def_id {
+            ty::MethodTraitItemId(def_id) => def_id,
+            _ => continue,
+        };
+        let method = match tcx.impl_or_trait_item(method_def_id) {
+            ty::MethodTraitItem(method) => method,
+            _ => continue,
+        };
+        if !traits::is_vtable_safe_method(tcx, trait_id, &method) {
+            continue;
+        }
+        let mth = tcx.get_impl_method(impl_data.impl_def_id,
+                                      impl_data.substs.clone(),
+                                      method.name);
+        output.push(TransItem::Fn {
+            def_id: mth.method.def_id,
+            substs: tcx.mk_substs(mth.substs),
+        });
+    }
+}
+
+fn pointee_ty<'tcx>(ty: Ty<'tcx>) -> Option<Ty<'tcx>> {
+    match ty.sty {
+        ty::TyBox(inner) => Some(inner),
+        ty::TyRef(_, ty::TypeAndMut { ty: inner, .. }) |
+        ty::TyRawPtr(ty::TypeAndMut { ty: inner, .. }) => Some(inner),
+        _ => None,
+    }
+}
+
+struct RootCollector<'a, 'b: 'a, 'tcx: 'b> {
+    ccx: &'a CrateContext<'b, 'tcx>,
+    mode: TransItemCollectionMode,
+    output: &'a mut Vec<TransItem<'tcx>>,
+}
+
+impl<'a, 'b, 'tcx> RootCollector<'a, 'b, 'tcx> {
+    /// Whether a non-generic fn is a root in the current mode.
+    fn is_root(&self, node_id: NodeId) -> bool {
+        match self.mode {
+            TransItemCollectionMode::Eager => true,
+            TransItemCollectionMode::Lazy => {
+                self.ccx.reachable().contains(&node_id) ||
+                self.ccx.sess().entry_fn.borrow().map(|(id, _)| id) == Some(node_id)
+            }
+        }
+    }
+
+    fn push_fn(&mut self, node_id: NodeId) {
+        if self.is_root(node_id) {
+            self.output.push(TransItem::Fn {
+                def_id: self.ccx.tcx().map.local_def_id(node_id),
+                substs: self.ccx.tcx().mk_substs(Substs::trans_empty()),
+            });
+        }
+    }
+}
+
+impl<'a, 'b, 'tcx, 'v> hir_visit::Visitor<'v> for RootCollector<'a, 'b, 'tcx> {
+    fn visit_item(&mut self, item: &'v hir::Item) {
+        match item.node {
+            hir::ItemFn(_, _, _, _, ref generics, _) => {
+                if !generics.is_type_parameterized() {
+                    self.push_fn(item.id);
+                }
+            }
+            hir::ItemImpl(_, _, ref generics, _, _, ref impl_items) => {
+                if generics.is_type_parameterized() {
+                    return;
+                }
+                for impl_item in impl_items {
+                    if let hir::ImplItemKind::Method(ref sig, _) = impl_item.node {
+                        if !sig.generics.is_type_parameterized() {
+                            self.push_fn(impl_item.id);
+                        }
+                    }
+                }
+            }
+            hir::ItemStatic(..) => {
+                self.output.push(TransItem::Static(item.id));
+            }
+            hir::ItemStruct(_, ref generics) |
+            hir::ItemEnum(_, ref generics) => {
+                if self.mode == TransItemCollectionMode::Eager &&
+                   !generics.is_type_parameterized() {
+                    let tcx = self.ccx.tcx();
+                    let ty = tcx.node_id_to_type(item.id);
+                    if type_needs_drop(tcx, ty) {
+                        self.output.push(TransItem::DropGlue(ty));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
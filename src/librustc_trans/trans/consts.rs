@@ -27,6 +27,7 @@ use middle::const_eval::{const_int_checked_shr, const_uint_checked_shr};
 use middle::const_eval::EvalHint::ExprTypeChecked;
 use middle::const_eval::eval_const_expr_partial;
 use middle::def_id::DefId;
+use session::Session;
 use trans::{adt, closure, debuginfo, expr, inline, machine};
 use trans::base::{self, push_ctxt};
 use trans::common::{self, type_is_sized, ExprOrMethodCall, node_id_substs, C_nil, const_get_elt};
@@ -47,10 +48,10 @@ use util::nodemap::NodeMap;
 use rustc_front::hir;
 
 use std::ffi::{CStr, CString};
-use std::borrow::Cow;
 use libc::c_uint;
 use syntax::ast;
 use syntax::attr;
+use syntax::codemap::Span;
 use syntax::parse::token;
 use syntax::ptr::P;
 
@@ -249,11 +250,10 @@ impl ConstEvalFailure {
             Compiletime(e) => e,
         }
     }
-    pub fn description(&self) -> Cow<str> {
-        match self {
-            &Runtime(ref e) => e.description(),
-            &Compiletime(ref e) => e.description(),
-        }
+    /// Reports the failure at `span` as a fatal error, along with the notes
+    /// of the evaluation.
+    pub fn span_fatal(self, sess: &Session, span: Span) -> ! {
+        self.into_inner().span_fatal(sess, span)
     }
 }
 
@@ -273,6 +273,7 @@ fn get_const_val(ccx: &CrateContext,
                                    empty_substs, TrueConst::Yes) {
         Err(Runtime(err)) => {
             ccx.tcx().sess.span_err(expr.span, &err.description());
+            err.note(ccx.sess());
             Err(Compiletime(err))
         },
         other => other,
@@ -496,10 +497,12 @@ fn const_err(cx: &CrateContext,
         },
         (Err(err), TrueConst::Yes) => {
             cx.tcx().sess.span_err(e.span, &err.description());
+            err.note(cx.sess());
             Err(Compiletime(err))
         },
         (Err(err), TrueConst::No) => {
             cx.tcx().sess.span_warn(e.span, &err.description());
+            err.note(cx.sess());
             Err(Runtime(err))
         },
     }
@@ -709,6 +712,9 @@ fn const_expr_unadjusted<'a, 'tcx>(cx: &CrateContext<'a, 'tcx>,
                 // pass. Reporting here is a bit late.
                 span_err!(cx.sess(), e.span, E0515,
                           "const index-expr is out of bounds");
+                cx.sess().span_note(index.span,
+                                    &format!("the index is `{}` but the length is `{}`",
+                                             iv, len));
                 C_undef(val_ty(arr).element_type())
             } else {
                 const_get_elt(cx, arr, &[iv as c_uint])
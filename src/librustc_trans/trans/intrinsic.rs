@@ -1405,7 +1405,7 @@ fn generic_simd_intrinsic<'blk, 'tcx, 'a>
             consts::TrueConst::Yes, // this should probably help simd error reporting
         ) {
             Ok((vector, _)) => vector,
-            Err(err) => bcx.sess().span_fatal(call_info.span, &err.description()),
+            Err(err) => err.span_fatal(bcx.sess(), call_info.span),
         };
 
         let indices: Option<Vec<_>> = (0..n)
@@ -15,6 +15,7 @@ use rustc_mir::repr as mir;
 use trans::asm;
 use trans::base;
 use trans::build;
+#[cfg_attr(not(stage0), allow(imports_shadowing_prelude))] // `trans::common::Result`
 use trans::common::{self, Block, Result};
 use trans::debuginfo::DebugLoc;
 use trans::declare;
@@ -36,6 +36,7 @@ mod cabi_x86_win64;
 mod callee;
 mod cleanup;
 mod closure;
+mod collector;
 mod common;
 mod consts;
 mod context;
@@ -54,6 +55,7 @@ mod _match;
 mod meth;
 mod mir;
 mod monomorphize;
+mod symbol_names_test;
 mod tvec;
 mod type_;
 mod type_of;
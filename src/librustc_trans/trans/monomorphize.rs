@@ -8,7 +8,7 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use back::link::exported_name;
+use back::link::{self, exported_name};
 use session;
 use llvm::ValueRef;
 use llvm;
@@ -32,7 +32,6 @@ use rustc_front::hir;
 use syntax::abi;
 use syntax::ast;
 use syntax::attr;
-use std::hash::{Hasher, Hash, SipHasher};
 
 pub fn monomorphic_fn<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>,
                                 fn_id: DefId,
@@ -125,12 +124,8 @@ pub fn monomorphic_fn<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>,
 
     let hash;
     let s = {
-        let mut state = SipHasher::new();
-        hash_id.hash(&mut state);
-        mono_ty.hash(&mut state);
-
-        hash = format!("h{}", state.finish());
         let path = ccx.tcx().map.def_path_from_id(fn_node_id);
+        hash = link::mangle_hash(ccx, fn_id.krate, &path, mono_ty, hash_id.params.as_slice());
         exported_name(path, &hash[..])
     };
 
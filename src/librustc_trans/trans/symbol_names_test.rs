@@ -0,0 +1,75 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Walks the crate looking for items/impl-items/trait-items that have
+//! a `#[rustc_symbol_name]` attribute and reports the symbol they were
+//! given as an error. This is used to write compile-fail tests that
+//! pin down symbol names, so that changes to them are noticed.
+
+use rustc_front::hir;
+use rustc_front::intravisit::{self, Visitor};
+use syntax::ast;
+use syntax::attr::AttrMetaMethods;
+
+use trans::common::CrateContext;
+
+const SYMBOL_NAME: &'static str = "rustc_symbol_name";
+
+pub fn report_symbol_names(ccx: &CrateContext) {
+    // The attribute can't be used without the `rustc_attrs` feature, so
+    // there is no need to walk the crate looking for it then.
+    if !ccx.sess().features.borrow().rustc_attrs {
+        return;
+    }
+
+    let mut visitor = SymbolNamesTest { ccx: ccx };
+    ccx.tcx().map.krate().visit_all_items(&mut visitor);
+}
+
+struct SymbolNamesTest<'a, 'tcx: 'a> {
+    ccx: &'a CrateContext<'a, 'tcx>,
+}
+
+impl<'a, 'tcx> SymbolNamesTest<'a, 'tcx> {
+    fn process_attrs(&mut self, node_id: ast::NodeId, attrs: &[ast::Attribute]) {
+        let span = match attrs.iter().find(|attr| attr.check_name(SYMBOL_NAME)) {
+            Some(attr) => attr.span,
+            None => return,
+        };
+        let tcx = self.ccx.tcx();
+        match self.ccx.item_symbols().borrow().get(&node_id) {
+            Some(name) => {
+                tcx.sess.span_err(span, &format!("symbol-name({})", name));
+            }
+            None => {
+                tcx.sess.span_err(span, "no symbol was generated for this item; \
+                                         generic items do not have a symbol name of \
+                                         their own");
+            }
+        }
+    }
+}
+
+impl<'a, 'tcx, 'v> Visitor<'v> for SymbolNamesTest<'a, 'tcx> {
+    fn visit_item(&mut self, item: &'v hir::Item) {
+        self.process_attrs(item.id, &item.attrs);
+        intravisit::walk_item(self, item);
+    }
+
+    fn visit_trait_item(&mut self, ti: &'v hir::TraitItem) {
+        self.process_attrs(ti.id, &ti.attrs);
+        intravisit::walk_trait_item(self, ti)
+    }
+
+    fn visit_impl_item(&mut self, ii: &'v hir::ImplItem) {
+        self.process_attrs(ii.id, &ii.attrs);
+        intravisit::walk_impl_item(self, ii)
+    }
+}
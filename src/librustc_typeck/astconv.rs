@@ -1693,6 +1693,7 @@ pub fn ast_ty_to_ty<'tcx>(this: &AstConv<'tcx>,
                     span_err!(tcx.sess, r.span, E0250,
                               "array length constant evaluation error: {}",
                               r.description());
+                    r.note(tcx.sess);
                     if !ast_ty.span.contains(r.span) {
                         span_note!(tcx.sess, ast_ty.span, "for array length here")
                     }
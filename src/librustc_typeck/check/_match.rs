@@ -19,6 +19,7 @@ use check::{check_expr, check_expr_has_type, check_expr_with_expectation};
 use check::{check_expr_coercable_to_type, demand, FnCtxt, Expectation};
 use check::{check_expr_with_lvalue_pref};
 use check::{instantiate_path, resolve_ty_and_def_ufcs, structurally_resolved_type};
+use check::suggest_field_names;
 use require_same_types;
 use util::nodemap::FnvHashMap;
 
@@ -239,7 +240,7 @@ pub fn check_pat<'a, 'tcx>(pcx: &pat_ctxt<'a, 'tcx>,
                                         .collect();
             let pat_ty = tcx.mk_tup(element_tys.clone());
             fcx.write_ty(pat.id, pat_ty);
-            demand::eqtype(fcx, pat.span, expected, pat_ty);
+            demand_pat_eqtype(pcx, pat, expected, pat_ty);
             for (element_pat, element_ty) in elements.iter().zip(element_tys) {
                 check_pat(pcx, &**element_pat, element_ty);
             }
@@ -280,7 +281,7 @@ pub fn check_pat<'a, 'tcx>(pcx: &pat_ctxt<'a, 'tcx>,
                         let mt = ty::TypeAndMut { ty: inner_ty, mutbl: mutbl };
                         let region = fcx.infcx().next_region_var(infer::PatternRegion(pat.span));
                         let rptr_ty = tcx.mk_ref(tcx.mk_region(region), mt);
-                        demand::eqtype(fcx, pat.span, expected, rptr_ty);
+                        demand_pat_eqtype(pcx, pat, expected, rptr_ty);
                         (rptr_ty, inner_ty)
                     }
                 };
@@ -533,6 +534,72 @@ pub fn check_match<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
     fcx.write_ty(expr.id, result_ty);
 }
 
+/// Requires that the type `pat_ty` of the pattern `pat` be equal to
+/// `expected`. On a mismatch, in addition to the type error, suggest
+/// the `&`, `&mut` or `ref` that would make the pattern match.
+fn demand_pat_eqtype<'a, 'tcx>(pcx: &pat_ctxt<'a, 'tcx>,
+                               pat: &hir::Pat,
+                               expected: Ty<'tcx>,
+                               pat_ty: Ty<'tcx>) {
+    let fcx = pcx.fcx;
+    match infer::mk_eqty(fcx.infcx(), false, infer::Misc(pat.span), pat_ty, expected) {
+        Ok(()) => { /* ok */ }
+        Err(ref err) => {
+            fcx.report_mismatched_types(pat.span, expected, pat_ty, err);
+            suggest_pat_reference_fix(pcx, pat, expected, pat_ty);
+        }
+    }
+}
+
+fn suggest_pat_reference_fix<'a, 'tcx>(pcx: &pat_ctxt<'a, 'tcx>,
+                                       pat: &hir::Pat,
+                                       expected: Ty<'tcx>,
+                                       pat_ty: Ty<'tcx>) {
+    let fcx = pcx.fcx;
+    let sess = fcx.tcx().sess;
+    let expected = fcx.infcx().resolve_type_vars_if_possible(&expected);
+
+    match (&pat.node, &expected.sty) {
+        // `&x` matched against a value that is not a reference.
+        (&hir::PatRegion(ref inner, mutbl), _) if !expected.is_region_ptr() => {
+            match inner.node {
+                hir::PatIdent(hir::BindByValue(_), ref path1, None) => {
+                    let prefix = match mutbl {
+                        hir::MutMutable => "ref mut ",
+                        hir::MutImmutable => "ref ",
+                    };
+                    sess.span_suggestion(pat.span,
+                                         "to bind by reference, use `ref` instead of `&`:",
+                                         format!("{}{}", prefix, path1.node.name));
+                }
+                _ => {
+                    if let Ok(snippet) = sess.codemap().span_to_snippet(inner.span) {
+                        sess.span_suggestion(pat.span,
+                                             "the matched value is not a reference; \
+                                              consider removing the `&`:",
+                                             snippet);
+                    }
+                }
+            }
+        }
+        // A non-reference pattern matched against a reference to a
+        // value of the pattern's type.
+        (_, &ty::TyRef(_, mt)) if infer::can_mk_eqty(fcx.infcx(), mt.ty, pat_ty).is_ok() => {
+            if let Ok(snippet) = sess.codemap().span_to_snippet(pat.span) {
+                let prefix = match mt.mutbl {
+                    hir::MutMutable => "&mut ",
+                    hir::MutImmutable => "&",
+                };
+                sess.span_suggestion(pat.span,
+                                     "the matched value is a reference; \
+                                      consider matching through it:",
+                                     format!("{}{}", prefix, snippet));
+            }
+        }
+        _ => {}
+    }
+}
+
 pub struct pat_ctxt<'a, 'tcx: 'a> {
     pub fcx: &'a FnCtxt<'a, 'tcx>,
     pub map: PatIdMap,
@@ -565,7 +632,7 @@ pub fn check_pat_struct<'a, 'tcx>(pcx: &pat_ctxt<'a, 'tcx>, pat: &'tcx hir::Pat,
         ty::TyStruct(_, substs) | ty::TyEnum(_, substs) => substs,
         _ => tcx.sess.span_bug(pat.span, "struct variant is not an ADT")
     };
-    demand::eqtype(fcx, pat.span, expected, pat_ty);
+    demand_pat_eqtype(pcx, pat, expected, pat_ty);
     check_struct_pat_fields(pcx, pat.span, fields, variant, &item_substs, etc);
 
     fcx.write_ty(pat.id, pat_ty);
@@ -628,7 +695,7 @@ pub fn check_pat_enum<'a, 'tcx>(pcx: &pat_ctxt<'a, 'tcx>,
     }
 
     let pat_ty = fcx.node_ty(pat.id);
-    demand::eqtype(fcx, pat.span, expected, pat_ty);
+    demand_pat_eqtype(pcx, pat, expected, pat_ty);
 
 
     let real_path_ty = fcx.node_ty(pat.id);
@@ -740,6 +807,12 @@ pub fn check_struct_pat_fields<'a, 'tcx>(pcx: &pat_ctxt<'a, 'tcx>,
                             "struct `{}` does not have a field named `{}`",
                             tcx.item_path_str(variant.did),
                             field.name);
+                        // don't suggest the fields the pattern binds already
+                        let skip_fields = fields.iter().map(|f| f.node.name.as_str());
+                        suggest_field_names(variant,
+                                            &Spanned { node: field.name, span: span },
+                                            tcx,
+                                            skip_fields.collect());
                         tcx.types.err
                     })
             }
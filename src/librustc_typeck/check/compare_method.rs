@@ -8,15 +8,21 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use front::map as hir_map;
+use middle::def_id::DefId;
 use middle::free_region::FreeRegionMap;
 use middle::infer;
 use middle::traits;
 use middle::ty::{self};
 use middle::subst::{self, Subst, Substs, VecPerParamSpace};
 
+use rustc_front::hir;
 use syntax::ast;
 use syntax::codemap::Span;
 
+use std::cmp::max;
+use std::iter::repeat;
+
 use super::assoc;
 
 /// Checks that a method from an impl conforms to the signature of
@@ -338,6 +344,14 @@ pub fn compare_impl_method<'tcx>(tcx: &ty::ctxt<'tcx>,
                       "method `{}` has an incompatible type for trait: {}",
                       trait_m.name,
                       terr);
+            note_sig_differences(&infcx,
+                                 &mut fulfillment_cx,
+                                 impl_m,
+                                 impl_m_span,
+                                 impl_m_body_id,
+                                 trait_m,
+                                 impl_to_skol_substs,
+                                 &trait_to_skol_substs);
             return;
         }
     }
@@ -408,6 +422,184 @@ pub fn compare_impl_method<'tcx>(tcx: &ty::ctxt<'tcx>,
     }
 }
 
+/// A parameter or the return type in the signature of a method.
+#[derive(Copy, Clone, PartialEq)]
+enum SigPosition {
+    Input(usize),
+    Output,
+}
+
+impl SigPosition {
+    fn describe(&self) -> String {
+        match *self {
+            SigPosition::Input(i) => format!("parameter {}", i + 1),
+            SigPosition::Output => "the return type".to_string(),
+        }
+    }
+}
+
+/// Explains an incompatible method signature (E0053): renders the signatures
+/// of the method in the trait and in the impl one above the other, marks the
+/// parameters and the return type in which they differ, and points at each
+/// of these in both declarations.
+fn note_sig_differences<'a, 'tcx>(infcx: &infer::InferCtxt<'a, 'tcx>,
+                                  fulfillment_cx: &mut traits::FulfillmentContext<'tcx>,
+                                  impl_m: &ty::Method<'tcx>,
+                                  impl_m_span: Span,
+                                  impl_m_body_id: ast::NodeId,
+                                  trait_m: &ty::Method<'tcx>,
+                                  impl_to_skol_substs: &Substs<'tcx>,
+                                  trait_to_skol_substs: &Substs<'tcx>) {
+    let tcx = infcx.tcx;
+    let impl_sig = impl_m.fty.sig.subst(tcx, impl_to_skol_substs);
+    let trait_sig = trait_m.fty.sig.subst(tcx, trait_to_skol_substs);
+
+    // Relate the types at each position on their own, the way the whole
+    // signatures are related in `compare_impl_method`.
+    let positions = (0..impl_sig.0.inputs.len()).map(SigPosition::Input)
+                                               .chain(Some(SigPosition::Output));
+    let differences = positions.filter(|&position| {
+        !infcx.probe(|snapshot| {
+            let origin = infer::MethodCompatCheck(impl_m_span);
+            let (impl_sig, _) =
+                infcx.replace_late_bound_regions_with_fresh_var(impl_m_span,
+                                                                infer::HigherRankedType,
+                                                                &impl_sig);
+            let impl_sig = assoc::normalize_associated_types_in(infcx,
+                                                                fulfillment_cx,
+                                                                impl_m_span,
+                                                                impl_m_body_id,
+                                                                &impl_sig);
+            let (trait_sig, skol_map) = infcx.skolemize_late_bound_regions(&trait_sig, snapshot);
+            let trait_sig = assoc::normalize_associated_types_in(infcx,
+                                                                 fulfillment_cx,
+                                                                 impl_m_span,
+                                                                 impl_m_body_id,
+                                                                 &trait_sig);
+            let related = match position {
+                SigPosition::Input(i) => {
+                    infer::mk_subty(infcx, false, origin, trait_sig.inputs[i], impl_sig.inputs[i])
+                }
+                SigPosition::Output => {
+                    match (impl_sig.output, trait_sig.output) {
+                        (ty::FnConverging(impl_ty), ty::FnConverging(trait_ty)) => {
+                            infer::mk_subty(infcx, false, origin, impl_ty, trait_ty)
+                        }
+                        (ty::FnDiverging, ty::FnDiverging) => Ok(()),
+                        _ => return false,
+                    }
+                }
+            };
+            related.is_ok() && infcx.leak_check(&skol_map, snapshot).is_ok()
+        })
+    }).collect::<Vec<_>>();
+
+    let mut columns = trait_sig.0.inputs.iter().zip(&impl_sig.0.inputs).enumerate()
+        .map(|(i, (trait_ty, impl_ty))| {
+            (SigPosition::Input(i), trait_ty.to_string(), impl_ty.to_string())
+        }).collect::<Vec<_>>();
+    let output = |output: ty::FnOutput| {
+        match output {
+            ty::FnConverging(ty) if ty.is_nil() => None,
+            ty::FnConverging(ty) => Some(ty.to_string()),
+            ty::FnDiverging => Some("!".to_string()),
+        }
+    };
+    match (output(trait_sig.0.output), output(impl_sig.0.output)) {
+        (None, None) => {}
+        (trait_output, impl_output) => {
+            columns.push((SigPosition::Output,
+                          trait_output.unwrap_or("()".to_string()),
+                          impl_output.unwrap_or("()".to_string())));
+        }
+    }
+
+    tcx.sess.fileline_note(impl_m_span,
+                           &format!("the signatures of `{}` in the trait and in the impl:\n{}",
+                                    trait_m.name,
+                                    render_aligned_sigs(&columns, &differences)));
+
+    for &(position, ref trait_text, ref impl_text) in &columns {
+        if !differences.contains(&position) {
+            continue;
+        }
+        if let Some(span) = sig_position_span(tcx, trait_m.def_id, position) {
+            tcx.sess.span_note(span,
+                               &format!("{} is `{}` in the trait",
+                                        position.describe(),
+                                        trait_text));
+        }
+        if let Some(span) = sig_position_span(tcx, impl_m.def_id, position) {
+            tcx.sess.span_note(span,
+                               &format!("{} is `{}` in the impl",
+                                        position.describe(),
+                                        impl_text));
+        }
+    }
+}
+
+/// Renders the signatures given by `columns`, the text of each parameter and
+/// of the return type in the trait and in the impl, with each column as wide
+/// as its longer text, and marks the columns of `differences` below them.
+fn render_aligned_sigs(columns: &[(SigPosition, String, String)],
+                       differences: &[SigPosition])
+                       -> String {
+    let mut trait_line = "trait: fn(".to_string();
+    let mut impl_line = "impl:  fn(".to_string();
+    let mut marks = repeat(' ').take(trait_line.len()).collect::<String>();
+    for (i, &(position, ref trait_text, ref impl_text)) in columns.iter().enumerate() {
+        let separator = match position {
+            SigPosition::Input(_) if i == 0 => "",
+            SigPosition::Input(_) => ", ",
+            SigPosition::Output => ") -> ",
+        };
+        trait_line.push_str(separator);
+        impl_line.push_str(separator);
+        marks.extend(repeat(' ').take(separator.len()));
+
+        let width = max(trait_text.chars().count(), impl_text.chars().count());
+        trait_line.push_str(&format!("{:1$}", trait_text, width));
+        impl_line.push_str(&format!("{:1$}", impl_text, width));
+        let mark = if differences.contains(&position) { '^' } else { ' ' };
+        marks.extend(repeat(mark).take(width));
+    }
+    if columns.last().map_or(true, |&(position, _, _)| position != SigPosition::Output) {
+        trait_line.push(')');
+        impl_line.push(')');
+    }
+
+    let mut rendered = format!("{}\n{}", trait_line.trim_right(), impl_line.trim_right());
+    if !marks.trim().is_empty() {
+        rendered.push('\n');
+        rendered.push_str(marks.trim_right());
+    }
+    rendered
+}
+
+/// The span of the type at `position` in the declaration of the method
+/// `def_id`, if it is declared in this crate.
+fn sig_position_span(tcx: &ty::ctxt, def_id: DefId, position: SigPosition) -> Option<Span> {
+    let decl = match tcx.map.as_local_node_id(def_id).map(|id| tcx.map.get(id)) {
+        Some(hir_map::NodeImplItem(item)) => {
+            match item.node {
+                hir::ImplItemKind::Method(ref sig, _) => &sig.decl,
+                _ => return None,
+            }
+        }
+        Some(hir_map::NodeTraitItem(item)) => {
+            match item.node {
+                hir::MethodTraitItem(ref sig, _) => &sig.decl,
+                _ => return None,
+            }
+        }
+        _ => return None,
+    };
+    match position {
+        SigPosition::Input(i) => decl.inputs.get(i).map(|arg| arg.ty.span),
+        SigPosition::Output => Some(decl.output.span()),
+    }
+}
+
 pub fn compare_const_impl<'tcx>(tcx: &ty::ctxt<'tcx>,
                                 impl_c: &ty::AssociatedConst<'tcx>,
                                 impl_c_span: Span,
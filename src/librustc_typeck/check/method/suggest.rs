@@ -23,6 +23,7 @@ use middle::lang_items::FnOnceTraitLangItem;
 use middle::subst::Substs;
 use middle::traits::{Obligation, SelectionContext};
 use metadata::{csearch, cstore, decoder};
+use util::lev_distance::lev_distance;
 use util::nodemap::{FnvHashSet};
 
 use syntax::ast;
@@ -31,7 +32,7 @@ use rustc_front::print::pprust;
 use rustc_front::hir;
 
 use std::cell;
-use std::cmp::Ordering;
+use std::cmp::{self, Ordering};
 
 use super::{MethodError, NoMatchData, CandidateSource, impl_item, trait_item};
 use super::probe::Mode;
@@ -130,6 +131,11 @@ pub fn report_error<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
                 }
             }
 
+            // `Enum::Item` may be a misspelled variant
+            if let (&ty::TyEnum(def, _), Mode::Path) = (&rcvr_ty.sty, mode) {
+                suggest_similar_variant(fcx, span, def, item_name);
+            }
+
             if !static_sources.is_empty() {
                 cx.sess.fileline_note(
                     span,
@@ -232,6 +238,29 @@ pub fn report_error<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
     }
 }
 
+/// Suggests the variant of `def` whose name is closest to `item_name`, the
+/// name in an `Enum::item` path that is no variant or associated item.
+fn suggest_similar_variant<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
+                                     span: Span,
+                                     def: ty::AdtDef<'tcx>,
+                                     item_name: ast::Name) {
+    let name = item_name.as_str();
+    // Only accept an edit distance of up to half the length of the name, to
+    // avoid suggesting unrelated variants. This is looser than in resolve,
+    // since there are few variants to pick from, and swapping two letters
+    // (`Smoe`) is two edits already.
+    let max_distance = cmp::max(name.len(), 3) / 2;
+    let best = def.variants.iter()
+                           .map(|variant| (lev_distance(&name, &variant.name.as_str()), variant))
+                           .filter(|&(dist, _)| dist <= max_distance)
+                           .min_by(|&(dist, _)| dist);
+    if let Some((_, variant)) = best {
+        fcx.sess().span_help(span, &format!("did you mean the variant `{}::{}`?",
+                                            fcx.tcx().item_path_str(def.did),
+                                            variant.name));
+    }
+}
+
 
 pub type AllTraitsVec = Vec<TraitInfo>;
 
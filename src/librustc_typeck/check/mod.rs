@@ -83,12 +83,14 @@ use self::TupleArgumentsFlag::*;
 use astconv::{self, ast_region_to_region, ast_ty_to_ty, AstConv, PathParamMode};
 use check::_match::pat_ctxt;
 use fmt_macros::{Parser, Piece, Position};
+use front::map as hir_map;
 use metadata::cstore::LOCAL_CRATE;
 use middle::astconv_util::prohibit_type_params;
 use middle::def;
 use middle::def_id::DefId;
 use middle::infer;
 use middle::infer::type_variable;
+use middle::module_times::{self, Pass};
 use middle::pat_util::{self, pat_id_map};
 use middle::privacy::{AllPublic, LastMod};
 use middle::subst::{self, Subst, Substs, VecPerParamSpace, ParamSpace, TypeSpace};
@@ -101,7 +103,7 @@ use middle::ty::{MethodCall, MethodCallee};
 use middle::ty::adjustment;
 use middle::ty::error::TypeError;
 use middle::ty::fold::{TypeFolder, TypeFoldable};
-use middle::ty::util::Representability;
+use middle::ty::util::{IntTypeExt, Representability};
 use require_c_abi_if_variadic;
 use rscope::{ElisionFailureInfo, RegionScope};
 use session::Session;
@@ -362,7 +364,7 @@ struct CheckItemBodiesVisitor<'a, 'tcx: 'a> { ccx: &'a CrateCtxt<'a, 'tcx> }
 
 impl<'a, 'tcx> Visitor<'tcx> for CheckItemTypesVisitor<'a, 'tcx> {
     fn visit_item(&mut self, i: &'tcx hir::Item) {
-        check_item_type(self.ccx, i);
+        module_times::time_item(self.ccx.tcx, Pass::Typeck, i.id, || check_item_type(self.ccx, i));
         intravisit::walk_item(self, i);
     }
 
@@ -380,7 +382,7 @@ impl<'a, 'tcx> Visitor<'tcx> for CheckItemTypesVisitor<'a, 'tcx> {
 
 impl<'a, 'tcx> Visitor<'tcx> for CheckItemBodiesVisitor<'a, 'tcx> {
     fn visit_item(&mut self, i: &'tcx hir::Item) {
-        check_item_body(self.ccx, i);
+        module_times::time_item(self.ccx.tcx, Pass::Typeck, i.id, || check_item_body(self.ccx, i));
     }
 }
 
@@ -677,8 +679,10 @@ pub fn check_item_type<'a,'tcx>(ccx: &CrateCtxt<'a,'tcx>, it: &'tcx hir::Item) {
     let _indenter = indenter();
     match it.node {
       // Consts can play a role in type-checking, so they are included here.
-      hir::ItemStatic(_, _, ref e) |
-      hir::ItemConst(_, ref e) => check_const(ccx, it.span, &**e, it.id),
+      hir::ItemStatic(..) |
+      hir::ItemConst(..) => {
+        typeck_item_body(ccx, ccx.tcx.map.local_def_id(it.id));
+      }
       hir::ItemEnum(ref enum_definition, _) => {
         check_enum_variants(ccx,
                             it.span,
@@ -744,24 +748,17 @@ pub fn check_item_body<'a,'tcx>(ccx: &CrateCtxt<'a,'tcx>, it: &'tcx hir::Item) {
            ccx.tcx.item_path_str(ccx.tcx.map.local_def_id(it.id)));
     let _indenter = indenter();
     match it.node {
-      hir::ItemFn(ref decl, _, _, _, _, ref body) => {
-        let fn_pty = ccx.tcx.lookup_item_type(ccx.tcx.map.local_def_id(it.id));
-        let param_env = ParameterEnvironment::for_item(ccx.tcx, it.id);
-        check_bare_fn(ccx, &**decl, &**body, it.id, it.span, fn_pty.ty, param_env);
+      hir::ItemFn(..) => {
+        typeck_item_body(ccx, ccx.tcx.map.local_def_id(it.id));
       }
       hir::ItemImpl(_, _, _, _, _, ref impl_items) => {
         debug!("ItemImpl {} with id {}", it.name, it.id);
 
-        let impl_pty = ccx.tcx.lookup_item_type(ccx.tcx.map.local_def_id(it.id));
-
         for impl_item in impl_items {
             match impl_item.node {
-                hir::ImplItemKind::Const(_, ref expr) => {
-                    check_const(ccx, impl_item.span, &*expr, impl_item.id)
-                }
-                hir::ImplItemKind::Method(ref sig, ref body) => {
-                    check_method_body(ccx, &impl_pty.generics, sig, body,
-                                      impl_item.id, impl_item.span);
+                hir::ImplItemKind::Const(..) |
+                hir::ImplItemKind::Method(..) => {
+                    typeck_item_body(ccx, ccx.tcx.map.local_def_id(impl_item.id));
                 }
                 hir::ImplItemKind::Type(_) => {
                     // Nothing to do here.
@@ -770,17 +767,14 @@ pub fn check_item_body<'a,'tcx>(ccx: &CrateCtxt<'a,'tcx>, it: &'tcx hir::Item) {
         }
       }
       hir::ItemTrait(_, _, _, ref trait_items) => {
-        let trait_def = ccx.tcx.lookup_trait_def(ccx.tcx.map.local_def_id(it.id));
         for trait_item in trait_items {
             match trait_item.node {
-                hir::ConstTraitItem(_, Some(ref expr)) => {
-                    check_const(ccx, trait_item.span, &*expr, trait_item.id)
+                hir::ConstTraitItem(_, Some(_)) => {
+                    typeck_item_body(ccx, ccx.tcx.map.local_def_id(trait_item.id));
                 }
-                hir::MethodTraitItem(ref sig, Some(ref body)) => {
+                hir::MethodTraitItem(ref sig, Some(_)) => {
                     check_trait_fn_not_const(ccx, trait_item.span, sig.constness);
-
-                    check_method_body(ccx, &trait_def.generics, sig, body,
-                                      trait_item.id, trait_item.span);
+                    typeck_item_body(ccx, ccx.tcx.map.local_def_id(trait_item.id));
                 }
                 hir::MethodTraitItem(ref sig, None) => {
                     check_trait_fn_not_const(ccx, trait_item.span, sig.constness);
@@ -796,6 +790,64 @@ pub fn check_item_body<'a,'tcx>(ccx: &CrateCtxt<'a,'tcx>, it: &'tcx hir::Item) {
     }
 }
 
+/// Type-checks the body of the fn, method, static or constant
+/// `def_id`, writing the inferred types into `tcx.tables`. Each body
+/// is checked at most once, as recorded in `tcx.checked_bodies`, so a
+/// single body can also be checked on demand without walking the whole
+/// crate.
+pub fn typeck_item_body<'a,'tcx>(ccx: &CrateCtxt<'a,'tcx>, def_id: DefId) {
+    if !ccx.tcx.checked_bodies.borrow_mut().insert(def_id) {
+        return;
+    }
+
+    let node_id = match ccx.tcx.map.as_local_node_id(def_id) {
+        Some(node_id) => node_id,
+        None => ccx.tcx.sess.bug(&format!("typeck_item_body: non-local def-id {:?}",
+                                          def_id))
+    };
+
+    debug!("typeck_item_body(def_id={:?})", def_id);
+    let body_checks = &ccx.tcx.sess.perf_stats.body_checks;
+    body_checks.set(body_checks.get() + 1);
+
+    match ccx.tcx.map.get(node_id) {
+        hir_map::NodeItem(it) => match it.node {
+            hir::ItemFn(ref decl, _, _, _, _, ref body) => {
+                let fn_pty = ccx.tcx.lookup_item_type(def_id);
+                let param_env = ParameterEnvironment::for_item(ccx.tcx, it.id);
+                check_bare_fn(ccx, &**decl, &**body, it.id, it.span, fn_pty.ty, param_env);
+            }
+            hir::ItemStatic(_, _, ref e) |
+            hir::ItemConst(_, ref e) => check_const(ccx, it.span, &**e, it.id),
+            _ => ccx.tcx.sess.span_bug(it.span, "typeck_item_body: item without a body")
+        },
+        hir_map::NodeImplItem(impl_item) => match impl_item.node {
+            hir::ImplItemKind::Const(_, ref expr) => {
+                check_const(ccx, impl_item.span, &*expr, impl_item.id)
+            }
+            hir::ImplItemKind::Method(ref sig, ref body) => {
+                check_method_body(ccx, sig, body, impl_item.id, impl_item.span);
+            }
+            hir::ImplItemKind::Type(_) => {
+                ccx.tcx.sess.span_bug(impl_item.span,
+                                      "typeck_item_body: associated type has no body")
+            }
+        },
+        hir_map::NodeTraitItem(trait_item) => match trait_item.node {
+            hir::ConstTraitItem(_, Some(ref expr)) => {
+                check_const(ccx, trait_item.span, &*expr, trait_item.id)
+            }
+            hir::MethodTraitItem(ref sig, Some(ref body)) => {
+                check_method_body(ccx, sig, body, trait_item.id, trait_item.span);
+            }
+            _ => ccx.tcx.sess.span_bug(trait_item.span,
+                                       "typeck_item_body: trait item without a default")
+        },
+        _ => ccx.tcx.sess.bug(&format!("typeck_item_body: unexpected node {}",
+                                       ccx.tcx.map.node_to_string(node_id)))
+    }
+}
+
 fn check_trait_fn_not_const<'a,'tcx>(ccx: &CrateCtxt<'a, 'tcx>,
                                      span: Span,
                                      constness: hir::Constness)
@@ -854,21 +906,13 @@ fn check_trait_on_unimplemented<'a, 'tcx>(ccx: &CrateCtxt<'a, 'tcx>,
     }
 }
 
-/// Type checks a method body.
-///
-/// # Parameters
-///
-/// * `item_generics`: generics defined on the impl/trait that contains
-///   the method
-/// * `self_bound`: bound for the `Self` type parameter, if any
-/// * `method`: the method definition
+/// Type checks the body of the method `id`, which may be an inherent or
+/// trait impl method or a default method of a trait.
 fn check_method_body<'a, 'tcx>(ccx: &CrateCtxt<'a, 'tcx>,
-                               item_generics: &ty::Generics<'tcx>,
                                sig: &'tcx hir::MethodSig,
                                body: &'tcx hir::Block,
                                id: ast::NodeId, span: Span) {
-    debug!("check_method_body(item_generics={:?}, id={})",
-            item_generics, id);
+    debug!("check_method_body(id={})", id);
     let param_env = ParameterEnvironment::for_item(ccx.tcx, id);
 
     let fty = ccx.tcx.node_id_to_type(id);
@@ -2780,6 +2824,37 @@ fn expected_types_for_fn_args<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
     expected_args
 }
 
+// displays hints about the closest matches in field names
+fn suggest_field_names<'tcx>(variant: ty::VariantDef<'tcx>,
+                             field: &Spanned<ast::Name>,
+                             tcx: &ty::ctxt<'tcx>,
+                             skip : Vec<InternedString>) {
+    let name = field.node.as_str();
+    // only find fits with at least one matching letter
+    let mut best_dist = name.len();
+    let mut best = None;
+    for elem in &variant.fields {
+        let n = elem.name.as_str();
+        // ignore already set fields
+        if skip.iter().any(|x| *x == n) {
+            continue;
+        }
+        // ignore private fields from non-local crates
+        if variant.did.krate != LOCAL_CRATE && elem.vis != Visibility::Public {
+            continue;
+        }
+        let dist = lev_distance(&n, &name);
+        if dist < best_dist {
+            best = Some(n);
+            best_dist = dist;
+        }
+    }
+    if let Some(n) = best {
+        tcx.sess.span_help(field.span,
+            &format!("did you mean `{}`?", n));
+    }
+}
+
 /// Invariant:
 /// If an expression has any sub-expressions that result in a type error,
 /// inspecting that expression's type with `ty.references_error()` will return
@@ -2962,37 +3037,6 @@ fn check_expr_with_unifier<'a, 'tcx, F>(fcx: &FnCtxt<'a, 'tcx>,
         fcx.write_error(expr.id);
     }
 
-    // displays hints about the closest matches in field names
-    fn suggest_field_names<'tcx>(variant: ty::VariantDef<'tcx>,
-                                 field: &Spanned<ast::Name>,
-                                 tcx: &ty::ctxt<'tcx>,
-                                 skip : Vec<InternedString>) {
-        let name = field.node.as_str();
-        // only find fits with at least one matching letter
-        let mut best_dist = name.len();
-        let mut best = None;
-        for elem in &variant.fields {
-            let n = elem.name.as_str();
-            // ignore already set fields
-            if skip.iter().any(|x| *x == n) {
-                continue;
-            }
-            // ignore private fields from non-local crates
-            if variant.did.krate != LOCAL_CRATE && elem.vis != Visibility::Public {
-                continue;
-            }
-            let dist = lev_distance(&n, &name);
-            if dist < best_dist {
-                best = Some(n);
-                best_dist = dist;
-            }
-        }
-        if let Some(n) = best {
-            tcx.sess.span_help(field.span,
-                &format!("did you mean `{}`?", n));
-        }
-    }
-
     // Check tuple index expressions
     fn check_tup_field<'a,'tcx>(fcx: &FnCtxt<'a,'tcx>,
                                 expr: &'tcx hir::Expr,
@@ -3992,7 +4036,8 @@ fn check_block_with_expected<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
         replace(&mut *fcx_ps, unsafety_state)
     };
 
-    let mut warned = false;
+    // With `-Z mir-lints`, unreachable code is reported from the MIR.
+    let mut warned = fcx.tcx().sess.opts.debugging_opts.mir_lints;
     let mut any_diverges = false;
     let mut any_err = false;
     for s in &blk.stmts {
@@ -4168,33 +4213,6 @@ pub fn check_enum_variants<'a,'tcx>(ccx: &CrateCtxt<'a,'tcx>,
                                     vs: &'tcx [P<hir::Variant>],
                                     id: ast::NodeId) {
 
-    fn disr_in_range(ccx: &CrateCtxt,
-                     ty: attr::IntType,
-                     disr: ty::Disr) -> bool {
-        fn uint_in_range(ccx: &CrateCtxt, ty: ast::UintTy, disr: ty::Disr) -> bool {
-            match ty {
-                ast::TyU8 => disr as u8 as Disr == disr,
-                ast::TyU16 => disr as u16 as Disr == disr,
-                ast::TyU32 => disr as u32 as Disr == disr,
-                ast::TyU64 => disr as u64 as Disr == disr,
-                ast::TyUs => uint_in_range(ccx, ccx.tcx.sess.target.uint_type, disr)
-            }
-        }
-        fn int_in_range(ccx: &CrateCtxt, ty: ast::IntTy, disr: ty::Disr) -> bool {
-            match ty {
-                ast::TyI8 => disr as i8 as Disr == disr,
-                ast::TyI16 => disr as i16 as Disr == disr,
-                ast::TyI32 => disr as i32 as Disr == disr,
-                ast::TyI64 => disr as i64 as Disr == disr,
-                ast::TyIs => int_in_range(ccx, ccx.tcx.sess.target.int_type, disr)
-            }
-        }
-        match ty {
-            attr::UnsignedInt(ty) => uint_in_range(ccx, ty, disr),
-            attr::SignedInt(ty) => int_in_range(ccx, ty, disr)
-        }
-    }
-
     fn do_check<'a, 'tcx>(ccx: &CrateCtxt<'a, 'tcx>,
                           vs: &'tcx [P<hir::Variant>],
                           id: ast::NodeId,
@@ -4202,7 +4220,6 @@ pub fn check_enum_variants<'a,'tcx>(ccx: &CrateCtxt<'a,'tcx>,
         #![allow(trivial_numeric_casts)]
 
         let rty = ccx.tcx.node_id_to_type(id);
-        let mut disr_vals: Vec<ty::Disr> = Vec::new();
 
         let tables = RefCell::new(ty::Tables::empty());
         let inh = static_inherited_fields(ccx, &tables);
@@ -4216,19 +4233,41 @@ pub fn check_enum_variants<'a,'tcx>(ccx: &CrateCtxt<'a,'tcx>,
         }
 
         let def_id = ccx.tcx.map.local_def_id(id);
-
+        let (repr_type, disr_vals) = ccx.tcx.enum_discriminants(def_id);
         let variants = &ccx.tcx.lookup_adt_def(def_id).variants;
-        for (v, variant) in vs.iter().zip(variants.iter()) {
-            let current_disr_val = variant.disr_val;
 
+        // The span of the discriminant of a variant: its explicit
+        // discriminant expression, or the whole variant if implicit.
+        fn disr_span(v: &hir::Variant) -> Span {
+            v.node.disr_expr.as_ref().map_or(v.span, |e| e.span)
+        }
+        // Values are displayed as evaluated rather than truncated to
+        // the representation, so that out-of-range values (including
+        // negative values for an unsigned representation) read the
+        // same as in the source.
+        let disr_string = |val: ty::Disr| {
+            match repr_type {
+                attr::UnsignedInt(_) if repr_type.disr_in_range(val) => format!("{}", val),
+                _ => format!("{}", val as i64),
+            }
+        };
+
+        for (i, (v, &current_disr_val)) in vs.iter().zip(disr_vals.iter()).enumerate() {
             // Check for duplicate discriminant values
-            match disr_vals.iter().position(|&x| x == current_disr_val) {
-                Some(i) => {
-                    span_err!(ccx.tcx.sess, v.span, E0081,
-                        "discriminant value `{}` already exists", disr_vals[i]);
-                    let variant_i_node_id = ccx.tcx.map.as_local_node_id(variants[i].did).unwrap();
-                    span_note!(ccx.tcx.sess, ccx.tcx.map.span(variant_i_node_id),
-                        "conflicting discriminant here")
+            match disr_vals[..i].iter().position(|&x| x == current_disr_val) {
+                Some(j) => {
+                    span_err!(ccx.tcx.sess, disr_span(v), E0081,
+                        "discriminant value `{}` already exists",
+                        disr_string(current_disr_val));
+                    let variant_j_node_id = ccx.tcx.map.as_local_node_id(variants[j].did)
+                                                       .unwrap();
+                    let first_span = match ccx.tcx.map.get(variant_j_node_id) {
+                        hir_map::NodeVariant(first) => disr_span(first),
+                        _ => ccx.tcx.map.span(variant_j_node_id),
+                    };
+                    span_note!(ccx.tcx.sess, first_span,
+                        "first use of `{}` here (by variant `{}`)",
+                        disr_string(current_disr_val), variants[j].name)
                 }
                 None => {}
             }
@@ -4236,9 +4275,11 @@ pub fn check_enum_variants<'a,'tcx>(ccx: &CrateCtxt<'a,'tcx>,
             match hint {
                 attr::ReprAny | attr::ReprExtern => (),
                 attr::ReprInt(sp, ity) => {
-                    if !disr_in_range(ccx, ity, current_disr_val) {
-                        span_err!(ccx.tcx.sess, v.span, E0082,
-                            "discriminant value outside specified type");
+                    if !repr_type.disr_in_range(current_disr_val) {
+                        span_err!(ccx.tcx.sess, disr_span(v), E0082,
+                            "discriminant value `{}` does not fit in `{}`",
+                            disr_string(current_disr_val),
+                            ity.to_ty(ccx.tcx));
                         span_note!(ccx.tcx.sess, sp,
                             "discriminant type specified here");
                     }
@@ -4250,7 +4291,6 @@ pub fn check_enum_variants<'a,'tcx>(ccx: &CrateCtxt<'a,'tcx>,
                     ccx.tcx.sess.bug("range_to_inttype: found ReprPacked on an enum");
                 }
             }
-            disr_vals.push(current_disr_val);
         }
     }
 
@@ -36,6 +36,7 @@ use CrateCtxt;
 use middle::infer::{self, InferCtxt, new_infer_ctxt};
 use std::cell::RefCell;
 use std::rc::Rc;
+use syntax::attr;
 use syntax::codemap::Span;
 use syntax::parse::token;
 use util::nodemap::{DefIdMap, FnvHashMap};
@@ -297,21 +298,39 @@ impl<'a, 'tcx> CoherenceChecker<'a, 'tcx> {
             debug!("check_implementations_of_copy: self_type={:?} (free)",
                    self_type);
 
+            // A derived impl has no code of its own to point at, so point
+            // at the field that is in the way instead.
+            let derived = attr::contains_name(tcx.map.attrs(impl_node_id),
+                                              "automatically_derived");
+            let field_span = |field_did| {
+                if derived {
+                    derived_field_span(tcx, self_type, field_did).unwrap_or(span)
+                } else {
+                    span
+                }
+            };
+
             match param_env.can_type_implement_copy(self_type, span) {
                 Ok(()) => {}
-                Err(CopyImplementationError::InfrigingField(name)) => {
-                       span_err!(tcx.sess, span, E0204,
+                Err(CopyImplementationError::InfrigingField(field_did, name)) => {
+                       span_err!(tcx.sess, field_span(field_did), E0204,
                                  "the trait `Copy` may not be \
                                           implemented for this type; field \
                                           `{}` does not implement `Copy`",
-                                         name)
+                                         name);
+                       if derived {
+                           tcx.sess.span_note(span, "the trait `Copy` is derived here");
+                       }
                 }
-                Err(CopyImplementationError::InfrigingVariant(name)) => {
-                       span_err!(tcx.sess, span, E0205,
+                Err(CopyImplementationError::InfrigingVariant(field_did, name)) => {
+                       span_err!(tcx.sess, field_span(field_did), E0205,
                                  "the trait `Copy` may not be \
                                           implemented for this type; variant \
                                           `{}` does not implement `Copy`",
-                                         name)
+                                         name);
+                       if derived {
+                           tcx.sess.span_note(span, "the trait `Copy` is derived here");
+                       }
                 }
                 Err(CopyImplementationError::NotAnAdt) => {
                        span_err!(tcx.sess, span, E0206,
@@ -486,6 +505,32 @@ impl<'a, 'tcx> CoherenceChecker<'a, 'tcx> {
     }
 }
 
+/// The span of the field `field_did` in the definition of `self_type`, if
+/// both are in this crate.
+fn derived_field_span(tcx: &ty::ctxt, self_type: Ty, field_did: DefId) -> Option<Span> {
+    let adt_id = match self_type.ty_adt_def() {
+        Some(adt) => match tcx.map.as_local_node_id(adt.did) {
+            Some(id) => id,
+            None => return None,
+        },
+        None => return None,
+    };
+    let field_id = match tcx.map.as_local_node_id(field_did) {
+        Some(id) => id,
+        None => return None,
+    };
+    let find = |fields: &[hir::StructField]| {
+        fields.iter().find(|f| f.node.id == field_id).map(|f| f.span)
+    };
+    match tcx.map.expect_item(adt_id).node {
+        hir::ItemStruct(ref data, _) => find(data.fields()),
+        hir::ItemEnum(ref def, _) => {
+            def.variants.iter().filter_map(|v| find(v.node.data.fields())).next()
+        }
+        _ => None,
+    }
+}
+
 fn enforce_trait_manually_implementable(tcx: &ty::ctxt, sp: Span, trait_def_id: DefId) {
     if tcx.sess.features.borrow().unboxed_closures {
         // the feature gate allows all of them
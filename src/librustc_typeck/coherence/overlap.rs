@@ -15,7 +15,10 @@ use metadata::cstore::LOCAL_CRATE;
 use middle::def_id::DefId;
 use middle::traits;
 use middle::ty;
+use middle::ty::fast_reject::{self, SimplifiedType};
 use middle::infer::{self, new_infer_ctxt};
+use std::cell::RefCell;
+use std::rc::Rc;
 use syntax::ast;
 use syntax::codemap::Span;
 use rustc_front::hir;
@@ -23,7 +26,9 @@ use rustc_front::intravisit;
 use util::nodemap::DefIdMap;
 
 pub fn check(tcx: &ty::ctxt) {
-    let mut overlap = OverlapChecker { tcx: tcx, default_impls: DefIdMap() };
+    let mut overlap = OverlapChecker { tcx: tcx,
+                                       default_impls: DefIdMap(),
+                                       impl_fingerprints: RefCell::new(DefIdMap()) };
     overlap.check_for_overlapping_impls();
 
     // this secondary walk specifically checks for some other cases,
@@ -36,6 +41,10 @@ struct OverlapChecker<'cx, 'tcx:'cx> {
 
     // maps from a trait def-id to an impl id
     default_impls: DefIdMap<ast::NodeId>,
+
+    // maps from an impl def-id to the simplified form of each of the
+    // input types of its trait reference; see `impl_fingerprint`
+    impl_fingerprints: RefCell<DefIdMap<Rc<Vec<Option<SimplifiedType>>>>>,
 }
 
 impl<'cx, 'tcx> OverlapChecker<'cx, 'tcx> {
@@ -133,6 +142,11 @@ impl<'cx, 'tcx> OverlapChecker<'cx, 'tcx> {
                    impl1_def_id,
                    impl2_def_id);
 
+            if self.fast_reject_impls(impl1_def_id, impl2_def_id) {
+                debug!("check_if_impls_overlap: fast rejected");
+                return;
+            }
+
             let infcx = infer::new_infer_ctxt(self.tcx, &self.tcx.tables, None, false);
             if traits::overlapping_impls(&infcx, impl1_def_id, impl2_def_id) {
                 self.report_overlap_error(trait_def_id, impl1_def_id, impl2_def_id);
@@ -140,6 +154,39 @@ impl<'cx, 'tcx> OverlapChecker<'cx, 'tcx> {
         }
     }
 
+    /// Returns the simplified form of each input type of the trait
+    /// reference of `impl_def_id`. Each impl takes part in many
+    /// pairwise comparisons, so the result is computed only once.
+    fn impl_fingerprint(&self, impl_def_id: DefId) -> Rc<Vec<Option<SimplifiedType>>> {
+        if let Some(fingerprint) = self.impl_fingerprints.borrow().get(&impl_def_id) {
+            return fingerprint.clone();
+        }
+
+        let trait_ref = self.tcx.impl_trait_ref(impl_def_id).unwrap();
+        let fingerprint: Rc<Vec<_>> = Rc::new(
+            trait_ref.input_types()
+                     .iter()
+                     .map(|&ty| fast_reject::simplify_type(self.tcx, ty, false))
+                     .collect());
+        self.impl_fingerprints.borrow_mut().insert(impl_def_id, fingerprint.clone());
+        fingerprint
+    }
+
+    /// True if the two impls certainly do not overlap because some
+    /// input type of their trait references (not only the self type,
+    /// which is already used to group the impls) can never unify.
+    /// This lets us skip creating an inference context for the vast
+    /// majority of pairs, e.g. for the many `From<T> for X` impls.
+    fn fast_reject_impls(&self, impl1_def_id: DefId, impl2_def_id: DefId) -> bool {
+        let fingerprint1 = self.impl_fingerprint(impl1_def_id);
+        let fingerprint2 = self.impl_fingerprint(impl2_def_id);
+        fingerprint1.iter()
+                    .zip(fingerprint2.iter())
+                    .any(|(simp1, simp2)| {
+                        simp1.is_some() && simp2.is_some() && simp1 != simp2
+                    })
+    }
+
     fn report_overlap_error(&self, trait_def_id: DefId,
                             impl1: DefId, impl2: DefId) {
 
@@ -1163,6 +1163,7 @@ fn convert_enum_def<'tcx>(tcx: &ty::ctxt<'tcx>,
                 span_err!(tcx.sess, err.span, E0080,
                           "constant evaluation error: {}",
                           err.description());
+                err.note(tcx.sess);
                 if !e.span.contains(err.span) {
                     tcx.sess.span_note(e.span, "for enum discriminant here");
                 }
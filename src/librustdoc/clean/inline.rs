@@ -122,6 +122,7 @@ fn try_inline_def(cx: &DocContext, tcx: &ty::ctxt,
         inner: inner,
         visibility: Some(hir::Public),
         stability: stability::lookup(tcx, did).clean(cx),
+        deprecation: stability::lookup_deprecation(tcx, did).clean(cx),
         def_id: did,
     });
     Some(ret)
@@ -140,8 +141,15 @@ pub fn load_attrs(cx: &DocContext, tcx: &ty::ctxt,
 pub fn record_extern_fqn(cx: &DocContext, did: DefId, kind: clean::TypeKind) {
     match cx.tcx_opt() {
         Some(tcx) => {
-            let fqn = csearch::get_item_path(tcx, did);
-            let fqn = fqn.into_iter().map(|i| i.to_string()).collect();
+            // Items only reachable through re-exports are documented at
+            // one of those instead of where they are defined.
+            let fqn = match cx.visible_paths.get(&did) {
+                Some(path) => path.iter().map(|name| name.to_string()).collect(),
+                None => {
+                    let fqn = csearch::get_item_path(tcx, did);
+                    fqn.into_iter().map(|i| i.to_string()).collect()
+                }
+            };
             cx.external_paths.borrow_mut().as_mut().unwrap().insert(did, (fqn, kind));
         }
         None => {}
@@ -310,6 +318,7 @@ pub fn build_impl(cx: &DocContext,
             attrs: attrs,
             visibility: Some(hir::Inherited),
             stability: stability::lookup(tcx, did).clean(cx),
+            deprecation: stability::lookup_deprecation(tcx, did).clean(cx),
             def_id: did,
         });
     }
@@ -340,6 +349,7 @@ pub fn build_impl(cx: &DocContext,
                     attrs: vec![],
                     visibility: None,
                     stability: stability::lookup(tcx, did).clean(cx),
+                    deprecation: stability::lookup_deprecation(tcx, did).clean(cx),
                     def_id: did
                 })
             }
@@ -388,6 +398,7 @@ pub fn build_impl(cx: &DocContext,
                     attrs: vec![],
                     visibility: None,
                     stability: stability::lookup(tcx, did).clean(cx),
+                    deprecation: stability::lookup_deprecation(tcx, did).clean(cx),
                     def_id: did
                 })
             }
@@ -421,6 +432,7 @@ pub fn build_impl(cx: &DocContext,
         attrs: attrs,
         visibility: Some(hir::Inherited),
         stability: stability::lookup(tcx, did).clean(cx),
+        deprecation: stability::lookup_deprecation(tcx, did).clean(cx),
         def_id: did,
     });
 
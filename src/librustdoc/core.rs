@@ -53,6 +53,9 @@ pub struct DocContext<'a, 'tcx: 'a> {
     pub inlined: RefCell<Option<HashSet<DefId>>>,
     pub populated_crate_impls: RefCell<HashSet<ast::CrateNum>>,
     pub deref_trait_did: Cell<Option<DefId>>,
+    /// The paths of the external items which aren't documented where they
+    /// are defined, as found by resolve.
+    pub visible_paths: ty::VisiblePaths,
 }
 
 impl<'b, 'tcx> DocContext<'b, 'tcx> {
@@ -146,8 +149,9 @@ pub fn run_core(search_paths: SearchPaths, cfgs: Vec<String>, externs: Externs,
                                         &arenas,
                                         &name,
                                         resolve::MakeGlobMap::No,
+                                        resolve::MakeVisiblePaths::Yes,
                                         |tcx, _, analysis| {
-        let ty::CrateAnalysis { exported_items, public_items, .. } = analysis;
+        let ty::CrateAnalysis { exported_items, public_items, visible_paths, .. } = analysis;
 
         // Convert from a NodeId set to a DefId set since we don't always have easy access
         // to the map from defid -> nodeid
@@ -170,6 +174,7 @@ pub fn run_core(search_paths: SearchPaths, cfgs: Vec<String>, externs: Externs,
             inlined: RefCell::new(Some(HashSet::new())),
             populated_crate_impls: RefCell::new(HashSet::new()),
             deref_trait_did: Cell::new(None),
+            visible_paths: visible_paths.unwrap(),
         };
         debug!("crate: {:?}", ctxt.map.krate());
 
@@ -533,41 +533,51 @@ fn build_index(krate: &clean::Crate, cache: &mut Cache) -> String {
         assert_eq!(nodeid_to_pathid.len(), pathid_to_nodeid.len());
     }
 
+    // Module paths are shared by many items, so each one is written out once
+    // and the items refer to it by its position.
+    let mut module_ids = HashMap::new();
+    let mut modules = Vec::new();
+
     // Collect the index into a string
     let mut w = io::Cursor::new(Vec::new());
     write!(&mut w, r#"searchIndex['{}'] = {{"items":["#, krate.name).unwrap();
 
-    let mut lastpath = "".to_string();
     for (i, item) in cache.search_index.iter().enumerate() {
-        // Omit the path if it is same to that of the prior item.
-        let path;
-        if lastpath == item.path {
-            path = "";
-        } else {
-            lastpath = item.path.to_string();
-            path = &item.path;
-        };
+        let module_id = *module_ids.entry(&item.path[..]).or_insert_with(|| {
+            modules.push(&item.path[..]);
+            modules.len() - 1
+        });
 
         if i > 0 {
             write!(&mut w, ",").unwrap();
         }
-        write!(&mut w, r#"[{},"{}","{}",{}"#,
-               item.ty as usize, item.name, path,
+        write!(&mut w, r#"[{},"{}",{},{}"#,
+               item.ty as usize, item.name, module_id,
                item.desc.to_json().to_string()).unwrap();
-        match item.parent {
-            Some(nodeid) => {
-                let pathid = *nodeid_to_pathid.get(&nodeid).unwrap();
-                write!(&mut w, ",{}", pathid).unwrap();
+        // Trailing fields that are not present are left out.
+        let pathid = item.parent.map(|nodeid| *nodeid_to_pathid.get(&nodeid).unwrap());
+        match (pathid, &item.search_type) {
+            (None, &None) => {}
+            (Some(pathid), &None) => write!(&mut w, ",{}", pathid).unwrap(),
+            (pathid, &Some(ref t)) => {
+                match pathid {
+                    Some(pathid) => write!(&mut w, ",{}", pathid).unwrap(),
+                    None => write!(&mut w, ",null").unwrap(),
+                }
+                write!(&mut w, ",{}", t).unwrap();
             }
-            None => write!(&mut w, ",null").unwrap()
-        }
-        match item.search_type {
-            Some(ref t) => write!(&mut w, ",{}", t).unwrap(),
-            None => write!(&mut w, ",null").unwrap()
         }
         write!(&mut w, "]").unwrap();
     }
 
+    write!(&mut w, r#"],"modules":["#).unwrap();
+    for (i, module) in modules.iter().enumerate() {
+        if i > 0 {
+            write!(&mut w, ",").unwrap();
+        }
+        write!(&mut w, r#""{}""#, module).unwrap();
+    }
+
     write!(&mut w, r#"],"paths":["#).unwrap();
 
     for (i, &did) in pathid_to_nodeid.iter().enumerate() {
@@ -580,6 +590,7 @@ fn build_index(krate: &clean::Crate, cache: &mut Cache) -> String {
     }
 
     write!(&mut w, "]}};").unwrap();
+    write!(&mut w, "searchIndexLoaded();").unwrap();
 
     String::from_utf8(w.into_inner()).unwrap()
 }
@@ -653,12 +664,23 @@ fn write_shared(cx: &Context,
         return Ok(ret);
     }
 
-    // Update the search index
+    // Each crate's search index goes in its own file, which is only loaded
+    // once something is searched for.
+    let dst = cx.dst.join("search-index");
+    try_err!(mkdir(&dst), &dst);
+    let dst = dst.join(&format!("{}.js", krate.name));
+    let mut w = try_err!(File::create(&dst), &dst);
+    try_err!(writeln!(&mut w, "{}", search_index), &dst);
+
+    // Update the list of crates with a search index. Only a short description
+    // of each crate is kept here, for the crates sidebar.
     let dst = cx.dst.join("search-index.js");
     let all_indexes = try_err!(collect(&dst, &krate.name, "searchIndex"), &dst);
+    let desc = shorter(krate.module.as_ref().and_then(|m| m.doc_value()));
     let mut w = try_err!(File::create(&dst), &dst);
     try_err!(writeln!(&mut w, "var searchIndex = {{}};"), &dst);
-    try_err!(writeln!(&mut w, "{}", search_index), &dst);
+    try_err!(writeln!(&mut w, r#"searchIndex['{}'] = {{"desc":{}}};"#,
+                      krate.name, desc.to_json().to_string()), &dst);
     for index in &all_indexes {
         try_err!(writeln!(&mut w, "{}", *index), &dst);
     }
@@ -1755,6 +1777,21 @@ fn item_module(w: &mut fmt::Formatter, cx: &Context,
 }
 
 fn short_stability(item: &clean::Item, cx: &Context, show_reason: bool) -> Option<String> {
+    if let Some(ref depr) = item.deprecation {
+        let note = if show_reason && !depr.note.is_empty() {
+            format!(": {}", depr.note)
+        } else {
+            String::new()
+        };
+        let since = if show_reason && !depr.since.is_empty() {
+            format!(" since {}", Escape(&depr.since))
+        } else {
+            String::new()
+        };
+        return Some(format!("<em class='stab {}'>Deprecated{}{}</em>",
+                            item.stability_class(), since, Markdown(&note)))
+    }
+
     item.stability.as_ref().and_then(|stab| {
         let reason = if show_reason && !stab.reason.is_empty() {
             format!(": {}", stab.reason)
@@ -634,6 +634,11 @@
                 return;
             }
 
+            if (!index) {
+                loadIndex(search);
+                return;
+            }
+
             // Update document title to maintain a meaningful browser history
             $(document).prop("title", "Results for " + query.query + " - Rust");
 
@@ -672,19 +677,68 @@
             return -1;
         }
 
+        // The index of each crate is in its own file, which is only loaded
+        // the first time something is searched for. The files call
+        // `searchIndexLoaded` once they've added themselves to
+        // `rawSearchIndex`.
+        var loadingIndex = false, onIndexLoaded = [];
+        function loadIndex(callback) {
+            if (index) {
+                callback();
+                return;
+            }
+            onIndexLoaded.push(callback);
+            if (loadingIndex) {
+                return;
+            }
+            loadingIndex = true;
+
+            var crates = [];
+            for (var crate in rawSearchIndex) {
+                if (!rawSearchIndex.hasOwnProperty(crate)) { continue; }
+                if (!rawSearchIndex[crate].items) {
+                    crates.push(crate);
+                }
+            }
+
+            var pending = crates.length + 1;
+            window.searchIndexLoaded = function() {
+                pending -= 1;
+                if (pending > 0) { return; }
+                index = buildIndex(rawSearchIndex);
+                var callbacks = onIndexLoaded;
+                onIndexLoaded = [];
+                for (var i = 0; i < callbacks.length; ++i) {
+                    callbacks[i]();
+                }
+            };
+            for (var i = 0; i < crates.length; ++i) {
+                var script = document.createElement('script');
+                script.src = rootPath + 'search-index/' + crates[i] + '.js';
+                // a missing index shouldn't break searching the others
+                script.onerror = window.searchIndexLoaded;
+                document.head.appendChild(script);
+            }
+            window.searchIndexLoaded();
+        }
+
         function buildIndex(rawSearchIndex) {
             searchIndex = [];
             var searchWords = [];
             for (var crate in rawSearchIndex) {
                 if (!rawSearchIndex.hasOwnProperty(crate)) { continue; }
+                if (!rawSearchIndex[crate].items) { continue; }
 
                 // an array of [(Number) item type,
                 //              (String) name,
-                //              (String) full path or empty string for previous path,
+                //              (Number) the module path index to `modules`,
                 //              (String) description,
-                //              (Number | null) the parent path index to `paths`]
-                //              (Object | null) the type of the function (if any)
+                //              (Number | null) the parent path index to `paths`,
+                //              (Object | null) the type of the function (if any)]
+                // where trailing fields that are not present are left out
                 var items = rawSearchIndex[crate].items;
+                // an array of (String) module paths
+                var modules = rawSearchIndex[crate].modules;
                 // an array of [(Number) item type,
                 //              (String) name]
                 var paths = rawSearchIndex[crate].paths;
@@ -703,20 +757,24 @@
                 // all other search operations have access to this cached data for
                 // faster analysis operations
                 var len = items.length;
+                // crates documented by an older rustdoc into the same
+                // directory have no `modules`, and give the full path in
+                // each row, or an empty string for the previous row's path
                 var lastPath = "";
                 for (var i = 0; i < len; ++i) {
                     var rawRow = items[i];
+                    var path = modules ? modules[rawRow[2]] : rawRow[2] || lastPath;
                     var row = {crate: crate, ty: rawRow[0], name: rawRow[1],
-                               path: rawRow[2] || lastPath, desc: rawRow[3],
-                               parent: paths[rawRow[4]], type: rawRow[5]};
+                               path: path, desc: rawRow[3],
+                               parent: paths[rawRow[4]], type: rawRow[5] || null};
                     searchIndex.push(row);
+                    lastPath = path;
                     if (typeof row.name === "string") {
                         var word = row.name.toLowerCase();
                         searchWords.push(word);
                     } else {
                         searchWords.push("");
                     }
-                    lastPath = row.path;
                 }
             }
             return searchWords;
@@ -793,9 +851,13 @@
             .replace(/\[(.*?)\]\[.*?\]/g, "$1");
         }
 
-        index = buildIndex(rawSearchIndex);
         startSearch();
 
+        // Start fetching the index as soon as it looks like it'll be needed.
+        $('.search-input').on('focus', function() {
+            loadIndex(function() {});
+        });
+
         // Draw a convenient sidebar of known crates if we have a listing
         if (rootPath === '../') {
             var sidebar = $('.sidebar');
@@ -814,13 +876,17 @@
                 if (crates[i] === window.currentCrate) {
                     klass += ' current';
                 }
-                if (rawSearchIndex[crates[i]].items[0]) {
-                    var desc = rawSearchIndex[crates[i]].items[0][3];
-                    var link = $('<a>', {'href': '../' + crates[i] + '/index.html',
-                                         'title': plainSummaryLine(desc),
-                                         'class': klass}).text(crates[i]);
-                    ul.append($('<li>').append(link));
+                var desc = rawSearchIndex[crates[i]].desc;
+                // older indexes only have the description of the crate's item
+                var items = rawSearchIndex[crates[i]].items;
+                if (desc === undefined) {
+                    if (!items || !items[0]) { continue; }
+                    desc = items[0][3];
                 }
+                var link = $('<a>', {'href': '../' + crates[i] + '/index.html',
+                                     'title': plainSummaryLine(desc),
+                                     'class': klass}).text(crates[i]);
+                ul.append($('<li>').append(link));
             }
             sidebar.append(div);
         }
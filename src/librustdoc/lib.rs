@@ -51,7 +51,7 @@ extern crate rustc_unicode;
 
 extern crate serialize as rustc_serialize; // used by deriving
 
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
 use std::env;
 use std::fs::File;
@@ -100,7 +100,7 @@ type Pass = (&'static str,                                      // name
 
 const PASSES: &'static [Pass] = &[
     ("strip-hidden", passes::strip_hidden,
-     "strips all doc(hidden) items from the output"),
+     "strips all doc(hidden) items, and everything inside them, from the output"),
     ("unindent-comments", passes::unindent_comments,
      "removes excess indentation on comments in order for markdown to like it"),
     ("collapse-docs", passes::collapse_docs,
@@ -120,6 +120,9 @@ thread_local!(pub static ANALYSISKEY: Rc<RefCell<Option<core::CrateAnalysis>>> =
     Rc::new(RefCell::new(None))
 });
 
+// Whether `--show-hidden` was passed, read by the strip-hidden pass.
+thread_local!(pub static SHOW_HIDDEN: Cell<bool> = Cell::new(false));
+
 struct Output {
     krate: clean::Crate,
     json_plugins: Vec<plugins::PluginJson>,
@@ -159,10 +162,16 @@ pub fn opts() -> Vec<getopts::OptGroup> {
         optmulti("", "plugins", "space separated list of plugins to also load",
                  "PLUGINS"),
         optflag("", "no-defaults", "don't run the default passes"),
+        optflag("", "show-hidden", "keep #[doc(hidden)] items in the output and \
+                                    list them on stderr, for debugging"),
         optflag("", "test", "run code examples as tests"),
         optmulti("", "test-args", "arguments to pass to the test runner",
                  "ARGS"),
         optopt("", "target", "target triple to document", "TRIPLE"),
+        optopt("", "linker", "linker to link code examples for the target with when testing",
+               "PATH"),
+        optopt("", "runner", "command to run code examples for the target with when testing, \
+                              e.g. an emulator", "COMMAND"),
         optmulti("", "markdown-css", "CSS files to include via <link> in a rendered Markdown file",
                  "FILES"),
         optmulti("", "html-in-header",
@@ -258,13 +267,20 @@ pub fn main_args(args: &[String]) -> isize {
         None => return 3
     };
     let crate_name = matches.opt_str("crate-name");
+    let test_target = test::TestTarget {
+        triple: matches.opt_str("target"),
+        linker: matches.opt_str("linker"),
+        runner: matches.opt_str("runner").and_then(|runner| {
+            if runner.trim().is_empty() { None } else { Some(runner) }
+        }),
+    };
 
     match (should_test, markdown_input) {
         (true, true) => {
-            return markdown::test(input, libs, externs, test_args)
+            return markdown::test(input, cfgs, libs, externs, test_args, test_target)
         }
         (true, false) => {
-            return test::run(input, cfgs, libs, externs, test_args, crate_name)
+            return test::run(input, cfgs, libs, externs, test_args, crate_name, test_target)
         }
         (false, true) => return markdown::render(input,
                                                  output.unwrap_or(PathBuf::from("doc")),
@@ -359,6 +375,7 @@ fn rust_input(cratefile: &str, externs: core::Externs, matches: &getopts::Matche
     let mut default_passes = !matches.opt_present("no-defaults");
     let mut passes = matches.opt_strs("passes");
     let mut plugins = matches.opt_strs("plugins");
+    SHOW_HIDDEN.with(|s| s.set(matches.opt_present("show-hidden")));
 
     // First, parse the crate and extract all relevant information.
     let mut paths = SearchPaths::new();
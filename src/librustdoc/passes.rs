@@ -8,8 +8,10 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use rustc::middle::def_id::DefId;
 use rustc::util::nodemap::DefIdSet;
 use std::cmp;
+use std::io::{self, Write};
 use std::string::String;
 use std::usize;
 use rustc_front::hir;
@@ -20,46 +22,108 @@ use plugins;
 use fold;
 use fold::DocFolder;
 
-/// Strip items marked `#[doc(hidden)]`
+/// Strip items marked `#[doc(hidden)]`, along with everything inside them.
+///
+/// Impls of a stripped type or trait are stripped as well, unless the type
+/// or trait was also surfaced somewhere else, e.g. through a `pub use` with
+/// `#[doc(inline)]`. With `--show-hidden` nothing is stripped, and the hidden
+/// items are listed on stderr instead.
 pub fn strip_hidden(krate: clean::Crate) -> plugins::PluginResult {
     let mut stripped = DefIdSet();
+    let mut retained = DefIdSet();
+    let show_hidden = super::SHOW_HIDDEN.with(|s| s.get());
 
     // strip all #[doc(hidden)] items
     let krate = {
         struct Stripper<'a> {
-            stripped: &'a mut DefIdSet
+            stripped: &'a mut DefIdSet,
+            retained: &'a mut DefIdSet,
+            show_hidden: bool,
+            // path to the item being folded, for `--show-hidden`
+            path: Vec<String>,
+            // the hidden item we're inside of, for `--show-hidden`
+            hidden_parent: Option<String>,
+        }
+        impl<'a> Stripper<'a> {
+            fn report(&self, i: &Item) {
+                let name = match i.name {
+                    Some(ref name) => name,
+                    None => return,
+                };
+                let mut path = self.path.clone();
+                path.push(name.clone());
+                let reason = match self.hidden_parent {
+                    Some(ref parent) => format!("inside `{}`", parent),
+                    None => "marked #[doc(hidden)]".to_string(),
+                };
+                let _ = writeln!(&mut io::stderr(), "hidden: `{}` ({})",
+                                 path.join("::"), reason);
+            }
         }
         impl<'a> fold::DocFolder for Stripper<'a> {
             fn fold_item(&mut self, i: Item) -> Option<Item> {
-                if i.is_hidden_from_doc() {
+                let hidden = i.is_hidden_from_doc();
+                if self.show_hidden && (hidden || self.hidden_parent.is_some()) {
+                    self.report(&i);
+                } else if hidden {
                     debug!("found one in strip_hidden; removing");
-                    self.stripped.insert(i.def_id);
 
                     // use a dedicated hidden item for given item type if any
-                    match i.inner {
-                        clean::StructFieldItem(..) => {
-                            return Some(clean::Item {
-                                inner: clean::StructFieldItem(clean::HiddenStructField),
-                                ..i
-                            });
-                        }
-                        _ => {
-                            return None;
-                        }
+                    if let clean::StructFieldItem(..) = i.inner {
+                        self.stripped.insert(i.def_id);
+                        return Some(clean::Item {
+                            inner: clean::StructFieldItem(clean::HiddenStructField),
+                            ..i
+                        });
                     }
+
+                    // everything inside a hidden item is hidden as well
+                    HiddenCollector(&mut *self.stripped).fold_item(i);
+                    return None;
                 }
 
-                self.fold_item_recur(i)
+                let prev_parent = self.hidden_parent.clone();
+                if self.show_hidden && hidden && prev_parent.is_none() {
+                    let mut path = self.path.clone();
+                    path.extend(i.name.clone());
+                    self.hidden_parent = Some(path.join("::"));
+                }
+                let named = i.name.is_some();
+                if let Some(ref name) = i.name {
+                    self.path.push(name.clone());
+                }
+                let i = self.fold_item_recur(i);
+                if named {
+                    self.path.pop();
+                }
+                self.hidden_parent = prev_parent;
+
+                if let Some(ref i) = i {
+                    self.retained.insert(i.def_id);
+                }
+                i
             }
         }
-        let mut stripper = Stripper{ stripped: &mut stripped };
+        let mut stripper = Stripper {
+            stripped: &mut stripped,
+            retained: &mut retained,
+            show_hidden: show_hidden,
+            path: vec![],
+            hidden_parent: None,
+        };
         stripper.fold_crate(krate)
     };
 
     // strip any traits implemented on stripped items
     let krate = {
         struct ImplStripper<'a> {
-            stripped: &'a mut DefIdSet
+            stripped: &'a DefIdSet,
+            retained: &'a DefIdSet,
+        }
+        impl<'a> ImplStripper<'a> {
+            fn is_stripped(&self, did: &DefId) -> bool {
+                self.stripped.contains(did) && !self.retained.contains(did)
+            }
         }
         impl<'a> fold::DocFolder for ImplStripper<'a> {
             fn fold_item(&mut self, i: Item) -> Option<Item> {
@@ -68,12 +132,12 @@ pub fn strip_hidden(krate: clean::Crate) -> plugins::PluginResult {
                            ref trait_, ..
                 }) = i.inner {
                     // Impls for stripped types don't need to exist
-                    if self.stripped.contains(&did) {
+                    if self.is_stripped(&did) {
                         return None;
                     }
                     // Impls of stripped traits also don't need to exist
                     if let Some(clean::ResolvedPath { did, .. }) = *trait_ {
-                        if self.stripped.contains(&did) {
+                        if self.is_stripped(&did) {
                             return None;
                         }
                     }
@@ -81,13 +145,22 @@ pub fn strip_hidden(krate: clean::Crate) -> plugins::PluginResult {
                 self.fold_item_recur(i)
             }
         }
-        let mut stripper = ImplStripper{ stripped: &mut stripped };
+        let mut stripper = ImplStripper { stripped: &stripped, retained: &retained };
         stripper.fold_crate(krate)
     };
 
     (krate, None)
 }
 
+// Records the ids of an item and everything inside of it.
+struct HiddenCollector<'a>(&'a mut DefIdSet);
+impl<'a> fold::DocFolder for HiddenCollector<'a> {
+    fn fold_item(&mut self, i: Item) -> Option<Item> {
+        self.0.insert(i.def_id);
+        self.fold_item_recur(i)
+    }
+}
+
 /// Strip private items from the point of view of a crate or externally from a
 /// crate, specified by the `xcrate` flag.
 pub fn strip_private(mut krate: clean::Crate) -> plugins::PluginResult {
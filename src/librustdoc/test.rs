@@ -28,6 +28,7 @@ use rustc::front::map as hir_map;
 use rustc::session::{self, config};
 use rustc::session::config::{get_unstable_features_setting, OutputType};
 use rustc::session::search_paths::{SearchPaths, PathKind};
+use rustc::util::nodemap::DefIdMap;
 use rustc_front::lowering::{lower_crate, LoweringContext};
 use rustc_back::tempdir::TempDir;
 use rustc_driver::{driver, Compilation};
@@ -48,12 +49,24 @@ pub struct TestOptions {
     pub attrs: Vec<String>,
 }
 
+/// The target the code examples are compiled for, when it isn't the host,
+/// and how to link and run them.
+#[derive(Clone, Default)]
+pub struct TestTarget {
+    pub triple: Option<String>,
+    pub linker: Option<String>,
+    /// The command the examples are run with, e.g. an emulator, followed by
+    /// its arguments. The path of the example is appended to them.
+    pub runner: Option<String>,
+}
+
 pub fn run(input: &str,
            cfgs: Vec<String>,
            libs: SearchPaths,
            externs: core::Externs,
            mut test_args: Vec<String>,
-           crate_name: Option<String>)
+           crate_name: Option<String>,
+           target: TestTarget)
            -> isize {
     let input_path = PathBuf::from(input);
     let input = config::Input::File(input_path.clone());
@@ -64,6 +77,7 @@ pub fn run(input: &str,
         search_paths: libs.clone(),
         crate_types: vec!(config::CrateTypeDylib),
         externs: externs.clone(),
+        target_triple: target.triple.clone().unwrap_or(config::host_triple().to_string()),
         unstable_features: get_unstable_features_setting(),
         ..config::basic_options().clone()
     };
@@ -79,7 +93,7 @@ pub fn run(input: &str,
     rustc_lint::register_builtins(&mut sess.lint_store.borrow_mut(), Some(&sess));
 
     let mut cfg = config::build_configuration(&sess);
-    cfg.extend(config::parse_cfgspecs(cfgs));
+    cfg.extend(config::parse_cfgspecs(cfgs.clone()));
     let krate = driver::phase_1_parse_input(&sess, cfg, &input);
     let krate = driver::phase_2_configure_and_expand(&sess, krate,
                                                      "rustdoc-test", None)
@@ -103,6 +117,7 @@ pub fn run(input: &str,
         inlined: RefCell::new(None),
         populated_crate_impls: RefCell::new(HashSet::new()),
         deref_trait_did: Cell::new(None),
+        visible_paths: DefIdMap(),
     };
 
     let mut v = RustdocVisitor::new(&ctx, None);
@@ -116,10 +131,12 @@ pub fn run(input: &str,
     let (krate, _) = passes::unindent_comments(krate);
 
     let mut collector = Collector::new(krate.name.to_string(),
+                                       cfgs,
                                        libs,
                                        externs,
                                        false,
-                                       opts);
+                                       opts,
+                                       target);
     collector.fold_crate(krate);
 
     test_args.insert(0, "rustdoctest".to_string());
@@ -162,10 +179,10 @@ fn scrape_test_config(krate: &::rustc_front::hir::Crate) -> TestOptions {
     return opts;
 }
 
-fn runtest(test: &str, cratename: &str, libs: SearchPaths,
+fn runtest(test: &str, cratename: &str, cfgs: Vec<String>, libs: SearchPaths,
            externs: core::Externs,
            should_panic: bool, no_run: bool, as_test_harness: bool,
-           opts: &TestOptions) {
+           opts: &TestOptions, target: &TestTarget) {
     // the test harness wants its own `main` & top level functions, so
     // never wrap the test in `fn main() { ... }`
     let test = maketest(test, Some(cratename), as_test_harness, opts);
@@ -180,8 +197,10 @@ fn runtest(test: &str, cratename: &str, libs: SearchPaths,
         crate_types: vec!(config::CrateTypeExecutable),
         output_types: outputs,
         externs: externs,
+        target_triple: target.triple.clone().unwrap_or(config::host_triple().to_string()),
         cg: config::CodegenOptions {
             prefer_dynamic: true,
+            linker: target.linker.clone(),
             .. config::basic_codegen_options()
         },
         test: as_test_harness,
@@ -230,7 +249,8 @@ fn runtest(test: &str, cratename: &str, libs: SearchPaths,
 
     let outdir = TempDir::new("rustdoctest").ok().expect("rustdoc needs a tempdir");
     let out = Some(outdir.path().to_path_buf());
-    let cfg = config::build_configuration(&sess);
+    let mut cfg = config::build_configuration(&sess);
+    cfg.extend(config::parse_cfgspecs(cfgs));
     let libdir = sess.target_filesearch(PathKind::All).get_lib_path();
     let mut control = driver::CompileController::basic();
     if no_run {
@@ -246,7 +266,16 @@ fn runtest(test: &str, cratename: &str, libs: SearchPaths,
     // environment to ensure that the target loads the right libraries at
     // runtime. It would be a sad day if the *host* libraries were loaded as a
     // mistake.
-    let mut cmd = Command::new(&outdir.path().join("rust_out"));
+    let exe = outdir.path().join("rust_out");
+    let runner = target.runner.as_ref().map(|r| r.split_whitespace().collect::<Vec<_>>());
+    let mut cmd = match runner {
+        Some(ref runner) if !runner.is_empty() => {
+            let mut cmd = Command::new(runner[0]);
+            cmd.args(&runner[1..]).arg(&exe);
+            cmd
+        }
+        _ => Command::new(&exe),
+    };
     let var = DynamicLibrary::envvar();
     let newpath = {
         let path = env::var_os(var).unwrap_or(OsString::new());
@@ -340,6 +369,7 @@ fn partition_source(s: &str) -> (String, String) {
 pub struct Collector {
     pub tests: Vec<testing::TestDescAndFn>,
     names: Vec<String>,
+    cfgs: Vec<String>,
     libs: SearchPaths,
     externs: core::Externs,
     cnt: usize,
@@ -347,14 +377,17 @@ pub struct Collector {
     current_header: Option<String>,
     cratename: String,
     opts: TestOptions,
+    target: TestTarget,
 }
 
 impl Collector {
-    pub fn new(cratename: String, libs: SearchPaths, externs: core::Externs,
-               use_headers: bool, opts: TestOptions) -> Collector {
+    pub fn new(cratename: String, cfgs: Vec<String>, libs: SearchPaths,
+               externs: core::Externs, use_headers: bool,
+               opts: TestOptions, target: TestTarget) -> Collector {
         Collector {
             tests: Vec::new(),
             names: Vec::new(),
+            cfgs: cfgs,
             libs: libs,
             externs: externs,
             cnt: 0,
@@ -362,6 +395,7 @@ impl Collector {
             current_header: None,
             cratename: cratename,
             opts: opts,
+            target: target,
         }
     }
 
@@ -375,10 +409,12 @@ impl Collector {
             format!("{}_{}", self.names.join("::"), self.cnt)
         };
         self.cnt += 1;
+        let cfgs = self.cfgs.clone();
         let libs = self.libs.clone();
         let externs = self.externs.clone();
         let cratename = self.cratename.to_string();
         let opts = self.opts.clone();
+        let target = self.target.clone();
         debug!("Creating test {}: {}", name, test);
         self.tests.push(testing::TestDescAndFn {
             desc: testing::TestDesc {
@@ -390,12 +426,14 @@ impl Collector {
             testfn: testing::DynTestFn(Box::new(move|| {
                 runtest(&test,
                         &cratename,
+                        cfgs,
                         libs,
                         externs,
                         should_panic,
                         no_run,
                         as_test_harness,
-                        &opts);
+                        &opts,
+                        &target);
             }))
         });
     }
@@ -17,7 +17,6 @@ use std::mem;
 use syntax::abi;
 use syntax::ast;
 use syntax::attr;
-use syntax::attr::AttrMetaMethods;
 use syntax::codemap::Span;
 
 use rustc::front::map as hir_map;
@@ -25,6 +24,7 @@ use rustc::middle::stability;
 
 use rustc_front::hir;
 
+use clean;
 use core;
 use doctree::*;
 
@@ -43,6 +43,9 @@ pub struct RustdocVisitor<'a, 'tcx: 'a> {
     pub analysis: Option<&'a core::CrateAnalysis>,
     view_item_stack: HashSet<ast::NodeId>,
     inlining_from_glob: bool,
+    /// Set while visiting an item re-exported with `#[doc(inline)]`, which
+    /// documents it even if it is `#[doc(hidden)]` where it's defined.
+    surfacing: bool,
 }
 
 impl<'a, 'tcx> RustdocVisitor<'a, 'tcx> {
@@ -58,6 +61,7 @@ impl<'a, 'tcx> RustdocVisitor<'a, 'tcx> {
             analysis: analysis,
             view_item_stack: stack,
             inlining_from_glob: false,
+            surfacing: false,
         }
     }
 
@@ -69,6 +73,13 @@ impl<'a, 'tcx> RustdocVisitor<'a, 'tcx> {
         })
     }
 
+    fn deprecation(&self, id: ast::NodeId) -> Option<attr::Deprecation> {
+        self.cx.tcx_opt().and_then(|tcx| {
+            self.cx.map.opt_local_def_id(id)
+                       .and_then(|def_id| stability::lookup_deprecation(tcx, def_id))
+        })
+    }
+
     pub fn visit(&mut self, krate: &hir::Crate) {
         self.attrs = krate.attrs.clone();
 
@@ -95,6 +106,7 @@ impl<'a, 'tcx> RustdocVisitor<'a, 'tcx> {
             name: name,
             vis: item.vis,
             stab: self.stability(item.id),
+            depr: self.deprecation(item.id),
             attrs: item.attrs.clone(),
             generics: generics.clone(),
             fields: sd.fields().iter().cloned().collect(),
@@ -112,11 +124,13 @@ impl<'a, 'tcx> RustdocVisitor<'a, 'tcx> {
                 name: v.node.name,
                 attrs: v.node.attrs.clone(),
                 stab: self.stability(v.node.data.id()),
+                depr: self.deprecation(v.node.data.id()),
                 def: v.node.data.clone(),
                 whence: v.span,
             }).collect(),
             vis: it.vis,
             stab: self.stability(it.id),
+            depr: self.deprecation(it.id),
             generics: params.clone(),
             attrs: it.attrs.clone(),
             id: it.id,
@@ -135,6 +149,7 @@ impl<'a, 'tcx> RustdocVisitor<'a, 'tcx> {
             id: item.id,
             vis: item.vis,
             stab: self.stability(item.id),
+            depr: self.deprecation(item.id),
             attrs: item.attrs.clone(),
             decl: fd.clone(),
             name: name,
@@ -156,6 +171,7 @@ impl<'a, 'tcx> RustdocVisitor<'a, 'tcx> {
         om.attrs = attrs;
         om.vis = vis;
         om.stab = self.stability(id);
+        om.depr = self.deprecation(id);
         om.id = id;
         for i in &m.item_ids {
             let item = self.cx.map.expect_item(i.id);
@@ -235,7 +251,9 @@ impl<'a, 'tcx> RustdocVisitor<'a, 'tcx> {
                     }
                     self.inlining_from_glob = prev;
                 } else {
+                    let prev = mem::replace(&mut self.surfacing, please_inline);
                     self.visit_item(it, renamed, om);
+                    self.surfacing = prev;
                 }
                 true
             }
@@ -248,6 +266,19 @@ impl<'a, 'tcx> RustdocVisitor<'a, 'tcx> {
     pub fn visit_item(&mut self, item: &hir::Item,
                       renamed: Option<ast::Name>, om: &mut Module) {
         debug!("Visiting item {:?}", item);
+        let surfaced;
+        let item = if mem::replace(&mut self.surfacing, false) {
+            surfaced = hir::Item {
+                attrs: item.attrs.iter()
+                                 .filter(|a| !clean::is_doc_flag(a, "hidden"))
+                                 .cloned()
+                                 .collect(),
+                ..item.clone()
+            };
+            &surfaced
+        } else {
+            item
+        };
         let name = renamed.unwrap_or(item.name);
         match item.node {
             hir::ItemExternCrate(ref p) => {
@@ -265,15 +296,13 @@ impl<'a, 'tcx> RustdocVisitor<'a, 'tcx> {
             }
             hir::ItemUse(ref vpath) => {
                 let node = vpath.node.clone();
-                let node = if item.vis == hir::Public {
-                    let please_inline = item.attrs.iter().any(|item| {
-                        match item.meta_item_list() {
-                            Some(list) => {
-                                list.iter().any(|i| &i.name()[..] == "inline")
-                            }
-                            None => false,
-                        }
-                    });
+                // `#[doc(no_inline)]` keeps the re-export as a `pub use`
+                // linking to the original item, even if that item is
+                // private. `#[doc(inline)]` documents the item here even if
+                // it is public elsewhere.
+                let node = if item.vis == hir::Public &&
+                              !clean::has_doc_flag(&item.attrs, "no_inline") {
+                    let please_inline = clean::has_doc_flag(&item.attrs, "inline");
                     match self.visit_view_path(node, om, item.id, please_inline) {
                         None => return,
                         Some(p) => p
@@ -314,6 +343,7 @@ impl<'a, 'tcx> RustdocVisitor<'a, 'tcx> {
                     whence: item.span,
                     vis: item.vis,
                     stab: self.stability(item.id),
+                    depr: self.deprecation(item.id),
                 };
                 om.typedefs.push(t);
             },
@@ -328,6 +358,7 @@ impl<'a, 'tcx> RustdocVisitor<'a, 'tcx> {
                     whence: item.span,
                     vis: item.vis,
                     stab: self.stability(item.id),
+                    depr: self.deprecation(item.id),
                 };
                 om.statics.push(s);
             },
@@ -341,6 +372,7 @@ impl<'a, 'tcx> RustdocVisitor<'a, 'tcx> {
                     whence: item.span,
                     vis: item.vis,
                     stab: self.stability(item.id),
+                    depr: self.deprecation(item.id),
                 };
                 om.constants.push(s);
             },
@@ -356,6 +388,7 @@ impl<'a, 'tcx> RustdocVisitor<'a, 'tcx> {
                     whence: item.span,
                     vis: item.vis,
                     stab: self.stability(item.id),
+                    depr: self.deprecation(item.id),
                 };
                 om.traits.push(t);
             },
@@ -372,6 +405,7 @@ impl<'a, 'tcx> RustdocVisitor<'a, 'tcx> {
                     whence: item.span,
                     vis: item.vis,
                     stab: self.stability(item.id),
+                    depr: self.deprecation(item.id),
                 };
                 // Don't duplicate impls when inlining glob imports, we'll pick
                 // them up regardless of where they're located.
@@ -406,6 +440,7 @@ impl<'a, 'tcx> RustdocVisitor<'a, 'tcx> {
             name: def.name,
             whence: def.span,
             stab: self.stability(def.id),
+            depr: self.deprecation(def.id),
             imported_from: def.imported_from,
         }
     }
@@ -386,7 +386,7 @@ pub fn cfg_matches(diagnostic: &SpanHandler, cfgs: &[P<MetaItem>], cfg: &ast::Me
 pub struct Stability {
     pub level: StabilityLevel,
     pub feature: InternedString,
-    pub depr: Option<Deprecation>,
+    pub depr: Option<RustcDeprecation>,
 }
 
 /// The available stability levels.
@@ -397,12 +397,21 @@ pub enum StabilityLevel {
     Stable { since: InternedString },
 }
 
+/// The `#[deprecated]` attribute of the standard library, which has to
+/// go with a stability level.
 #[derive(RustcEncodable, RustcDecodable, PartialEq, PartialOrd, Clone, Debug, Eq, Hash)]
-pub struct Deprecation {
+pub struct RustcDeprecation {
     pub since: InternedString,
     pub reason: InternedString,
 }
 
+/// The `#[deprecated]` attribute of crates outside the standard library.
+#[derive(RustcEncodable, RustcDecodable, PartialEq, PartialOrd, Clone, Debug, Eq, Hash)]
+pub struct Deprecation {
+    pub since: Option<InternedString>,
+    pub note: Option<InternedString>,
+}
+
 impl StabilityLevel {
     pub fn is_unstable(&self) -> bool { if let Unstable {..} = *self { true } else { false }}
     pub fn is_stable(&self) -> bool { if let Stable {..} = *self { true } else { false }}
@@ -415,7 +424,7 @@ fn find_stability_generic<'a, I>(diagnostic: &SpanHandler,
     where I: Iterator<Item = &'a Attribute>
 {
     let mut stab: Option<Stability> = None;
-    let mut depr: Option<Deprecation> = None;
+    let mut depr: Option<RustcDeprecation> = None;
 
     'outer: for attr in attrs_iter {
         let tag = attr.name();
@@ -465,7 +474,7 @@ fn find_stability_generic<'a, I>(diagnostic: &SpanHandler,
 
                     match (since, reason) {
                         (Some(since), Some(reason)) => {
-                            depr = Some(Deprecation {
+                            depr = Some(RustcDeprecation {
                                 since: since,
                                 reason: reason,
                             })
@@ -600,6 +609,67 @@ pub fn find_stability(diagnostic: &SpanHandler, attrs: &[Attribute],
     find_stability_generic(diagnostic, attrs.iter(), item_sp)
 }
 
+/// Find the `#[deprecated]` attribute of an item in a crate outside the
+/// standard library. Both `since` and `note` are optional, and so is the
+/// list: `#[deprecated]` on its own is fine.
+pub fn find_deprecation(diagnostic: &SpanHandler, attrs: &[Attribute],
+                        item_sp: Span) -> Option<Deprecation> {
+    let mut depr: Option<Deprecation> = None;
+
+    'outer: for attr in attrs {
+        if attr.name() != "deprecated" {
+            continue
+        }
+
+        mark_used(attr);
+
+        if depr.is_some() {
+            diagnostic.span_err(item_sp, "multiple deprecated attributes");
+            break
+        }
+
+        depr = if let Some(metas) = attr.meta_item_list() {
+            let get = |meta: &MetaItem, item: &mut Option<InternedString>| {
+                if item.is_some() {
+                    diagnostic.span_err(meta.span, &format!("multiple '{}' items",
+                                                             meta.name()));
+                    return false
+                }
+                if let Some(v) = meta.value_str() {
+                    *item = Some(v);
+                    true
+                } else {
+                    diagnostic.span_err(meta.span, "incorrect meta item");
+                    false
+                }
+            };
+
+            let mut since = None;
+            let mut note = None;
+            for meta in metas {
+                match &*meta.name() {
+                    "since" => if !get(meta, &mut since) { continue 'outer },
+                    "note" => if !get(meta, &mut note) { continue 'outer },
+                    _ => {
+                        diagnostic.span_err(meta.span, &format!("unknown meta item '{}'",
+                                                                meta.name()));
+                        continue 'outer
+                    }
+                }
+            }
+
+            Some(Deprecation { since: since, note: note })
+        } else if let ast::MetaWord(_) = attr.node.value.node {
+            Some(Deprecation { since: None, note: None })
+        } else {
+            diagnostic.span_err(attr.span(), "incorrect deprecated attribute type");
+            continue
+        }
+    }
+
+    depr
+}
+
 pub fn require_unique_names(diagnostic: &SpanHandler, metas: &[P<MetaItem>]) {
     let mut set = HashSet::new();
     for meta in metas {
@@ -194,6 +194,42 @@ impl fmt::Debug for Span {
     }
 }
 
+/// A span a diagnostic is reported at, along with secondary spans that are
+/// part of the same diagnostic, each with a label saying why it is shown,
+/// e.g. the first definition of a name that is defined twice.
+#[derive(Clone, Debug)]
+pub struct MultiSpan {
+    pub primary: Span,
+    /// What is shown under the primary span when the spans are printed
+    /// together, e.g. "`A` redefined here".
+    pub primary_label: Option<String>,
+    pub labels: Vec<(Span, String)>,
+}
+
+impl MultiSpan {
+    pub fn new(primary: Span) -> MultiSpan {
+        MultiSpan {
+            primary: primary,
+            primary_label: None,
+            labels: Vec::new(),
+        }
+    }
+
+    pub fn set_primary_label(&mut self, label: String) {
+        self.primary_label = Some(label);
+    }
+
+    pub fn push_label(&mut self, sp: Span, label: String) {
+        self.labels.push((sp, label));
+    }
+}
+
+impl From<Span> for MultiSpan {
+    fn from(sp: Span) -> MultiSpan {
+        MultiSpan::new(sp)
+    }
+}
+
 pub fn spanned<T>(lo: BytePos, hi: BytePos, t: T) -> Spanned<T> {
     respan(mk_sp(lo, hi), t)
 }
@@ -622,7 +658,9 @@ impl CodeMap {
 
     pub fn load_file(&self, path: &Path) -> io::Result<Rc<FileMap>> {
         let src = try!(self.file_loader.read_file(path));
-        Ok(self.new_filemap(path.to_str().unwrap().to_string(), src))
+        // The name is only displayed, so a non-UTF-8 path is made readable
+        // rather than refused.
+        Ok(self.new_filemap(path.to_string_lossy().into_owned(), src))
     }
 
     fn next_start_pos(&self) -> usize {
@@ -286,36 +286,33 @@ fn process_cfg_attr(diagnostic: &SpanHandler, krate: ast::Crate,
 }
 
 impl<'a,'b> fold::Folder for CfgAttrFolder<'a,'b> {
-    fn fold_attribute(&mut self, attr: ast::Attribute) -> Option<ast::Attribute> {
+    fn fold_attribute(&mut self, attr: ast::Attribute) -> SmallVector<ast::Attribute> {
         if !attr.check_name("cfg_attr") {
             return fold::noop_fold_attribute(attr, self);
         }
 
-        let attr_list = match attr.meta_item_list() {
-            Some(attr_list) => attr_list,
-            None => {
-                self.diag.span_err(attr.span, "expected `#[cfg_attr(<cfg pattern>, <attr>)]`");
-                return None;
-            }
-        };
-        let (cfg, mi) = match (attr_list.len(), attr_list.get(0), attr_list.get(1)) {
-            (2, Some(cfg), Some(mi)) => (cfg, mi),
+        // `#[cfg_attr(<cfg pattern>, <attr>, <attr>...)]` expands to all the
+        // attributes after the pattern if it matches.
+        let (cfg, mis) = match attr.meta_item_list() {
+            Some(attr_list) if attr_list.len() >= 2 => (&attr_list[0], &attr_list[1..]),
             _ => {
                 self.diag.span_err(attr.span, "expected `#[cfg_attr(<cfg pattern>, <attr>)]`");
-                return None;
+                return SmallVector::zero();
             }
         };
 
         if attr::cfg_matches(self.diag, &self.config[..], &cfg,
                              self.feature_gated_cfgs) {
-            Some(respan(mi.span, ast::Attribute_ {
-                id: attr::mk_attr_id(),
-                style: attr.node.style,
-                value: mi.clone(),
-                is_sugared_doc: false,
-            }))
+            mis.iter().map(|mi| {
+                respan(mi.span, ast::Attribute_ {
+                    id: attr::mk_attr_id(),
+                    style: attr.node.style,
+                    value: mi.clone(),
+                    is_sugared_doc: false,
+                })
+            }).collect()
         } else {
-            None
+            SmallVector::zero()
         }
     }
 
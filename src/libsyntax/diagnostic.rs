@@ -13,7 +13,7 @@ pub use self::RenderSpan::*;
 pub use self::ColorConfig::*;
 use self::Destination::*;
 
-use codemap::{self, COMMAND_LINE_SP, COMMAND_LINE_EXPN, Pos, Span};
+use codemap::{self, CharPos, COMMAND_LINE_SP, COMMAND_LINE_EXPN, MultiSpan, Pos, Span};
 use diagnostics;
 
 use std::cell::{RefCell, Cell};
@@ -74,6 +74,17 @@ pub trait Emitter {
             msg: &str, code: Option<&str>, lvl: Level);
     fn custom_emit(&mut self, cm: &codemap::CodeMap,
                    sp: RenderSpan, msg: &str, lvl: Level);
+
+    /// Emits a diagnostic at `msp.primary` whose labeled spans are part of
+    /// it. Emitters that can't show them as one unit emit the labels as
+    /// notes following the diagnostic.
+    fn emit_multi(&mut self, cm: &codemap::CodeMap, msp: &MultiSpan,
+                  msg: &str, code: Option<&str>, lvl: Level) {
+        self.emit(Some((cm, msp.primary)), msg, code, lvl);
+        for &(sp, ref label) in &msp.labels {
+            self.emit(Some((cm, sp)), label, None, Note);
+        }
+    }
 }
 
 /// Used as a return value to signify a fatal error occurred. (It is also
@@ -143,6 +154,12 @@ impl SpanHandler {
         self.handler.emit_with_code(Some((&self.cm, sp)), msg, code, Error);
         self.handler.bump_err_count();
     }
+    /// Reports an error at `msp.primary`, with the labeled spans of `msp`
+    /// as part of it.
+    pub fn span_err_multi_with_code(&self, msp: &MultiSpan, msg: &str, code: &str) {
+        self.handler.emit_multi(&self.cm, msp, msg, Some(code), Error);
+        self.handler.bump_err_count();
+    }
     pub fn span_warn(&self, sp: Span, msg: &str) {
         self.handler.emit(Some((&self.cm, sp)), msg, Warning);
     }
@@ -275,6 +292,15 @@ impl Handler {
         if lvl == Warning && !self.can_emit_warnings { return }
         self.emit.borrow_mut().custom_emit(cm, sp, msg, lvl);
     }
+    pub fn emit_multi(&self,
+                      cm: &codemap::CodeMap,
+                      msp: &MultiSpan,
+                      msg: &str,
+                      code: Option<&str>,
+                      lvl: Level) {
+        if lvl == Warning && !self.can_emit_warnings { return }
+        self.emit.borrow_mut().emit_multi(cm, msp, msg, code, lvl);
+    }
 }
 
 #[derive(Copy, PartialEq, Clone, Debug)]
@@ -425,6 +451,14 @@ impl EmitterWriter {
 
     fn emit_(&mut self, cm: &codemap::CodeMap, rsp: RenderSpan,
              msg: &str, code: Option<&str>, lvl: Level) -> io::Result<()> {
+        let ss = try!(self.emit_span(cm, rsp, msg, code, lvl));
+        self.print_explanation_help(&ss[..], code)
+    }
+
+    /// Prints the diagnostic and the source it points at, and returns the
+    /// position it was printed with.
+    fn emit_span(&mut self, cm: &codemap::CodeMap, rsp: RenderSpan,
+                 msg: &str, code: Option<&str>, lvl: Level) -> io::Result<String> {
         let sp = rsp.span();
 
         // We cannot check equality directly with COMMAND_LINE_SP
@@ -458,11 +492,16 @@ impl EmitterWriter {
             }
         }
 
+        Ok(ss)
+    }
+
+    /// Points at `rustc --explain` if there is an explanation for `code`.
+    fn print_explanation_help(&mut self, topic: &str, code: Option<&str>) -> io::Result<()> {
         match code {
             Some(code) =>
                 match self.registry.as_ref().and_then(|registry| registry.find_description(code)) {
                     Some(_) => {
-                        try!(self.print_diagnostic(&ss[..], Help,
+                        try!(self.print_diagnostic(topic, Help,
                                                    &format!("run `rustc --explain {}` to see a \
                                                              detailed explanation", code), None));
                     }
@@ -473,6 +512,58 @@ impl EmitterWriter {
         Ok(())
     }
 
+    fn emit_multi_(&mut self, cm: &codemap::CodeMap, msp: &MultiSpan,
+                   msg: &str, code: Option<&str>, lvl: Level) -> io::Result<()> {
+        // The explanation of the code comes after the labels, which are part
+        // of the diagnostic.
+        let ss = match snippet_spans(cm, msp) {
+            Some(spans) => try!(self.emit_snippet(cm, msp.primary, &spans, msg, code, lvl)),
+            None => {
+                let ss = try!(self.emit_span(cm, FullSpan(msp.primary), msg, code, lvl));
+                for &(sp, ref label) in &msp.labels {
+                    try!(self.emit_span(cm, FullSpan(sp), label, None, Note));
+                }
+                ss
+            }
+        };
+        self.print_explanation_help(&ss[..], code)
+    }
+
+    /// Prints the diagnostic and one snippet of source showing all of its
+    /// spans, each marked and followed by its label, and returns the position
+    /// it was printed with.
+    fn emit_snippet(&mut self, cm: &codemap::CodeMap, primary: Span, spans: &[SnippetSpan],
+                    msg: &str, code: Option<&str>, lvl: Level) -> io::Result<String> {
+        let ss = cm.span_to_string(primary);
+        try!(self.print_diagnostic(&ss[..], lvl, msg, code));
+
+        let fm = spans[0].lo.file.clone();
+        let first = spans[0].lo.line;
+        let last = spans[spans.len() - 1].lo.line;
+        let digits = last.to_string().len();
+        let skip = fm.name.chars().count() + digits + 2;
+        for line in first..last + 1 {
+            let text = match fm.get_line(line - 1) {
+                Some(text) => text,
+                None => continue,
+            };
+            try!(write!(&mut self.dst, "{}:{:>width$} {}\n", fm.name, line, text, width=digits));
+            for span in spans.iter().filter(|span| span.lo.line == line) {
+                let (indent, s) = underline(text, skip, span.lo.col, span.hi.col);
+                let color = if span.is_primary { lvl.color() } else { Note.color() };
+                try!(write!(&mut self.dst, "{}", indent));
+                try!(match span.label {
+                    Some(label) => println_maybe_styled!(self,
+                                                         term::attr::ForegroundColor(color),
+                                                         "{} {}", s, label),
+                    None => println_maybe_styled!(self, term::attr::ForegroundColor(color),
+                                                  "{}", s),
+                });
+            }
+        }
+        Ok(ss)
+    }
+
     fn highlight_suggestion(&mut self,
                             cm: &codemap::CodeMap,
                             sp: Span,
@@ -588,64 +679,13 @@ impl EmitterWriter {
             // how many digits must be indent past?
             while num > 0 { num /= 10; digits += 1; }
 
-            let mut s = String::new();
             // Skip is the number of characters we need to skip because they are
             // part of the 'filename:line ' part of the previous line.
             let skip = fm.name.chars().count() + digits + 3;
-            for _ in 0..skip {
-                s.push(' ');
-            }
             if let Some(orig) = fm.get_line(lines.lines[0].line_index) {
-                let mut col = skip;
-                let mut lastc = ' ';
-                let mut iter = orig.chars().enumerate();
-                for (pos, ch) in iter.by_ref() {
-                    lastc = ch;
-                    if pos >= lo.col.to_usize() { break; }
-                    // Whenever a tab occurs on the previous line, we insert one on
-                    // the error-point-squiggly-line as well (instead of a space).
-                    // That way the squiggly line will usually appear in the correct
-                    // position.
-                    match ch {
-                        '\t' => {
-                            col += 8 - col%8;
-                            s.push('\t');
-                        },
-                        _ => {
-                            col += 1;
-                            s.push(' ');
-                        },
-                    }
-                }
-
-                try!(write!(&mut self.dst, "{}", s));
-                let mut s = String::from("^");
-                let count = match lastc {
-                    // Most terminals have a tab stop every eight columns by default
-                    '\t' => 8 - col%8,
-                    _ => 1,
-                };
-                col += count;
-                s.extend(::std::iter::repeat('~').take(count));
-
                 let hi = cm.lookup_char_pos(sp.hi);
-                if hi.col != lo.col {
-                    for (pos, ch) in iter {
-                        if pos >= hi.col.to_usize() { break; }
-                        let count = match ch {
-                            '\t' => 8 - col%8,
-                            _ => 1,
-                        };
-                        col += count;
-                        s.extend(::std::iter::repeat('~').take(count));
-                    }
-                }
-
-                if s.len() > 1 {
-                    // One extra squiggly is replaced by a "^"
-                    s.pop();
-                }
-
+                let (indent, s) = underline(orig, skip, lo.col, hi.col);
+                try!(write!(&mut self.dst, "{}", indent));
                 try!(println_maybe_styled!(self, term::attr::ForegroundColor(lvl.color()),
                                            "{}", s));
             }
@@ -831,6 +871,14 @@ impl Emitter for EmitterWriter {
             Err(e) => panic!("failed to print diagnostics: {:?}", e),
         }
     }
+
+    fn emit_multi(&mut self, cm: &codemap::CodeMap, msp: &MultiSpan,
+                  msg: &str, code: Option<&str>, lvl: Level) {
+        match self.emit_multi_(cm, msp, msg, code, lvl) {
+            Ok(()) => {}
+            Err(e) => panic!("failed to print diagnostics: {:?}", e),
+        }
+    }
 }
 
 pub fn expect<T, M>(diag: &SpanHandler, opt: Option<T>, msg: M) -> T where
@@ -842,24 +890,127 @@ pub fn expect<T, M>(diag: &SpanHandler, opt: Option<T>, msg: M) -> T where
     }
 }
 
+/// A span of a diagnostic shown in a snippet along with the others.
+struct SnippetSpan<'a> {
+    lo: codemap::Loc,
+    hi: codemap::Loc,
+    label: Option<&'a str>,
+    is_primary: bool,
+}
+
+/// The spans of `msp` sorted by their position, if they are close enough to
+/// each other to be shown as one snippet: all in the same file, each on a
+/// single line, and no more than `MAX_LINES` lines apart. They aren't shown
+/// together if some of them come from macros, whose backtraces belong to
+/// each span.
+fn snippet_spans<'a>(cm: &codemap::CodeMap, msp: &'a MultiSpan) -> Option<Vec<SnippetSpan<'a>>> {
+    if msp.labels.is_empty() {
+        return None;
+    }
+
+    let primary = (msp.primary, msp.primary_label.as_ref().map(|label| &label[..]), true);
+    let labels = msp.labels.iter().map(|&(sp, ref label)| (sp, Some(&label[..]), false));
+    let mut spans = vec![];
+    for (sp, label, is_primary) in Some(primary).into_iter().chain(labels) {
+        if sp.expn_id != codemap::NO_EXPANSION || sp == codemap::DUMMY_SP {
+            return None;
+        }
+        let lo = cm.lookup_char_pos(sp.lo);
+        let hi = cm.lookup_char_pos(sp.hi);
+        if lo.line != hi.line {
+            return None;
+        }
+        if spans.first().map_or(false, |first: &SnippetSpan| first.lo.file.name != lo.file.name) {
+            return None;
+        }
+        spans.push(SnippetSpan { lo: lo, hi: hi, label: label, is_primary: is_primary });
+    }
+
+    spans.sort_by(|a, b| (a.lo.line, a.lo.col.0).cmp(&(b.lo.line, b.lo.col.0)));
+    if spans[spans.len() - 1].lo.line - spans[0].lo.line >= MAX_LINES {
+        return None;
+    }
+    Some(spans)
+}
+
+/// The indentation and the `^~~~` marker pointing at the columns `lo` to
+/// `hi` of `line`, when the line is printed after `skip` characters of
+/// `filename:line ` prefix.
+fn underline(line: &str, skip: usize, lo: CharPos, hi: CharPos) -> (String, String) {
+    let mut indent = String::new();
+    for _ in 0..skip {
+        indent.push(' ');
+    }
+    let mut col = skip;
+    let mut lastc = ' ';
+    let mut iter = line.chars().enumerate();
+    for (pos, ch) in iter.by_ref() {
+        lastc = ch;
+        if pos >= lo.to_usize() { break; }
+        // Whenever a tab occurs on the previous line, we insert one on
+        // the error-point-squiggly-line as well (instead of a space).
+        // That way the squiggly line will usually appear in the correct
+        // position.
+        match ch {
+            '\t' => {
+                col += 8 - col%8;
+                indent.push('\t');
+            },
+            _ => {
+                col += 1;
+                indent.push(' ');
+            },
+        }
+    }
+
+    let mut s = String::from("^");
+    let count = match lastc {
+        // Most terminals have a tab stop every eight columns by default
+        '\t' => 8 - col%8,
+        _ => 1,
+    };
+    col += count;
+    s.extend(::std::iter::repeat('~').take(count));
+
+    if hi != lo {
+        for (pos, ch) in iter {
+            if pos >= hi.to_usize() { break; }
+            let count = match ch {
+                '\t' => 8 - col%8,
+                _ => 1,
+            };
+            col += count;
+            s.extend(::std::iter::repeat('~').take(count));
+        }
+    }
+
+    if s.len() > 1 {
+        // One extra squiggly is replaced by a "^"
+        s.pop();
+    }
+    (indent, s)
+}
+
 #[cfg(test)]
 mod test {
-    use super::{EmitterWriter, Level};
-    use codemap::{mk_sp, CodeMap};
+    use super::{Emitter, EmitterWriter, Level};
+    use codemap::{mk_sp, BytePos, CodeMap, MultiSpan};
+    use diagnostics::registry::Registry;
     use std::sync::{Arc, Mutex};
     use std::io::{self, Write};
     use std::str::from_utf8;
 
+    struct Sink(Arc<Mutex<Vec<u8>>>);
+    impl Write for Sink {
+        fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+            Write::write(&mut *self.0.lock().unwrap(), data)
+        }
+        fn flush(&mut self) -> io::Result<()> { Ok(()) }
+    }
+
     // Diagnostic doesn't align properly in span where line number increases by one digit
     #[test]
     fn test_hilight_suggestion_issue_11715() {
-        struct Sink(Arc<Mutex<Vec<u8>>>);
-        impl Write for Sink {
-            fn write(&mut self, data: &[u8]) -> io::Result<usize> {
-                Write::write(&mut *self.0.lock().unwrap(), data)
-            }
-            fn flush(&mut self) -> io::Result<()> { Ok(()) }
-        }
         let data = Arc::new(Mutex::new(Vec::new()));
         let mut ew = EmitterWriter::new(Box::new(Sink(data.clone())), None);
         let cm = CodeMap::new();
@@ -897,4 +1048,53 @@ mod test {
                          dummy.txt:11         e-lä-vän\n\
                          dummy.txt:12         tolv\n");
     }
+
+    #[test]
+    fn test_multi_span_labels_before_explanation() {
+        let data = Arc::new(Mutex::new(Vec::new()));
+        let registry = Registry::new(&[("E0428", "duplicate definitions")]);
+        let mut ew = EmitterWriter::new(Box::new(Sink(data.clone())), Some(registry));
+        let cm = CodeMap::new();
+        // Too far apart to be shown in one snippet.
+        let file = cm.new_filemap_and_lines("dummy.rs", "struct A;\n\n\n\n\n\n\n\nstruct A;\n");
+        let first = file.lines.borrow()[0];
+        let second = file.lines.borrow()[8];
+        let mut msp = MultiSpan::new(mk_sp(second, second + BytePos(9)));
+        msp.push_label(mk_sp(first, first + BytePos(9)),
+                       "first definition here".to_string());
+        ew.emit_multi(&cm, &msp, "duplicate definition", Some("E0428"), Level::Error);
+
+        let vec = data.lock().unwrap().clone();
+        let str = from_utf8(&vec).unwrap();
+        println!("{}", str);
+        let error = str.find("dummy.rs:9:1: 9:10 error: duplicate definition [E0428]").unwrap();
+        let note = str.find("dummy.rs:1:1: 1:10 note: first definition here").unwrap();
+        let help = str.find("help: run `rustc --explain E0428`").unwrap();
+        assert!(error < note && note < help);
+    }
+
+    #[test]
+    fn test_multi_span_close_spans_in_one_snippet() {
+        let data = Arc::new(Mutex::new(Vec::new()));
+        let mut ew = EmitterWriter::new(Box::new(Sink(data.clone())), None);
+        let cm = CodeMap::new();
+        let file = cm.new_filemap_and_lines("dummy.rs", "struct A;\n\nstruct A;\n");
+        let first = file.lines.borrow()[0];
+        let second = file.lines.borrow()[2];
+        let mut msp = MultiSpan::new(mk_sp(second + BytePos(7), second + BytePos(8)));
+        msp.set_primary_label("`A` redefined here".to_string());
+        msp.push_label(mk_sp(first + BytePos(7), first + BytePos(8)),
+                       "previous definition here".to_string());
+        ew.emit_multi(&cm, &msp, "duplicate definition", Some("E0428"), Level::Error);
+
+        let vec = data.lock().unwrap().clone();
+        let str = from_utf8(&vec).unwrap();
+        println!("{}", str);
+        assert_eq!(str, "dummy.rs:3:8: 3:9 error: duplicate definition [E0428]\n\
+                         dummy.rs:1 struct A;\n\
+                         \x20                 ^ previous definition here\n\
+                         dummy.rs:2 \n\
+                         dummy.rs:3 struct A;\n\
+                         \x20                 ^ `A` redefined here\n");
+    }
 }
@@ -30,6 +30,14 @@ macro_rules! span_err {
     })
 }
 
+#[macro_export]
+macro_rules! span_err_multi {
+    ($session:expr, $msp:expr, $code:ident, $($message:tt)*) => ({
+        __diagnostic_used!($code);
+        $session.span_err_multi_with_code($msp, &format!($($message)*), stringify!($code))
+    })
+}
+
 #[macro_export]
 macro_rules! span_err_or_warn {
     ($is_warning:expr, $session:expr, $span:expr, $code:ident, $($message:tt)*) => ({
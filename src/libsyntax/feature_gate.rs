@@ -36,6 +36,7 @@ use diagnostic::SpanHandler;
 use visit;
 use visit::{FnKind, Visitor};
 use parse::token::InternedString;
+use util::lev_distance::lev_distance;
 
 use std::ascii::AsciiExt;
 use std::cmp;
@@ -224,6 +225,21 @@ const KNOWN_FEATURES: &'static [(&'static str, &'static str, Option<u32>, Status
 
     // Allows cfg(target_vendor = "...").
     ("cfg_target_vendor", "1.5.0", Some(29718), Active),
+
+    // allow `Self(..)` and unit-like `Self` as struct constructors
+    // inside impls
+    ("self_struct_ctor", "1.6.0", None, Active),
+
+    // Allows the `modifiers` key of `#[link]`
+    ("link_modifiers", "1.6.0", None, Active),
+
+    // Allows `#![generated_code_lints = "..."]` to cap the lints in items
+    // marked `#[automatically_derived]`
+    ("generated_code_lints", "1.6.0", None, Active),
+
+    // Allows `use crate::...` to start an import path at the crate root
+    // explicitly
+    ("crate_in_paths", "1.6.0", None, Active),
 ];
 // (changing above list without updating src/doc/reference.md makes @cmr sad)
 
@@ -286,6 +302,9 @@ pub const KNOWN_ATTRIBUTES: &'static [(&'static str, AttributeType, AttributeGat
                                  "no_std is experimental")),
     ("no_core", CrateLevel, Gated("no_core",
                                   "no_core is experimental")),
+    ("generated_code_lints", CrateLevel, Gated("generated_code_lints",
+                                               "the `generated_code_lints` attribute \
+                                                is experimental")),
     ("lang", Normal, Gated("lang_items",
                            "language items are subject to change")),
     ("linkage", Whitelisted, Gated("linkage",
@@ -318,6 +337,10 @@ pub const KNOWN_ATTRIBUTES: &'static [(&'static str, AttributeType, AttributeGat
                                            "the `#[rustc_move_fragments]` attribute \
                                             is just used for rustc unit tests \
                                             and will never be stable")),
+    ("rustc_symbol_name", Whitelisted, Gated("rustc_attrs",
+                                             "the `#[rustc_symbol_name]` attribute \
+                                              is just used for rustc unit tests \
+                                              and will never be stable")),
     ("rustc_mir", Normal, Gated("rustc_attrs",
                                 "the `#[rustc_mir]` attribute \
                                  is just used for rustc unit tests \
@@ -501,6 +524,9 @@ pub struct Features {
     pub cfg_target_vendor: bool,
     pub augmented_assignments: bool,
     pub braced_empty_structs: bool,
+    pub self_struct_ctor: bool,
+    pub rustc_attrs: bool,
+    pub crate_in_paths: bool,
 }
 
 impl Features {
@@ -532,6 +558,9 @@ impl Features {
             cfg_target_vendor: false,
             augmented_assignments: false,
             braced_empty_structs: false,
+            self_struct_ctor: false,
+            rustc_attrs: false,
+            crate_in_paths: false,
         }
     }
 }
@@ -649,6 +678,18 @@ fn find_lang_feature_issue(feature: &str) -> Option<u32> {
     issue
 }
 
+/// The active language feature that `name` is most likely a typo of, if
+/// there is one within a third of `name`'s length in edits.
+pub fn find_similar_lang_feature(name: &str) -> Option<&'static str> {
+    let max_distance = cmp::max(name.len(), 3) / 3;
+    KNOWN_FEATURES.iter()
+                  .filter(|info| if let Active = info.3 { true } else { false })
+                  .map(|info| (lev_distance(name, info.0), info.0))
+                  .filter(|&(distance, _)| distance <= max_distance)
+                  .min()
+                  .map(|(_, feature)| feature)
+}
+
 pub enum GateIssue {
     Language,
     Library(Option<u32>)
@@ -663,12 +704,21 @@ pub fn emit_feature_err(diag: &SpanHandler, feature: &str, span: Span, issue: Ga
 
     if let Some(n) = issue {
         diag.span_err(span, &format!("{} (see issue #{})", explain, n));
+        diag.fileline_note(span, &format!("for more information, see \
+                                           https://github.com/rust-lang/rust/issues/{}",
+                                          n));
     } else {
         diag.span_err(span, explain);
     }
 
     // #23973: do not suggest `#![feature(...)]` if we are in beta/stable
-    if option_env!("CFG_DISABLE_UNSTABLE_FEATURES").is_some() { return; }
+    if option_env!("CFG_DISABLE_UNSTABLE_FEATURES").is_some() {
+        let release_channel = option_env!("CFG_RELEASE_CHANNEL").unwrap_or("(unknown)");
+        diag.fileline_note(span, &format!("`{}` is only available on the nightly release \
+                                           channel, not on {}",
+                                          feature, release_channel));
+        return;
+    }
     diag.fileline_help(span, &format!("add #![feature({})] to the \
                                    crate attributes to enable",
                                   feature));
@@ -793,6 +843,16 @@ impl<'a, 'v> Visitor<'v> for PostExpansionVisitor<'a> {
                                        across platforms, it is recommended to \
                                        use `#[link(name = \"foo\")]` instead")
                 }
+                for attr in i.attrs.iter().filter(|a| a.check_name("link")) {
+                    let has_modifiers = attr.meta_item_list().map_or(false, |items| {
+                        items.iter().any(|item| item.check_name("modifiers"))
+                    });
+                    if has_modifiers {
+                        self.gate_feature("link_modifiers", attr.span,
+                                          "linking modifiers are experimental \
+                                           and subject to change");
+                    }
+                }
                 let maybe_feature = match foreign_module.abi {
                     Abi::RustIntrinsic => Some(("intrinsics", "intrinsics are subject to change")),
                     Abi::PlatformIntrinsic => {
@@ -1104,6 +1164,9 @@ fn check_crate_inner<F>(cm: &CodeMap, span_handler: &SpanHandler,
         cfg_target_vendor: cx.has_feature("cfg_target_vendor"),
         augmented_assignments: cx.has_feature("augmented_assignments"),
         braced_empty_structs: cx.has_feature("braced_empty_structs"),
+        self_struct_ctor: cx.has_feature("self_struct_ctor"),
+        rustc_attrs: cx.has_feature("rustc_attrs"),
+        crate_in_paths: cx.has_feature("crate_in_paths"),
     }
 }
 
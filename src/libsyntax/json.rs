@@ -0,0 +1,200 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A diagnostic emitter for tools, enabled with `-Z json-errors`.
+//!
+//! Every diagnostic is written to stderr as a JSON object on a line of its
+//! own. Its spans are given both as byte offsets into their file and as
+//! 1-based lines and columns, and the secondary spans of a diagnostic carry
+//! their label. Notes and help messages following an error are diagnostics
+//! of their own, in the order they are reported.
+
+use codemap::{self, CodeMap, MultiSpan, Span, COMMAND_LINE_EXPN};
+use diagnostic::{self, Emitter, Level, RenderSpan};
+
+use serialize::json::as_json;
+use std::io::{self, Write};
+
+pub struct JsonEmitter {
+    dst: Box<Write + Send>,
+}
+
+impl JsonEmitter {
+    pub fn stderr() -> JsonEmitter {
+        JsonEmitter::new(Box::new(io::stderr()))
+    }
+
+    pub fn new(dst: Box<Write + Send>) -> JsonEmitter {
+        JsonEmitter { dst: dst }
+    }
+
+    fn emit_diagnostic(&mut self, diagnostic: &Diagnostic) {
+        if let Err(e) = writeln!(&mut self.dst, "{}", as_json(diagnostic)) {
+            panic!("failed to print diagnostics: {:?}", e);
+        }
+    }
+}
+
+impl Emitter for JsonEmitter {
+    fn emit(&mut self, cmsp: Option<(&CodeMap, Span)>,
+            msg: &str, code: Option<&str>, lvl: Level) {
+        let spans = match cmsp {
+            Some((cm, sp)) => DiagnosticSpan::from_span(cm, sp, true, None, None),
+            None => vec![],
+        };
+        self.emit_diagnostic(&Diagnostic::new(msg, code, lvl, spans));
+    }
+
+    fn custom_emit(&mut self, cm: &CodeMap,
+                   sp: RenderSpan, msg: &str, lvl: Level) {
+        let spans = match sp {
+            diagnostic::Suggestion(sp, suggestion) => {
+                DiagnosticSpan::from_span(cm, sp, true, None, Some(suggestion))
+            }
+            diagnostic::FullSpan(sp) |
+            diagnostic::EndSpan(sp) |
+            diagnostic::FileLine(sp) => DiagnosticSpan::from_span(cm, sp, true, None, None),
+        };
+        self.emit_diagnostic(&Diagnostic::new(msg, None, lvl, spans));
+    }
+
+    fn emit_multi(&mut self, cm: &CodeMap, msp: &MultiSpan,
+                  msg: &str, code: Option<&str>, lvl: Level) {
+        let mut spans = DiagnosticSpan::from_span(cm, msp.primary, true,
+                                                  msp.primary_label.clone(), None);
+        for &(sp, ref label) in &msp.labels {
+            spans.extend(DiagnosticSpan::from_span(cm, sp, false, Some(label.clone()), None));
+        }
+        self.emit_diagnostic(&Diagnostic::new(msg, code, lvl, spans));
+    }
+}
+
+#[derive(RustcEncodable)]
+struct Diagnostic {
+    message: String,
+    /// The error code, e.g. `E0428`.
+    code: Option<String>,
+    /// "error", "warning", "note", "help" or "internal compiler error".
+    level: &'static str,
+    spans: Vec<DiagnosticSpan>,
+}
+
+impl Diagnostic {
+    fn new(msg: &str, code: Option<&str>, lvl: Level, spans: Vec<DiagnosticSpan>) -> Diagnostic {
+        Diagnostic {
+            message: msg.to_string(),
+            code: code.map(|code| code.to_string()),
+            level: match lvl {
+                diagnostic::Bug => "internal compiler error",
+                diagnostic::Fatal | diagnostic::Error => "error",
+                diagnostic::Warning => "warning",
+                diagnostic::Note => "note",
+                diagnostic::Help => "help",
+            },
+            spans: spans,
+        }
+    }
+}
+
+#[derive(RustcEncodable)]
+struct DiagnosticSpan {
+    file_name: String,
+    /// The offsets of the span in its file.
+    byte_start: usize,
+    byte_end: usize,
+    /// 1-based, inclusive.
+    line_start: usize,
+    line_end: usize,
+    /// 1-based, the end is exclusive.
+    column_start: usize,
+    column_end: usize,
+    /// Whether this is the span the diagnostic is reported at, rather than
+    /// one it merely refers to.
+    is_primary: bool,
+    label: Option<String>,
+    /// The code the span should be replaced with, for suggestions.
+    suggested_replacement: Option<String>,
+}
+
+impl DiagnosticSpan {
+    /// The span `sp`, unless it doesn't point into any file, e.g. because it
+    /// comes from the command line.
+    fn from_span(cm: &CodeMap,
+                 sp: Span,
+                 is_primary: bool,
+                 label: Option<String>,
+                 suggested_replacement: Option<String>)
+                 -> Vec<DiagnosticSpan> {
+        if sp.expn_id == COMMAND_LINE_EXPN || sp == codemap::DUMMY_SP {
+            return vec![];
+        }
+        let start = cm.lookup_char_pos(sp.lo);
+        let end = cm.lookup_char_pos(sp.hi);
+        vec![DiagnosticSpan {
+            file_name: start.file.name.clone(),
+            byte_start: (sp.lo - start.file.start_pos).0 as usize,
+            byte_end: (sp.hi - start.file.start_pos).0 as usize,
+            line_start: start.line,
+            line_end: end.line,
+            column_start: start.col.0 + 1,
+            column_end: end.col.0 + 1,
+            is_primary: is_primary,
+            label: label,
+            suggested_replacement: suggested_replacement,
+        }]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::JsonEmitter;
+    use codemap::{mk_sp, BytePos, CodeMap, MultiSpan};
+    use diagnostic::{Emitter, Level};
+    use std::io::{self, Write};
+    use std::str::from_utf8;
+    use std::sync::{Arc, Mutex};
+
+    struct Sink(Arc<Mutex<Vec<u8>>>);
+    impl Write for Sink {
+        fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+            Write::write(&mut *self.0.lock().unwrap(), data)
+        }
+        fn flush(&mut self) -> io::Result<()> { Ok(()) }
+    }
+
+    #[test]
+    fn labels_are_secondary_spans() {
+        let data = Arc::new(Mutex::new(Vec::new()));
+        let mut emitter = JsonEmitter::new(Box::new(Sink(data.clone())));
+        let cm = CodeMap::new();
+        let file = cm.new_filemap_and_lines("dummy.rs", "struct A;\nstruct A;\n");
+        let first = file.lines.borrow()[0];
+        let second = file.lines.borrow()[1];
+        let mut msp = MultiSpan::new(mk_sp(second + BytePos(7), second + BytePos(8)));
+        msp.push_label(mk_sp(first + BytePos(7), first + BytePos(8)),
+                       "first definition here".to_string());
+        emitter.emit_multi(&cm, &msp, "duplicate definition", Some("E0428"), Level::Error);
+        emitter.emit(None, "aborting due to previous error", None, Level::Fatal);
+
+        let output = data.lock().unwrap().clone();
+        assert_eq!(from_utf8(&output).unwrap(),
+                   "{\"message\":\"duplicate definition\",\"code\":\"E0428\",\"level\":\"error\",\
+                    \"spans\":[\
+                    {\"file_name\":\"dummy.rs\",\"byte_start\":17,\"byte_end\":18,\
+                    \"line_start\":2,\"line_end\":2,\"column_start\":8,\"column_end\":9,\
+                    \"is_primary\":true,\"label\":null,\"suggested_replacement\":null},\
+                    {\"file_name\":\"dummy.rs\",\"byte_start\":7,\"byte_end\":8,\
+                    \"line_start\":1,\"line_end\":1,\"column_start\":8,\"column_end\":9,\
+                    \"is_primary\":false,\"label\":\"first definition here\",\
+                    \"suggested_replacement\":null}]}\n\
+                    {\"message\":\"aborting due to previous error\",\"code\":null,\
+                    \"level\":\"error\",\"spans\":[]}\n");
+    }
+}
@@ -63,6 +63,7 @@ macro_rules! panictry {
 
 pub mod util {
     pub mod interner;
+    pub mod lev_distance;
     pub mod node_count;
     pub mod parser;
     #[cfg(test)]
@@ -93,6 +94,7 @@ pub mod diagnostic;
 pub mod entry;
 pub mod feature_gate;
 pub mod fold;
+pub mod json;
 pub mod owned_slice;
 pub mod parse;
 pub mod ptr;
@@ -182,7 +182,9 @@ pub fn new_parser_from_source_str<'a>(sess: &'a ParseSess,
 pub fn new_parser_from_file<'a>(sess: &'a ParseSess,
                                 cfg: ast::CrateConfig,
                                 path: &Path) -> Parser<'a> {
-    filemap_to_parser(sess, file_to_filemap(sess, path, None), cfg)
+    let mut p = filemap_to_parser(sess, file_to_filemap(sess, path, None), cfg);
+    p.filename = Some(path.to_path_buf());
+    p
 }
 
 /// Given a session, a crate config, a path, and a span, add
@@ -197,6 +199,7 @@ pub fn new_sub_parser_from_file<'a>(sess: &'a ParseSess,
     let mut p = filemap_to_parser(sess, file_to_filemap(sess, path, Some(sp)), cfg);
     p.owns_directory = owns_directory;
     p.root_module_name = module_name;
+    p.filename = Some(path.to_path_buf());
     p
 }
 
@@ -278,6 +278,11 @@ pub struct Parser<'a> {
     /// name is not known. This does not change while the parser is descending
     /// into modules, and sub-parsers have new values for this name.
     pub root_module_name: Option<String>,
+    /// The file this parser reads, if it was created for one. The modules
+    /// declared in it are looked up relative to it, rather than to the file
+    /// the current span points into, which may be a macro definition. Unlike
+    /// the name of the file in the codemap, the path needn't be UTF-8.
+    pub filename: Option<PathBuf>,
     pub expected_tokens: Vec<TokenType>,
 }
 
@@ -317,6 +322,8 @@ pub struct ModulePathSuccess {
 pub struct ModulePathError {
     pub err_msg: String,
     pub help_msg: String,
+    /// The paths at which no file was found.
+    pub paths_tried: Vec<::std::path::PathBuf>,
 }
 
 
@@ -358,6 +365,7 @@ impl<'a> Parser<'a> {
             open_braces: Vec::new(),
             owns_directory: true,
             root_module_name: None,
+            filename: None,
             expected_tokens: Vec::new(),
         }
     }
@@ -4879,6 +4887,7 @@ impl<'a> Parser<'a> {
                                   default_path_str,
                                   secondary_path_str,
                                   dir_path.display()),
+                paths_tried: vec![default_path, secondary_path],
             }),
             (true, true) => Err(ModulePathError {
                 err_msg: format!("file for module `{}` found at both {} and {}",
@@ -4886,6 +4895,7 @@ impl<'a> Parser<'a> {
                                  default_path_str,
                                  secondary_path_str),
                 help_msg: "delete or rename one of them to remove the ambiguity".to_owned(),
+                paths_tried: vec![],
             }),
         };
 
@@ -4900,14 +4910,24 @@ impl<'a> Parser<'a> {
                    id: ast::Ident,
                    outer_attrs: &[ast::Attribute],
                    id_sp: Span) -> PResult<ModulePathSuccess> {
-        let mut prefix = PathBuf::from(&self.sess.codemap().span_to_filename(self.span));
-        prefix.pop();
-        let mut dir_path = prefix;
+        let mut dir_path = match self.filename {
+            Some(ref filename) => filename.clone(),
+            None => PathBuf::from(&self.sess.codemap().span_to_filename(self.span)),
+        };
+        dir_path.pop();
         for part in &self.mod_path_stack {
             dir_path.push(&**part);
         }
 
         if let Some(p) = Parser::submod_path_from_attr(outer_attrs, &dir_path) {
+            if !self.sess.codemap().file_exists(&p) {
+                let err = self.span_fatal_help(id_sp,
+                                               &format!("file not found for module `{}`", id),
+                                               "the `#[path]` attribute of the module names \
+                                                a file that doesn't exist");
+                self.note_paths_tried(id_sp, &[p]);
+                return Err(err);
+            }
             return Ok(ModulePathSuccess { path: p, owns_directory: true });
         }
 
@@ -4934,10 +4954,25 @@ impl<'a> Parser<'a> {
 
         match paths.result {
             Ok(succ) => Ok(succ),
-            Err(err) => Err(self.span_fatal_help(id_sp, &err.err_msg, &err.help_msg)),
+            Err(err) => {
+                let fatal = self.span_fatal_help(id_sp, &err.err_msg, &err.help_msg);
+                self.note_paths_tried(id_sp, &err.paths_tried);
+                Err(fatal)
+            }
         }
     }
 
+    fn note_paths_tried(&self, sp: Span, paths: &[PathBuf]) {
+        if paths.is_empty() {
+            return;
+        }
+        let paths = paths.iter()
+                         .map(|path| format!("{:?}", path.display()))
+                         .collect::<Vec<_>>();
+        self.sess.span_diagnostic.fileline_note(sp, &format!("paths tried: {}",
+                                                             paths.join(", ")));
+    }
+
     /// Read a module from a source file.
     fn eval_src_mod(&mut self,
                     id: ast::Ident,
@@ -5605,7 +5640,14 @@ impl<'a> Parser<'a> {
             return Ok(P(spanned(lo, self.span.hi, ViewPathList(path, idents))));
         }
 
-        let first_ident = try!(self.parse_ident());
+        // `crate::` names the crate root, which resolve checks is allowed.
+        let first_ident = if self.token.is_keyword_allow_following_colon(keywords::Crate) &&
+                             self.look_ahead(1, |t| *t == token::ModSep) {
+            try!(self.bump());
+            ast::Ident::with_empty_ctxt(keywords::Crate.to_name())
+        } else {
+            try!(self.parse_ident())
+        };
         let mut path = vec!(first_ident);
         if let token::ModSep = self.token {
             // foo::bar or foo::{a,b,c} or foo::*
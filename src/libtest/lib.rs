@@ -62,6 +62,7 @@ use self::OutputLocation::*;
 use stats::Stats;
 use getopts::{OptGroup, optflag, optopt};
 use serialize::Encodable;
+use serialize::json;
 use std::boxed::FnBox;
 use term::Terminal;
 use term::color::{Color, RED, YELLOW, GREEN, CYAN};
@@ -84,7 +85,7 @@ use std::time::Duration;
 // to be used by rustc to compile tests in libtest
 pub mod test {
     pub use {Bencher, TestName, TestResult, TestDesc,
-             TestDescAndFn, TestOpts, TrFailed, TrIgnored, TrOk,
+             TestDescAndFn, TestOpts, BenchOpts, TrFailed, TrIgnored, TrOk,
              Metric, MetricMap,
              StaticTestFn, StaticTestName, DynTestName, DynTestFn,
              run_test, test_main, test_main_static, filter_tests,
@@ -290,6 +291,31 @@ pub struct TestOpts {
     pub logfile: Option<PathBuf>,
     pub nocapture: bool,
     pub color: ColorConfig,
+    pub bench_opts: BenchOpts,
+    pub bench_results: Option<PathBuf>,
+}
+
+/// How benchmarks are measured, set with the `--bench-*` flags.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct BenchOpts {
+    /// How long each benchmark runs before it's measured, to warm up
+    /// caches and let the CPU frequency settle.
+    pub warmup: Duration,
+    /// How many times each round of the measurement is repeated.
+    pub samples: usize,
+    /// The percentage of the lowest and of the highest samples that are
+    /// taken as outliers and clamped to the remaining ones.
+    pub outlier_pct: f64,
+}
+
+impl Default for BenchOpts {
+    fn default() -> BenchOpts {
+        BenchOpts {
+            warmup: Duration::new(0, 0),
+            samples: 50,
+            outlier_pct: 5.0,
+        }
+    }
 }
 
 impl TestOpts {
@@ -303,6 +329,8 @@ impl TestOpts {
             logfile: None,
             nocapture: false,
             color: AutoColor,
+            bench_opts: BenchOpts::default(),
+            bench_results: None,
         }
     }
 }
@@ -322,7 +350,15 @@ fn optgroups() -> Vec<getopts::OptGroup> {
       getopts::optopt("", "color", "Configure coloring of output:
             auto   = colorize if stdout is a tty and tests are run on serially (default);
             always = always colorize output;
-            never  = never colorize output;", "auto|always|never"))
+            never  = never colorize output;", "auto|always|never"),
+      getopts::optopt("", "bench-warmup", "Run each benchmark for this long before \
+                                           measuring it (default: 0)", "MILLISECONDS"),
+      getopts::optopt("", "bench-samples", "Number of samples taken in each round of \
+                                            a measurement (default: 50)", "N"),
+      getopts::optopt("", "bench-outliers", "Percentage of samples at either end \
+                                             clamped as outliers (default: 5)", "PCT"),
+      getopts::optopt("", "bench-results", "Write the results of the benchmarks to \
+                                            the specified file as JSON", "PATH"))
 }
 
 fn usage(binary: &str) {
@@ -397,6 +433,12 @@ pub fn parse_opts(args: &[String]) -> Option<OptRes> {
                                             v))),
     };
 
+    let bench_opts = match parse_bench_opts(&matches) {
+        Ok(bench_opts) => bench_opts,
+        Err(e) => return Some(Err(e)),
+    };
+    let bench_results = matches.opt_str("bench-results").map(|s| PathBuf::from(&s));
+
     let test_opts = TestOpts {
         filter: filter,
         run_ignored: run_ignored,
@@ -405,17 +447,57 @@ pub fn parse_opts(args: &[String]) -> Option<OptRes> {
         logfile: logfile,
         nocapture: nocapture,
         color: color,
+        bench_opts: bench_opts,
+        bench_results: bench_results,
     };
 
     Some(Ok(test_opts))
 }
 
+fn parse_bench_opts(matches: &getopts::Matches) -> Result<BenchOpts, String> {
+    let mut bench_opts = BenchOpts::default();
+    if let Some(ms) = matches.opt_str("bench-warmup") {
+        match ms.parse::<u64>() {
+            Ok(ms) => bench_opts.warmup = Duration::from_millis(ms),
+            Err(_) => return Err(format!("argument for --bench-warmup must be a number of \
+                                          milliseconds (was {})", ms)),
+        }
+    }
+    if let Some(n) = matches.opt_str("bench-samples") {
+        match n.parse::<usize>() {
+            Ok(n) if n > 0 => bench_opts.samples = n,
+            _ => return Err(format!("argument for --bench-samples must be a positive \
+                                     integer (was {})", n)),
+        }
+    }
+    if let Some(pct) = matches.opt_str("bench-outliers") {
+        match pct.parse::<f64>() {
+            Ok(pct) if pct >= 0.0 && pct < 50.0 => bench_opts.outlier_pct = pct,
+            _ => return Err(format!("argument for --bench-outliers must be a percentage \
+                                     below 50 (was {})", pct)),
+        }
+    }
+    Ok(bench_opts)
+}
+
 #[derive(Clone, PartialEq)]
 pub struct BenchSamples {
     ns_iter_summ: stats::Summary,
     mb_s: usize,
 }
 
+/// The result of a benchmark as written by `--bench-results`, in
+/// nanoseconds per iteration.
+#[derive(RustcEncodable)]
+struct BenchResult {
+    name: String,
+    median: f64,
+    median_abs_dev: f64,
+    min: f64,
+    max: f64,
+    mb_s: usize,
+}
+
 #[derive(Clone, PartialEq)]
 pub enum TestResult {
     TrOk,
@@ -442,6 +524,7 @@ struct ConsoleTestState<T> {
     ignored: usize,
     measured: usize,
     metrics: MetricMap,
+    bench_results: Vec<BenchResult>,
     failures: Vec<(TestDesc, Vec<u8> )> ,
     max_name_len: usize, // number of columns to fill when aligning names
 }
@@ -468,6 +551,7 @@ impl<T: Write> ConsoleTestState<T> {
             ignored: 0,
             measured: 0,
             metrics: MetricMap::new(),
+            bench_results: Vec::new(),
             failures: Vec::new(),
             max_name_len: 0,
         })
@@ -695,6 +779,14 @@ pub fn run_tests_console(opts: &TestOpts, tests: Vec<TestDescAndFn> ) -> io::Res
                         st.metrics.insert_metric(test.name.as_slice(),
                                                  bs.ns_iter_summ.median,
                                                  bs.ns_iter_summ.max - bs.ns_iter_summ.min);
+                        st.bench_results.push(BenchResult {
+                            name: test.name.to_string(),
+                            median: bs.ns_iter_summ.median,
+                            median_abs_dev: bs.ns_iter_summ.median_abs_dev,
+                            min: bs.ns_iter_summ.min,
+                            max: bs.ns_iter_summ.max,
+                            mb_s: bs.mb_s,
+                        });
                         st.measured += 1
                     }
                     TrFailed => {
@@ -722,6 +814,14 @@ pub fn run_tests_console(opts: &TestOpts, tests: Vec<TestDescAndFn> ) -> io::Res
         None => {}
     }
     try!(run_tests(opts, tests, |x| callback(&x, &mut st)));
+    // Only when something was measured, so that the results of a run that
+    // only had tests don't replace those of the last benchmarks.
+    if let Some(ref path) = opts.bench_results {
+        if !st.bench_results.is_empty() {
+            let mut file = try!(File::create(path));
+            try!(writeln!(file, "{}", json::as_pretty_json(&st.bench_results)));
+        }
+    }
     return st.write_run_finish();
 }
 
@@ -750,6 +850,7 @@ fn should_sort_failures_before_printing_them() {
         measured: 0,
         max_name_len: 10,
         metrics: MetricMap::new(),
+        bench_results: Vec::new(),
         failures: vec!((test_b, Vec::new()), (test_a, Vec::new()))
     };
 
@@ -1019,12 +1120,13 @@ pub fn run_test(opts: &TestOpts,
 
     match testfn {
         DynBenchFn(bencher) => {
-            let bs = ::bench::benchmark(|harness| bencher.run(harness));
+            let bs = ::bench::benchmark(&opts.bench_opts, |harness| bencher.run(harness));
             monitor_ch.send((desc, TrBench(bs), Vec::new())).unwrap();
             return;
         }
         StaticBenchFn(benchfn) => {
-            let bs = ::bench::benchmark(|harness| (benchfn.clone())(harness));
+            let bs = ::bench::benchmark(&opts.bench_opts,
+                                        |harness| (benchfn.clone())(harness));
             monitor_ch.send((desc, TrBench(bs), Vec::new())).unwrap();
             return;
         }
@@ -1147,7 +1249,19 @@ impl Bencher {
     }
 
     // This is a more statistics-driven benchmark algorithm
-    pub fn auto_bench<F>(&mut self, mut f: F) -> stats::Summary where F: FnMut(&mut Bencher) {
+    pub fn auto_bench<F>(&mut self, opts: &BenchOpts, mut f: F) -> stats::Summary
+        where F: FnMut(&mut Bencher)
+    {
+        // Warm up without measuring anything. Runs too short for the clock
+        // to see never add up to the warm-up time, so the number of runs is
+        // bounded as well.
+        let mut warmup = Duration::new(0, 0);
+        let mut warmup_runs = 0;
+        while warmup < opts.warmup && warmup_runs < 1_000_000 {
+            warmup = warmup + Duration::span(|| self.bench_n(1, |x| f(x)));
+            warmup_runs += 1;
+        }
+
         // Initial bench run to get ballpark figure.
         let mut n = 1;
         self.bench_n(n, |x| f(x));
@@ -1167,7 +1281,7 @@ impl Bencher {
         if n == 0 { n = 1; }
 
         let mut total_run = Duration::new(0, 0);
-        let samples : &mut [f64] = &mut [0.0_f64; 50];
+        let samples : &mut [f64] = &mut vec![0.0_f64; opts.samples];
         loop {
             let mut summ = None;
             let mut summ5 = None;
@@ -1179,7 +1293,7 @@ impl Bencher {
                     *p = self.ns_per_iter() as f64;
                 };
 
-                stats::winsorize(samples, 5.0);
+                stats::winsorize(samples, opts.outlier_pct);
                 summ = Some(stats::Summary::new(samples));
 
                 for p in &mut *samples {
@@ -1187,7 +1301,7 @@ impl Bencher {
                     *p = self.ns_per_iter() as f64;
                 };
 
-                stats::winsorize(samples, 5.0);
+                stats::winsorize(samples, opts.outlier_pct);
                 summ5 = Some(stats::Summary::new(samples));
             });
             let summ = summ.unwrap();
@@ -1222,16 +1336,16 @@ impl Bencher {
 pub mod bench {
     use std::cmp;
     use std::time::Duration;
-    use super::{Bencher, BenchSamples};
+    use super::{Bencher, BenchOpts, BenchSamples};
 
-    pub fn benchmark<F>(f: F) -> BenchSamples where F: FnMut(&mut Bencher) {
+    pub fn benchmark<F>(opts: &BenchOpts, f: F) -> BenchSamples where F: FnMut(&mut Bencher) {
         let mut bs = Bencher {
             iterations: 0,
             dur: Duration::new(0, 0),
             bytes: 0
         };
 
-        let ns_iter_summ = bs.auto_bench(f);
+        let ns_iter_summ = bs.auto_bench(opts, f);
 
         let ns_iter = cmp::max(ns_iter_summ.median as u64, 1);
         let iter_s = 1_000_000_000 / ns_iter;
@@ -1256,10 +1370,17 @@ pub mod bench {
 #[cfg(test)]
 mod tests {
     use test::{TrFailed, TrIgnored, TrOk, filter_tests, parse_opts,
-               TestDesc, TestDescAndFn, TestOpts, run_test,
-               MetricMap,
-               StaticTestName, DynTestName, DynTestFn, ShouldPanic};
+               TestDesc, TestDescAndFn, TestOpts, BenchOpts, run_test,
+               MetricMap, Bencher,
+               StaticTestName, DynTestName, DynTestFn, StaticBenchFn, ShouldPanic};
+    use run_tests_console;
+    use serialize::json::Json;
+    use std::env;
+    use std::fs::{self, File};
+    use std::io::prelude::*;
+    use std::path::PathBuf;
     use std::sync::mpsc::channel;
+    use std::time::Duration;
 
     #[test]
     pub fn do_not_run_ignored_tests() {
@@ -1375,6 +1496,88 @@ mod tests {
         assert!((opts.run_ignored));
     }
 
+    #[test]
+    fn parse_bench_flags() {
+        let args = vec!("progname".to_string(),
+                        "--bench".to_string(),
+                        "--bench-warmup".to_string(), "200".to_string(),
+                        "--bench-samples".to_string(), "20".to_string(),
+                        "--bench-outliers".to_string(), "2.5".to_string(),
+                        "--bench-results".to_string(), "results.json".to_string());
+        let opts = match parse_opts(&args) {
+            Some(Ok(o)) => o,
+            _ => panic!("Malformed arg in parse_bench_flags")
+        };
+        assert_eq!(opts.bench_opts, BenchOpts {
+            warmup: Duration::from_millis(200),
+            samples: 20,
+            outlier_pct: 2.5,
+        });
+        assert_eq!(opts.bench_results, Some(PathBuf::from("results.json")));
+
+        let args = vec!("progname".to_string(),
+                        "--bench-outliers".to_string(), "50".to_string());
+        assert!(parse_opts(&args).unwrap().is_err());
+    }
+
+    fn bench_and_test() -> Vec<TestDescAndFn> {
+        fn b(b: &mut Bencher) { b.iter(|| 1 + 1) }
+        fn t() {}
+        vec![TestDescAndFn {
+                 desc: TestDesc {
+                     name: StaticTestName("b"),
+                     ignore: false,
+                     should_panic: ShouldPanic::No,
+                 },
+                 testfn: StaticBenchFn(b),
+             },
+             TestDescAndFn {
+                 desc: TestDesc {
+                     name: StaticTestName("t"),
+                     ignore: false,
+                     should_panic: ShouldPanic::No,
+                 },
+                 testfn: DynTestFn(Box::new(move || t())),
+             }]
+    }
+
+    #[test]
+    fn bench_results_as_json() {
+        let path = env::temp_dir().join("libtest-bench-results-as-json.json");
+        let _ = fs::remove_file(&path);
+        let mut opts = TestOpts::new();
+        opts.bench_benchmarks = true;
+        opts.bench_opts.samples = 5;
+        opts.bench_results = Some(path.clone());
+        assert!(run_tests_console(&opts, bench_and_test()).unwrap());
+
+        let mut contents = String::new();
+        File::open(&path).unwrap().read_to_string(&mut contents).unwrap();
+        fs::remove_file(&path).unwrap();
+        let results = Json::from_str(&contents).unwrap();
+        let results = results.as_array().unwrap();
+        // The test isn't measured
+        assert_eq!(results.len(), 1);
+        let result = results[0].as_object().unwrap();
+        assert_eq!(result["name"].as_string(), Some("b"));
+        for key in &["median", "median_abs_dev", "min", "max"] {
+            assert!(result[*key].as_f64().unwrap() >= 0.0);
+        }
+        assert!(result["min"].as_f64() <= result["max"].as_f64());
+        assert_eq!(result["mb_s"].as_u64(), Some(0));
+    }
+
+    #[test]
+    fn no_bench_results_without_benchmarks() {
+        let path = env::temp_dir().join("libtest-no-bench-results.json");
+        let _ = fs::remove_file(&path);
+        let mut opts = TestOpts::new();
+        opts.run_tests = true;
+        opts.bench_results = Some(path.clone());
+        assert!(run_tests_console(&opts, bench_and_test()).unwrap());
+        assert!(!path.exists());
+    }
+
     #[test]
     pub fn filter_for_ignored_option() {
         // When we run ignored tests the test filter should filter out all the
@@ -0,0 +1,295 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Checks that all the links in a directory of generated documentation
+//! resolve.
+//!
+//! Every relative `href` and `src` in every HTML file has to point to a file
+//! that exists. If the link has a fragment, the page it points to also has to
+//! have an element with that `id` (or `name`). Links with a scheme, e.g.
+//! `http://`, are external and aren't checked.
+//!
+//! Links into the docs of the crates given after the doc dir aren't checked
+//! either: the compiler crates are only documented with
+//! `--enable-compiler-docs`, but the book links to them anyway.
+//!
+//! Usage: `linkchecker <doc dir> [<crate without docs>...]`
+
+#![feature(path_relative_from)]
+
+use std::char;
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::fs::{read_dir, File};
+use std::io::{self, Read, Write};
+use std::path::{Component, Path, PathBuf};
+use std::process;
+
+/// The ids defined by an HTML file, loaded on demand.
+type Cache = HashMap<PathBuf, HashSet<String>>;
+
+fn main() {
+    let mut args = env::args().skip(1);
+    let docs = match args.next() {
+        Some(docs) => env::current_dir().unwrap().join(docs),
+        None => {
+            let _ = writeln!(&mut io::stderr(),
+                             "usage: linkchecker <doc dir> [<crate without docs>...]");
+            process::exit(2);
+        }
+    };
+    let undocumented = args.map(|krate| docs.join(krate)).collect::<Vec<_>>();
+
+    let mut cache = Cache::new();
+    let mut errors = 0;
+    walk(&mut cache, &docs, &undocumented, &docs, &mut errors);
+    if errors > 0 {
+        let _ = writeln!(&mut io::stderr(), "found {} broken links", errors);
+        process::exit(1);
+    }
+}
+
+fn walk(cache: &mut Cache, root: &Path, undocumented: &[PathBuf], dir: &Path,
+        errors: &mut usize) {
+    for entry in read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            walk(cache, root, undocumented, &path, errors);
+        } else if is_html(&path) {
+            check(cache, root, undocumented, &path, errors);
+        }
+    }
+}
+
+fn is_html(path: &Path) -> bool {
+    path.extension().and_then(|s| s.to_str()) == Some("html")
+}
+
+fn check(cache: &mut Cache, root: &Path, undocumented: &[PathBuf], file: &Path,
+         errors: &mut usize) {
+    let contents = read(file);
+    let pretty_file = file.relative_from(root).unwrap_or(file);
+
+    for attr in &[" href", " src"] {
+        with_attrs_in_source(&contents, attr, |url, line| {
+            // external links, and links to the search results
+            if url.contains("://") || url.starts_with("mailto:") ||
+               url.starts_with("javascript:") || url.starts_with("data:") ||
+               url.starts_with('?') || url.starts_with('/') {
+                return
+            }
+
+            let (path, fragment) = match url.find('#') {
+                Some(i) => (&url[..i], &url[i + 1..]),
+                None => (url, ""),
+            };
+            let path = match path.find('?') {
+                Some(i) => &path[..i],
+                None => path,
+            };
+
+            let mut target = if path.is_empty() {
+                file.to_path_buf()
+            } else {
+                normalize(&file.parent().unwrap().join(path))
+            };
+            // Links out of the documentation, e.g. from a crate's docs to the
+            // book when only the crate was documented, can't be checked.
+            if !target.starts_with(root) ||
+               undocumented.iter().any(|dir| target.starts_with(dir)) {
+                return
+            }
+            if target.is_dir() {
+                target.push("index.html");
+            }
+
+            if !target.exists() {
+                *errors += 1;
+                println!("{}:{}: broken link - {}", pretty_file.display(), line, url);
+                return
+            }
+
+            // Fragments of source pages are line numbers, which are only
+            // resolved by javascript.
+            if fragment.is_empty() || !is_html(&target) ||
+               fragment.chars().all(|c| c.is_digit(10) || c == '-') {
+                return
+            }
+            let ids = cache.entry(target.clone()).or_insert_with(|| {
+                let mut ids = HashSet::new();
+                let contents = read(&target);
+                for attr in &[" id", " name"] {
+                    with_attrs_in_source(&contents, attr, |id, _| {
+                        ids.insert(id.to_string());
+                    });
+                }
+                ids
+            });
+            if !ids.contains(fragment) {
+                *errors += 1;
+                println!("{}:{}: broken link fragment `#{}` pointing to `{}`",
+                         pretty_file.display(), line, fragment,
+                         target.relative_from(root).unwrap_or(&target).display());
+            }
+        });
+    }
+}
+
+fn read(file: &Path) -> String {
+    let mut contents = String::new();
+    File::open(file).and_then(|mut f| f.read_to_string(&mut contents)).unwrap();
+    contents
+}
+
+/// Resolves the `..` and `.` components of `path`, without looking at the
+/// file system.
+fn normalize(path: &Path) -> PathBuf {
+    let mut ret = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => { ret.pop(); }
+            c => ret.push(c.as_os_str()),
+        }
+    }
+    ret
+}
+
+/// Calls `f` with the value and line number of every `attr="value"` (or
+/// `attr='value'`) in `contents`, where `attr` includes the leading space.
+/// The character references in the value are decoded.
+fn with_attrs_in_source<F: FnMut(&str, usize)>(contents: &str, attr: &str, mut f: F) {
+    for (i, mut line) in contents.lines().enumerate() {
+        while let Some(j) = line.find(attr) {
+            let rest = &line[j + attr.len()..];
+            line = rest;
+
+            let pos_equals = match rest.find('=') {
+                Some(i) => i,
+                None => continue,
+            };
+            if !rest[..pos_equals].trim().is_empty() {
+                continue
+            }
+            let rest = &rest[pos_equals + 1..];
+
+            let pos_quote = match rest.find(&['"', '\''][..]) {
+                Some(i) => i,
+                None => continue,
+            };
+            if !rest[..pos_quote].trim().is_empty() {
+                continue
+            }
+            let quote = rest.as_bytes()[pos_quote] as char;
+            let rest = &rest[pos_quote + 1..];
+
+            let value = match rest.find(quote) {
+                Some(i) => &rest[..i],
+                None => continue,
+            };
+            f(&decode_entities(value), i + 1)
+        }
+    }
+}
+
+/// Decodes the character references in an attribute value, e.g. the `&amp;`
+/// between the parameters of a query or the `&lt;` of a generic type in a
+/// fragment. Unknown references are left as they are.
+fn decode_entities(mut value: &str) -> String {
+    let mut ret = String::new();
+    while let Some(i) = value.find('&') {
+        ret.push_str(&value[..i]);
+        value = &value[i..];
+
+        let entity = match value.find(';') {
+            Some(j) => &value[..j + 1],
+            None => break,
+        };
+        let decoded = match entity {
+            "&amp;" => Some('&'),
+            "&lt;" => Some('<'),
+            "&gt;" => Some('>'),
+            "&quot;" => Some('"'),
+            "&apos;" => Some('\''),
+            _ if entity.starts_with("&#x") || entity.starts_with("&#X") => {
+                u32::from_str_radix(&entity[3..entity.len() - 1], 16).ok()
+                                                                      .and_then(char::from_u32)
+            }
+            _ if entity.starts_with("&#") => {
+                entity[2..entity.len() - 1].parse().ok().and_then(char::from_u32)
+            }
+            _ => None,
+        };
+        match decoded {
+            Some(c) => {
+                ret.push(c);
+                value = &value[entity.len()..];
+            }
+            None => {
+                ret.push('&');
+                value = &value[1..];
+            }
+        }
+    }
+    ret.push_str(value);
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_entities, normalize, with_attrs_in_source};
+    use std::path::{Path, PathBuf};
+
+    fn attrs(contents: &str, attr: &str) -> Vec<(String, usize)> {
+        let mut ret = vec![];
+        with_attrs_in_source(contents, attr, |value, line| ret.push((value.to_string(), line)));
+        ret
+    }
+
+    #[test]
+    fn quoted_values() {
+        let contents = "<a href=\"a.html\">a</a>\n\
+                        <a href = 'b.html'>b</a> <a href=\"c.html#x\">c</a>";
+        assert_eq!(attrs(contents, " href"),
+                   [("a.html".to_string(), 1), ("b.html".to_string(), 2),
+                    ("c.html#x".to_string(), 2)]);
+    }
+
+    #[test]
+    fn other_attributes() {
+        let contents = "<a data-href=\"a.html\" hreflang=\"en\" href=\"b.html\" \
+                        title=\"href\">b</a> <a href>c</a>";
+        assert_eq!(attrs(contents, " href"), [("b.html".to_string(), 1)]);
+    }
+
+    #[test]
+    fn entities_in_values() {
+        let contents = "<a href=\"a.html?x=1&amp;y=2\">a</a>\n\
+                        <a href=\"b.html#impl-From&lt;T&gt;\">b</a>";
+        assert_eq!(attrs(contents, " href"),
+                   [("a.html?x=1&y=2".to_string(), 1), ("b.html#impl-From<T>".to_string(), 2)]);
+    }
+
+    #[test]
+    fn entities() {
+        assert_eq!(decode_entities("a&quot;b&apos;c"), "a\"b'c");
+        assert_eq!(decode_entities("&#39;&#x27;&#X27;"), "'''");
+        assert_eq!(decode_entities("&nbsp;&&#xzz;&"), "&nbsp;&&#xzz;&");
+        assert_eq!(decode_entities("&amp;lt;"), "&lt;");
+    }
+
+    #[test]
+    fn normalized_paths() {
+        assert_eq!(normalize(Path::new("/doc/std/../core/./index.html")),
+                   PathBuf::from("/doc/core/index.html"));
+        assert_eq!(normalize(Path::new("/doc/std/fmt/../../index.html")),
+                   PathBuf::from("/doc/index.html"));
+    }
+}
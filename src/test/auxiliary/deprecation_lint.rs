@@ -0,0 +1,49 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[deprecated(since = "1.0.0", note = "text")]
+pub fn deprecated_text() {}
+
+#[deprecated]
+pub fn deprecated() {}
+
+pub fn not_deprecated() {}
+
+pub struct MethodTester;
+
+impl MethodTester {
+    #[deprecated(since = "1.0.0", note = "text")]
+    pub fn method_deprecated_text(&self) {}
+
+    pub fn method_not_deprecated(&self) {}
+}
+
+pub trait Trait {
+    #[deprecated(note = "text")]
+    fn trait_deprecated_text(&self) {}
+}
+
+impl Trait for MethodTester {}
+
+#[deprecated(since = "1.0.0", note = "text")]
+pub struct DeprecatedStruct {
+    pub i: isize,
+}
+
+pub struct Stable {
+    #[deprecated(note = "text")]
+    pub override1: u8,
+    pub stable: u8,
+}
+
+#[deprecated(note = "text")]
+pub mod deprecated_mod {
+    pub fn inherited() {}
+}
@@ -0,0 +1,48 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-tidy-linelength
+// compile-flags:-Zprint-trans-items=eager
+
+#![deny(dead_code)]
+
+trait Trait {
+    fn method(&self) -> u32;
+}
+
+impl Trait for u32 {
+    fn method(&self) -> u32 { *self }
+}
+
+impl Trait for i64 {
+    fn method(&self) -> u32 { *self as u32 }
+}
+
+fn identity<T>(x: T) -> T { x }
+
+fn pair<T1, T2>(a: T1, b: T2) -> (T1, T2) { (a, b) }
+
+fn call_method<T: Trait>(x: T) -> u32 { x.method() }
+
+//~ TRANS_ITEM fn main
+fn main() {
+    //~ TRANS_ITEM fn identity<u32>
+    let _ = identity(0u32);
+    //~ TRANS_ITEM fn identity<char>
+    let _ = identity('c');
+    //~ TRANS_ITEM fn pair<u8, i16>
+    let _ = pair(0u8, 0i16);
+    //~ TRANS_ITEM fn call_method<u32>
+    //~ TRANS_ITEM fn <u32 as Trait>::method
+    let _ = call_method(1u32);
+    //~ TRANS_ITEM fn call_method<i64>
+    //~ TRANS_ITEM fn <i64 as Trait>::method
+    let _ = call_method(1i64);
+}
@@ -0,0 +1,34 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags:-Zprint-trans-items=lazy
+
+// In lazy mode only the entry point is a root of this executable, so
+// functions that are never called from `main` are not collected, and
+// neither is anything only they reference.
+
+#![allow(dead_code)]
+
+fn identity<T>(x: T) -> T { x }
+
+fn used() -> u32 { identity(1u32) }
+
+fn unused() -> u64 { identity(2u64) }
+
+fn only_called_from_unused() {
+    let _ = unused();
+}
+
+//~ TRANS_ITEM fn main
+fn main() {
+    //~ TRANS_ITEM fn used
+    //~ TRANS_ITEM fn identity<u32>
+    let _ = used();
+}
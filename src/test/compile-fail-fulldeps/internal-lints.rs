@@ -0,0 +1,50 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -D rustc-internal
+
+#![feature(rustc_private)]
+#![allow(dead_code)]
+
+extern crate rustc;
+extern crate syntax;
+
+use rustc::session::Session;
+use syntax::ast::Name;
+use syntax::codemap::DUMMY_SP;
+use syntax::diagnostic::Handler;
+
+fn check(sess: &Session, handler: &Handler, name: Name) {
+    sess.span_err(DUMMY_SP, "oops"); //~ ERROR has no error code
+    handler.err("oops"); //~ ERROR has no error code
+    sess.note("fine");
+    if name.as_str() == "foo" {} //~ ERROR comparing a name's string with a literal
+    if &*name.as_str() != "bar" {} //~ ERROR comparing a name's string with a literal
+}
+
+#[allow(missing_error_codes)]
+fn allowed(sess: &Session) {
+    sess.span_err(DUMMY_SP, "oops");
+}
+
+// Types that only share their names with the compiler's aren't linted
+mod local {
+    pub struct Session;
+
+    impl Session {
+        pub fn span_err(&self, _msg: &str) {}
+    }
+}
+
+fn unrelated(sess: &local::Session) {
+    sess.span_err("fine");
+}
+
+fn main() {}
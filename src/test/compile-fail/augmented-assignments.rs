@@ -24,7 +24,8 @@ fn main() {
     let mut x = Int(1);
     x   //~ error: use of moved value: `x`
     +=
-    x;  //~ note: `x` moved here because it has type `Int`, which is non-copyable
+    x;
+    //~^ note: `x` moved here because it has type `Int`, which does not implement the `Copy` trait
 
     let y = Int(2);
     y   //~ error: cannot borrow immutable local variable `y` as mutable
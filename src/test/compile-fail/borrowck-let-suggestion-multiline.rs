@@ -0,0 +1,41 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// The `let` binding suggested for a temporary in a statement over several
+// lines goes before the whole statement, and the temporary is replaced
+// where it is.
+
+fn temporary_on_second_line() {
+    let x = 1;
+    let mut v = Vec::new();
+    v.push(
+        &[x].len()
+    );
+    //~^^ ERROR borrowed value does not live long enough
+    //~^^^^ NOTE ...but borrowed value is only valid for the statement
+    //~| HELP consider using a `let` binding to increase its lifetime
+    //~| SUGGESTION &binding
+}
+
+fn temporary_over_two_lines() {
+    let x = 1;
+    let mut v = Vec::new();
+    v.push(&[x,
+             x].len());
+    //~^^ ERROR borrowed value does not live long enough
+    //~| NOTE ...but borrowed value is only valid for the statement
+    //~| HELP consider using a `let` binding to increase its lifetime
+    //~| SUGGESTION v.push(&binding);
+}
+
+fn main() {
+    temporary_on_second_line();
+    temporary_over_two_lines();
+}
@@ -0,0 +1,26 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// The variable suggested for a temporary that doesn't live long enough gets
+// a name that the function doesn't use already.
+
+fn f() {
+    let binding = 1;
+    let mut v = Vec::new();
+    v.push(&[binding].len());
+    //~^ ERROR borrowed value does not live long enough
+    //~| NOTE ...but borrowed value is only valid for the statement
+    //~| HELP consider using a `let` binding to increase its lifetime
+    //~| SUGGESTION let binding1 = [binding].len();
+}
+
+fn main() {
+    f();
+}
@@ -0,0 +1,33 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test the help offered when a value whose type does not implement
+// `Copy` is used after being moved.
+
+struct Point { x: i32, y: i32 }
+
+fn consume(_: Vec<i32>) {}
+
+fn main() {
+    let v = vec![1, 2, 3];
+    consume(v);
+    //~^ NOTE `v` moved here because it has type
+    //~| HELP consider borrowing the value instead
+    //~| SUGGESTION consume(&v);
+    v.len(); //~ ERROR use of moved value: `v`
+
+    let p = Point { x: 1, y: 2 };
+    let q = p;
+    //~^ NOTE `p` moved here because it has type `Point`, which does not implement the `Copy` trait
+    //~| HELP consider cloning the value instead, if `Point` implements `Clone`
+    //~| SUGGESTION let q = p.clone();
+    let _ = p.x; //~ ERROR use of moved value: `p.x`
+    let _ = q;
+}
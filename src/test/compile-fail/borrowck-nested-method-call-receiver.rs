@@ -0,0 +1,27 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that using the receiver of a `&mut self` method inside one of
+// its arguments explains that the receiver is borrowed for the whole
+// call.
+
+fn main() {
+    let mut v = vec![1, 2, 3];
+    v.push(v.len());
+    //~^ ERROR cannot borrow `v` as immutable because it is also borrowed as mutable
+    //~| NOTE `v` is mutably borrowed as the receiver of the method call for the whole call
+    //~| HELP consider evaluating the argument into a local before the call: `let arg = v.len();`
+
+    let mut w = vec![1, 2, 3];
+    w.push(w.pop().unwrap());
+    //~^ ERROR cannot borrow `w` as mutable more than once at a time
+    //~| NOTE `w` is mutably borrowed as the receiver of the method call for the whole call
+    //~| HELP consider evaluating the argument into a local before the call: `let arg = w.pop()
+}
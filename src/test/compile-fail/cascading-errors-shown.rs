@@ -0,0 +1,31 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z show-cascading-errors
+
+// -Z show-cascading-errors reports the errors about types that an earlier
+// error left as `[type error]`, along with their help messages.
+
+struct S;
+
+struct W<T> {
+    value: T,
+}
+
+fn make<T>() -> W<T> {
+    loop {}
+}
+
+fn main() {
+    let w: W<S::Missing> = make(); //~ ERROR ambiguous associated type
+    let _ = w.valeu;
+    //~^ ERROR attempted access of field `valeu` on type `W<[type error]>`
+    //~| HELP did you mean `value`?
+}
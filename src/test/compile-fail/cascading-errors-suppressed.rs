@@ -0,0 +1,31 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Only the unresolved type is reported, not the errors about the values
+// of that type, which mention `[type error]`.
+
+struct Wrapper {
+    inner: Missing, //~ ERROR use of undeclared type name `Missing`
+}
+
+fn unwrap(w: Wrapper) -> Vec<Missing> { //~ ERROR use of undeclared type name `Missing`
+    let mut v = vec![w.inner];
+    v.push(w.inner);
+    v[0] = v[1] + 1;
+    *v[0];
+    -v[0];
+    v
+}
+
+fn main() {
+    let w = Wrapper { inner: 1 };
+    let v: Vec<u32> = unwrap(w);
+    let _: u32 = v[0];
+}
@@ -0,0 +1,30 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that an overlap in a non-self input type of the trait is
+// still detected when the other impls for the same self type can be
+// rejected without unification.
+
+trait Convert<T> {
+}
+
+struct Foo;
+
+impl<T> Convert<T> for Foo { //~ ERROR E0119
+//~^ ERROR E0119
+}
+
+impl Convert<u8> for Foo {
+}
+
+impl Convert<u16> for Foo {
+}
+
+fn main() { }
@@ -10,6 +10,7 @@
 
 const FOO: [u32; 3] = [1, 2, 3];
 const BAR: u32 = FOO[5]; //~ ERROR const index-expr is out of bounds
+//~^ NOTE the index is `5` but the length is `3`
 
 fn main() {
     let _ = BAR;
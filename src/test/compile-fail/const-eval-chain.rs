@@ -0,0 +1,26 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// When the evaluation of a constant fails, the values of the operands of the
+// failed operation and the constants that were being evaluated are noted.
+
+const A: u8 = 200;
+const B: u8 = A * 2;
+//~^ ERROR constant evaluation error: attempted to mul with overflow [E0080]
+//~| NOTE this evaluates to `200`
+
+#[repr(u8)]
+enum E {
+    V = B,
+    //~^ NOTE while evaluating the constant `B`
+    //~| NOTE for enum discriminant here
+}
+
+fn main() {}
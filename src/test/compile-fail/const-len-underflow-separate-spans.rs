@@ -16,8 +16,11 @@ const ONE: usize = 1;
 const TWO: usize = 2;
 const LEN: usize = ONE - TWO;
 //~^ ERROR array length constant evaluation error: attempted to sub with overflow [E0250]
+//~| NOTE this evaluates to `1`
+//~| NOTE this evaluates to `2`
 
 fn main() {
     let a: [i8; LEN] = unimplemented!();
-    //~^ NOTE for array length here
+    //~^ NOTE while evaluating the constant `LEN`
+    //~| NOTE for array length here
 }
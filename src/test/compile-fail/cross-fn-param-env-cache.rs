@@ -0,0 +1,39 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Evaluation results under where-clauses are shared between fns with
+// the same where-clauses. Check that a fn with different ones doesn't
+// see them: `Vec<T>: Clone` holds in `cloned` and `cloned_again`, but
+// not in `defaulted`.
+
+fn require_clone<C: Clone>(_: &C) {
+}
+
+fn cloned<T: Clone>(v: Vec<T>) -> Vec<T> {
+    require_clone(&v);
+    v
+}
+
+fn cloned_again<T: Clone>(v: Vec<T>) -> Vec<T> {
+    require_clone(&v);
+    v
+}
+
+fn defaulted<T: Default>(v: Vec<T>) -> Vec<T> {
+    require_clone(&v);
+    //~^ ERROR the trait `core::clone::Clone` is not implemented for the type `T`
+    v
+}
+
+fn main() {
+    cloned(vec![1]);
+    cloned_again(vec![1]);
+    defaulted(vec![1]);
+}
@@ -0,0 +1,60 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:deprecation_lint.rs
+
+#![deny(deprecated)]
+#![allow(dead_code)]
+
+extern crate deprecation_lint;
+
+mod cross_crate {
+    use deprecation_lint::*;
+
+    fn test() {
+        let foo = MethodTester;
+
+        deprecated_text(); //~ ERROR use of deprecated item: text
+        deprecated(); //~ ERROR use of deprecated item
+        not_deprecated();
+
+        foo.method_deprecated_text(); //~ ERROR use of deprecated item: text
+        MethodTester::method_deprecated_text(&foo); //~ ERROR use of deprecated item: text
+        foo.method_not_deprecated();
+
+        foo.trait_deprecated_text(); //~ ERROR use of deprecated item: text
+        <MethodTester as Trait>::trait_deprecated_text(&foo); //~ ERROR use of deprecated item: text
+
+        let _ = DeprecatedStruct { //~ ERROR use of deprecated item: text
+            i: 0 //~ ERROR use of deprecated item: text
+        };
+
+        let x = Stable { override1: 1, stable: 2 };
+        //~^ ERROR use of deprecated item: text
+        let _ = x.override1; //~ ERROR use of deprecated item: text
+        let _ = x.stable;
+
+        deprecated_mod::inherited(); //~ ERROR use of deprecated item: text
+    }
+}
+
+mod this_crate {
+    #[deprecated(since = "1.0.0", note = "text")]
+    fn deprecated_text() {}
+
+    fn not_deprecated() {}
+
+    fn test() {
+        deprecated_text(); //~ ERROR use of deprecated item: text
+        not_deprecated();
+    }
+}
+
+fn main() {}
@@ -0,0 +1,26 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// A derived `Copy` that can't be implemented is reported at the field
+// that isn't `Copy`, not at the `#[derive]` attribute.
+
+#[derive(Copy, Clone)] //~ NOTE the trait `Copy` is derived here
+struct Struct {
+    x: u32,
+    y: String, //~ ERROR field `y` does not implement `Copy`
+}
+
+#[derive(Copy, Clone)] //~ NOTE the trait `Copy` is derived here
+enum Enum {
+    A(u32),
+    B { name: String }, //~ ERROR variant `B` does not implement `Copy`
+}
+
+fn main() {}
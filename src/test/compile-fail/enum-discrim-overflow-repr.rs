@@ -0,0 +1,28 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that discriminants which do not fit their representation
+// are reported with their value, and that duplicates point at both
+// discriminants.
+
+#[repr(u8)] //~ NOTE discriminant type specified here
+enum Small {
+    A = 255,
+    B = 1024, //~ ERROR discriminant value `1024` does not fit in `u8`
+}
+
+#[repr(i16)]
+enum Signed {
+    A = -1, //~ NOTE first use of `-1` here (by variant `A`)
+    B = 0xffff, //~ ERROR discriminant value `65535` does not fit in `i16`
+    C = -1, //~ ERROR discriminant value `-1` already exists
+}
+
+fn main() {}
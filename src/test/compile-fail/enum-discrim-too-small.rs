@@ -14,42 +14,42 @@
 enum Eu8 {
     Au8 = 23,
     Bu8 = 223,
-    Cu8 = -23, //~ ERROR discriminant value outside specified type
+    Cu8 = -23, //~ ERROR does not fit in `u8`
 }
 
 #[repr(i8)] //~ NOTE discriminant type specified here
 enum Ei8 {
     Ai8 = 23,
     Bi8 = -23,
-    Ci8 = 223, //~ ERROR discriminant value outside specified type
+    Ci8 = 223, //~ ERROR does not fit in `i8`
 }
 
 #[repr(u16)] //~ NOTE discriminant type specified here
 enum Eu16 {
     Au16 = 23,
     Bu16 = 55555,
-    Cu16 = -22333, //~ ERROR discriminant value outside specified type
+    Cu16 = -22333, //~ ERROR does not fit in `u16`
 }
 
 #[repr(i16)] //~ NOTE discriminant type specified here
 enum Ei16 {
     Ai16 = 23,
     Bi16 = -22333,
-    Ci16 = 55555, //~ ERROR discriminant value outside specified type
+    Ci16 = 55555, //~ ERROR does not fit in `i16`
 }
 
 #[repr(u32)] //~ NOTE discriminant type specified here
 enum Eu32 {
     Au32 = 23,
     Bu32 = 3_000_000_000,
-    Cu32 = -2_000_000_000, //~ ERROR discriminant value outside specified type
+    Cu32 = -2_000_000_000, //~ ERROR does not fit in `u32`
 }
 
 #[repr(i32)] //~ NOTE discriminant type specified here
 enum Ei32 {
     Ai32 = 23,
     Bi32 = -2_000_000_000,
-    Ci32 = 3_000_000_000, //~ ERROR discriminant value outside specified type
+    Ci32 = 3_000_000_000, //~ ERROR does not fit in `i32`
 }
 
 // u64 currently allows negative numbers, and i64 allows numbers greater than `1<<63`.  This is a
@@ -0,0 +1,34 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+struct Pair(u8, u8);
+
+impl Pair {
+    fn new() -> Self {
+        Self(0, 0) //~ ERROR `Self` as a struct constructor is experimental
+    }
+}
+
+struct Point {
+    x: u8,
+}
+
+impl Point {
+    fn new() -> Self {
+        Self { x: 0 } //~ ERROR `Self` as a struct name is experimental
+    }
+
+    fn x(&self) -> u8 {
+        let Self { x } = *self; //~ ERROR `Self` as a struct name is experimental
+        x
+    }
+}
+
+fn main() {}
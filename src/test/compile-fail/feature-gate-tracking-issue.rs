@@ -0,0 +1,17 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that feature gate errors link to the feature's tracking issue.
+
+fn main() {
+    let x = box 'c'; //~ ERROR box expression syntax is experimental (see issue #27779)
+    //~^ NOTE for more information, see https://github.com/rust-lang/rust/issues/27779
+    println!("x: {}", x);
+}
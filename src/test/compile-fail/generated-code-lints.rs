@@ -0,0 +1,35 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Lints inside items marked `#[automatically_derived]` are capped at the
+// level given by `#![generated_code_lints]`, even where generated code
+// asks for a higher one.
+
+#![feature(generated_code_lints)]
+#![generated_code_lints = "allow"]
+#![deny(non_snake_case, dead_code)]
+
+#[automatically_derived]
+mod generated {
+    pub fn BadName() {}
+
+    #[deny(non_snake_case)]
+    pub fn AnotherBadName() {}
+
+    fn unused() {}
+}
+
+pub fn NotGenerated() {} //~ ERROR function `NotGenerated` should have a snake case name
+
+fn main() {
+    generated::BadName();
+    generated::AnotherBadName();
+    NotGenerated();
+}
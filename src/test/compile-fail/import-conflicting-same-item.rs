@@ -0,0 +1,26 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// An import of the very item it conflicts with is suggested to be removed
+// rather than renamed.
+
+mod geometry {
+    use self::Point;
+    //~^ ERROR import `Point` conflicts with type in this module
+    //~| HELP `Point` is already defined in this module, the import can be removed
+    use self::origin;
+    //~^ ERROR import `origin` conflicts with value in this module
+    //~| HELP `origin` is already defined in this module, the import can be removed
+
+    pub struct Point { x: f64, y: f64 }
+    pub fn origin() {}
+}
+
+fn main() {}
@@ -0,0 +1,36 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// An import that conflicts with an item of its module is suggested a name
+// made from the module it is imported from.
+
+mod shapes {
+    pub struct Circle { pub radius: f64 }
+    pub fn area() {}
+    pub const SIDES: u32 = 0;
+}
+
+use shapes::Circle;
+//~^ ERROR import `Circle` conflicts with type in this module
+//~| HELP rename the import
+//~| SUGGESTION shapes::Circle as ShapesCircle
+use shapes::{area, SIDES as SIDES};
+//~^ ERROR import `area` conflicts with value in this module
+//~| HELP rename the import
+//~| SUGGESTION area as shapes_area
+//~| ERROR import `SIDES` conflicts with value in this module
+//~| HELP rename the import
+//~| SUGGESTION SIDES as SHAPES_SIDES
+
+struct Circle { x: f64, y: f64 }
+fn area() {}
+const SIDES: u32 = 4;
+
+fn main() {}
@@ -0,0 +1,30 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// An import whose `super`s don't lead to what it imports is suggested to
+// start at the crate root instead, when that is where it is.
+
+#![feature(crate_in_paths)]
+
+pub mod a {
+    pub fn f() {}
+}
+
+mod b {
+    mod c {
+        mod d {
+            use super::super::a::f;
+            //~^ ERROR unresolved import `super::super::a::f`
+            //~| NOTE did you mean `use crate::a::f;`?
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,45 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![deny(imports_shadowing_prelude)]
+#![allow(dead_code, unused_imports)]
+
+mod my {
+    pub struct Vec;
+    pub enum Option { Yes, No }
+    pub fn drop() {}
+}
+
+mod shadowing {
+    use my::Vec; //~ ERROR the import of `Vec` shadows the `Vec` of the prelude
+    //~^ NOTE the `Vec` of the prelude is defined in the crate `collections`
+    use my::{drop, Option};
+    //~^ ERROR the import of `drop` shadows the `drop` of the prelude
+    //~| NOTE the `drop` of the prelude is defined in the crate `core`
+    //~| ERROR the import of `Option` shadows the `Option` of the prelude
+    //~| NOTE the `Option` of the prelude is defined in the crate `core`
+}
+
+mod not_shadowing {
+    // The items of the prelude themselves.
+    use std::vec::Vec;
+    use std::option::Option;
+    // Renamed.
+    use my::Vec as MyVec;
+    // Re-exported.
+    pub use my::drop;
+}
+
+mod allowed {
+    #[allow(imports_shadowing_prelude)]
+    use my::Vec;
+}
+
+fn main() {}
@@ -13,12 +13,12 @@ const N: isize = 1;
 enum Foo {
     A = 1,
     B = 1, //~ ERROR discriminant value `1` already exists
-    //~^^ NOTE conflicting
+    //~^^ NOTE first use of `1`
     C = 0,
     D, //~ ERROR discriminant value `1` already exists
-    //~^^^^^ NOTE conflicting
+    //~^^^^^ NOTE first use of `1`
     E = N, //~ ERROR discriminant value `1` already exists
-    //~^^^^^^^ NOTE conflicting
+    //~^^^^^^^ NOTE first use of `1`
 }
 
 fn main() {}
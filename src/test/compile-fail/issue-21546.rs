@@ -10,6 +10,8 @@
 
 // Also works as a test for #14564
 
+#![feature(rustc_attrs)]
+
 #[allow(non_snake_case)]
 mod Foo { }
 //~^ NOTE first definition of type or module `Foo`
@@ -55,11 +57,5 @@ mod Quux { }
 //~^ WARNING duplicate definition of type or module `Quux`
 
 
-#[allow(dead_code)]
-enum Corge { A, B }
-
-#[allow(non_snake_case)]
-mod Corge { }
-//~^ ERROR duplicate definition of type or module `Corge`
-
-fn main() { }
+#[rustc_error]
+fn main() { } //~ ERROR compilation successful
@@ -0,0 +1,19 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(link_modifiers)]
+
+#[link(name = "foo", kind = "static", modifiers = "-whole-archive")]
+#[link(name = "bar", kind = "static", modifiers = "+whole-archive,-whole-archive")]
+#[link(name = "baz", modifiers = "-whole-archive")] //~ ERROR only compatible with `static`
+#[link(name = "qux", kind = "static", modifiers = "+bundle")] //~ ERROR unknown linking modifier
+extern {}
+
+fn main() {}
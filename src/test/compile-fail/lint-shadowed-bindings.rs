@@ -0,0 +1,54 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![deny(shadowed_bindings)]
+#![allow(unused_variables)]
+
+fn args(x: u32) {
+    let x = x as u64; //~ ERROR binding `x: u64` shadows an earlier binding of the same name
+}
+
+fn lets() {
+    let a = "a";
+    {
+        let a = 1; //~ ERROR binding `a: i32` shadows an earlier binding of the same name
+    }
+    let b = 1;
+    let b = b + 1; //~ ERROR binding `b: i32` shadows an earlier binding of the same name
+}
+
+fn arms(o: Option<u8>) {
+    let v = 1u8;
+    match o {
+        Some(v) => {} //~ ERROR binding `v: u8` shadows an earlier binding of the same name
+        None => {}
+    }
+    if let Some(v) = o {} //~ ERROR binding `v: u8` shadows an earlier binding of the same name
+}
+
+fn no_shadowing(o: Result<u8, u8>) {
+    // Bindings of sibling scopes, of the alternatives of a match arm and of
+    // nested `for` loops don't shadow each other.
+    {
+        let c = 1;
+    }
+    let c = 1;
+    match o {
+        Ok(d) | Err(d) => {}
+    }
+    match o {
+        Ok(d) | Err(d) => {}
+    }
+    for i in 0..1 {
+        for j in 0..1 {}
+    }
+}
+
+fn main() {}
@@ -21,6 +21,10 @@ extern crate libc; //~ ERROR: unused extern crate
 
 extern crate collections as collecs; // no error, it is used
 
+extern crate collections as unused_collecs; //~ ERROR: unused extern crate
+                                            // even if the crate is used through
+                                            // another `extern crate`
+
 extern crate rand; // no error, the use marks it as used
                    // even if imported objects aren't used
 
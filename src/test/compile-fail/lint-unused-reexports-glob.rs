@@ -0,0 +1,52 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Uses of a name through a glob import are uses of the re-exports the name
+// comes from, so `pub use` items in private modules that are only used
+// through a glob import aren't linted.
+
+#![crate_type = "lib"]
+#![deny(unused_reexports)]
+#![allow(dead_code)]
+
+mod a {
+    pub fn f() {}
+    pub trait T { fn t(&self) {} }
+    impl T for () {}
+}
+
+mod used {
+    // Only used through the glob import of this module below.
+    pub use a::*;
+}
+
+mod unused {
+    pub use a::*; //~ ERROR unused re-export
+}
+
+mod renamed {
+    // Only used for a method call through the glob import below, under
+    // its new name.
+    pub use a::T as Renamed;
+}
+
+use used::*;
+
+mod method {
+    use renamed::*;
+
+    fn g() {
+        ().t();
+    }
+}
+
+pub fn call() {
+    f();
+}
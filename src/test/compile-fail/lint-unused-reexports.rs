@@ -0,0 +1,56 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![crate_type = "lib"]
+#![deny(unused_reexports)]
+#![allow(dead_code)]
+
+mod private {
+    pub use std::mem::swap; //~ ERROR unused re-export
+    pub use std::mem::{replace, forget}; //~ ERROR unused re-export
+
+    // Hides the `Vec` of the prelude, so it isn't unused.
+    pub use self::inner::Vec;
+
+    mod inner {
+        pub struct Vec;
+    }
+}
+
+mod facade {
+    // Used through the glob import below.
+    pub use std::mem::size_of;
+}
+
+use facade::*;
+
+pub fn size() -> usize {
+    size_of::<u8>()
+}
+
+pub mod public {
+    // Other crates can use it.
+    pub use std::mem::drop;
+}
+
+mod reexported {
+    // Other crates can use it through the glob re-export below.
+    pub use std::mem::swap;
+}
+
+pub use reexported::*;
+
+pub fn replace_it(x: &mut u8) -> u8 {
+    private::replace(x, 0)
+}
+
+pub fn body() {
+    pub use std::mem::size_of; //~ ERROR unused re-export
+}
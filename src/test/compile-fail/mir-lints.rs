@@ -0,0 +1,60 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z mir-lints
+
+// Test the MIR-based `unreachable_code` and `unused_assignments` lints.
+
+#![deny(unreachable_code, unused_assignments)]
+#![allow(unused_variables)]
+
+fn dead_after_return() -> i32 {
+    return 1;
+    let x = 2; //~ ERROR unreachable code
+    x
+}
+
+fn dead_after_loop() -> i32 {
+    loop {}
+    3 //~ ERROR unreachable code
+}
+
+fn reassigned_before_read() -> i32 {
+    let mut x = 1; //~ ERROR value assigned to `x` is never read
+    x = 2;
+    x
+}
+
+fn assigned_on_one_branch(c: bool) -> i32 {
+    let mut x = 1; // read when `c` is false
+    if c {
+        x = 2;
+    }
+    x
+}
+
+fn overwritten_in_loop(n: i32) -> i32 {
+    let mut total = 0;
+    let mut last = 0; //~ ERROR value assigned to `last` is never read
+    for i in 0..n {
+        last = i; //~ ERROR value assigned to `last` is never read
+        last = i + 1;
+        total = total + last;
+    }
+    total
+}
+
+fn main() {
+    dead_after_return();
+    dead_after_loop();
+    reassigned_before_read();
+    assigned_on_one_branch(true);
+    overwritten_in_loop(3);
+}
@@ -0,0 +1,27 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![deny(module_and_struct_with_same_name)]
+#![allow(dead_code, non_snake_case)]
+
+struct Foo;
+//~^ NOTE first definition of type or module `Foo`
+
+mod Foo { }
+//~^ ERROR duplicate definition of type or module `Foo`
+//~| NOTE this was previously accepted by the compiler but is being phased out
+
+#[allow(module_and_struct_with_same_name)]
+mod Bar {
+    pub struct Baz;
+    mod Baz { }
+}
+
+fn main() { }
@@ -0,0 +1,51 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test the suggestions to add or remove a `&` when a pattern does
+// not match a reference (or a non-reference) value.
+
+struct Foo { x: u8 }
+
+fn main() {
+    let opt = Some(1u8);
+    match &opt {
+        Some(x) => {}
+        //~^ ERROR mismatched types
+        //~| HELP the matched value is a reference; consider matching through it:
+        //~| SUGGESTION &Some(x) => {}
+        _ => {}
+    }
+
+    let foo = Foo { x: 1 };
+    let Foo { x } = &mut Foo { x: 2 };
+    //~^ ERROR mismatched types
+    //~| HELP the matched value is a reference; consider matching through it:
+    //~| SUGGESTION let &mut Foo { x } = &mut Foo { x: 2 };
+
+    let (a, b) = (1u8, 2u8);
+    match a {
+        &y => {}
+        //~^ ERROR mismatched types
+        //~| HELP to bind by reference, use `ref` instead of `&`:
+        //~| SUGGESTION ref y => {}
+    }
+    match b {
+        &mut z => {}
+        //~^ ERROR mismatched types
+        //~| HELP to bind by reference, use `ref` instead of `&`:
+        //~| SUGGESTION ref mut z => {}
+    }
+    match (foo, b) {
+        &(_, _) => {}
+        //~^ ERROR mismatched types
+        //~| HELP the matched value is not a reference; consider removing the `&`:
+        //~| SUGGESTION (_, _) => {}
+    }
+}
@@ -0,0 +1,34 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Importing a private item points at its definition, and suggests making it
+// public or using a public re-export of it.
+
+#![allow(unused_imports)]
+
+mod a {
+    mod inner {
+        pub fn reexported() {}
+    }
+
+    pub use self::inner::reexported;
+
+    fn private() {}
+    //~^ NOTE function `private` is defined here
+
+}
+
+use a::private; //~ ERROR function `private` is private
+//~^ HELP consider marking function `private` as `pub`
+use a::inner::reexported; //~ ERROR function `reexported` is inaccessible
+//~^ NOTE module `inner` is private
+//~| HELP it is re-exported publicly as `a::reexported`, which can be used instead
+
+fn main() {}
@@ -0,0 +1,32 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// An item imported through a chain of re-exports that passes through a
+// private module is traced back through the chain to its definition.
+
+mod outer {
+    pub mod c {
+        pub fn f() {} //~ NOTE function `f` is defined here
+    }
+
+    pub mod a {
+        pub use outer::c::f; //~ NOTE which is re-exported here, in module `outer::a`
+    }
+
+    mod b {
+        pub use outer::a::f; //~ NOTE which is re-exported here, in module `outer::b`
+    }
+}
+
+use outer::b::f;
+//~^ ERROR function `f` is inaccessible
+//~| NOTE module `b` is private
+
+fn main() {}
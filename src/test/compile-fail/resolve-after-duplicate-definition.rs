@@ -0,0 +1,34 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// A duplicate definition doesn't stop resolution, which goes on with the
+// first definition and reports the errors that follow.
+
+mod a {
+    pub fn f() {}
+}
+
+mod a { //~ ERROR duplicate definition of type or module `a`
+    pub fn g() {}
+}
+
+fn f() {}
+fn f() {} //~ ERROR duplicate definition of value `f`
+
+enum E { A }
+enum E { B } //~ ERROR duplicate definition of type or module `E`
+
+fn main() {
+    a::f();
+    f();
+    let _ = E::A;
+    let _ = E::B; //~ ERROR unresolved name `E::B`
+    undefined(); //~ ERROR unresolved name `undefined`
+}
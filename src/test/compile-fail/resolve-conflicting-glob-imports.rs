@@ -0,0 +1,25 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// When two glob imports bring in the same name, both the imports and the
+// items they refer to are pointed out.
+
+mod a {
+    pub fn f() {} //~ NOTE the first `f` is defined here
+}
+
+mod b {
+    pub fn f() {} //~ NOTE the second `f` is defined here
+}
+
+use a::*; //~ NOTE previous import of `f` here
+use b::*; //~ ERROR a value named `f` has already been imported in this module
+
+fn main() {}
@@ -0,0 +1,55 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// A name imported as a value by a glob import and as a type by an explicit
+// import, or the other way around, refers to two unrelated items. This is
+// accepted with a warning for now.
+
+#![feature(rustc_attrs)]
+#![allow(dead_code, non_snake_case, unused_imports)]
+
+mod foo {
+    pub fn Bar() {}
+    pub struct Baz { pub x: i32 }
+}
+
+mod bar {
+    pub struct Bar { pub x: i32 }
+    pub fn Baz() {}
+}
+
+mod a {
+    use foo::*; //~ NOTE the value `Bar` is imported here
+    use bar::Bar;
+    //~^ WARN `Bar` is imported as a value by a glob import and as a type by an explicit import
+    //~| NOTE this was previously accepted by the compiler but is being phased out
+}
+
+mod b {
+    use bar::Baz; //~ NOTE the value `Baz` is imported here
+    use foo::*;
+    //~^ WARN `Baz` is imported as a type by a glob import and as a value by an explicit import
+    //~| NOTE this was previously accepted by the compiler but is being phased out
+}
+
+mod c {
+    // Importing both items explicitly is fine.
+    use foo::Bar;
+    use bar::Bar;
+}
+
+#[allow(glob_and_explicit_import_collisions)]
+mod d {
+    use foo::*;
+    use bar::Bar;
+}
+
+#[rustc_error]
+fn main() {} //~ ERROR compilation successful
@@ -0,0 +1,29 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z group-import-errors
+
+// The imports that fail because of the same missing module are reported as
+// one error, which lists all of them. They are far enough apart for the
+// imports to be listed in notes rather than in one snippet.
+
+use a::missing::Foo; //~ ERROR unresolved import path `a::missing` in 3 imports
+use a::missing::Bar; //~ NOTE `a::missing::Bar` is imported here
+
+mod a {
+    pub struct Found;
+}
+
+use a::Found;
+use a::missing::baz::Baz; //~ NOTE `a::missing::baz::Baz` is imported here
+
+use a::NotFound; //~ ERROR unresolved import `a::NotFound`
+
+fn main() {}
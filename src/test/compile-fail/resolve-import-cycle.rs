@@ -0,0 +1,26 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Imports waiting for each other are reported as one cycle, at the first of
+// them, rather than as unresolved imports.
+
+mod a {
+    pub use b::X; //~ ERROR import cycle: `b::X` -> `c::Y` -> `a::X` -> `b::X`
+}
+
+mod b {
+    pub use c::Y as X;
+}
+
+mod c {
+    pub use a::X as Y;
+}
+
+fn main() {}
@@ -0,0 +1,33 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// `self` can only be imported within a list; `use foo::self;` is
+// `use foo;`, which is suggested instead.
+
+mod foo {
+    pub mod bar {
+        pub fn baz() {}
+    }
+}
+
+use foo::bar::self;
+//~^ ERROR `self` imports are only allowed within a { } list
+//~| HELP import the module directly
+//~| HELP write `use foo::bar::{self, ...};`
+
+use foo::self as renamed;
+//~^ ERROR `self` imports are only allowed within a { } list
+//~| HELP import the module directly
+//~| HELP write `use foo::{self, ...};`
+
+fn main() {
+    bar::baz();
+    renamed::bar::baz();
+}
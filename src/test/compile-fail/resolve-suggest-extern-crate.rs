@@ -0,0 +1,21 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// A crate which is available but not declared with `extern crate` can't be
+// imported from, so suggest declaring it.
+
+use core::mem::swap;
+//~^ ERROR unresolved import `core::mem::swap`
+//~| HELP add `extern crate core;`
+
+use no_such_crate::Foo;
+//~^ ERROR unresolved import `no_such_crate::Foo`. Maybe a missing `extern crate no_such_crate`?
+
+fn main() {}
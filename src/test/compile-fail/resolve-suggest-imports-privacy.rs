@@ -0,0 +1,48 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Private items are only suggested for imports in the modules that can use
+// them, and don't take the place of the suggestions that can be used.
+
+#![allow(dead_code)]
+
+mod a {
+    fn thing() {}
+
+    mod inner {
+        fn f() {
+            thing();
+            //~^ ERROR unresolved name `thing`
+            //~| HELP you can import it with `use a::thing;`
+        }
+    }
+}
+
+mod b {
+    fn thing() {}
+}
+
+mod c {
+    fn thing() {}
+}
+
+mod d {
+    fn thing() {}
+}
+
+pub mod e {
+    pub fn thing() {}
+}
+
+fn main() {
+    thing();
+    //~^ ERROR unresolved name `thing`
+    //~| HELP you can import it with `use e::thing;`
+}
@@ -0,0 +1,33 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Unresolved names suggest the imports that would bring them into scope.
+
+mod foo {
+    pub struct Bar;
+
+    pub fn baz() {}
+}
+
+fn main() {
+    let _: HashMap<u8, u8>;
+    //~^ ERROR use of undeclared type name `HashMap`
+    //~| HELP you can import it with `use std::collections::HashMap;`
+    let _ = HashSet::<u8>::new();
+    //~^ ERROR failed to resolve. Use of undeclared type or module `HashSet`
+    //~| HELP you can import it with `use std::collections::HashSet;`
+    //~| ERROR unresolved name `HashSet::new`
+    let _ = Bar;
+    //~^ ERROR unresolved name `Bar`
+    //~| HELP you can import it with `use foo::Bar;`
+    baz();
+    //~^ ERROR unresolved name `baz`
+    //~| HELP you can import it with `use foo::baz;`
+}
@@ -0,0 +1,27 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Import paths are relative to the crate root, so importing from a child of
+// the current module needs `self::`.
+
+mod a {
+    use foo::Bar;
+    //~^ ERROR unresolved import `foo::Bar`
+    //~| NOTE did you mean `use self::foo::Bar;`?
+    use foo::*;
+    //~^ ERROR unresolved import `foo::*`
+    //~| NOTE did you mean `use self::foo::*;`?
+
+    mod foo {
+        pub struct Bar;
+    }
+}
+
+fn main() {}
@@ -0,0 +1,39 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Import paths are relative to the crate root, so importing from a module
+// containing the current one needs `super::`.
+
+mod a {
+    pub struct Baz;
+
+    mod foo {
+        pub struct Bar;
+    }
+
+    mod b {
+        use foo::Bar;
+        //~^ ERROR unresolved import `foo::Bar`
+        //~| NOTE did you mean `use super::foo::Bar;`?
+        use Baz;
+        //~^ ERROR unresolved import `Baz`
+        //~| NOTE did you mean `use super::Baz;`?
+
+        mod c {
+            fn f() {
+                use foo::Bar;
+                //~^ ERROR unresolved import `foo::Bar`
+                //~| NOTE did you mean `use super::super::foo::Bar;`?
+            }
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,25 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// An unresolved name that is an associated item of a trait suggests
+// importing the trait and going through it.
+
+mod shapes {
+    pub trait Area {
+        fn unit_area() -> f64;
+    }
+}
+
+fn main() {
+    let a = unit_area();
+    //~^ ERROR unresolved name `unit_area`
+    //~| HELP the following trait defines an item `unit_area`
+    //~| HELP candidate #1: `use shapes::Area;` and write `Area::unit_area`
+}
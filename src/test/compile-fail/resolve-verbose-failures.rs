@@ -0,0 +1,36 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z verbose-resolve-failures
+
+// Every module searched for a name that fails to resolve is noted, along with
+// what was found there instead.
+
+mod a {
+    pub fn foo() {}
+
+    use self::b::Bar;
+
+    pub mod b {
+        pub struct Bar;
+    }
+}
+
+fn main() {
+    let _: a::foo;
+    //~^ ERROR use of undeclared type name `a::foo`
+    //~| NOTE searched `a` for the type or module `foo`, which only defines it as a value
+    a::Bar;
+    //~^ ERROR unresolved name `a::Bar`
+    //~| NOTE searched `a` for the value `Bar`, skipping a private import of it
+    missing();
+    //~^ ERROR unresolved name `missing`
+    //~| NOTE searched the crate root for the value `missing`
+}
@@ -0,0 +1,21 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// `Self` only names a struct inside an impl whose self type is one.
+
+enum Bar { A }
+
+impl Bar {
+    fn new() -> Bar {
+        Self { x: 1 } //~ ERROR `Self` does not name a structure
+    }
+}
+
+fn main() {}
@@ -10,5 +10,4 @@
 
 #[unstable] //~ ERROR: stability attributes may not be used
 #[stable] //~ ERROR: stability attributes may not be used
-#[deprecated] //~ ERROR: stability attributes may not be used
 fn main() { }
@@ -0,0 +1,38 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+enum Fruit {
+    Apple,
+    Banana(u32),
+    Cherry,
+}
+
+impl Fruit {
+    fn ripe() -> Fruit { Fruit::Apple }
+}
+
+fn main() {
+    let _ = Fruit::Banan(3);
+    //~^ ERROR no associated item named `Banan` found for type `Fruit` in the current scope
+    //~| HELP did you mean the variant `Fruit::Banana`?
+    let _ = Option::Smoe(3);
+    //~^ ERROR no associated item named `Smoe` found for type
+    //~| HELP Option::Some`?
+    let _ = Fruit::ripe();
+    // no variant is close to `Durian`
+    let _ = Fruit::Durian; //~ ERROR no associated item named `Durian` found for type `Fruit`
+
+    match Fruit::ripe() {
+        Fruit::Aple => {}
+        //~^ ERROR no associated item named `Aple` found for type `Fruit` in the current scope
+        //~| HELP did you mean the variant `Fruit::Apple`?
+        _ => {}
+    }
+}
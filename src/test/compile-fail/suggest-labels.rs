@@ -0,0 +1,26 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+fn main() {
+    'outer: loop {
+        'inner: loop {
+            break 'outerr; //~ ERROR use of undeclared label `'outerr`
+            //~^ HELP did you mean `'outer`?
+            continue 'innr; //~ ERROR use of undeclared label `'innr`
+            //~^ HELP did you mean `'inner`?
+        }
+    }
+
+    'a: loop {
+        // labels outside of the closure can't be used in it
+        let _ = || loop { break 'b; }; //~ ERROR use of undeclared label `'b`
+        break 'unrelated; //~ ERROR use of undeclared label `'unrelated`
+    }
+}
@@ -0,0 +1,42 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:struct_field_privacy.rs
+
+extern crate struct_field_privacy as xc;
+
+use xc::B;
+
+struct A {
+    pub a: u32,
+    b: u32,
+}
+
+struct C {
+    ab: u32,
+    ac: u32,
+}
+
+fn f(k: B, l: A, m: C) {
+    // external crate struct
+    let B { aa, .. } = k; //~ ERROR struct `xc::B` does not have a field named `aa`
+    //~^ HELP did you mean `a`?
+    let B { bb, .. } = k; //~ ERROR struct `xc::B` does not have a field named `bb`
+    // local crate struct
+    let A { aa, .. } = l; //~ ERROR struct `A` does not have a field named `aa`
+    //~^ HELP did you mean `a`?
+    let A { bb, .. } = l; //~ ERROR struct `A` does not have a field named `bb`
+    //~^ HELP did you mean `b`?
+    // fields the pattern binds already aren't suggested
+    let C { ab, ax, .. } = m; //~ ERROR struct `C` does not have a field named `ax`
+    //~^ HELP did you mean `ac`?
+}
+
+fn main() {}
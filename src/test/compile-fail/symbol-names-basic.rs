@@ -0,0 +1,40 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that `#[rustc_symbol_name]` reports the symbols of items, and that
+// identically named nested items get distinct symbols.
+
+#![feature(rustc_attrs)]
+#![allow(dead_code)]
+
+#[rustc_symbol_name] //~ ERROR symbol-name(_ZN18symbol_names_basic4main17h
+fn main() {
+}
+
+mod foo {
+    #[rustc_symbol_name] //~ ERROR symbol-name(_ZN18symbol_names_basic3foo3bar17h
+    pub fn bar() {
+        {
+            #[rustc_symbol_name] //~ ERROR symbol-name(_ZN18symbol_names_basic3foo3bar1a17h
+            fn a() {}
+        }
+        {
+            #[rustc_symbol_name] //~ ERROR symbol-name(_ZN18symbol_names_basic3foo3bar1a17h
+            fn a() {}
+        }
+    }
+}
+
+struct Foo;
+
+impl Foo {
+    #[rustc_symbol_name] //~ ERROR symbol-name(_ZN18symbol_names_basic12$LT$impl$GT$3baz17h
+    fn baz() {}
+}
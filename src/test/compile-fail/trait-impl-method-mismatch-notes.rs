@@ -0,0 +1,30 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// The signatures are rendered aligned, and the parameters and return types
+// that differ are pointed out in both the trait and the impl.
+
+trait Foo {
+    fn foo(&self, x: u32, y: &str) -> bool;
+    //~^ NOTE parameter 2 is `u32` in the trait
+    //~| NOTE the return type is `bool` in the trait
+}
+
+struct Bar;
+
+impl Foo for Bar {
+    fn foo(&self, x: i32, y: &str) -> u8 { 0 }
+    //~^ ERROR method `foo` has an incompatible type for trait
+    //~| NOTE the signatures of `foo` in the trait and in the impl
+    //~| NOTE parameter 2 is `i32` in the impl
+    //~| NOTE the return type is `u8` in the impl
+}
+
+fn main() {}
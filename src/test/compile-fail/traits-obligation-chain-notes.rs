@@ -0,0 +1,36 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that when a bound deep inside a chain of impls fails, the
+// error names that bound and notes each impl that required it.
+
+trait Leaf {}
+trait Mid {}
+trait Top {}
+
+impl<T: Leaf> Mid for Vec<T> {}
+impl<T: Mid> Top for Option<T> {}
+
+fn need_top<T: Top>(_: T) {}
+
+// `Vec<T>: Mid` has two candidates here, the where-clause and the impl.
+// The impl's `T: Leaf` fails, but the where-clause holds, so winnowing
+// keeps it and nothing is reported.
+fn with_where_clause<T>(v: Vec<T>) where Vec<T>: Mid {
+    need_top(Some(v));
+}
+
+fn main() {
+    need_top(Some(vec![1u8]));
+    //~^ ERROR the trait `Leaf` is not implemented for the type `u8`
+    //~| NOTE required because of the requirements on the impl of `Mid`
+    //~| NOTE required because of the requirements on the impl of `Top`
+    //~| NOTE required by `need_top`
+}
@@ -0,0 +1,76 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: --cfg set --cfg feature="enabled"
+
+#![deny(unexpected_cfgs)]
+#![allow(dead_code)]
+
+#[cfg(set)]
+fn a() {}
+
+#[cfg(unset)] //~ ERROR unexpected `cfg` name: `unset`
+fn b() {}
+
+#[cfg(feature = "enabled")]
+fn c() {}
+
+#[cfg(featur = "enabled")] //~ ERROR unexpected `cfg` name: `featur`
+fn d() {}
+
+#[cfg(feature = "disabled")] //~ ERROR unexpected `cfg` value: `feature = "disabled"`
+fn e() {}
+
+#[cfg(any(unix, windows, target_os = "no-such-os", test, debug_assertions))]
+fn f() {}
+
+#[cfg(not(all(set, tset)))] //~ ERROR unexpected `cfg` name: `tset`
+fn g() {}
+
+#[cfg_attr(st, derive(Debug))] //~ ERROR unexpected `cfg` name: `st`
+struct S;
+
+fn h() {
+    #[cfg(inner)] //~ ERROR unexpected `cfg` name: `inner`
+    fn inner() {}
+
+    match 0 {
+        #[cfg(arm)] //~ ERROR unexpected `cfg` name: `arm`
+        1 => {}
+        _ => {}
+    }
+}
+
+// The lint is reported at the item owning the attribute, or at the item
+// around it if it is stripped, so it can be allowed there.
+#[allow(unexpected_cfgs)]
+#[cfg_attr(allowed, derive(Debug))]
+struct T;
+
+#[allow(unexpected_cfgs)]
+mod m {
+    #[cfg(allowed)]
+    fn stripped() {}
+}
+
+struct U;
+
+impl U {
+    #[allow(unexpected_cfgs)]
+    fn allowed() {
+        #[cfg(allowed)]
+        fn stripped() {}
+    }
+
+    #[cfg(stripped)] //~ ERROR unexpected `cfg` name: `stripped`
+    fn stripped() {}
+}
+
+fn main() {}
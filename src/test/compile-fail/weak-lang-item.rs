@@ -11,6 +11,8 @@
 // aux-build:weak-lang-items.rs
 // error-pattern: language item required, but not found: `panic_fmt`
 // error-pattern: language item required, but not found: `eh_personality`
+// error-pattern: `weak_lang_items`, and is normally provided by `std`
+// error-pattern: e.g. `#[lang = "eh_personality"] extern fn eh_personality() {}`
 
 #![feature(no_std)]
 #![no_std]
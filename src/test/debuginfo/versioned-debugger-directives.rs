@@ -0,0 +1,60 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Checks that `<debugger><major>-command` and `<debugger><major>-check`
+// directives are only used with that major version of the debugger. There
+// is no version 1 of either debugger, so those lines must be skipped.
+
+// min-lldb-version: 310
+
+// compile-flags:-g
+
+// === GDB TESTS ===================================================================================
+
+// gdb-command:run
+
+// gdb-command:print x
+// gdb-check:$1 = 42
+
+// gdb1-command:print x
+// gdb1-check:$2 = 0
+
+// gdb7-command:print y
+// gdb7-check:$2 = 7
+// gdb8-command:print y
+// gdb8-check:$2 = 7
+
+// gdb-command:continue
+
+
+// === LLDB TESTS ==================================================================================
+
+// lldb-command:run
+
+// lldb-command:print x
+// lldb-check:[...]$0 = 42
+
+// lldb1-command:print x
+// lldb1-check:[...]$1 = 0
+
+// lldb-command:continue
+
+#![allow(unused_variables)]
+#![feature(omit_gdb_pretty_printer_section)]
+#![omit_gdb_pretty_printer_section]
+
+fn main() {
+    let x = 42;
+    let y = 7;
+
+    zzz(); // #break
+}
+
+fn zzz() {()}
@@ -0,0 +1,28 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// The error about the field of a type that an earlier error left as
+// `[type error]` is left out, and so is the help message that follows it,
+// rather than showing up on its own.
+
+struct S;
+
+struct W<T> {
+    value: T,
+}
+
+fn make<T>() -> W<T> {
+    loop {}
+}
+
+fn main() {
+    let w: W<S::Missing> = make();
+    let _ = w.valeu;
+}
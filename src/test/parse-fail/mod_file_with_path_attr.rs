@@ -11,7 +11,9 @@
 // compile-flags: -Z parse-only
 
 #[path = "not_a_real_file.rs"]
-mod m; //~ ERROR not_a_real_file.rs
+mod m; //~ ERROR file not found for module `m`
+//~^ HELP the `#[path]` attribute of the module names a file that doesn't exist
+//~| NOTE not_a_real_file.rs"
 
 fn main() {
     assert_eq!(m::foo(), 10);
@@ -0,0 +1,22 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+pub fn add(a: i32, b: i32) -> i32 {
+    let c = a + b;
+    c
+}
+
+pub fn apply<F: Fn(i32) -> i32>(f: F) -> i32 {
+    f(1)
+}
+
+pub fn main() {
+    apply(|x| add(x, 2));
+}
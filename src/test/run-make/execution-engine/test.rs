@@ -32,7 +32,7 @@ use rustc::session::config::{self, basic_options, build_configuration, Input, Op
 use rustc::session::build_session;
 use rustc_driver::driver;
 use rustc_front::lowering::{lower_crate, LoweringContext};
-use rustc_resolve::MakeGlobMap;
+use rustc_resolve::{MakeGlobMap, MakeVisiblePaths};
 use libc::c_void;
 
 use syntax::diagnostics::registry::Registry;
@@ -229,7 +229,8 @@ fn compile_program(input: &str, sysroot: PathBuf)
         let ast_map = driver::make_map(&sess, &mut hir_forest);
 
         driver::phase_3_run_analysis_passes(
-            &sess, ast_map, &arenas, &id, MakeGlobMap::No, |tcx, mir_map, analysis| {
+            &sess, ast_map, &arenas, &id, MakeGlobMap::No, MakeVisiblePaths::No,
+            |tcx, mir_map, analysis| {
 
             let trans = driver::phase_4_translate_to_llvm(tcx, mir_map, analysis);
 
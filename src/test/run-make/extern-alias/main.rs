@@ -0,0 +1,25 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// `baz` is `foo` passed as `--extern baz=...`, and `qux` is `foo` found
+// through `-L`. All of them, and the `foo` that `bar` depends on, must be
+// the same crate.
+
+extern crate bar;
+extern crate baz;
+extern crate foo as qux;
+
+fn take_baz(_: baz::Foo) {}
+fn take_qux(_: qux::Foo) {}
+
+fn main() {
+    take_baz(bar::make());
+    take_qux(bar::make());
+}
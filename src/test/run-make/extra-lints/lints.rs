@@ -0,0 +1,42 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![crate_type = "dylib"]
+#![feature(box_syntax, rustc_private)]
+
+#[macro_use]
+extern crate rustc;
+extern crate rustc_front;
+
+use rustc::lint::LintArray;
+use rustc::plugin::lints::{ExtraLintContext, ExtraLintPass, LintRegistry};
+use rustc_front::hir;
+
+declare_lint!(TEST_LINT, Warn, "Warn about items named 'lintme'");
+
+struct Pass;
+
+impl ExtraLintPass for Pass {
+    fn get_lints(&self) -> LintArray {
+        lint_array!(TEST_LINT)
+    }
+
+    fn check_item(&mut self, cx: &ExtraLintContext, it: &hir::Item) {
+        if it.name.as_str() == "lintme" {
+            cx.span_lint(TEST_LINT, it.span, "item is named 'lintme'");
+        }
+    }
+}
+
+fn register(reg: &mut LintRegistry) {
+    reg.register_lint_pass(box Pass);
+}
+
+declare_lint_registrar!(register);
@@ -0,0 +1,21 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// A lint registrar exported without the version of rustc it was built with
+
+#![crate_type = "dylib"]
+#![feature(rustc_private)]
+
+extern crate rustc;
+
+use rustc::plugin::lints::LintRegistry;
+
+#[no_mangle]
+pub fn __rustc_lint_registrar(_: &mut LintRegistry) {}
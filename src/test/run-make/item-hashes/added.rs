@@ -0,0 +1,43 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+#![crate_type = "lib"]
+
+// The items of base.rs, with unrelated items added around them. This
+// changes the node ids and def indices of the items of base.rs, but not
+// their hashes.
+
+pub struct Before;
+
+pub fn before(p: Point) -> i32 {
+    p.x
+}
+
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+pub mod between {
+    pub fn triple(a: i32) -> i32 {
+        a * 3
+    }
+}
+
+pub fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+pub mod inner {
+    pub fn double(a: i32) -> i32 {
+        a * 2
+    }
+}
+
+pub const AFTER: i32 = 1;
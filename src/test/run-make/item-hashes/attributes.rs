@@ -0,0 +1,31 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![crate_type = "lib"]
+
+// The same as base.rs, except for a `#[doc]` attribute, which doesn't
+// change the hash, and an `#[inline]` attribute on `add`, which does.
+
+#[doc = "A point."]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+#[inline]
+pub fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+pub mod inner {
+    pub fn double(a: i32) -> i32 {
+        a * 2
+    }
+}
@@ -0,0 +1,28 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![crate_type = "lib"]
+
+// The same items as base.rs, in a different order and with different
+// whitespace and comments.
+
+pub mod inner {
+    /// Doubles `a`.
+    pub fn double(a: i32) -> i32 { a * 2 }
+}
+
+pub fn add(a: i32,
+           b: i32) -> i32 {
+    // comment
+    a + b
+}
+
+
+pub struct Point { pub x: i32, pub y: i32 }
@@ -0,0 +1,23 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+extern crate tools;
+
+use std::fs;
+use tools::{dylib, rustc};
+
+fn main() {
+    rustc().arg("rlib.rs").run();
+    rustc().arg("dylib.rs").run();
+    rustc().arg("rlib.rs").arg("--crate-type=dylib").run();
+    rustc().arg("dylib.rs").run();
+    fs::remove_file(dylib("rlib")).unwrap();
+    rustc().arg("prog.rs").run_fail();
+}
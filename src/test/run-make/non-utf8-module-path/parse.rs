@@ -0,0 +1,42 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(rustc_private)]
+
+extern crate syntax;
+
+use syntax::parse::{self, ParseSess};
+
+use std::env;
+use std::ffi::OsStr;
+use std::fs::{self, File};
+use std::io::Write;
+use std::os::unix::ffi::OsStrExt;
+use std::path::PathBuf;
+
+fn main() {
+    let tmpdir = PathBuf::from(env::args().nth(1).unwrap());
+    let dir = tmpdir.join(OsStr::from_bytes(b"non-utf8-\xff"));
+    fs::create_dir(&dir).unwrap();
+    File::create(dir.join("lib.rs")).unwrap().write_all(b"mod foo;\n").unwrap();
+    File::create(dir.join("foo.rs")).unwrap().write_all(b"pub fn foo() {}\n").unwrap();
+
+    let sess = ParseSess::new();
+    let krate = parse::parse_crate_from_file(&dir.join("lib.rs"), vec![], &sess);
+    assert_eq!(krate.module.items.len(), 1);
+
+    // Both files are named lossily in the codemap.
+    let names: Vec<String> = sess.codemap().files.borrow()
+                                 .iter()
+                                 .map(|fm| fm.name.clone())
+                                 .collect();
+    assert!(names.iter().any(|name| name.ends_with("non-utf8-\u{FFFD}/lib.rs")));
+    assert!(names.iter().any(|name| name.ends_with("non-utf8-\u{FFFD}/foo.rs")));
+}
@@ -0,0 +1,25 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// base.rs with another function changed and an item added.
+
+#![feature(rustc_attrs)]
+#![allow(dead_code)]
+
+pub struct Added;
+
+#[rustc_symbol_name]
+pub fn a() {}
+
+pub fn b() -> u32 {
+    2
+}
+
+fn main() {}
@@ -0,0 +1,239 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Helpers for run-make tests written in Rust.
+//!
+//! A run-make test directory with an `rmake.rs` instead of a `Makefile` is
+//! compiled against this crate and run from the test directory, with the
+//! same environment `tools.mk` gets. Unlike a `Makefile`, it doesn't need a
+//! unix shell, so it also runs on Windows without MSYS.
+//!
+//! ```ignore
+//! extern crate tools;
+//!
+//! use tools::{rustc, run};
+//!
+//! fn main() {
+//!     rustc().arg("bar.rs").run();
+//!     rustc().arg("foo.rs").run();
+//!     run("foo");
+//! }
+//! ```
+
+#![crate_name = "tools"]
+#![crate_type = "rlib"]
+
+use std::env;
+use std::ffi::OsStr;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+fn env_var(name: &str) -> String {
+    match env::var(name) {
+        Ok(v) => v,
+        Err(..) => panic!("environment variable `{}` is not set", name),
+    }
+}
+
+/// The directory where tests should put everything they generate.
+pub fn tmpdir() -> PathBuf {
+    PathBuf::from(env_var("TMPDIR"))
+}
+
+/// The target triple the test is run for.
+pub fn target() -> String {
+    env_var("TARGET")
+}
+
+pub fn is_windows() -> bool {
+    target().contains("windows")
+}
+
+pub fn is_msvc() -> bool {
+    target().contains("msvc")
+}
+
+pub fn is_darwin() -> bool {
+    target().contains("darwin")
+}
+
+/// The file name of the executable `name`, e.g. `foo.exe` on Windows.
+pub fn bin_name(name: &str) -> String {
+    if is_windows() { format!("{}.exe", name) } else { name.to_string() }
+}
+
+/// The file name of the dynamic library `name`, e.g. `libfoo.so`.
+pub fn dylib_name(name: &str) -> String {
+    if is_windows() {
+        format!("{}.dll", name)
+    } else if is_darwin() {
+        format!("lib{}.dylib", name)
+    } else {
+        format!("lib{}.so", name)
+    }
+}
+
+/// The file name of the static library `name`, e.g. `libfoo.a`.
+pub fn staticlib_name(name: &str) -> String {
+    if is_msvc() { format!("{}.lib", name) } else { format!("lib{}.a", name) }
+}
+
+/// The file name of the rlib `name`, e.g. `libfoo.rlib`.
+pub fn rlib_name(name: &str) -> String {
+    format!("lib{}.rlib", name)
+}
+
+/// The path of the dynamic library `name` in `tmpdir()`.
+pub fn dylib(name: &str) -> PathBuf {
+    tmpdir().join(dylib_name(name))
+}
+
+/// The path of the static library `name` in `tmpdir()`.
+pub fn staticlib(name: &str) -> PathBuf {
+    tmpdir().join(staticlib_name(name))
+}
+
+/// The path of the rlib `name` in `tmpdir()`.
+pub fn rlib(name: &str) -> PathBuf {
+    tmpdir().join(rlib_name(name))
+}
+
+/// Adds `dir` and `tmpdir()` to the front of the library search path of
+/// `cmd`, so it finds the dynamic libraries in them.
+fn add_lib_path(cmd: &mut Command, dir: &str) {
+    let var = env_var("LD_LIB_PATH_ENVVAR");
+    let mut paths = vec![tmpdir(), PathBuf::from(dir)];
+    if let Some(old) = env::var_os(&var) {
+        paths.extend(env::split_paths(&old));
+    }
+    cmd.env(&var, env::join_paths(paths).unwrap());
+}
+
+/// A command run by a test, which by default has to succeed.
+pub struct Cmd {
+    cmd: Command,
+}
+
+impl Cmd {
+    pub fn new<S: AsRef<OsStr>>(program: S) -> Cmd {
+        Cmd { cmd: Command::new(program) }
+    }
+
+    pub fn arg<S: AsRef<OsStr>>(&mut self, arg: S) -> &mut Cmd {
+        self.cmd.arg(arg);
+        self
+    }
+
+    pub fn args<S: AsRef<OsStr>>(&mut self, args: &[S]) -> &mut Cmd {
+        self.cmd.args(args);
+        self
+    }
+
+    pub fn env<K: AsRef<OsStr>, V: AsRef<OsStr>>(&mut self, key: K, val: V) -> &mut Cmd {
+        self.cmd.env(key, val);
+        self
+    }
+
+    fn output(&mut self) -> Output {
+        match self.cmd.output() {
+            Ok(output) => output,
+            Err(e) => panic!("failed to run {:?}: {}", self.cmd, e),
+        }
+    }
+
+    /// Runs the command and panics, showing its output, unless it succeeds.
+    pub fn run(&mut self) -> Output {
+        let output = self.output();
+        if !output.status.success() {
+            panic!("{:?} failed with {}\n--- stdout\n{}\n--- stderr\n{}",
+                   self.cmd, output.status,
+                   String::from_utf8_lossy(&output.stdout),
+                   String::from_utf8_lossy(&output.stderr));
+        }
+        output
+    }
+
+    /// Runs the command and panics unless it fails.
+    pub fn run_fail(&mut self) -> Output {
+        let output = self.output();
+        if output.status.success() {
+            panic!("{:?} succeeded but was expected to fail\n--- stdout\n{}\n--- stderr\n{}",
+                   self.cmd,
+                   String::from_utf8_lossy(&output.stdout),
+                   String::from_utf8_lossy(&output.stderr));
+        }
+        output
+    }
+}
+
+/// `rustc`, putting its output in and looking for crates in `tmpdir()`, the
+/// same as `$(RUSTC)` in `tools.mk`.
+pub fn rustc() -> Cmd {
+    let mut cmd = bare_rustc();
+    cmd.arg("--out-dir").arg(tmpdir()).arg("-L").arg(tmpdir());
+    cmd
+}
+
+/// `rustc` without any extra arguments, the same as `$(BARE_RUSTC)`.
+pub fn bare_rustc() -> Cmd {
+    let mut cmd = Cmd::new(env_var("RUSTC"));
+    add_lib_path(&mut cmd.cmd, &env_var("HOST_RPATH_DIR"));
+    cmd
+}
+
+/// `rustdoc`, the same as `$(RUSTDOC)`.
+pub fn rustdoc() -> Cmd {
+    let mut cmd = Cmd::new(env_var("RUSTDOC"));
+    add_lib_path(&mut cmd.cmd, &env_var("HOST_RPATH_DIR"));
+    cmd
+}
+
+/// The executable `name` built in `tmpdir()`, set up to find the target's
+/// dynamic libraries.
+pub fn bin(name: &str) -> Cmd {
+    let mut cmd = Cmd::new(tmpdir().join(bin_name(name)));
+    add_lib_path(&mut cmd.cmd, &env_var("TARGET_RPATH_DIR"));
+    cmd
+}
+
+/// Runs the executable `name` built in `tmpdir()`, which has to succeed.
+pub fn run(name: &str) -> Output {
+    bin(name).run()
+}
+
+/// Runs the executable `name` built in `tmpdir()`, which has to fail.
+pub fn run_fail(name: &str) -> Output {
+    bin(name).run_fail()
+}
+
+pub fn read_file<P: AsRef<Path>>(path: P) -> String {
+    let path = path.as_ref();
+    let mut contents = String::new();
+    if let Err(e) = File::open(path).and_then(|mut f| f.read_to_string(&mut contents)) {
+        panic!("failed to read `{}`: {}", path.display(), e);
+    }
+    contents
+}
+
+/// Panics unless the two files have the same contents, ignoring the
+/// difference between `\n` and `\r\n` line endings.
+pub fn compare_files<P: AsRef<Path>, Q: AsRef<Path>>(a: P, b: Q) {
+    let (a, b) = (a.as_ref(), b.as_ref());
+    let a_contents = read_file(a).replace("\r\n", "\n");
+    let b_contents = read_file(b).replace("\r\n", "\n");
+    if a_contents != b_contents {
+        panic!("`{}` and `{}` differ\n--- {}\n{}\n--- {}\n{}",
+               a.display(), b.display(),
+               a.display(), a_contents,
+               b.display(), b_contents);
+    }
+}
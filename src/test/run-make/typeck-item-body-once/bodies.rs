@@ -0,0 +1,46 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(associated_consts)]
+#![allow(dead_code)]
+
+static S: u32 = 1;
+const C: u32 = 2;
+
+fn f() -> u32 {
+    // Closures are checked along with the body they are in.
+    let g = |x: u32| x + 1;
+    g(3)
+}
+
+trait Tr {
+    const TC: u32 = 4;
+    fn default_method(&self) -> u32 {
+        5
+    }
+    fn required(&self) -> u32;
+}
+
+struct X;
+
+impl X {
+    const IC: u32 = 6;
+    fn inherent(&self) -> u32 {
+        7
+    }
+}
+
+impl Tr for X {
+    fn required(&self) -> u32 {
+        8
+    }
+}
+
+fn main() {}
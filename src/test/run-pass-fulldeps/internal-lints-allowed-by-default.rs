@@ -0,0 +1,28 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -D warnings
+
+// The internal lints are only checked when they are asked for
+
+#![feature(rustc_private)]
+#![allow(dead_code)]
+
+extern crate rustc;
+extern crate syntax;
+
+use rustc::session::Session;
+use syntax::codemap::DUMMY_SP;
+
+fn check(sess: &Session) {
+    sess.span_err(DUMMY_SP, "oops");
+}
+
+fn main() {}
@@ -0,0 +1,26 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// A `cfg_attr` can apply several attributes at once.
+
+// compile-flags: --cfg set
+
+#[cfg_attr(set, derive(Clone), derive(Debug, PartialEq))]
+struct Applied(i32);
+
+#[cfg_attr(unset, derive(Clone), cfg(unset))]
+fn not_applied() {}
+
+fn main() {
+    let a = Applied(1);
+    assert_eq!(a.clone(), a);
+    assert_eq!(format!("{:?}", a), "Applied(1)");
+    not_applied();
+}
@@ -0,0 +1,34 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that impls for the same self type which differ only in the
+// other input types of the trait do not conflict, including when
+// those input types are references.
+
+trait Convert<T> {
+    fn convert(&self) -> usize;
+}
+
+struct Foo;
+struct Bar;
+
+impl Convert<u8> for Foo { fn convert(&self) -> usize { 0 } }
+impl Convert<u16> for Foo { fn convert(&self) -> usize { 1 } }
+impl Convert<Bar> for Foo { fn convert(&self) -> usize { 2 } }
+impl<'a> Convert<&'a Bar> for Foo { fn convert(&self) -> usize { 3 } }
+impl Convert<(u8, u8)> for Foo { fn convert(&self) -> usize { 4 } }
+
+fn main() {
+    assert_eq!(<Foo as Convert<u8>>::convert(&Foo), 0);
+    assert_eq!(<Foo as Convert<u16>>::convert(&Foo), 1);
+    assert_eq!(<Foo as Convert<Bar>>::convert(&Foo), 2);
+    assert_eq!(<Foo as Convert<&Bar>>::convert(&Foo), 3);
+    assert_eq!(<Foo as Convert<(u8, u8)>>::convert(&Foo), 4);
+}
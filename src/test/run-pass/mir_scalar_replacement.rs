@@ -0,0 +1,45 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -O
+
+// Aggregates that are only accessed field by field are split into
+// scalars in the MIR; check that the values survive.
+
+#![feature(rustc_attrs)]
+
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[rustc_mir]
+fn swap(a: i32, b: i32) -> (i32, i32) {
+    let (x, y) = (b, a);
+    (x, y)
+}
+
+#[rustc_mir]
+fn manhattan(x: i32, y: i32) -> i32 {
+    let p = Point { x: x, y: y };
+    let Point { x, y } = p;
+    x + y
+}
+
+#[rustc_mir]
+fn first(a: u8, b: u16) -> u8 {
+    (a, b).0
+}
+
+fn main() {
+    assert_eq!(swap(1, 2), (2, 1));
+    assert_eq!(manhattan(3, 4), 7);
+    assert_eq!(first(5, 6), 5);
+}
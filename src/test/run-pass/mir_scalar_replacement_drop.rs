@@ -0,0 +1,92 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -O
+
+// Splitting an aggregate into its fields must not change when, in which
+// order or how often the fields are dropped. Structs with a destructor
+// of their own are not split, and still run it before their fields'.
+
+#![feature(rustc_attrs)]
+
+use std::cell::RefCell;
+
+struct Noisy<'a> {
+    id: u32,
+    log: &'a RefCell<Vec<u32>>,
+}
+
+impl<'a> Drop for Noisy<'a> {
+    fn drop(&mut self) {
+        self.log.borrow_mut().push(self.id);
+    }
+}
+
+struct Pair<'a> {
+    a: Noisy<'a>,
+    b: Noisy<'a>,
+}
+
+struct Guarded<'a> {
+    inner: Noisy<'a>,
+    id: u32,
+    log: &'a RefCell<Vec<u32>>,
+}
+
+impl<'a> Drop for Guarded<'a> {
+    fn drop(&mut self) {
+        self.log.borrow_mut().push(self.id);
+    }
+}
+
+#[rustc_mir]
+fn tuple<'a>(log: &'a RefCell<Vec<u32>>) -> u32 {
+    (Noisy { id: 1, log: log }, Noisy { id: 2, log: log }).1.id
+}
+
+#[rustc_mir]
+fn pair<'a>(log: &'a RefCell<Vec<u32>>) -> u32 {
+    Pair { a: Noisy { id: 3, log: log }, b: Noisy { id: 4, log: log } }.a.id
+}
+
+#[rustc_mir]
+fn guarded<'a>(log: &'a RefCell<Vec<u32>>) -> u32 {
+    Guarded { inner: Noisy { id: 6, log: log }, id: 5, log: log }.inner.id
+}
+
+#[rustc_mir]
+fn in_loop<'a>(log: &'a RefCell<Vec<u32>>, n: u32) -> u32 {
+    let mut sum = 0;
+    let mut i = 0;
+    while i < n {
+        sum += (Noisy { id: 7, log: log }, Noisy { id: 8, log: log }).0.id;
+        i += 1;
+    }
+    sum
+}
+
+fn main() {
+    let log = RefCell::new(vec![]);
+
+    assert_eq!(tuple(&log), 2);
+    assert_eq!(*log.borrow(), [1, 2]);
+    log.borrow_mut().clear();
+
+    assert_eq!(pair(&log), 3);
+    assert_eq!(*log.borrow(), [3, 4]);
+    log.borrow_mut().clear();
+
+    assert_eq!(guarded(&log), 6);
+    assert_eq!(*log.borrow(), [5, 6]);
+    log.borrow_mut().clear();
+
+    assert_eq!(in_loop(&log, 3), 21);
+    assert_eq!(*log.borrow(), [7, 8, 7, 8, 7, 8]);
+}
@@ -0,0 +1,63 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that `Self` can be used in struct literals, struct patterns,
+// as a constructor and to name associated items inside impls.
+
+#![feature(associated_consts, self_struct_ctor)]
+
+#[derive(PartialEq, Debug)]
+struct AVeryLongStructName<T> {
+    value: T,
+}
+
+impl<T: Clone> AVeryLongStructName<T> {
+    const ANSWER: u32 = 42;
+
+    fn new(value: T) -> Self {
+        Self { value: value }
+    }
+
+    fn duplicate(&self) -> Self {
+        let Self { ref value } = *self;
+        Self::new(value.clone())
+    }
+
+    fn answer() -> u32 {
+        Self::ANSWER
+    }
+}
+
+#[derive(PartialEq, Debug)]
+struct Pair(u8, u8);
+
+impl Pair {
+    fn swap(self) -> Self {
+        let Self(a, b) = self;
+        Self(b, a)
+    }
+}
+
+#[derive(PartialEq, Debug)]
+struct Unit;
+
+impl Default for Unit {
+    fn default() -> Self {
+        Self
+    }
+}
+
+fn main() {
+    let s = AVeryLongStructName::new(3);
+    assert_eq!(s.duplicate(), AVeryLongStructName { value: 3 });
+    assert_eq!(AVeryLongStructName::<u8>::answer(), 42);
+    assert_eq!(Pair(1, 2).swap(), Pair(2, 1));
+    assert_eq!(Unit::default(), Unit);
+}
@@ -0,0 +1,42 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// `use crate::...` starts an import path at the crate root explicitly, even
+// after a leading `::`.
+
+#![feature(crate_in_paths)]
+
+pub mod a {
+    pub mod b {
+        pub fn f() -> u32 { 1 }
+        pub struct S;
+    }
+
+    pub fn g() -> u32 { 2 }
+}
+
+mod x {
+    pub mod y {
+        pub mod z {
+            use crate::a::b::f;
+            use ::crate::a::g;
+            use crate::a::b::{self, S};
+
+            pub fn sum() -> u32 {
+                let _ = S;
+                f() + g() + b::f()
+            }
+        }
+    }
+}
+
+fn main() {
+    assert_eq!(x::y::z::sum(), 4);
+}
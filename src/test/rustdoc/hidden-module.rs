@@ -0,0 +1,41 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![crate_name = "foo"]
+
+pub trait Public {}
+
+// @!has foo/hidden/index.html
+// @!has foo/hidden/struct.Inner.html
+#[doc(hidden)]
+pub mod hidden {
+    pub struct Inner;
+    pub struct Shown;
+}
+
+// Impls of types inside a hidden module are stripped along with them.
+// @!has foo/trait.Public.html '//code' 'impl Public for Inner'
+impl Public for hidden::Inner {}
+impl Public for hidden::Shown {}
+
+// `#[doc(inline)]` surfaces an item from a hidden module, with its impls.
+// @has foo/struct.Shown.html
+// @has foo/trait.Public.html '//code' 'impl Public for Shown'
+#[doc(inline)] pub use hidden::Shown;
+
+mod private {
+    #[doc(hidden)] pub struct Surfaced;
+    #[doc(hidden)] pub struct StillHidden;
+}
+
+// @has foo/struct.Surfaced.html
+#[doc(inline)] pub use private::Surfaced;
+// @!has foo/struct.StillHidden.html
+pub use private::StillHidden;
@@ -0,0 +1,46 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:rustdoc-reexports.rs
+// ignore-cross-compile
+
+#![crate_name = "foo"]
+
+extern crate rustdoc_reexports;
+
+pub mod public {
+    pub fn shown() {}
+    pub fn listed() {}
+}
+
+mod private {
+    pub fn inlined() {}
+}
+
+// Re-exports from other crates are inlined unless asked not to.
+// @has foo/fn.inlined.html
+pub use rustdoc_reexports::inlined;
+// @!has foo/fn.not_inlined.html
+// @has foo/index.html '//code' 'pub use rustdoc_reexports::not_inlined;'
+#[doc(no_inline)] pub use rustdoc_reexports::not_inlined;
+
+// Public items of this crate are only inlined when asked to.
+// @has foo/fn.shown.html
+#[doc(inline)] pub use public::shown;
+// @!has foo/fn.listed.html
+// @has foo/index.html '//code' 'pub use public::listed;'
+// @has foo/index.html '//code/a[@href="public/fn.listed.html"]' 'listed'
+pub use public::listed;
+
+// Private items are documented where they're re-exported.
+// @has foo/private_fn/fn.inlined.html
+pub mod private_fn {
+    pub use private::inlined;
+}
@@ -0,0 +1,33 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:rustdoc-reexport-paths.rs
+// ignore-cross-compile
+
+// Items of other crates are linked to where their documentation is: where
+// they are re-exported if they are defined in a private module, and where
+// they are defined otherwise.
+
+extern crate rustdoc_reexport_paths;
+
+use rustdoc_reexport_paths::{Hidden, Other, Shown};
+
+// @has reexport_paths/fn.hidden.html \
+//      '//*[@href="http://example.com/rustdoc_reexport_paths/struct.Hidden.html"]' 'Hidden'
+pub fn hidden(_: Hidden) {}
+
+// @has reexport_paths/fn.renamed.html \
+//      '//*[@href="http://example.com/rustdoc_reexport_paths/struct.Other.html"]' 'Other'
+pub fn renamed(_: Other) {}
+
+// @has reexport_paths/fn.shown.html \
+//      '//*[@href="http://example.com/rustdoc_reexport_paths/public/struct.Shown.html"]' \
+//      'Shown'
+pub fn shown(_: Shown) {}
@@ -12,7 +12,10 @@
 
 use std::ops::Deref;
 
-// @has search-index.js Foo
+// The shared index only lists the crates, each crate has its own file.
+// @has search-index.js rustdoc_test
+// @!has search-index.js Foo
+// @has search-index/rustdoc_test.js Foo
 pub use private::Foo;
 
 mod private {
@@ -30,7 +33,7 @@ mod private {
 pub struct Bar;
 
 impl Deref for Bar {
-    // @!has search-index.js Target
+    // @!has search-index/rustdoc_test.js Target
     type Target = Bar;
     fn deref(&self) -> &Bar { self }
 }